@@ -4,6 +4,7 @@
 //! Components emit Actions in response to events, and the App processes
 //! them to update state.
 
+use crate::services::editor::EditorTarget;
 use std::fmt;
 
 /// All possible actions in the application
@@ -62,6 +63,12 @@ pub enum Action {
     OpenHistory,
     /// Open run output overlay
     OpenRunOutput,
+    /// Mark/unmark the selected history entry for comparison
+    ToggleHistoryMark,
+    /// Compare the marked history entries
+    OpenRunComparison,
+    /// Open export dialog for the current run or selected history entry
+    OpenExport,
     /// Close the current modal
     CloseModal,
     /// Confirm the current modal action
@@ -78,10 +85,40 @@ pub enum Action {
     ToggleCodeView,
     /// Toggle lineage panel visibility
     ToggleLineage,
+    /// Open the multi-level lineage explorer for the selected node
+    OpenLineageExplorer,
+    /// Open the full-screen DAG view around the selected node
+    OpenDagView,
+    /// Open the lineage export dialog for the selected node or selection
+    OpenLineageExport,
+    /// Open the impact analysis report for the selected node
+    OpenImpactReport,
+    /// Build a model and everything downstream of it
+    BuildDownstream(String),
+    /// Open the project health audit
+    OpenHealthAudit,
+    /// Check models against the modeling conventions
+    OpenLint,
+    /// Open the dead and orphaned resource report
+    OpenCruftReport,
+    /// Open the test coverage matrix of the selected model
+    OpenTestMatrix,
+    /// Select a node in the main list by unique_id, switching tabs if needed
+    RevealNode(String),
     /// Toggle documentation panel visibility
     ToggleDocumentation,
+    /// Select the next column in the documentation panel
+    DocSelectNext,
+    /// Select the previous column (or the model) in the documentation panel
+    DocSelectPrevious,
+    /// Edit the description selected in the documentation panel
+    OpenDescriptionEditor,
+    /// Open the add/remove tests dialog for the selected column
+    OpenColumnTests,
     /// Toggle run output view mode (raw/graphical)
     ToggleOutputView,
+    /// Toggle run output timeline (Gantt) view
+    ToggleTimelineView,
 
     // ─────────────────────────────────────────────────────────────────────────
     // Search
@@ -136,12 +173,26 @@ pub enum Action {
     // ─────────────────────────────────────────────────────────────────────────
     /// Open current file in external $EDITOR
     OpenEditor,
+    /// Open a file (relative to the project root) in $EDITOR, optionally at a line
+    OpenFileInEditor(EditorTarget),
+    /// Pick one of the selected node's related files to open
+    OpenRelatedFiles,
+    /// Jump to the selected node's declaration in schema YAML
+    OpenYamlDefinition,
+    /// Generate a schema YAML entry from warehouse columns
+    OpenYamlScaffold,
+    /// Create a new model from a template
+    OpenNewModelWizard,
+    /// Rename or move the selected model
+    OpenRefactor,
 
     // ─────────────────────────────────────────────────────────────────────────
     // Sample Data
     // ─────────────────────────────────────────────────────────────────────────
     /// Open sample data preview (dbt show)
     OpenSampleData,
+    /// Preview the rows a test flags, by test name
+    PreviewFailingRows(String),
     /// Open help dialog showing all keyboard shortcuts
     OpenHelp,
 
@@ -184,14 +235,32 @@ impl fmt::Display for Action {
             Action::OpenProjectInfo => write!(f, "OpenProjectInfo"),
             Action::OpenHistory => write!(f, "OpenHistory"),
             Action::OpenRunOutput => write!(f, "OpenRunOutput"),
+            Action::ToggleHistoryMark => write!(f, "ToggleHistoryMark"),
+            Action::OpenRunComparison => write!(f, "OpenRunComparison"),
+            Action::OpenExport => write!(f, "OpenExport"),
             Action::CloseModal => write!(f, "CloseModal"),
             Action::ConfirmModal => write!(f, "ConfirmModal"),
             Action::ModalUp => write!(f, "ModalUp"),
             Action::ModalDown => write!(f, "ModalDown"),
             Action::ToggleCodeView => write!(f, "ToggleCodeView"),
             Action::ToggleLineage => write!(f, "ToggleLineage"),
+            Action::OpenLineageExplorer => write!(f, "OpenLineageExplorer"),
+            Action::OpenDagView => write!(f, "OpenDagView"),
+            Action::OpenLineageExport => write!(f, "OpenLineageExport"),
+            Action::OpenImpactReport => write!(f, "OpenImpactReport"),
+            Action::BuildDownstream(name) => write!(f, "BuildDownstream({})", name),
+            Action::OpenHealthAudit => write!(f, "OpenHealthAudit"),
+            Action::OpenLint => write!(f, "OpenLint"),
+            Action::OpenCruftReport => write!(f, "OpenCruftReport"),
+            Action::OpenTestMatrix => write!(f, "OpenTestMatrix"),
+            Action::RevealNode(id) => write!(f, "RevealNode({})", id),
             Action::ToggleDocumentation => write!(f, "ToggleDocumentation"),
+            Action::DocSelectNext => write!(f, "DocSelectNext"),
+            Action::DocSelectPrevious => write!(f, "DocSelectPrevious"),
+            Action::OpenDescriptionEditor => write!(f, "OpenDescriptionEditor"),
+            Action::OpenColumnTests => write!(f, "OpenColumnTests"),
             Action::ToggleOutputView => write!(f, "ToggleOutputView"),
+            Action::ToggleTimelineView => write!(f, "ToggleTimelineView"),
             Action::EnterSearchMode => write!(f, "EnterSearchMode"),
             Action::ExitSearchMode => write!(f, "ExitSearchMode"),
             Action::SearchInput(c) => write!(f, "SearchInput('{}')", c),
@@ -207,7 +276,17 @@ impl fmt::Display for Action {
             Action::OpenTargetSelector => write!(f, "OpenTargetSelector"),
             Action::SetupConfirm => write!(f, "SetupConfirm"),
             Action::OpenEditor => write!(f, "OpenEditor"),
+            Action::OpenFileInEditor(target) => match target.line {
+                Some(line) => write!(f, "OpenFileInEditor({}:{})", target.path, line),
+                None => write!(f, "OpenFileInEditor({})", target.path),
+            },
+            Action::OpenRelatedFiles => write!(f, "OpenRelatedFiles"),
+            Action::OpenYamlDefinition => write!(f, "OpenYamlDefinition"),
+            Action::OpenYamlScaffold => write!(f, "OpenYamlScaffold"),
+            Action::OpenNewModelWizard => write!(f, "OpenNewModelWizard"),
+            Action::OpenRefactor => write!(f, "OpenRefactor"),
             Action::OpenSampleData => write!(f, "OpenSampleData"),
+            Action::PreviewFailingRows(name) => write!(f, "PreviewFailingRows({})", name),
             Action::OpenHelp => write!(f, "OpenHelp"),
             Action::OpenGitDiff => write!(f, "OpenGitDiff"),
             Action::GitStageFile => write!(f, "GitStageFile"),
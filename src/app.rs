@@ -8,25 +8,42 @@
 use crate::action::Action;
 use crate::component::Component;
 use crate::components::{
-    draw_home_screen, DetailComponent, DocumentationComponent, HelpDialog, HistoryDialog,
-    HomeComponent, HomeRenderContext, LineageComponent, ProjectInfoDialog, QuitDialog,
-    RunOptionsDialog, RunOutputDialog, SampleDataDialog, SetupComponent, SplashComponent,
-    TagFilterDialog, TargetSelectorDialog,
+    draw_home_screen, AuditDialog, ColumnTestsDialog, CruftDialog, DagView, DescriptionEditor, DetailComponent, DocumentationComponent, ExportDialog, HelpDialog, HistoryDialog, ImpactDialog,
+    HomeComponent, HomeRenderContext, LineageComponent, LineageExplorer, LineageExportDialog, LintDialog, NewModelWizard, ProjectInfoDialog, QuitDialog, RefactorDialog, RelatedFilesDialog,
+    RunComparisonDialog, RunOptionsDialog, RunOutputDialog, SampleDataDialog, SetupComponent, SplashComponent,
+    TagFilterDialog, TargetSelectorDialog, TestMatrixDialog, YamlScaffoldDialog,
 };
+use crate::components::column_tests_dialog::{AddedTest, TestEdit};
+use crate::components::description_editor::DescriptionTarget;
+use crate::components::new_model_wizard::NewModel;
+use crate::components::related_files_dialog::RelatedFile;
 use crate::config::Config;
+use crate::model::catalog::WarehouseColumn;
+use crate::model::comparison::RunComparison;
 use crate::model::domain::DomainState;
-use crate::model::history::{RunHistory, RunHistoryEntry};
+use crate::model::estimate::{ModelDurations, RunEstimate};
+use crate::model::history::{RunHistory, RunHistoryEntry, DEFAULT_HISTORY_RETENTION};
+use crate::model::impact::ImpactReport;
+use crate::model::audit::HealthAudit;
+use crate::model::cruft::CruftReport;
+use crate::model::test_matrix::TestMatrix;
+use crate::model::lint::lint;
+use crate::model::column_lineage::ColumnLineage;
 use crate::model::lineage::LineageGraph;
 use crate::model::modal::{Modal, ModalStack};
+use crate::model::node::Node;
 use crate::model::run::{DbtCommand, RunFlags, RunOutput, RunSelectMode, RunStatus};
+use crate::model::run_summary::RunSummary;
 use crate::model::sample_data::SampleDataOutput;
-use crate::model::ui::AppMode;
-use crate::services::{self, JobRunner};
+use crate::model::ui::{AppMode, Tab};
+use crate::services::editor::EditorTarget;
+use crate::services::model_template::StagingSource;
+use crate::services::{self, JobRunner, LineageExport};
 use anyhow::Result;
-use chrono::Local;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{layout::Rect, Frame};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // Error Message Helpers
@@ -101,6 +118,10 @@ pub struct App {
     /// Background job runner for sample data queries
     pub sample_data_runner: JobRunner,
 
+    /// Modification time of the last target/run_results.json handled
+    /// (imported or produced by a job started here)
+    pub run_results_seen: Option<SystemTime>,
+
     /// Flag to indicate the app should quit
     pub should_quit: bool,
 
@@ -119,8 +140,14 @@ pub struct App {
     /// Git file statuses by relative path
     pub git_file_statuses: std::collections::HashMap<String, services::GitFileStatus>,
 
-    /// Pending external editor file path (set by OpenEditor action, handled by main loop)
-    pub pending_editor_file: Option<String>,
+    /// Pending external editor file (set by editor actions, handled by main loop)
+    pub pending_editor: Option<EditorTarget>,
+
+    /// Test added from the TUI, offered to run once `dbt parse` has picked it up
+    pub added_test: Option<AddedTest>,
+
+    /// Model created from the TUI, selected once the manifest is refreshed
+    pub select_after_refresh: Option<String>,
 
     // ─────────────────────────────────────────────────────────────────────────
     // Child Components
@@ -133,6 +160,22 @@ pub struct App {
     pub quit_dialog: QuitDialog,
     pub run_options_dialog: RunOptionsDialog,
     pub history_dialog: HistoryDialog,
+    pub run_comparison_dialog: RunComparisonDialog,
+    pub export_dialog: ExportDialog,
+    pub related_files_dialog: RelatedFilesDialog,
+    pub description_editor: DescriptionEditor,
+    pub column_tests_dialog: ColumnTestsDialog,
+    pub yaml_scaffold_dialog: YamlScaffoldDialog,
+    pub new_model_wizard: NewModelWizard,
+    pub refactor_dialog: RefactorDialog,
+    pub lineage_explorer: LineageExplorer,
+    pub dag_view: DagView,
+    pub lineage_export_dialog: LineageExportDialog,
+    pub impact_dialog: ImpactDialog,
+    pub audit_dialog: AuditDialog,
+    pub lint_dialog: LintDialog,
+    pub cruft_dialog: CruftDialog,
+    pub test_matrix_dialog: TestMatrixDialog,
     pub run_output_dialog: RunOutputDialog,
     pub project_info_dialog: ProjectInfoDialog,
     pub setup: SetupComponent,
@@ -165,6 +208,7 @@ impl App {
             app.domain.dbt_binary_path = config.dbt_binary_path.clone();
             let project_path = PathBuf::from(&config.project_path);
             app.domain.project_path = Some(project_path.clone());
+            app.load_history();
 
             let manifest_path = project_path.join("target").join("manifest.json");
 
@@ -176,6 +220,11 @@ impl App {
             match services::load_manifest(&manifest_path) {
                 Ok(manifest) => {
                     app.domain.all_nodes = services::filter_nodes(&manifest);
+                    app.domain.sources = services::list_sources(&manifest);
+                    app.domain.exposures = services::list_exposures(&manifest);
+                    app.domain.analyses = services::list_analyses(&manifest);
+                    app.domain.macros = services::list_macros(&manifest);
+                    app.migrate_legacy_history();
 
                     let root_path_str = project_path.to_string_lossy().to_string();
                     for node in &mut app.domain.all_nodes {
@@ -183,6 +232,10 @@ impl App {
                     }
 
                     app.domain.lineage_graph = Some(LineageGraph::build(&app.domain.all_nodes));
+                    app.domain.column_lineage =
+                        ColumnLineage::build(&app.domain.all_nodes, &app.domain.sources);
+                    app.domain.yaml_index =
+                        services::load_yaml_index(&project_path, &app.domain.all_nodes, &app.domain.sources);
 
                     if !app.domain.all_nodes.is_empty() {
                         app.home.select_first(&app.domain.all_nodes);
@@ -204,13 +257,10 @@ impl App {
             // Load git status
             app.refresh_git_status();
 
-            app.domain.run_history = RunHistory::load();
             app
         } else {
             // No config exists, show splash then setup screen
-            let mut app = Self::create_app(AppMode::Setup);
-            app.domain.run_history = RunHistory::load();
-            app
+            Self::create_app(AppMode::Setup)
         }
     }
 
@@ -239,13 +289,16 @@ impl App {
             modals: ModalStack::new(),
             job_runner: JobRunner::new(),
             sample_data_runner: JobRunner::new(),
+            run_results_seen: None,
             should_quit: false,
             error: None,
             status_message: None,
             git_branch: None,
             git_is_dirty: false,
             git_file_statuses: std::collections::HashMap::new(),
-            pending_editor_file: None,
+            pending_editor: None,
+            added_test: None,
+            select_after_refresh: None,
             // Components
             splash: SplashComponent::new(),
             home: HomeComponent::new(),
@@ -255,6 +308,22 @@ impl App {
             quit_dialog: QuitDialog,
             run_options_dialog: RunOptionsDialog::default(),
             history_dialog: HistoryDialog::default(),
+            run_comparison_dialog: RunComparisonDialog::default(),
+            export_dialog: ExportDialog::default(),
+            related_files_dialog: RelatedFilesDialog::default(),
+            description_editor: DescriptionEditor::default(),
+            column_tests_dialog: ColumnTestsDialog::default(),
+            yaml_scaffold_dialog: YamlScaffoldDialog::default(),
+            new_model_wizard: NewModelWizard::default(),
+            refactor_dialog: RefactorDialog::default(),
+            lineage_explorer: LineageExplorer::default(),
+            dag_view: DagView::default(),
+            lineage_export_dialog: LineageExportDialog::default(),
+            impact_dialog: ImpactDialog::default(),
+            audit_dialog: AuditDialog::default(),
+            lint_dialog: LintDialog::default(),
+            cruft_dialog: CruftDialog::default(),
+            test_matrix_dialog: TestMatrixDialog::default(),
             run_output_dialog: RunOutputDialog::default(),
             project_info_dialog: ProjectInfoDialog::new(),
             setup: SetupComponent::new(),
@@ -288,43 +357,45 @@ impl App {
                 None,
                 flags,
             );
-            self.domain.run_output = Some(self.job_runner.spawn(cmd));
-            if let Some(ref mut output) = self.domain.run_output {
-                output.command = display_cmd;
-            }
-            self.modals.push(Modal::RunOutput);
+            self.spawn_run(cmd, display_cmd);
             return;
         }
 
         // Build selector based on bulk selection or single node
-        let selector = if !self.home.selected_nodes.is_empty() {
+        let roots: Vec<Node> = if !self.home.selected_nodes.is_empty() {
             // Bulk selection: build selector from all selected nodes
             let selected_nodes: Vec<_> = self.domain.all_nodes
                 .iter()
                 .filter(|n| self.home.selected_nodes.contains(&n.unique_id))
+                .cloned()
                 .collect();
 
             if selected_nodes.is_empty() {
                 return;
             }
 
-            // Build selector with all node names
-            let names: Vec<String> = selected_nodes
-                .iter()
-                .map(|n| mode.selector(&n.name))
-                .collect();
-
             // Clear selection after running
             self.home.clear_selection();
 
-            names.join(" ")
+            selected_nodes
         } else {
             // Single node selection
-            let node = match self.get_selected_node() {
-                Some(n) => n.clone(),
+            match self.get_selected_node() {
+                Some(n) => vec![n.clone()],
                 None => return,
-            };
-            mode.selector(&node.name)
+            }
+        };
+        let selector = roots
+            .iter()
+            .map(|n| mode.selector(&n.name))
+            .collect::<Vec<_>>()
+            .join(" ");
+        // Known up front unless exclusions apply; tests run no models
+        let selected_models = if flags.exclude.trim().is_empty() && command != DbtCommand::Test {
+            let roots: Vec<&Node> = roots.iter().collect();
+            mode.select_models(&roots, &self.domain.all_nodes)
+        } else {
+            Vec::new()
         };
 
         let (cmd, display_cmd) = services::build_dbt_command(
@@ -335,36 +406,239 @@ impl App {
             flags,
         );
 
+        self.spawn_run(cmd, display_cmd);
+        if let Some(ref mut output) = self.domain.run_output {
+            output.selected_models = selected_models;
+        }
+    }
+
+    /// Run `dbt test` for a single test node
+    fn run_test(&mut self, test_name: &str) {
+        let Some(project_path) = self.domain.project_path.clone() else {
+            return;
+        };
+        let (cmd, display_cmd) = services::build_dbt_command(
+            &self.domain.dbt_binary_path,
+            &project_path,
+            DbtCommand::Test,
+            Some(test_name),
+            &RunFlags::default(),
+        );
+        self.spawn_run(cmd, display_cmd);
+    }
+
+    /// Spawn a dbt job and show its output
+    fn spawn_run(&mut self, cmd: String, display_cmd: String) {
         self.domain.run_output = Some(self.job_runner.spawn(cmd));
+        self.run_output_dialog.reset();
         if let Some(ref mut output) = self.domain.run_output {
             output.command = display_cmd;
         }
         self.modals.push(Modal::RunOutput);
     }
+    /// Attach target/run_results.json to a finished run.
+    /// Results generated before the run started belong to an earlier invocation and are ignored.
+    fn load_run_results_into(
+        run_output: &mut RunOutput,
+        project_path: Option<&std::path::Path>,
+        all_nodes: &[Node],
+    ) {
+        let Some(project_path) = project_path else {
+            return;
+        };
+        let Ok(results) = services::load_run_results(project_path) else {
+            return;
+        };
+
+        let is_stale = match (results.generated_at, run_output.started_at) {
+            (Some(generated_at), Some(started_at)) => generated_at < started_at,
+            _ => false,
+        };
+        if !is_stale {
+            run_output.apply_run_results(results, all_nodes);
+        }
+    }
+
+    /// Progress estimate for the active job, based on historical model durations
+    fn run_estimate(&self, run_output: &RunOutput) -> Option<RunEstimate> {
+        if run_output.status != RunStatus::Running {
+            return None;
+        }
+        let elapsed = self.job_runner.start_instant()?.elapsed().as_secs_f64();
+        let threads = self
+            .domain
+            .project_info
+            .as_ref()
+            .and_then(|info| info.profile_threads.parse::<usize>().ok())
+            .unwrap_or(1);
+
+        RunEstimate::estimate(
+            run_output,
+            &self.domain.model_durations,
+            &self.domain.all_nodes,
+            elapsed,
+            threads,
+            Instant::now(),
+        )
+    }
+
     /// Save run to history when complete
     fn save_to_history(&mut self) {
-        if let Some(ref run_output) = self.domain.run_output {
-            if run_output.status != RunStatus::Running {
-                let duration = self.job_runner.start_instant()
-                    .map(|i| i.elapsed().as_secs_f64())
-                    .unwrap_or(0.0);
+        if let Some(run_output) = self.domain.run_output.clone() {
+            // Outputs reopened from history have no start time and are already saved
+            if let (false, Some(timestamp)) =
+                (run_output.status == RunStatus::Running, run_output.started_at)
+            {
+                let duration = run_output.duration_secs.unwrap_or(0.0);
 
                 let entry = RunHistoryEntry {
-                    timestamp: Local::now(),
+                    id: RunHistoryEntry::id_for(&timestamp),
+                    timestamp,
                     command: run_output.command.clone(),
                     status: run_output.status,
                     output: run_output.output.clone(),
+                    output_loaded: true,
                     duration_secs: duration,
+                    target: self.config().map(|c| c.target.clone()),
+                    invocation_id: run_output.invocation_id.clone(),
+                    node_results: run_output.node_results.clone(),
+                    external: false,
                 };
-
-                self.domain.run_history.insert(0, entry);
-                if self.domain.run_history.len() > 100 {
-                    self.domain.run_history.truncate(100);
+                let retention = self
+                    .config()
+                    .map(|c| c.history_retention)
+                    .unwrap_or(DEFAULT_HISTORY_RETENTION);
+
+                // History is ordered newest first; an external run may have started later
+                let position = self
+                    .domain
+                    .run_history
+                    .iter()
+                    .position(|e| e.timestamp < entry.timestamp)
+                    .unwrap_or(self.domain.run_history.len());
+                self.domain.run_history.insert(position, entry);
+                RunHistory::retain(&mut self.domain.run_history, retention);
+                self.domain.model_durations = ModelDurations::from_history(&self.domain.run_history);
+                if let Some(ref project_path) = self.domain.project_path {
+                    let _ = RunHistory::save(project_path, &self.domain.run_history);
                 }
-                let _ = RunHistory::save(&self.domain.run_history);
             }
         }
     }
+
+    /// Import a new target/run_results.json written by an external dbt invocation
+    fn import_external_run(&mut self) {
+        let Some(project_path) = self.domain.project_path.clone() else {
+            return;
+        };
+        let modified = services::run_results_modified(&project_path);
+        if modified.is_none() || modified == self.run_results_seen {
+            return;
+        }
+        self.run_results_seen = modified;
+
+        let Ok(results) = services::load_run_results(&project_path) else {
+            return;
+        };
+        let Some(invocation_id) = results.invocation_id.clone() else {
+            return;
+        };
+        let already_known = self
+            .domain
+            .run_history
+            .iter()
+            .any(|e| e.invocation_id.as_deref() == Some(invocation_id.as_str()));
+        // Compiles, sample data previews (dbt show), docs and operations are not runs
+        if already_known || !results.args.is_run() {
+            return;
+        }
+
+        let log = services::load_invocation_log(&project_path, &invocation_id);
+        let entry = RunHistoryEntry::from_run_results(results, log);
+        self.status_message = Some(format!("Imported external run: {}", entry.command));
+
+        // History is ordered newest first
+        let position = self
+            .domain
+            .run_history
+            .iter()
+            .position(|e| e.timestamp < entry.timestamp)
+            .unwrap_or(self.domain.run_history.len());
+        let retention = self
+            .config()
+            .map(|c| c.history_retention)
+            .unwrap_or(DEFAULT_HISTORY_RETENTION);
+
+        self.domain.run_history.insert(position, entry);
+        RunHistory::retain(&mut self.domain.run_history, retention);
+        self.domain.model_durations = ModelDurations::from_history(&self.domain.run_history);
+        let _ = RunHistory::save(&project_path, &self.domain.run_history);
+    }
+
+    /// Load run history for the current project
+    fn load_history(&mut self) {
+        self.domain.run_history = match self.domain.project_path {
+            Some(ref project_path) => RunHistory::load(project_path),
+            None => Vec::new(),
+        };
+        self.domain.model_durations = ModelDurations::from_history(&self.domain.run_history);
+    }
+
+    /// Adopt this project's runs from the legacy global history. Runs are
+    /// attributed by model name, so this needs the manifest.
+    fn migrate_legacy_history(&mut self) {
+        let Some(ref project_path) = self.domain.project_path else {
+            return;
+        };
+        let migrated = RunHistory::migrate_legacy(project_path, &self.domain.all_nodes);
+        if !migrated.is_empty() {
+            self.domain.run_history = migrated;
+            self.domain.model_durations = ModelDurations::from_history(&self.domain.run_history);
+        }
+    }
+
+    /// Read the outputs the history dialog needs: the selected and marked
+    /// entries', or every entry's while the filter searches outputs. Called when
+    /// the selection, filter or marks change rather than on every draw.
+    fn load_history_outputs(&mut self) {
+        let Some(project_path) = self.domain.project_path.clone() else {
+            return;
+        };
+        if self.history_dialog.searches_output() {
+            for entry in &mut self.domain.run_history {
+                RunHistory::load_output(&project_path, entry);
+            }
+            return;
+        }
+
+        let selected = self
+            .history_dialog
+            .selected_entry_index(&self.domain.run_history)
+            .and_then(|i| self.domain.run_history.get(i))
+            .map(|e| e.id.clone());
+        let marked = &self.history_dialog.marked;
+        for entry in self
+            .domain
+            .run_history
+            .iter_mut()
+            .filter(|e| selected.as_ref() == Some(&e.id) || marked.contains(&e.id))
+        {
+            RunHistory::load_output(&project_path, entry);
+        }
+    }
+
+    /// Editor command template from the config, if set
+    pub fn editor_template(&mut self) -> Option<String> {
+        self.config().and_then(|c| c.editor_command.clone())
+    }
+
+    /// Current config, loaded from disk on first use
+    fn config(&mut self) -> Option<&Config> {
+        if self.config.is_none() {
+            self.config = Config::load();
+        }
+        self.config.as_ref()
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -409,9 +683,23 @@ impl Component for App {
                 }
                 // Poll background jobs
                 if let Some(ref mut run_output) = self.domain.run_output {
+                    let was_running = run_output.status == RunStatus::Running;
                     self.job_runner.poll(run_output);
                     // Compute layers based on manifest dependency graph
                     run_output.compute_layers(&self.domain.all_nodes);
+                    // Load run_results.json once the command finishes
+                    if was_running && run_output.status != RunStatus::Running {
+                        Self::load_run_results_into(
+                            run_output,
+                            self.domain.project_path.as_deref(),
+                            &self.domain.all_nodes,
+                        );
+                        self.run_results_seen = self
+                            .domain
+                            .project_path
+                            .as_ref()
+                            .and_then(services::run_results_modified);
+                    }
                 }
                 // Poll sample data jobs
                 if let Some(ref mut sample_output) = self.domain.sample_data_output {
@@ -429,6 +717,12 @@ impl Component for App {
 
                         // Parse when complete
                         if sample_output.status != RunStatus::Running {
+                            // dbt show also writes run_results.json; don't import it
+                            self.run_results_seen = self
+                                .domain
+                                .project_path
+                                .as_ref()
+                                .and_then(services::run_results_modified);
                             sample_output.parse_output();
                             if sample_output.status == RunStatus::Failed
                                 && sample_output.headers.is_empty()
@@ -439,6 +733,44 @@ impl Component for App {
                         }
                     }
                 }
+                // Columns for the YAML scaffold come from an empty dbt show
+                if self.yaml_scaffold_dialog.loading
+                    && matches!(self.modals.top(), Some(Modal::YamlScaffold))
+                {
+                    let finished = self
+                        .domain
+                        .sample_data_output
+                        .take_if(|o| o.status != RunStatus::Running);
+                    if let Some(output) = finished {
+                        self.sample_data_runner.clear();
+                        match output.error_message {
+                            Some(error) => self.yaml_scaffold_dialog.set_error(error),
+                            None => {
+                                let columns = output
+                                    .headers
+                                    .into_iter()
+                                    .map(|name| WarehouseColumn {
+                                        name,
+                                        data_type: None,
+                                    })
+                                    .collect();
+                                self.yaml_scaffold_dialog.set_columns(columns, "dbt show");
+                            }
+                        }
+                    }
+                }
+                // Import runs executed outside the TUI while no job is active here
+                let sample_running = self
+                    .domain
+                    .sample_data_output
+                    .as_ref()
+                    .is_some_and(|o| o.status == RunStatus::Running);
+                if self.mode == AppMode::Running
+                    && self.domain.run_output.is_none()
+                    && !sample_running
+                {
+                    self.import_external_run();
+                }
             }
             Action::SplashComplete => {
                 self.mode = self.next_mode_after_splash;
@@ -459,10 +791,18 @@ impl Component for App {
             Action::LastItem => self.home.select_last(&self.domain.all_nodes),
 
             // ─────────────────────────────────────────────────────────────────
-            // Scrolling (delegate to DetailComponent)
+            // Scrolling (delegate to the active dialog or DetailComponent)
             // ─────────────────────────────────────────────────────────────────
             Action::ScrollUp | Action::ScrollDown | Action::PageUp | Action::PageDown => {
-                self.detail.update(action)?;
+                if matches!(self.modals.top(), Some(Modal::RunOutput)) {
+                    self.run_output_dialog.update(action)?;
+                } else if matches!(self.modals.top(), Some(Modal::History { .. })) {
+                    self.history_dialog.update(action)?;
+                } else if matches!(self.modals.top(), Some(Modal::SampleData { .. })) {
+                    self.sample_data_dialog.update(action)?;
+                } else {
+                    self.detail.update(action)?;
+                }
             }
 
             // ─────────────────────────────────────────────────────────────────
@@ -470,11 +810,203 @@ impl Component for App {
             // ─────────────────────────────────────────────────────────────────
             Action::ToggleCodeView => self.home.toggle_code_view_mode(),
             Action::ToggleLineage => self.home.toggle_lineage(),
+            Action::OpenLineageExplorer => {
+                let Some(unique_id) = self.get_selected_node().map(|n| n.unique_id.clone()) else {
+                    return Ok(None);
+                };
+                let Some(ref graph) = self.domain.lineage_graph else {
+                    return Ok(None);
+                };
+                self.lineage_explorer.open(
+                    &unique_id,
+                    graph,
+                    &self.domain.all_nodes,
+                    &self.domain.sources,
+                );
+                self.modals.push(Modal::LineageExplorer);
+            }
+            Action::OpenDagView => {
+                let Some(unique_id) = self.get_selected_node().map(|n| n.unique_id.clone()) else {
+                    return Ok(None);
+                };
+                let Some(ref graph) = self.domain.lineage_graph else {
+                    return Ok(None);
+                };
+                self.dag_view.open(
+                    &unique_id,
+                    graph,
+                    &self.domain.all_nodes,
+                    &self.domain.sources,
+                    &self.domain.run_history,
+                );
+                self.modals.push(Modal::DagView);
+            }
+            Action::OpenLineageExport => {
+                if self.domain.lineage_graph.is_none() {
+                    return Ok(None);
+                }
+                let root = self
+                    .get_selected_node()
+                    .map(|n| (n.unique_id.clone(), n.name.clone()));
+                let selection = self.home.selected_nodes.iter().cloned().collect();
+                self.lineage_export_dialog.open(
+                    root,
+                    selection,
+                    self.domain.project_path.as_deref(),
+                );
+                self.modals.push(Modal::LineageExport);
+            }
+            Action::OpenImpactReport => {
+                let Some(root) = self.get_selected_node().cloned() else {
+                    return Ok(None);
+                };
+                let Some(ref graph) = self.domain.lineage_graph else {
+                    return Ok(None);
+                };
+                self.impact_dialog.open(ImpactReport::build(
+                    &root,
+                    graph,
+                    &self.domain.all_nodes,
+                    &self.domain.exposures,
+                    &self.domain.run_history,
+                ));
+                self.modals.push(Modal::Impact);
+            }
+            Action::OpenHealthAudit => {
+                let catalog = self
+                    .domain
+                    .project_path
+                    .as_ref()
+                    .and_then(|path| services::load_catalog(path).ok());
+                self.audit_dialog.open(HealthAudit::build(
+                    &self.domain.all_nodes,
+                    &self.domain.sources,
+                    catalog.as_ref(),
+                ));
+                self.modals.push(Modal::HealthAudit);
+            }
+            Action::OpenLint => {
+                let Some(project_path) = self.domain.project_path.clone() else {
+                    return Ok(None);
+                };
+                let result = services::load_lint_config(&project_path).and_then(|config| {
+                    lint(&config, &self.domain.all_nodes, &self.domain.yaml_index)
+                });
+                self.lint_dialog.open(result);
+                self.modals.push(Modal::Lint);
+            }
+            Action::OpenCruftReport => {
+                let Some(ref graph) = self.domain.lineage_graph else {
+                    return Ok(None);
+                };
+                self.cruft_dialog.open(CruftReport::build(
+                    &self.domain.all_nodes,
+                    &self.domain.sources,
+                    &self.domain.exposures,
+                    &self.domain.macros,
+                    graph,
+                    &self.domain.run_history,
+                ));
+                self.modals.push(Modal::Cruft);
+            }
+            Action::OpenTestMatrix => {
+                let Some(node) = self.get_selected_node() else {
+                    return Ok(None);
+                };
+                // On a test, show the matrix of the node it tests
+                let model = if node.resource_type == "test" {
+                    node.tested_node_id()
+                        .and_then(|id| self.domain.all_nodes.iter().find(|n| n.unique_id == id))
+                } else {
+                    Some(node)
+                };
+                let Some(model) = model else {
+                    self.status_message = Some(format!("{} isn't attached to a model", node.name));
+                    return Ok(None);
+                };
+                self.test_matrix_dialog.open(TestMatrix::build(
+                    model,
+                    &self.domain.all_nodes,
+                    &self.domain.run_history,
+                ));
+                self.modals.push(Modal::TestMatrix);
+            }
+            Action::BuildDownstream(name) => {
+                let Some(project_path) = self.domain.project_path.clone() else {
+                    return Ok(None);
+                };
+                self.modals.pop();
+                let (cmd, display_cmd) = services::build_dbt_command(
+                    &self.domain.dbt_binary_path,
+                    &project_path,
+                    DbtCommand::Build,
+                    Some(&RunSelectMode::Downstream.selector(&name)),
+                    &RunFlags::default(),
+                );
+                self.spawn_run(cmd, display_cmd);
+            }
+            Action::RevealNode(unique_id) => {
+                // Jumping from a full-screen report leaves it, as it covers the list
+                if matches!(
+                    self.modals.top(),
+                    Some(Modal::HealthAudit | Modal::Lint | Modal::Cruft | Modal::TestMatrix)
+                ) {
+                    self.modals.pop();
+                }
+                self.status_message = if self.home.reveal_node(&self.domain.all_nodes, &unique_id) {
+                    None
+                } else {
+                    Some(format!("{} isn't listed in the main view", unique_id))
+                };
+            }
             Action::ToggleDocumentation => self.home.toggle_documentation(),
+            Action::DocSelectNext => self.documentation.select_next(),
+            Action::DocSelectPrevious => self.documentation.select_previous(),
+            Action::OpenDescriptionEditor => {
+                let Some(node) = self.get_selected_node() else {
+                    return Ok(None);
+                };
+                let Some(item) = self.documentation.selected_item() else {
+                    return Ok(None);
+                };
+                let text = match item {
+                    None => node.description.clone(),
+                    Some(column) => node.columns.get(column).and_then(|c| c.description.clone()),
+                };
+                let target = DescriptionTarget {
+                    unique_id: node.unique_id.clone(),
+                    node_name: node.name.clone(),
+                    column: item.map(String::from),
+                };
+                self.description_editor
+                    .open(target, text.as_deref().unwrap_or(""));
+                self.modals.push(Modal::DescriptionEditor);
+            }
+            Action::OpenColumnTests => {
+                let Some(node) = self.get_selected_node() else {
+                    return Ok(None);
+                };
+                let Some(Some(column)) = self.documentation.selected_item() else {
+                    self.status_message = Some("Select a column in the docs panel with [ and ]".to_string());
+                    return Ok(None);
+                };
+                let node = node.clone();
+                let column = column.to_string();
+                self.column_tests_dialog
+                    .open(&node, &column, &self.domain.all_nodes);
+                self.modals.push(Modal::ColumnTests);
+            }
             Action::ToggleOutputView => {
                 if let Some(ref mut run_output) = self.domain.run_output {
                     run_output.toggle_view_mode();
                 }
+                self.run_output_dialog.scroll_offset = 0;
+            }
+            Action::ToggleTimelineView => {
+                if let Some(ref mut run_output) = self.domain.run_output {
+                    run_output.toggle_timeline_view();
+                }
+                self.run_output_dialog.scroll_offset = 0;
             }
 
             // ─────────────────────────────────────────────────────────────────
@@ -504,6 +1036,7 @@ impl Component for App {
                         selected_index: 0,
                         detail_scroll: 0,
                     });
+                    self.load_history_outputs();
                 }
             }
             Action::OpenTargetSelector => {
@@ -536,7 +1069,8 @@ impl Component for App {
             }
             Action::OpenRunOutput => {
                 // View selected history entry as run output
-                if let Some(entry) = self.domain.run_history.get(self.history_dialog.selected_index) {
+                let entry_index = self.history_dialog.selected_entry_index(&self.domain.run_history);
+                if let Some(entry) = entry_index.and_then(|i| self.domain.run_history.get(i)) {
                     let mut run_output = RunOutput::new(entry.command.clone());
                     run_output.status = entry.status;
                     run_output.output = entry.output.clone();
@@ -546,16 +1080,77 @@ impl Component for App {
                     }
                     // Compute layers based on manifest
                     run_output.compute_layers(&self.domain.all_nodes);
+                    run_output.invocation_id = entry.invocation_id.clone();
+                    run_output.set_node_results(entry.node_results.clone(), &self.domain.all_nodes);
                     self.domain.run_output = Some(run_output);
+                    self.run_output_dialog.reset();
                     self.modals.pop();
                     self.modals.push(Modal::RunOutput);
                 }
             }
+            Action::ToggleHistoryMark => {
+                self.history_dialog.toggle_mark(&self.domain.run_history);
+                self.load_history_outputs();
+            }
+            Action::OpenRunComparison => {
+                let ids = self
+                    .history_dialog
+                    .comparison_entries(&self.domain.run_history)
+                    .map(|(a, b)| [a.id.clone(), b.id.clone()]);
+                if let (Some(ids), Some(project_path)) = (ids, self.domain.project_path.clone()) {
+                    for entry in self.domain.run_history.iter_mut().filter(|e| ids.contains(&e.id)) {
+                        RunHistory::load_output(&project_path, entry);
+                    }
+                }
+                match self.history_dialog.comparison_entries(&self.domain.run_history) {
+                    Some((a, b)) => {
+                        let comparison = RunComparison::build(a, b, &self.domain.all_nodes);
+                        self.run_comparison_dialog.comparison = Some(comparison);
+                        self.run_comparison_dialog.scroll_offset = 0;
+                        self.modals.push(Modal::RunComparison);
+                    }
+                    None => {
+                        self.status_message =
+                            Some("Mark two runs with Space to compare them".to_string());
+                    }
+                }
+            }
+            Action::OpenExport => {
+                let summary = match self.modals.top() {
+                    Some(Modal::History { .. }) => self
+                        .history_dialog
+                        .selected_entry_index(&self.domain.run_history)
+                        .and_then(|i| self.domain.run_history.get(i))
+                        .map(RunSummary::from_history_entry),
+                    // Totals and duration aren't known until the run finishes
+                    _ if self
+                        .domain
+                        .run_output
+                        .as_ref()
+                        .is_some_and(|o| o.status == RunStatus::Running) =>
+                    {
+                        self.status_message =
+                            Some("Run still in progress; export it once it finishes".to_string());
+                        None
+                    }
+                    _ => self.domain.run_output.clone().map(|run_output| {
+                        let mut summary = RunSummary::from_run_output(&run_output);
+                        summary.target = self.config().map(|c| c.target.clone());
+                        summary
+                    }),
+                };
+                if let Some(summary) = summary {
+                    self.export_dialog
+                        .open(summary, self.domain.project_path.as_deref());
+                    self.modals.push(Modal::Export);
+                }
+            }
             Action::CloseModal => {
                 if matches!(self.modals.top(), Some(Modal::RunOutput)) {
                     // Check if this was a compile command that succeeded
                     let should_refresh = self.domain.run_output.as_ref().is_some_and(|o| {
-                        o.status == RunStatus::Success && o.command.contains("compile")
+                        o.status == RunStatus::Success
+                            && matches!(o.subcommand(), Some("compile" | "parse"))
                     });
 
                     self.save_to_history();
@@ -567,8 +1162,23 @@ impl Component for App {
                         self.refresh_manifest();
                         self.refresh_git_status();
                     }
+
+                    // Offer to run a test added from the TUI once parse has picked it up
+                    if let Some(added) = self.added_test.take() {
+                        let test = added.find(&self.domain.all_nodes).map(|n| n.name.clone());
+                        self.modals.pop();
+                        match test {
+                            Some(test_name) => self.modals.push(Modal::RunNewTest { test_name }),
+                            None if should_refresh => {
+                                self.status_message =
+                                    Some("New test not found in the manifest".to_string());
+                            }
+                            None => {}
+                        }
+                        return Ok(None);
+                    }
                 }
-                if matches!(self.modals.top(), Some(Modal::SampleData { .. })) {
+                if matches!(self.modals.top(), Some(Modal::SampleData { .. } | Modal::YamlScaffold)) {
                     self.domain.sample_data_output = None;
                     self.sample_data_runner.clear();
                 }
@@ -589,6 +1199,69 @@ impl Component for App {
                             // Execute the command directly
                             self.execute_dbt_command(command, mode, &flags);
                         }
+                        Modal::Export => {
+                            if let Some(ref summary) = self.export_dialog.summary {
+                                let path = self.export_dialog.path.trim();
+                                self.status_message = Some(
+                                    match services::export_run(summary, self.export_dialog.format, path) {
+                                        Ok(()) => format!("Exported run to {}", path),
+                                        Err(e) => format!("Export failed: {}", e),
+                                    },
+                                );
+                            }
+                            self.modals.pop();
+                        }
+                        Modal::LineageExport => {
+                            if let Some(ref graph) = self.domain.lineage_graph {
+                                let dialog = &self.lineage_export_dialog;
+                                let export = LineageExport::build(
+                                    &dialog.scope(),
+                                    dialog.options,
+                                    graph,
+                                    &self.domain.all_nodes,
+                                    &self.domain.sources,
+                                    &self.domain.exposures,
+                                );
+                                let path = dialog.path.trim();
+                                self.status_message = Some(
+                                    match services::export_lineage(&export, dialog.format, path) {
+                                        Ok(()) => format!(
+                                            "Exported {} nodes and {} edges to {}",
+                                            export.nodes.len(),
+                                            export.edge_count(),
+                                            path
+                                        ),
+                                        Err(e) => format!("Export failed: {}", e),
+                                    },
+                                );
+                            }
+                            self.modals.pop();
+                        }
+                        Modal::DescriptionEditor => {
+                            self.modals.pop();
+                            self.save_description();
+                        }
+                        Modal::ColumnTests => {
+                            self.modals.pop();
+                            if let Some(edit) = self.column_tests_dialog.pending.take() {
+                                self.apply_test_edit(edit);
+                            }
+                        }
+                        Modal::RunNewTest { test_name } => {
+                            self.modals.pop();
+                            self.run_test(&test_name);
+                        }
+                        Modal::YamlScaffold => {
+                            self.modals.pop();
+                            self.save_yaml_scaffold();
+                        }
+                        Modal::NewModelWizard => {
+                            if let Some(model) = self.new_model_wizard.result() {
+                                self.modals.pop();
+                                return Ok(self.create_model(model));
+                            }
+                        }
+                        Modal::Refactor => self.confirm_refactor(),
                         Modal::TargetSelector { .. } => {
                             let selected_target = self.target_selector.get_selected_target().to_string();
                             self.change_target(&selected_target);
@@ -607,6 +1280,7 @@ impl Component for App {
                     if let Some(Modal::History { selected_index, .. }) = self.modals.top_mut() {
                         *selected_index = self.history_dialog.selected_index;
                     }
+                    self.load_history_outputs();
                 } else if let Some(Modal::TargetSelector { selected_index }) = self.modals.top_mut() {
                     if *selected_index > 0 {
                         *selected_index -= 1;
@@ -620,13 +1294,18 @@ impl Component for App {
                     self.run_options_dialog.update(Action::ModalDown)?;
                 } else if matches!(self.modals.top(), Some(Modal::History { .. })) {
                     // Clamp before incrementing
-                    let max = self.domain.run_history.len().saturating_sub(1);
+                    let max = self
+                        .history_dialog
+                        .visible_indices(&self.domain.run_history)
+                        .len()
+                        .saturating_sub(1);
                     if self.history_dialog.selected_index < max {
                         self.history_dialog.update(Action::ModalDown)?;
                     }
                     if let Some(Modal::History { selected_index, .. }) = self.modals.top_mut() {
                         *selected_index = self.history_dialog.selected_index;
                     }
+                    self.load_history_outputs();
                 } else if let Some(Modal::TargetSelector { selected_index }) = self.modals.top_mut() {
                     let max = self.target_selector.targets.len().saturating_sub(1);
                     if *selected_index < max {
@@ -681,7 +1360,9 @@ impl Component for App {
             Action::SetupConfirm => {
                 // Setup complete, load the config and switch to Running mode
                 if let Some(config) = self.setup.get_config() {
-                    self.load_project_from_config(config.clone());
+                    let config = config.clone();
+                    self.config = Some(config.clone());
+                    self.load_project_from_config(config);
                     self.mode = AppMode::Running;
                 }
             }
@@ -701,13 +1382,14 @@ impl Component for App {
             // Editor Actions
             // ─────────────────────────────────────────────────────────────────
             Action::OpenEditor => {
-                // Set pending_editor_file - the main loop will handle launching $EDITOR
+                // Set pending_editor - the main loop will handle launching $EDITOR
                 if let Some(node) = self.get_selected_node() {
                     if let Some(ref path) = node.original_file_path {
                         if let Some(ref root) = self.domain.project_path {
                             let full_path = root.join(path);
                             if full_path.exists() {
-                                self.pending_editor_file = Some(full_path.to_string_lossy().to_string());
+                                self.pending_editor =
+                                    Some(EditorTarget::file(full_path.to_string_lossy().to_string()));
                             } else {
                                 self.error = Some(format!("File not found: {}", full_path.display()));
                             }
@@ -716,6 +1398,161 @@ impl Component for App {
                 }
             }
 
+            Action::OpenFileInEditor(target) => {
+                if matches!(self.modals.top(), Some(Modal::RelatedFiles)) {
+                    self.modals.pop();
+                }
+                if let Some(ref root) = self.domain.project_path {
+                    let full_path = root.join(&target.path);
+                    if full_path.exists() {
+                        self.pending_editor = Some(EditorTarget {
+                            path: full_path.to_string_lossy().to_string(),
+                            ..target
+                        });
+                    } else {
+                        self.status_message = Some(format!("File not found: {}", full_path.display()));
+                    }
+                }
+            }
+
+            Action::OpenRelatedFiles => {
+                let node = self.get_selected_node().cloned();
+                if let (Some(node), Some(root)) = (node, self.domain.project_path.as_deref()) {
+                    self.related_files_dialog
+                        .open(&node, root, &self.domain.yaml_index);
+                    self.modals.push(Modal::RelatedFiles);
+                }
+            }
+
+            Action::OpenYamlDefinition => {
+                let Some(node) = self.get_selected_node().cloned() else {
+                    return Ok(None);
+                };
+                let Some(entry) = self.domain.yaml_index.find_node(&node) else {
+                    self.status_message = Some(format!("No YAML definition found for {}", node.name));
+                    return Ok(None);
+                };
+                let target = EditorTarget::at_line(entry.file.clone(), Some(entry.line));
+
+                // With the documentation pane open, offer the node's columns too
+                let columns = self.domain.yaml_index.columns(&node.name);
+                if !self.home.show_documentation || columns.is_empty() {
+                    return Ok(Some(Action::OpenFileInEditor(target)));
+                }
+                let mut locations = vec![RelatedFile {
+                    label: node.name.clone(),
+                    target,
+                }];
+                locations.extend(columns.iter().map(|column| RelatedFile {
+                    label: format!("  {}", column.column.as_deref().unwrap_or("")),
+                    target: EditorTarget::at_line(column.file.clone(), Some(column.line)),
+                }));
+                self.related_files_dialog
+                    .open_locations(format!("YAML: {}", node.name), locations);
+                self.modals.push(Modal::RelatedFiles);
+            }
+            Action::OpenYamlScaffold => {
+                let Some(node) = self.get_selected_node().cloned() else {
+                    return Ok(None);
+                };
+                let Some(project_path) = self.domain.project_path.clone() else {
+                    return Ok(None);
+                };
+                if !matches!(node.resource_type.as_str(), "model" | "seed" | "snapshot") {
+                    self.status_message =
+                        Some("YAML can only be generated for models, seeds and snapshots".to_string());
+                    return Ok(None);
+                }
+                if let Some(entry) = self.domain.yaml_index.find_node(&node) {
+                    self.status_message = Some(format!(
+                        "{} is already declared in {}:{}",
+                        node.name, entry.file, entry.line
+                    ));
+                    return Ok(None);
+                }
+
+                let files = services::yaml_edit::scaffold_files(&project_path, &node);
+                self.yaml_scaffold_dialog
+                    .open(&node.unique_id, &node.name, files, &project_path);
+
+                // Prefer catalog.json; otherwise ask the warehouse with an empty dbt show
+                let catalog_columns = services::load_catalog(&project_path)
+                    .ok()
+                    .and_then(|catalog| catalog.columns(&node.unique_id))
+                    .filter(|columns| !columns.is_empty());
+                match catalog_columns {
+                    Some(columns) => self.yaml_scaffold_dialog.set_columns(columns, "catalog.json"),
+                    None => {
+                        let (cmd, display_cmd) = services::build_dbt_show_command(
+                            &self.domain.dbt_binary_path,
+                            &project_path,
+                            &node.name,
+                            0,
+                        );
+                        let run_output = self.sample_data_runner.spawn(cmd);
+                        let mut sample_output = SampleDataOutput::new(node.name.clone());
+                        sample_output.display_command = display_cmd;
+                        sample_output.raw_output = run_output.output;
+                        sample_output.status = run_output.status;
+                        self.domain.sample_data_output = Some(sample_output);
+                    }
+                }
+                self.modals.push(Modal::YamlScaffold);
+            }
+            Action::OpenNewModelWizard => {
+                let Some(project_path) = self.domain.project_path.clone() else {
+                    return Ok(None);
+                };
+                let catalog = services::load_catalog(&project_path).unwrap_or_default();
+                let sources = self
+                    .domain
+                    .sources
+                    .iter()
+                    .map(|source| {
+                        // Warehouse columns when catalogued, else those declared in YAML
+                        let columns = catalog.columns(&source.unique_id).unwrap_or_else(|| {
+                            let mut declared: Vec<WarehouseColumn> = source
+                                .columns
+                                .values()
+                                .map(|c| WarehouseColumn {
+                                    name: c.name.clone(),
+                                    data_type: c.data_type.clone(),
+                                })
+                                .collect();
+                            declared.sort_by(|a, b| a.name.cmp(&b.name));
+                            declared
+                        });
+                        StagingSource {
+                            source_name: source.source_name.clone(),
+                            table: source.name.clone(),
+                            columns,
+                        }
+                    })
+                    .collect();
+                let folders =
+                    services::model_template::model_folders(&project_path, &self.domain.all_nodes);
+                self.new_model_wizard.open(sources, folders, &project_path);
+                self.modals.push(Modal::NewModelWizard);
+            }
+            Action::OpenRefactor => {
+                let Some(node) = self.get_selected_node() else {
+                    return Ok(None);
+                };
+                if node.resource_type != "model" {
+                    self.status_message = Some("Only models can be renamed or moved".to_string());
+                    return Ok(None);
+                }
+                let folder = node
+                    .original_file_path
+                    .as_deref()
+                    .and_then(|p| Path::new(p).parent())
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                self.refactor_dialog
+                    .open(&node.unique_id.clone(), &node.name.clone(), &folder);
+                self.modals.push(Modal::Refactor);
+            }
+
             // ─────────────────────────────────────────────────────────────────
             // Sample Data Preview
             // ─────────────────────────────────────────────────────────────────
@@ -731,9 +1568,12 @@ impl Component for App {
                 });
 
                 if let Some((node_name, resource_type)) = node_info {
+                    if resource_type == "test" {
+                        return Ok(Some(Action::PreviewFailingRows(node_name)));
+                    }
                     if resource_type == "model" {
                         if let Some(ref project_path) = self.domain.project_path.clone() {
-                            let (cmd, _display_cmd) = services::build_dbt_show_command(
+                            let (cmd, display_cmd) = services::build_dbt_show_command(
                                 &self.domain.dbt_binary_path,
                                 project_path,
                                 &node_name,
@@ -745,21 +1585,63 @@ impl Component for App {
 
                             // Create SampleDataOutput
                             let mut sample_output = SampleDataOutput::new(node_name.clone());
+                            sample_output.display_command = display_cmd;
                             sample_output.raw_output = run_output.output.clone();
                             sample_output.status = run_output.status;
 
                             self.domain.sample_data_output = Some(sample_output);
+                            self.sample_data_dialog.scroll_offset = 0;
                             self.modals.push(Modal::SampleData {
                                 model_name: node_name,
                                 scroll_offset: 0,
                             });
                         }
                     } else {
-                        self.error = Some("Sample data preview is only available for models".to_string());
+                        self.error = Some("Sample data preview is only available for models and tests".to_string());
                     }
                 }
             }
 
+            Action::PreviewFailingRows(test_name) => {
+                let Some(project_path) = self.domain.project_path.clone() else {
+                    return Ok(None);
+                };
+                let Some(test) = self
+                    .domain
+                    .all_nodes
+                    .iter()
+                    .find(|n| n.resource_type == "test" && n.name == test_name)
+                else {
+                    self.status_message = Some(format!("Test {} not found in the manifest", test_name));
+                    return Ok(None);
+                };
+                let sql = match test.failing_rows_query(&project_path) {
+                    Ok(sql) => sql,
+                    Err(e) => {
+                        self.status_message = Some(e);
+                        return Ok(None);
+                    }
+                };
+                let (cmd, display_cmd) = services::build_dbt_show_inline_command(
+                    &self.domain.dbt_binary_path,
+                    &project_path,
+                    &sql,
+                    100,
+                );
+                let run_output = self.sample_data_runner.spawn(cmd);
+                let mut sample_output = SampleDataOutput::failing_rows(test_name.clone());
+                sample_output.display_command = display_cmd;
+                sample_output.raw_output = run_output.output;
+                sample_output.status = run_output.status;
+
+                self.domain.sample_data_output = Some(sample_output);
+                self.sample_data_dialog.scroll_offset = 0;
+                self.modals.push(Modal::SampleData {
+                    model_name: test_name,
+                    scroll_offset: 0,
+                });
+            }
+
             // ─────────────────────────────────────────────────────────────────
             // Git Actions
             // ─────────────────────────────────────────────────────────────────
@@ -825,6 +1707,8 @@ impl Component for App {
                     all_nodes: &self.domain.all_nodes,
                     project_name: self.domain.project_info.as_ref().map(|i| i.project_name.as_str()),
                     lineage_graph: self.domain.lineage_graph.as_ref(),
+                    yaml_index: &self.domain.yaml_index,
+                    column_lineage: &self.domain.column_lineage,
                     error: self.error.as_deref(),
                     status_message: self.status_message.as_deref(),
                     git_branch: self.git_branch.as_deref(),
@@ -863,7 +1747,11 @@ impl App {
             Modal::QuitConfirm => self.quit_dialog.handle_key_event(key),
             Modal::RunOptions { .. } => self.run_options_dialog.handle_key_event(key),
             Modal::ProjectInfo => self.project_info_dialog.handle_key_event(key),
-            Modal::History { .. } => self.history_dialog.handle_key_event(key),
+            Modal::History { .. } => {
+                let action = self.history_dialog.handle_key_event(key);
+                self.load_history_outputs();
+                action
+            }
             Modal::RunOutput => self.run_output_dialog.handle_key_event(key),
             Modal::TargetSelector { .. } => self.target_selector.handle_key_event(key),
             Modal::TagFilter { .. } => self.tag_filter_dialog.handle_key_event(key),
@@ -917,6 +1805,30 @@ impl App {
             }
             Modal::SampleData { .. } => self.sample_data_dialog.handle_key_event(key),
             Modal::Help { .. } => self.help_dialog.handle_key_event(key),
+            Modal::RunComparison => self.run_comparison_dialog.handle_key_event(key),
+            Modal::Export => self.export_dialog.handle_key_event(key),
+            Modal::RelatedFiles => self.related_files_dialog.handle_key_event(key),
+            Modal::DescriptionEditor => self.description_editor.handle_key_event(key),
+            Modal::ColumnTests => self.column_tests_dialog.handle_key_event(key),
+            Modal::YamlScaffold => self.yaml_scaffold_dialog.handle_key_event(key),
+            Modal::NewModelWizard => self.new_model_wizard.handle_key_event(key),
+            Modal::Refactor => self.refactor_dialog.handle_key_event(key),
+            Modal::LineageExplorer => self.lineage_explorer.handle_key_event(key),
+            Modal::DagView => self.dag_view.handle_key_event(key),
+            Modal::LineageExport => self.lineage_export_dialog.handle_key_event(key),
+            Modal::Impact => self.impact_dialog.handle_key_event(key),
+            Modal::HealthAudit => self.audit_dialog.handle_key_event(key),
+            Modal::Lint => self.lint_dialog.handle_key_event(key),
+            Modal::Cruft => self.cruft_dialog.handle_key_event(key),
+            Modal::TestMatrix => self.test_matrix_dialog.handle_key_event(key),
+            Modal::RunNewTest { .. } => {
+                let action = match key.code {
+                    KeyCode::Char('y') | KeyCode::Enter => Some(Action::ConfirmModal),
+                    KeyCode::Char('n') | KeyCode::Esc => Some(Action::CloseModal),
+                    _ => None,
+                };
+                Ok(action)
+            }
         }
     }
 
@@ -948,7 +1860,9 @@ impl App {
             }
             Modal::RunOutput => {
                 if let Some(ref run_output) = self.domain.run_output {
-                    self.run_output_dialog.draw_with_output(frame, area, run_output)?;
+                    let estimate = self.run_estimate(run_output);
+                    self.run_output_dialog
+                        .draw_with_output(frame, area, run_output, estimate.as_ref())?;
                 }
             }
             Modal::TargetSelector { .. } => {
@@ -974,6 +1888,57 @@ impl App {
             Modal::Help { .. } => {
                 self.help_dialog.draw(frame, area)?;
             }
+            Modal::RunComparison => {
+                self.run_comparison_dialog.draw(frame, area)?;
+            }
+            Modal::Export => {
+                self.export_dialog.draw(frame, area)?;
+            }
+            Modal::RelatedFiles => {
+                self.related_files_dialog.draw(frame, area)?;
+            }
+            Modal::DescriptionEditor => {
+                self.description_editor.draw(frame, area)?;
+            }
+            Modal::ColumnTests => {
+                self.column_tests_dialog.draw(frame, area)?;
+            }
+            Modal::RunNewTest { test_name } => {
+                self.draw_run_new_test(frame, area, test_name)?;
+            }
+            Modal::YamlScaffold => {
+                self.yaml_scaffold_dialog.draw(frame, area)?;
+            }
+            Modal::NewModelWizard => {
+                self.new_model_wizard.draw(frame, area)?;
+            }
+            Modal::Refactor => {
+                self.refactor_dialog.draw(frame, area)?;
+            }
+            Modal::LineageExplorer => {
+                self.lineage_explorer.draw(frame, area)?;
+            }
+            Modal::DagView => {
+                self.dag_view.draw(frame, area)?;
+            }
+            Modal::LineageExport => {
+                self.lineage_export_dialog.draw(frame, area)?;
+            }
+            Modal::Impact => {
+                self.impact_dialog.draw(frame, area)?;
+            }
+            Modal::HealthAudit => {
+                self.audit_dialog.draw(frame, area)?;
+            }
+            Modal::Lint => {
+                self.lint_dialog.draw(frame, area)?;
+            }
+            Modal::Cruft => {
+                self.cruft_dialog.draw(frame, area)?;
+            }
+            Modal::TestMatrix => {
+                self.test_matrix_dialog.draw(frame, area)?;
+            }
         }
         Ok(())
     }
@@ -983,6 +1948,7 @@ impl App {
         self.domain.dbt_binary_path = config.dbt_binary_path.clone();
         let project_path = PathBuf::from(&config.project_path);
         self.domain.project_path = Some(project_path.clone());
+        self.load_history();
 
         let manifest_path = project_path.join("target").join("manifest.json");
 
@@ -994,6 +1960,11 @@ impl App {
         match services::load_manifest(&manifest_path) {
             Ok(manifest) => {
                 self.domain.all_nodes = services::filter_nodes(&manifest);
+                self.domain.sources = services::list_sources(&manifest);
+                self.domain.exposures = services::list_exposures(&manifest);
+                self.domain.analyses = services::list_analyses(&manifest);
+                self.domain.macros = services::list_macros(&manifest);
+                self.migrate_legacy_history();
 
                 let root_path_str = project_path.to_string_lossy().to_string();
                 for node in &mut self.domain.all_nodes {
@@ -1001,6 +1972,10 @@ impl App {
                 }
 
                 self.domain.lineage_graph = Some(LineageGraph::build(&self.domain.all_nodes));
+                self.domain.column_lineage =
+                    ColumnLineage::build(&self.domain.all_nodes, &self.domain.sources);
+                self.domain.yaml_index =
+                    services::load_yaml_index(&project_path, &self.domain.all_nodes, &self.domain.sources);
 
                 if !self.domain.all_nodes.is_empty() {
                     self.home.select_first(&self.domain.all_nodes);
@@ -1046,6 +2021,10 @@ impl App {
                 let current_selection = self.get_selected_node().map(|n| n.unique_id.clone());
 
                 self.domain.all_nodes = services::filter_nodes(&manifest);
+                self.domain.sources = services::list_sources(&manifest);
+                self.domain.exposures = services::list_exposures(&manifest);
+                self.domain.analyses = services::list_analyses(&manifest);
+                self.domain.macros = services::list_macros(&manifest);
 
                 let root_path_str = project_path.to_string_lossy().to_string();
                 for node in &mut self.domain.all_nodes {
@@ -1053,17 +2032,23 @@ impl App {
                 }
 
                 self.domain.lineage_graph = Some(LineageGraph::build(&self.domain.all_nodes));
-
-                // Try to restore selection, or select first
-                if let Some(unique_id) = current_selection {
-                    let idx = self.domain.all_nodes.iter().position(|n| n.unique_id == unique_id);
-                    if idx.is_some() {
-                        // Selection still exists, rebuild display list
-                        self.home.select_first(&self.domain.all_nodes);
-                    } else {
-                        self.home.select_first(&self.domain.all_nodes);
-                    }
-                } else {
+                self.domain.column_lineage =
+                    ColumnLineage::build(&self.domain.all_nodes, &self.domain.sources);
+                self.domain.yaml_index =
+                    services::load_yaml_index(&project_path, &self.domain.all_nodes, &self.domain.sources);
+
+                // Select a model just created here, else restore the selection, else the first node
+                let new_model = self.select_after_refresh.take().and_then(|name| {
+                    self.domain
+                        .all_nodes
+                        .iter()
+                        .find(|n| n.resource_type == "model" && n.name == name)
+                        .map(|n| n.unique_id.clone())
+                });
+                let restored = new_model
+                    .or(current_selection)
+                    .is_some_and(|unique_id| self.home.select_node(&self.domain.all_nodes, &unique_id));
+                if !restored {
                     self.home.select_first(&self.domain.all_nodes);
                 }
 
@@ -1083,6 +2068,16 @@ impl App {
 
     /// Run dbt compile to generate manifest.json, then reload
     fn compile_manifest(&mut self) {
+        self.run_project_command(services::build_dbt_compile_command);
+    }
+
+    /// Run `dbt parse` to pick up schema YAML edits; the manifest is reloaded when the output is closed
+    fn parse_manifest(&mut self) {
+        self.run_project_command(services::build_dbt_parse_command);
+    }
+
+    /// Run a project-wide dbt command (compile, parse) in the run output modal
+    fn run_project_command(&mut self, build_command: fn(&str, &Path) -> (String, String)) {
         let project_path = match &self.domain.project_path {
             Some(p) => p.clone(),
             None => {
@@ -1091,22 +2086,225 @@ impl App {
             }
         };
 
-        // Build the compile command
         let (full_command, display_command) =
-            services::build_dbt_compile_command(&self.domain.dbt_binary_path, &project_path);
+            build_command(&self.domain.dbt_binary_path, &project_path);
 
         // Clear error and set status
         self.error = None;
         self.status_message = Some(format!("Running {}...", display_command));
 
-        // Spawn the compile command and track output
-        self.domain.run_output = Some(self.job_runner.spawn(full_command));
-        if let Some(ref mut output) = self.domain.run_output {
-            output.command = display_command;
+        // Spawn the command and show its progress
+        self.spawn_run(full_command, display_command);
+    }
+
+    /// Write the edited description back to schema YAML, then re-parse the project
+    fn save_description(&mut self) {
+        let Some(target) = self.description_editor.target.clone() else {
+            return;
+        };
+        let Some(project_path) = self.domain.project_path.clone() else {
+            return;
+        };
+        let Some(node) = self
+            .domain
+            .all_nodes
+            .iter_mut()
+            .find(|n| n.unique_id == target.unique_id)
+        else {
+            return;
+        };
+        let text = self.description_editor.text();
+
+        match services::yaml_edit::write_description(
+            &project_path,
+            &self.domain.yaml_index,
+            node,
+            target.column.as_deref(),
+            &text,
+        ) {
+            Ok(file) => {
+                // Show the change right away; dbt parse refreshes the manifest
+                match target.column {
+                    None => node.description = Some(text),
+                    Some(ref column) => {
+                        if let Some(info) = node.columns.get_mut(column) {
+                            info.description = Some(text);
+                        }
+                    }
+                }
+                self.domain.yaml_index =
+                    services::load_yaml_index(&project_path, &self.domain.all_nodes, &self.domain.sources);
+                self.parse_manifest();
+                self.status_message = Some(format!("Description saved to {}", file));
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to save description: {}", e));
+            }
         }
+    }
 
-        // Show the run output modal so user can see progress
-        self.modals.push(Modal::RunOutput);
+    /// Write a test added or removed in the column tests dialog to schema YAML, then re-parse
+    fn apply_test_edit(&mut self, edit: TestEdit) {
+        let Some(project_path) = self.domain.project_path.clone() else {
+            return;
+        };
+        let node_id = self.column_tests_dialog.node_id.clone();
+        let column = self.column_tests_dialog.column.clone();
+        let Some(node) = self.domain.all_nodes.iter().find(|n| n.unique_id == node_id) else {
+            return;
+        };
+
+        let result = match edit {
+            TestEdit::Add(ref test) => services::yaml_edit::add_column_test(
+                &project_path,
+                &self.domain.yaml_index,
+                node,
+                &column,
+                test,
+            )
+            .map(|file| {
+                let message = format!("Added {} test on {}.{} in {}", test.name(), node.name, column, file);
+                self.added_test = Some(AddedTest::new(&node.unique_id, &column, test, &self.domain.all_nodes));
+                message
+            }),
+            TestEdit::Remove { ref unique_id } => {
+                match self.domain.all_nodes.iter().find(|n| &n.unique_id == unique_id) {
+                    Some(test) => services::yaml_edit::remove_test(&project_path, &self.domain.yaml_index, test)
+                        .map(|file| format!("Removed {} from {}", test.name, file)),
+                    None => Err("Test no longer exists".to_string()),
+                }
+            }
+        };
+
+        match result {
+            Ok(message) => {
+                self.domain.yaml_index =
+                    services::load_yaml_index(&project_path, &self.domain.all_nodes, &self.domain.sources);
+                self.parse_manifest();
+                self.status_message = Some(message);
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to update tests: {}", e));
+            }
+        }
+    }
+
+    /// Append the generated YAML scaffold to the chosen file, then re-parse
+    fn save_yaml_scaffold(&mut self) {
+        let Some(project_path) = self.domain.project_path.clone() else {
+            return;
+        };
+        let Some(file) = self.yaml_scaffold_dialog.selected_file().map(String::from) else {
+            return;
+        };
+        let node_id = &self.yaml_scaffold_dialog.node_id;
+        let Some(node) = self.domain.all_nodes.iter().find(|n| &n.unique_id == node_id) else {
+            return;
+        };
+
+        match services::yaml_edit::append_scaffold(
+            &project_path,
+            &self.domain.yaml_index,
+            node,
+            &file,
+            &self.yaml_scaffold_dialog.columns,
+        ) {
+            Ok(()) => {
+                let message = format!("Appended {} to {}", node.name, file);
+                self.domain.yaml_index =
+                    services::load_yaml_index(&project_path, &self.domain.all_nodes, &self.domain.sources);
+                self.parse_manifest();
+                self.status_message = Some(message);
+            }
+            Err(e) => {
+                self.status_message = Some(format!("Failed to write YAML: {}", e));
+            }
+        }
+    }
+
+    /// Write a model from the wizard and its YAML stub, then compile so it
+    /// shows up in the list. Returns the action opening it in the editor.
+    fn create_model(&mut self, model: NewModel) -> Option<Action> {
+        let project_path = self.domain.project_path.clone()?;
+        let file = match services::model_template::create_model_file(
+            &project_path,
+            &self.domain.all_nodes,
+            &model.folder,
+            &model.name,
+            &model.sql,
+        ) {
+            Ok(file) => file,
+            Err(e) => {
+                self.status_message = Some(format!("Failed to create model: {}", e));
+                return None;
+            }
+        };
+
+        let columns = model
+            .source
+            .as_ref()
+            .map(|source| source.renamed_columns())
+            .unwrap_or_default();
+        self.status_message = Some(
+            match services::yaml_edit::append_entry(
+                &project_path,
+                &model.yaml_file,
+                "models",
+                &model.name,
+                &columns,
+            ) {
+                Ok(()) => format!("Created {} and its entry in {}", file, model.yaml_file),
+                Err(e) => format!("Created {}, but the YAML stub failed: {}", file, e),
+            },
+        );
+
+        self.home.active_tab = Tab::Models;
+        self.select_after_refresh = Some(model.name);
+        self.compile_manifest();
+        Some(Action::OpenFileInEditor(EditorTarget::file(file)))
+    }
+
+    /// First confirmation builds the rename/move preview, the second applies it
+    fn confirm_refactor(&mut self) {
+        let Some(project_path) = self.domain.project_path.clone() else {
+            return;
+        };
+
+        if let Some(plan) = self.refactor_dialog.plan.take() {
+            self.modals.pop();
+            match services::refactor::apply_plan(&project_path, &plan) {
+                Ok(()) => {
+                    self.select_after_refresh = Some(plan.new_name.clone());
+                    self.parse_manifest();
+                    self.refresh_git_status();
+                    self.status_message = Some(format!(
+                        "Refactored {} → {} ({} files updated)",
+                        plan.old_name,
+                        plan.to,
+                        plan.edits.len()
+                    ));
+                }
+                Err(e) => {
+                    self.status_message = Some(format!("Refactoring failed: {}", e));
+                }
+            }
+            return;
+        }
+
+        let node_id = &self.refactor_dialog.node_id;
+        let Some(node) = self.domain.all_nodes.iter().find(|n| &n.unique_id == node_id) else {
+            return;
+        };
+        match services::refactor::plan_rename(
+            &project_path,
+            node,
+            self.refactor_dialog.name.trim(),
+            self.refactor_dialog.folder.trim(),
+            &self.domain,
+        ) {
+            Ok(plan) => self.refactor_dialog.set_plan(plan),
+            Err(e) => self.refactor_dialog.set_error(e),
+        }
     }
 
     /// Handle key events when in error state (e.g., missing manifest)
@@ -1255,6 +2453,46 @@ impl App {
         Ok(())
     }
 
+    /// Draw the prompt to run a newly added test
+    fn draw_run_new_test(&self, frame: &mut Frame, area: Rect, test_name: &str) -> Result<()> {
+        use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+        use ratatui::style::{Color, Modifier, Style};
+        use ratatui::text::{Line, Span};
+        use crate::components::centered_popup;
+
+        let popup_area = centered_popup(area, 70.min(area.width.saturating_sub(4)), 8);
+        frame.render_widget(Clear, popup_area);
+
+        let content = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "Run the new test now?",
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(test_name.to_string(), Style::default().fg(Color::Cyan))),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(" y ", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+                Span::raw("Run test  "),
+                Span::styled(" n ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::raw("Skip"),
+            ]),
+        ];
+
+        let paragraph = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(" Test Added ")
+                    .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            )
+            .alignment(ratatui::layout::Alignment::Center);
+
+        frame.render_widget(paragraph, popup_area);
+        Ok(())
+    }
+
     /// Draw git log modal
     fn draw_git_log(&self, frame: &mut Frame, area: Rect, scroll_offset: usize) -> Result<()> {
         use ratatui::widgets::{Block, Borders, Clear, Paragraph};
@@ -0,0 +1,351 @@
+//! Project health audit dialog component
+//!
+//! The overview lists each kind of finding with its count, above a table
+//! of documentation, primary key test and owner coverage per folder or
+//! schema. Enter drills into the models or sources behind a finding.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::model::audit::{percent, AuditGrouping, Coverage, FindingKind, HealthAudit};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Health audit dialog
+#[derive(Default)]
+pub struct AuditDialog {
+    audit: HealthAudit,
+    grouping: AuditGrouping,
+    /// Selected finding kind in the overview
+    selected_kind: usize,
+    /// Finding kind being drilled into
+    drill: Option<FindingKind>,
+    selected_finding: usize,
+    scroll_offset: usize,
+}
+
+impl AuditDialog {
+    pub fn open(&mut self, audit: HealthAudit) {
+        *self = Self {
+            audit,
+            grouping: self.grouping,
+            ..Self::default()
+        };
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let (selected, len) = match self.drill {
+            Some(kind) => (&mut self.selected_finding, self.audit.findings_of(kind).len()),
+            None => (&mut self.selected_kind, FindingKind::ALL.len()),
+        };
+        if len == 0 {
+            return;
+        }
+        *selected = selected.saturating_add_signed(delta).min(len - 1);
+    }
+
+    fn overview_lines(&self) -> (Vec<Line<'static>>, usize) {
+        let mut lines = vec![section("Findings")];
+        let mut selected_line = 0;
+        for (i, kind) in FindingKind::ALL.iter().enumerate() {
+            let count = self.audit.findings_of(*kind).len();
+            let selected = i == self.selected_kind;
+            if selected {
+                selected_line = lines.len();
+            }
+            let (marker, style) = if selected {
+                (
+                    "▶",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                (" ", Style::default().fg(Color::White))
+            };
+            let count_style = if count == 0 {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::Red)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {} {:<40}", marker, kind.title()), style),
+                Span::styled(format!("{:>5}", count), count_style),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        let heading = match self.grouping {
+            AuditGrouping::Folder => "Coverage by folder",
+            AuditGrouping::Schema => "Coverage by schema",
+        };
+        lines.push(section(heading));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "   {:<40} {:>6} {:>9} {:>9} {:>9} {:>9}",
+                "", "Models", "Described", "Columns", "PK tests", "Owners"
+            ),
+            Style::default().fg(Color::DarkGray),
+        )));
+        for coverage in self.audit.coverage(self.grouping) {
+            lines.push(coverage_line(coverage, false));
+        }
+        lines.push(coverage_line(&self.audit.total, true));
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("   Source freshness  ", Style::default().fg(Color::DarkGray)),
+            percent_span(percent(self.audit.fresh_sources, self.audit.sources)),
+            Span::styled(
+                format!(
+                    "  ({} of {} tables)",
+                    self.audit.fresh_sources, self.audit.sources
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        (lines, selected_line)
+    }
+
+    fn drill_lines(&self, kind: FindingKind) -> (Vec<Line<'static>>, usize) {
+        let findings = self.audit.findings_of(kind);
+        let mut lines = vec![section(&format!("{} ({})", kind.title(), findings.len()))];
+        if findings.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "   Nothing to fix",
+                Style::default().fg(Color::Green),
+            )));
+        }
+        let mut selected_line = 0;
+        for (i, finding) in findings.iter().enumerate() {
+            let selected = i == self.selected_finding;
+            if selected {
+                selected_line = lines.len();
+            }
+            let (marker, style) = if selected {
+                (
+                    "▶",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                (" ", Style::default().fg(Color::White))
+            };
+            let mut spans = vec![
+                Span::styled(format!(" {} {:<40} ", marker, finding.name), style),
+                Span::styled(
+                    format!("{:<30}", finding.group),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ];
+            if let Some(ref detail) = finding.detail {
+                spans.push(Span::styled(
+                    format!(" {}", detail),
+                    Style::default().fg(Color::Cyan),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+        (lines, selected_line)
+    }
+}
+
+impl Component for AuditDialog {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match (self.drill, key.code) {
+            (None, KeyCode::Esc | KeyCode::Char('q')) => Some(Action::CloseModal),
+            (Some(_), KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') | KeyCode::Left) => {
+                self.drill = None;
+                self.scroll_offset = 0;
+                None
+            }
+            (_, KeyCode::Char('j') | KeyCode::Down) => {
+                self.move_selection(1);
+                None
+            }
+            (_, KeyCode::Char('k') | KeyCode::Up) => {
+                self.move_selection(-1);
+                None
+            }
+            (Some(_), KeyCode::PageDown) => {
+                self.move_selection(20);
+                None
+            }
+            (Some(_), KeyCode::PageUp) => {
+                self.move_selection(-20);
+                None
+            }
+            // The overview scrolls freely, for long coverage tables
+            (None, KeyCode::PageDown) => {
+                self.scroll_offset = self.scroll_offset.saturating_add(10);
+                None
+            }
+            (None, KeyCode::PageUp) => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(10);
+                None
+            }
+            (None, KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right) => {
+                self.drill = Some(FindingKind::ALL[self.selected_kind]);
+                self.selected_finding = 0;
+                self.scroll_offset = 0;
+                None
+            }
+            (None, KeyCode::Char('s')) => {
+                self.grouping = match self.grouping {
+                    AuditGrouping::Folder => AuditGrouping::Schema,
+                    AuditGrouping::Schema => AuditGrouping::Folder,
+                };
+                None
+            }
+            (Some(kind), KeyCode::Enter) => self
+                .audit
+                .findings_of(kind)
+                .get(self.selected_finding)
+                .map(|f| Action::RevealNode(f.unique_id.clone())),
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        frame.render_widget(Clear, area);
+
+        let margin = 2;
+        let overlay_area = Rect::new(
+            margin,
+            margin,
+            area.width.saturating_sub(margin * 2),
+            area.height.saturating_sub(margin * 2),
+        );
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(3)])
+            .split(overlay_area);
+
+        let (content, selected_line) = match self.drill {
+            Some(kind) => self.drill_lines(kind),
+            None => self.overview_lines(),
+        };
+
+        let visible_height = chunks[0].height.saturating_sub(2) as usize;
+        if self.drill.is_some() {
+            // Keep the selected row visible
+            if selected_line < self.scroll_offset {
+                self.scroll_offset = selected_line.saturating_sub(1);
+            } else if visible_height > 0 && selected_line >= self.scroll_offset + visible_height {
+                self.scroll_offset = selected_line + 1 - visible_height;
+            }
+        } else {
+            self.scroll_offset = self
+                .scroll_offset
+                .min(content.len().saturating_sub(visible_height));
+        }
+
+        let paragraph = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Magenta))
+                    .title(" Project Health ")
+                    .title_style(
+                        Style::default()
+                            .fg(Color::Magenta)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .scroll((self.scroll_offset as u16, 0));
+        frame.render_widget(paragraph, chunks[0]);
+
+        let key_style = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+        let mut help = vec![
+            Span::styled(
+                " Esc ",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(if self.drill.is_some() { "Back  " } else { "Close  " }),
+            Span::styled(" j/k ", key_style),
+            Span::raw("Select  "),
+        ];
+        if self.drill.is_some() {
+            help.push(Span::styled(" Enter ", key_style));
+            help.push(Span::raw("Show in list"));
+        } else {
+            help.push(Span::styled(" Enter ", key_style));
+            help.push(Span::raw("Drill down  "));
+            help.push(Span::styled(" s ", key_style));
+            help.push(Span::raw("Folder/schema"));
+        }
+        let help = Paragraph::new(Line::from(help))
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(help, chunks[1]);
+
+        Ok(())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helper functions
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn section(title: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        format!(" {}", title),
+        Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD),
+    ))
+}
+
+fn coverage_line(coverage: &Coverage, total: bool) -> Line<'static> {
+    let group = if coverage.group.is_empty() {
+        "(no folder)".to_string()
+    } else {
+        coverage.group.clone()
+    };
+    let name_style = if total {
+        Style::default().add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+    Line::from(vec![
+        Span::styled(format!("   {:<40} ", group), name_style),
+        Span::raw(format!("{:>6} ", coverage.models)),
+        percent_span(percent(coverage.described, coverage.models)),
+        Span::raw(" "),
+        percent_span(percent(coverage.documented_columns, coverage.columns)),
+        Span::raw(" "),
+        percent_span(percent(coverage.primary_keys, coverage.models)),
+        Span::raw(" "),
+        percent_span(percent(coverage.owned, coverage.models)),
+    ])
+}
+
+/// Right-aligned percentage, red below 50%, yellow below 80%
+fn percent_span(value: Option<f64>) -> Span<'static> {
+    match value {
+        None => Span::styled(format!("{:>9}", "-"), Style::default().fg(Color::DarkGray)),
+        Some(value) => {
+            let color = if value < 50.0 {
+                Color::Red
+            } else if value < 80.0 {
+                Color::Yellow
+            } else {
+                Color::Green
+            };
+            Span::styled(format!("{:>8.0}%", value), Style::default().fg(color))
+        }
+    }
+}
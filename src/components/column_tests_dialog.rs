@@ -0,0 +1,474 @@
+//! Column tests dialog component
+//!
+//! Adds a generic test (unique, not_null, accepted_values, relationships)
+//! to a documented column, or removes one of its existing tests. The App
+//! writes the chosen edit to the model's schema YAML.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::components::centered_popup;
+use crate::model::Node;
+use crate::services::yaml_edit::GenericTest;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::collections::HashSet;
+
+/// The edit chosen in the dialog
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestEdit {
+    Add(GenericTest),
+    Remove { unique_id: String },
+}
+
+/// A test written to YAML, waiting for `dbt parse` to give it a node
+#[derive(Debug, Clone)]
+pub struct AddedTest {
+    pub node_id: String,
+    pub column: String,
+    pub test_name: &'static str,
+    /// Tests that existed before the edit
+    pub known_ids: HashSet<String>,
+}
+
+impl AddedTest {
+    pub fn new(node_id: &str, column: &str, test: &GenericTest, all_nodes: &[Node]) -> Self {
+        Self {
+            node_id: node_id.to_string(),
+            column: column.to_string(),
+            test_name: test.name(),
+            known_ids: all_nodes
+                .iter()
+                .filter(|n| n.resource_type == "test")
+                .map(|n| n.unique_id.clone())
+                .collect(),
+        }
+    }
+
+    /// The new test node in a reloaded manifest
+    pub fn find<'a>(&self, all_nodes: &'a [Node]) -> Option<&'a Node> {
+        all_nodes.iter().find(|n| {
+            n.resource_type == "test"
+                && !self.known_ids.contains(&n.unique_id)
+                && n.tested_node_id() == Some(self.node_id.as_str())
+                && n.column_name
+                    .as_deref()
+                    .is_some_and(|c| c.eq_ignore_ascii_case(&self.column))
+                && n.test_metadata.as_ref().is_some_and(|m| m.name == self.test_name)
+        })
+    }
+}
+
+/// An existing test on the column
+#[derive(Debug, Clone)]
+struct ExistingTest {
+    unique_id: String,
+    label: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum Step {
+    #[default]
+    Menu,
+    /// Comma separated accepted values
+    Values,
+    /// Model the relationship points to
+    RefPicker,
+    /// Column in the referenced model
+    Field,
+}
+
+const ADD_OPTIONS: [&str; 4] = ["unique", "not_null", "accepted_values", "relationships"];
+
+/// Column tests dialog
+#[derive(Default)]
+pub struct ColumnTestsDialog {
+    pub node_id: String,
+    pub column: String,
+    node_name: String,
+    existing: Vec<ExistingTest>,
+    models: Vec<String>,
+    step: Step,
+    selected: usize,
+    input: String,
+    ref_model: String,
+    pub pending: Option<TestEdit>,
+}
+
+impl ColumnTestsDialog {
+    pub fn open(&mut self, node: &Node, column: &str, all_nodes: &[Node]) {
+        self.node_id = node.unique_id.clone();
+        self.node_name = node.name.clone();
+        self.column = column.to_string();
+        self.existing = all_nodes
+            .iter()
+            .filter(|n| {
+                n.resource_type == "test"
+                    && n.tested_node_id() == Some(node.unique_id.as_str())
+                    && n.column_name
+                        .as_deref()
+                        .is_some_and(|c| c.eq_ignore_ascii_case(column))
+            })
+            .map(|n| ExistingTest {
+                unique_id: n.unique_id.clone(),
+                label: n
+                    .test_metadata
+                    .as_ref()
+                    .map(|m| m.name.clone())
+                    .unwrap_or_else(|| n.name.clone()),
+            })
+            .collect();
+        self.models = all_nodes
+            .iter()
+            .filter(|n| matches!(n.resource_type.as_str(), "model" | "seed" | "snapshot"))
+            .map(|n| n.name.clone())
+            .collect();
+        self.models.sort();
+        self.models.dedup();
+        self.step = Step::Menu;
+        self.selected = 0;
+        self.input.clear();
+        self.ref_model.clear();
+        self.pending = None;
+    }
+
+    fn menu_len(&self) -> usize {
+        ADD_OPTIONS.len() + self.existing.len()
+    }
+
+    fn filtered_models(&self) -> Vec<&str> {
+        let filter = self.input.to_lowercase();
+        self.models
+            .iter()
+            .filter(|m| m.to_lowercase().contains(&filter))
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn confirm(&mut self, edit: TestEdit) -> Option<Action> {
+        self.pending = Some(edit);
+        Some(Action::ConfirmModal)
+    }
+
+    fn choose_menu_item(&mut self) -> Option<Action> {
+        match self.selected {
+            0 => self.confirm(TestEdit::Add(GenericTest::Unique)),
+            1 => self.confirm(TestEdit::Add(GenericTest::NotNull)),
+            2 => {
+                self.step = Step::Values;
+                self.input.clear();
+                None
+            }
+            3 => {
+                self.step = Step::RefPicker;
+                self.input.clear();
+                self.selected = 0;
+                None
+            }
+            i => {
+                let test = self.existing.get(i - ADD_OPTIONS.len())?;
+                let unique_id = test.unique_id.clone();
+                self.confirm(TestEdit::Remove { unique_id })
+            }
+        }
+    }
+
+    fn back_to_menu(&mut self) {
+        self.step = Step::Menu;
+        self.selected = 0;
+        self.input.clear();
+    }
+
+    fn handle_menu_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(Action::CloseModal),
+            KeyCode::Enter => self.choose_menu_item(),
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.selected + 1 < self.menu_len() {
+                    self.selected += 1;
+                }
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Keys for the text prompts; Enter is handled by the caller
+    fn edit_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            _ => {}
+        }
+    }
+
+    fn handle_values_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc => {
+                self.back_to_menu();
+                None
+            }
+            KeyCode::Enter => {
+                let values: Vec<String> = self
+                    .input
+                    .split(',')
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty())
+                    .collect();
+                if values.is_empty() {
+                    return None;
+                }
+                self.confirm(TestEdit::Add(GenericTest::AcceptedValues(values)))
+            }
+            _ => {
+                self.edit_input(key);
+                None
+            }
+        }
+    }
+
+    fn handle_ref_picker_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc => self.back_to_menu(),
+            KeyCode::Enter => {
+                if let Some(model) = self.filtered_models().get(self.selected) {
+                    self.ref_model = model.to_string();
+                    self.input = self.column.clone();
+                    self.step = Step::Field;
+                }
+            }
+            KeyCode::Down => {
+                if self.selected + 1 < self.filtered_models().len() {
+                    self.selected += 1;
+                }
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            _ => {
+                self.edit_input(key);
+                self.selected = 0;
+            }
+        }
+        None
+    }
+
+    fn handle_field_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc => {
+                self.step = Step::RefPicker;
+                self.input.clear();
+                self.selected = 0;
+                None
+            }
+            KeyCode::Enter => {
+                let field = self.input.trim().to_string();
+                if field.is_empty() {
+                    return None;
+                }
+                let to = self.ref_model.clone();
+                self.confirm(TestEdit::Add(GenericTest::Relationships { to, field }))
+            }
+            _ => {
+                self.edit_input(key);
+                None
+            }
+        }
+    }
+
+    fn menu_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::from(Span::styled(
+            " Add test",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ))];
+        let item = |i: usize, text: String| {
+            let selected = i == self.selected;
+            let (marker, style) = if selected {
+                (
+                    "▶",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                (" ", Style::default().fg(Color::White))
+            };
+            Line::from(Span::styled(format!(" {} {}", marker, text), style))
+        };
+        for (i, option) in ADD_OPTIONS.iter().enumerate() {
+            lines.push(item(i, option.to_string()));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            " Remove test",
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )));
+        if self.existing.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "   No tests on this column",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        for (i, test) in self.existing.iter().enumerate() {
+            lines.push(item(ADD_OPTIONS.len() + i, test.label.clone()));
+        }
+        lines
+    }
+
+    fn prompt_lines(&self, prompt: &str) -> Vec<Line<'static>> {
+        vec![
+            Line::from(Span::styled(
+                format!(" {}", prompt),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(" > {}_", self.input),
+                Style::default().fg(Color::Cyan),
+            )),
+        ]
+    }
+
+    fn ref_picker_lines(&self, visible_rows: usize) -> Vec<Line<'static>> {
+        let mut lines = self.prompt_lines("Model to reference (type to filter):");
+        lines.push(Line::from(""));
+        let models = self.filtered_models();
+        if models.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "   No matching models",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        let first_row = (self.selected + 1).saturating_sub(visible_rows);
+        for (i, model) in models.iter().enumerate().skip(first_row).take(visible_rows) {
+            let style = if i == self.selected {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let marker = if i == self.selected { "▶" } else { " " };
+            lines.push(Line::from(Span::styled(format!(" {} {}", marker, model), style)));
+        }
+        lines
+    }
+}
+
+impl Component for ColumnTestsDialog {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match self.step {
+            Step::Menu => self.handle_menu_key(key),
+            Step::Values => self.handle_values_key(key),
+            Step::RefPicker => self.handle_ref_picker_key(key),
+            Step::Field => self.handle_field_key(key),
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let popup_width = 64.min(area.width.saturating_sub(4));
+        let popup_height = 20.min(area.height.saturating_sub(2));
+        let popup_area = centered_popup(area, popup_width, popup_height);
+
+        frame.render_widget(Clear, popup_area);
+
+        let mut content = match self.step {
+            Step::Menu => self.menu_lines(),
+            Step::Values => {
+                self.prompt_lines("Accepted values, separated by commas:")
+            }
+            Step::RefPicker => {
+                let visible_rows = (popup_area.height as usize).saturating_sub(9).max(1);
+                self.ref_picker_lines(visible_rows)
+            }
+            Step::Field => self.prompt_lines(&format!(
+                "Column in {} that {} references:",
+                self.ref_model, self.column
+            )),
+        };
+        content.push(Line::from(""));
+
+        let (enter_label, esc_label) = match self.step {
+            Step::Menu => ("Select  ", "Close"),
+            Step::RefPicker => ("Choose  ", "Back"),
+            Step::Values | Step::Field => ("Add test  ", "Back"),
+        };
+        content.push(Line::from(vec![
+            Span::styled(
+                " Enter ",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(enter_label),
+            Span::styled(
+                " Esc ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(esc_label),
+        ]));
+
+        let paragraph = Paragraph::new(content).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(format!(" Tests: {}.{} ", self.node_name, self.column))
+                .title_style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+        );
+
+        frame.render_widget(paragraph, popup_area);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::node::TestMetadata;
+
+    fn column_test(name: &str, tested: &str, column: &str) -> Node {
+        Node {
+            attached_node: Some(tested.to_string()),
+            column_name: Some(column.to_string()),
+            ..Node::fixture(name, "test", &[tested])
+        }
+    }
+
+    #[test]
+    fn test_seed_column_tests_are_listed_and_found() {
+        let seed = Node::fixture("countries", "seed", &[]);
+        let existing = column_test("unique_countries_code", "seed.shop.countries", "code");
+        let mut all_nodes = vec![seed.clone(), existing];
+
+        let mut dialog = ColumnTestsDialog::default();
+        dialog.open(&seed, "code", &all_nodes);
+        assert_eq!(dialog.existing.len(), 1);
+        assert_eq!(dialog.existing[0].unique_id, "test.shop.unique_countries_code");
+
+        let added = AddedTest::new(&seed.unique_id, "code", &GenericTest::NotNull, &all_nodes);
+        let mut new_test = column_test("not_null_countries_code", "seed.shop.countries", "code");
+        new_test.test_metadata = Some(TestMetadata {
+            name: "not_null".to_string(),
+            ..TestMetadata::default()
+        });
+        all_nodes.push(new_test);
+
+        let found = added.find(&all_nodes).map(|n| n.unique_id.as_str());
+        assert_eq!(found, Some("test.shop.not_null_countries_code"));
+    }
+}
@@ -0,0 +1,314 @@
+//! Dead and orphaned resource dialog component
+//!
+//! The overview lists each kind of pruning candidate with its count.
+//! Enter drills into the models, sources or macros behind it; `+`/`-`
+//! change how many days without a run make a model stale.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::model::cruft::{CruftItem, CruftKind, CruftReport, DEFAULT_STALE_DAYS};
+use crate::services::editor::EditorTarget;
+use anyhow::Result;
+use chrono::Local;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Dead and orphaned resource dialog
+pub struct CruftDialog {
+    report: CruftReport,
+    stale_days: i64,
+    /// Selected kind in the overview
+    selected_kind: usize,
+    /// Kind being drilled into
+    drill: Option<CruftKind>,
+    selected_item: usize,
+    scroll_offset: usize,
+}
+
+impl Default for CruftDialog {
+    fn default() -> Self {
+        Self {
+            report: CruftReport::default(),
+            stale_days: DEFAULT_STALE_DAYS,
+            selected_kind: 0,
+            drill: None,
+            selected_item: 0,
+            scroll_offset: 0,
+        }
+    }
+}
+
+impl CruftDialog {
+    pub fn open(&mut self, report: CruftReport) {
+        *self = Self {
+            report,
+            stale_days: self.stale_days,
+            ..Self::default()
+        };
+    }
+
+    fn items(&self, kind: CruftKind) -> Vec<&CruftItem> {
+        self.report.items(kind, self.stale_days, Local::now())
+    }
+
+    fn selected(&self) -> Option<&CruftItem> {
+        let kind = self.drill?;
+        self.items(kind).get(self.selected_item).copied()
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = match self.drill {
+            Some(kind) => self.items(kind).len(),
+            None => CruftKind::ALL.len(),
+        };
+        let selected = match self.drill {
+            Some(_) => &mut self.selected_item,
+            None => &mut self.selected_kind,
+        };
+        if len == 0 {
+            return;
+        }
+        *selected = selected.saturating_add_signed(delta).min(len - 1);
+    }
+
+    fn change_stale_days(&mut self, delta: i64) {
+        self.stale_days = (self.stale_days + delta).max(1);
+        self.selected_item = 0;
+    }
+
+    fn overview_lines(&self) -> (Vec<Line<'static>>, usize) {
+        let mut lines = vec![section("Pruning candidates")];
+        let mut selected_line = 0;
+        for (i, kind) in CruftKind::ALL.iter().enumerate() {
+            let count = self.items(*kind).len();
+            let selected = i == self.selected_kind;
+            if selected {
+                selected_line = lines.len();
+            }
+            let (marker, style) = if selected {
+                (
+                    "▶",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                (" ", Style::default().fg(Color::White))
+            };
+            let count_span = if *kind == CruftKind::StaleModel && !self.report.has_history {
+                Span::styled(format!("{:>5}", "-"), Style::default().fg(Color::DarkGray))
+            } else if count == 0 {
+                Span::styled(format!("{:>5}", count), Style::default().fg(Color::Green))
+            } else {
+                Span::styled(format!("{:>5}", count), Style::default().fg(Color::Red))
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {} {:<48}", marker, kind.title(self.stale_days)), style),
+                count_span,
+            ]));
+        }
+        if !self.report.has_history {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "   No model runs in history yet, so staleness can't be judged",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        (lines, selected_line)
+    }
+
+    fn drill_lines(&self, kind: CruftKind) -> (Vec<Line<'static>>, usize) {
+        let items = self.items(kind);
+        let mut lines = vec![section(&format!(
+            "{} ({})",
+            kind.title(self.stale_days),
+            items.len()
+        ))];
+        if items.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "   Nothing to prune",
+                Style::default().fg(Color::Green),
+            )));
+        }
+        let now = Local::now();
+        let mut selected_line = 0;
+        for (i, item) in items.iter().enumerate() {
+            let selected = i == self.selected_item;
+            if selected {
+                selected_line = lines.len();
+            }
+            let (marker, style) = if selected {
+                (
+                    "▶",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                (" ", Style::default().fg(Color::White))
+            };
+            let mut spans = vec![
+                Span::styled(format!(" {} {:<40} ", marker, item.name), style),
+                Span::styled(
+                    format!("{:<50}", item.path.as_deref().unwrap_or_default()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ];
+            if matches!(kind, CruftKind::LeafModel | CruftKind::StaleModel) {
+                let last_run = match item.last_run {
+                    Some(t) => format!(" last run {} days ago", (now - t).num_days()),
+                    None => " never run".to_string(),
+                };
+                spans.push(Span::styled(last_run, Style::default().fg(Color::Cyan)));
+            }
+            lines.push(Line::from(spans));
+        }
+        (lines, selected_line)
+    }
+}
+
+impl Component for CruftDialog {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match (self.drill, key.code) {
+            (None, KeyCode::Esc | KeyCode::Char('q')) => Some(Action::CloseModal),
+            (Some(_), KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('h') | KeyCode::Left) => {
+                self.drill = None;
+                self.scroll_offset = 0;
+                None
+            }
+            (_, KeyCode::Char('j') | KeyCode::Down) => {
+                self.move_selection(1);
+                None
+            }
+            (_, KeyCode::Char('k') | KeyCode::Up) => {
+                self.move_selection(-1);
+                None
+            }
+            (Some(_), KeyCode::PageDown) => {
+                self.move_selection(20);
+                None
+            }
+            (Some(_), KeyCode::PageUp) => {
+                self.move_selection(-20);
+                None
+            }
+            (_, KeyCode::Char('+') | KeyCode::Char('=')) => {
+                self.change_stale_days(1);
+                None
+            }
+            (_, KeyCode::Char('-')) => {
+                self.change_stale_days(-1);
+                None
+            }
+            (None, KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right) => {
+                self.drill = Some(CruftKind::ALL[self.selected_kind]);
+                self.selected_item = 0;
+                self.scroll_offset = 0;
+                None
+            }
+            // Macros aren't in the main list
+            (Some(CruftKind::UnusedMacro), KeyCode::Enter) => None,
+            (Some(_), KeyCode::Enter) => self
+                .selected()
+                .map(|item| Action::RevealNode(item.unique_id.clone())),
+            (Some(_), KeyCode::Char('e')) => self
+                .selected()
+                .and_then(|item| item.path.clone())
+                .map(|path| Action::OpenFileInEditor(EditorTarget::file(path))),
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        frame.render_widget(Clear, area);
+
+        let margin = 2;
+        let overlay_area = Rect::new(
+            margin,
+            margin,
+            area.width.saturating_sub(margin * 2),
+            area.height.saturating_sub(margin * 2),
+        );
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(3)])
+            .split(overlay_area);
+
+        let (content, selected_line) = match self.drill {
+            Some(kind) => self.drill_lines(kind),
+            None => self.overview_lines(),
+        };
+
+        // Keep the selected row visible
+        let visible_height = chunks[0].height.saturating_sub(2) as usize;
+        if selected_line < self.scroll_offset {
+            self.scroll_offset = selected_line.saturating_sub(1);
+        } else if visible_height > 0 && selected_line >= self.scroll_offset + visible_height {
+            self.scroll_offset = selected_line + 1 - visible_height;
+        }
+
+        let paragraph = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Magenta))
+                    .title(" Dead and Orphaned ")
+                    .title_style(
+                        Style::default()
+                            .fg(Color::Magenta)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .scroll((self.scroll_offset as u16, 0));
+        frame.render_widget(paragraph, chunks[0]);
+
+        let key_style = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+        let mut help = vec![
+            Span::styled(
+                " Esc ",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(if self.drill.is_some() { "Back  " } else { "Close  " }),
+            Span::styled(" j/k ", key_style),
+            Span::raw("Select  "),
+        ];
+        if self.drill.is_some() {
+            help.push(Span::styled(" Enter ", key_style));
+            help.push(Span::raw("Show in list  "));
+            help.push(Span::styled(" e ", key_style));
+            help.push(Span::raw("Edit  "));
+        } else {
+            help.push(Span::styled(" Enter ", key_style));
+            help.push(Span::raw("Drill down  "));
+        }
+        help.push(Span::styled(" +/- ", key_style));
+        help.push(Span::raw("Stale days"));
+        let help = Paragraph::new(Line::from(help))
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(help, chunks[1]);
+
+        Ok(())
+    }
+}
+
+fn section(title: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        format!(" {}", title),
+        Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD),
+    ))
+}
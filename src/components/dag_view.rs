@@ -0,0 +1,769 @@
+//! Full-screen DAG view component
+//!
+//! Draws a model's neighborhood, or every node in its folder, as boxes
+//! joined by box-drawing edges, using the layered layout from
+//! `model::dag`. Boxes are colored by resource type, with the border
+//! showing the node's last run status. The view pans and zooms, and the
+//! selected node is kept in sync with the main list.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::model::dag::DagLayout;
+use crate::model::lineage::{LineageGraph, LineageNode};
+use crate::model::node::SourceTable;
+use crate::model::{Node, RunHistoryEntry};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+/// Neighborhood depth the view opens with
+const DEFAULT_DEPTH: usize = 2;
+const MAX_DEPTH: usize = 10;
+
+// Edge directions, combined per cell to pick a box-drawing character
+const UP: u8 = 1;
+const DOWN: u8 = 2;
+const LEFT: u8 = 4;
+const RIGHT: u8 = 8;
+
+/// Box size and spacing for one zoom level
+#[derive(Debug, Clone, Copy)]
+struct Zoom {
+    box_width: i32,
+    /// 1 draws the bare name; 3 and up draw a bordered box
+    box_height: i32,
+    layer_gap: i32,
+    row_gap: i32,
+}
+
+const ZOOMS: [Zoom; 3] = [
+    Zoom { box_width: 12, box_height: 1, layer_gap: 4, row_gap: 0 },
+    Zoom { box_width: 20, box_height: 3, layer_gap: 6, row_gap: 1 },
+    Zoom { box_width: 28, box_height: 4, layer_gap: 8, row_gap: 1 },
+];
+
+/// What the graph shows
+#[derive(Debug, Clone, PartialEq)]
+enum Scope {
+    /// Ancestors and descendants of a node
+    Neighborhood { root: String, depth: usize },
+    /// Every node whose file is under a folder
+    Folder(String),
+}
+
+/// Display data for a node, resolved by unique_id
+#[derive(Debug, Clone)]
+struct NodeInfo {
+    name: String,
+    resource_type: String,
+    path: Option<String>,
+}
+
+/// Full-screen DAG view
+pub struct DagView {
+    graph: Option<LineageGraph>,
+    info: HashMap<String, NodeInfo>,
+    /// Latest run status by unique_id
+    statuses: HashMap<String, String>,
+    scope: Scope,
+    show_tests: bool,
+    layout: Option<DagLayout>,
+    zoom: usize,
+    /// Real vertex under the cursor
+    selected: usize,
+    /// Top-left corner of the viewport in graph cells
+    pan: (i32, i32),
+    /// Scroll the selection into view on the next draw
+    follow: bool,
+    /// Edge cells and arrow heads for the current layout and zoom
+    edge_cells: HashMap<(i32, i32), u8>,
+    arrows: HashSet<(i32, i32)>,
+}
+
+impl Default for DagView {
+    fn default() -> Self {
+        Self {
+            graph: None,
+            info: HashMap::new(),
+            statuses: HashMap::new(),
+            scope: Scope::Folder(String::new()),
+            show_tests: false,
+            layout: None,
+            zoom: 1,
+            selected: 0,
+            pan: (0, 0),
+            follow: true,
+            edge_cells: HashMap::new(),
+            arrows: HashSet::new(),
+        }
+    }
+}
+
+impl DagView {
+    pub fn open(
+        &mut self,
+        root: &str,
+        graph: &LineageGraph,
+        all_nodes: &[Node],
+        sources: &[SourceTable],
+        history: &[RunHistoryEntry],
+    ) {
+        let mut info: HashMap<String, NodeInfo> = all_nodes
+            .iter()
+            .map(|n| {
+                (
+                    n.unique_id.clone(),
+                    NodeInfo {
+                        name: n.name.clone(),
+                        resource_type: n.resource_type.clone(),
+                        path: n.original_file_path.clone(),
+                    },
+                )
+            })
+            .collect();
+        for source in sources {
+            info.insert(
+                source.unique_id.clone(),
+                NodeInfo {
+                    name: format!("{}.{}", source.source_name, source.name),
+                    resource_type: "source".to_string(),
+                    path: None,
+                },
+            );
+        }
+        // History is newest first
+        let mut statuses = HashMap::new();
+        for entry in history {
+            for result in &entry.node_results {
+                statuses
+                    .entry(result.unique_id.clone())
+                    .or_insert_with(|| result.status.clone());
+            }
+        }
+        *self = Self {
+            graph: Some(graph.clone()),
+            info,
+            statuses,
+            ..Self::default()
+        };
+        self.set_scope(
+            Scope::Neighborhood {
+                root: root.to_string(),
+                depth: DEFAULT_DEPTH,
+            },
+            root,
+        );
+    }
+
+    fn info(&self, unique_id: &str) -> NodeInfo {
+        self.info.get(unique_id).cloned().unwrap_or_else(|| {
+            let node = LineageNode::from_unique_id(unique_id);
+            NodeInfo {
+                name: node.name,
+                resource_type: node.resource_type,
+                path: None,
+            }
+        })
+    }
+
+    /// unique_id under the cursor
+    fn selected_id(&self) -> Option<&str> {
+        self.layout
+            .as_ref()
+            .and_then(|l| l.nodes.get(self.selected))
+            .map(String::as_str)
+    }
+
+    fn neighbors(&self, unique_id: &str, upstream: bool) -> Vec<String> {
+        let Some(ref graph) = self.graph else {
+            return Vec::new();
+        };
+        let nodes = if upstream {
+            graph.get_upstream(unique_id)
+        } else {
+            graph.get_downstream(unique_id)
+        };
+        nodes
+            .into_iter()
+            .filter(|n| self.show_tests || n.resource_type != "test")
+            .map(|n| n.unique_id)
+            .collect()
+    }
+
+    /// Node ids in scope, sorted by name
+    fn scope_nodes(&self) -> Vec<String> {
+        let mut ids: Vec<String> = match self.scope {
+            Scope::Neighborhood { ref root, depth } => {
+                let mut seen: HashSet<String> = HashSet::from([root.clone()]);
+                for upstream in [true, false] {
+                    let mut queue = VecDeque::from([(root.clone(), 0)]);
+                    while let Some((id, level)) = queue.pop_front() {
+                        if level == depth {
+                            continue;
+                        }
+                        for next in self.neighbors(&id, upstream) {
+                            if seen.insert(next.clone()) {
+                                queue.push_back((next, level + 1));
+                            }
+                        }
+                    }
+                }
+                seen.into_iter().collect()
+            }
+            Scope::Folder(ref folder) => self
+                .info
+                .iter()
+                .filter(|(_, info)| self.show_tests || info.resource_type != "test")
+                .filter(|(_, info)| {
+                    info.path
+                        .as_deref()
+                        .is_some_and(|p| Path::new(p).starts_with(folder))
+                })
+                .map(|(id, _)| id.clone())
+                .collect(),
+        };
+        ids.sort_by_cached_key(|id| (self.info(id).name, id.clone()));
+        ids
+    }
+
+    /// Rebuild the layout, keeping the cursor on `select` if it is still shown
+    fn set_scope(&mut self, scope: Scope, select: &str) {
+        self.scope = scope;
+        let nodes = self.scope_nodes();
+        let included: HashSet<&String> = nodes.iter().collect();
+        let edges: Vec<(String, String)> = nodes
+            .iter()
+            .flat_map(|id| {
+                self.neighbors(id, true)
+                    .into_iter()
+                    .filter(|up| included.contains(up))
+                    .map(move |up| (up, id.clone()))
+            })
+            .collect();
+        let layout = DagLayout::build(&nodes, &edges);
+        self.selected = layout.vertex_of(select).unwrap_or(0);
+        self.layout = Some(layout);
+        self.follow = true;
+        self.route_edges();
+    }
+
+    fn zoom(&self) -> Zoom {
+        ZOOMS[self.zoom]
+    }
+
+    /// Top-left corner of a vertex's box, in graph cells. Layers are
+    /// centered vertically against the longest one.
+    fn position(&self, vertex: usize) -> (i32, i32) {
+        let Some(ref layout) = self.layout else {
+            return (0, 0);
+        };
+        let zoom = self.zoom();
+        let slot = layout.slot(vertex);
+        let offset = (layout.height() - layout.layers[slot.layer].len()) as i32 / 2;
+        (
+            slot.layer as i32 * (zoom.box_width + zoom.layer_gap),
+            (slot.index as i32 + offset) * (zoom.box_height + zoom.row_gap),
+        )
+    }
+
+    /// Row an edge enters or leaves a box on
+    fn center_row(&self, vertex: usize) -> i32 {
+        self.position(vertex).1 + self.zoom().box_height / 2
+    }
+
+    /// Trace every edge into cells: out of the source's right side, across
+    /// the gap with one vertical jog, into the target's left side
+    fn route_edges(&mut self) {
+        let mut cells: HashMap<(i32, i32), u8> = HashMap::new();
+        let mut arrows = HashSet::new();
+        let Some(ref layout) = self.layout else {
+            return;
+        };
+        let zoom = self.zoom();
+        let mut mark = |x: i32, y: i32, bits: u8| *cells.entry((x, y)).or_default() |= bits;
+        for chain in &layout.edges {
+            for pair in chain.windows(2) {
+                let (from, to) = (pair[0], pair[1]);
+                let (from_x, _) = self.position(from);
+                let (to_x, _) = self.position(to);
+                let from_y = self.center_row(from);
+                let to_y = self.center_row(to);
+                // Dummies are a straight line through the box column
+                let start = if layout.is_dummy(from) { from_x } else { from_x + zoom.box_width };
+                let end = if layout.is_dummy(to) { to_x + zoom.box_width } else { to_x - 1 };
+                let jog = from_x + zoom.box_width + zoom.layer_gap / 2;
+                for x in start..jog {
+                    mark(x, from_y, LEFT | RIGHT);
+                }
+                if from_y == to_y {
+                    mark(jog, from_y, LEFT | RIGHT);
+                } else {
+                    let (top, bottom) = (from_y.min(to_y), from_y.max(to_y));
+                    mark(jog, from_y, LEFT | if to_y > from_y { DOWN } else { UP });
+                    for y in top + 1..bottom {
+                        mark(jog, y, UP | DOWN);
+                    }
+                    mark(jog, to_y, RIGHT | if to_y > from_y { UP } else { DOWN });
+                }
+                for x in jog + 1..end {
+                    mark(x, to_y, LEFT | RIGHT);
+                }
+                if !layout.is_dummy(to) {
+                    arrows.insert((end, to_y));
+                }
+            }
+        }
+        self.edge_cells = cells;
+        self.arrows = arrows;
+    }
+
+    fn set_zoom(&mut self, zoom: usize) {
+        let zoom = zoom.min(ZOOMS.len() - 1);
+        if zoom != self.zoom {
+            self.zoom = zoom;
+            self.follow = true;
+            self.route_edges();
+        }
+    }
+
+    /// Move the cursor to another real vertex
+    fn select(&mut self, vertex: Option<usize>) -> Option<Action> {
+        let vertex = vertex.filter(|&v| v != self.selected)?;
+        self.selected = vertex;
+        self.follow = true;
+        self.selected_id().map(|id| Action::RevealNode(id.to_string()))
+    }
+
+    /// Next real vertex above or below in the same layer
+    fn vertical_neighbor(&self, down: bool) -> Option<usize> {
+        self.selected_id()?;
+        let layout = self.layout.as_ref()?;
+        let slot = layout.slot(self.selected);
+        let layer = &layout.layers[slot.layer];
+        let real = |v: &&usize| !layout.is_dummy(**v);
+        if down {
+            layer[slot.index + 1..].iter().find(real).copied()
+        } else {
+            layer[..slot.index].iter().rev().find(real).copied()
+        }
+    }
+
+    /// Closest real vertex in the nearest layer to the left or right that has one
+    fn horizontal_neighbor(&self, right: bool) -> Option<usize> {
+        self.selected_id()?;
+        let layout = self.layout.as_ref()?;
+        let slot = layout.slot(self.selected);
+        let row = self.position(self.selected).1;
+        let layers: Vec<usize> = if right {
+            (slot.layer + 1..layout.layers.len()).collect()
+        } else {
+            (0..slot.layer).rev().collect()
+        };
+        layers.into_iter().find_map(|layer| {
+            layout.layers[layer]
+                .iter()
+                .filter(|&&v| !layout.is_dummy(v))
+                .min_by_key(|&&v| (self.position(v).1 - row).abs())
+                .copied()
+        })
+    }
+
+    fn border_color(&self, unique_id: &str) -> Color {
+        match self.statuses.get(unique_id).map(String::as_str) {
+            Some("success" | "pass") => Color::Green,
+            Some("error" | "fail" | "runtime error") => Color::Red,
+            Some("warn") => Color::Yellow,
+            Some("skipped") => Color::DarkGray,
+            _ => Color::Gray,
+        }
+    }
+
+    /// Draw the graph into `area` with the viewport's top-left at `pan`
+    fn render_graph(&self, buf: &mut Buffer, area: Rect) {
+        let Some(ref layout) = self.layout else {
+            return;
+        };
+        let (pan_x, pan_y) = self.pan;
+        let to_screen = |x: i32, y: i32| {
+            let (sx, sy) = (x - pan_x, y - pan_y);
+            (sx >= 0 && sy >= 0 && sx < area.width as i32 && sy < area.height as i32)
+                .then(|| (area.x + sx as u16, area.y + sy as u16))
+        };
+
+        let edge_style = Style::default().fg(Color::DarkGray);
+        for (&(x, y), &bits) in &self.edge_cells {
+            if let Some((sx, sy)) = to_screen(x, y) {
+                buf[(sx, sy)].set_char(edge_char(bits)).set_style(edge_style);
+            }
+        }
+        for &(x, y) in &self.arrows {
+            if let Some((sx, sy)) = to_screen(x, y) {
+                buf[(sx, sy)].set_char('▶').set_style(edge_style);
+            }
+        }
+
+        let zoom = self.zoom();
+        for vertex in 0..layout.nodes.len() {
+            let (x, y) = self.position(vertex);
+            let id = &layout.nodes[vertex];
+            let info = self.info(id);
+            let selected = vertex == self.selected;
+            let root = matches!(self.scope, Scope::Neighborhood { ref root, .. } if root == id);
+
+            let mut name_style = Style::default().fg(type_color(&info.resource_type));
+            if root {
+                name_style = name_style.add_modifier(Modifier::BOLD);
+            }
+            let mut border_style = Style::default().fg(self.border_color(id));
+            if selected {
+                name_style = name_style.add_modifier(Modifier::REVERSED);
+                border_style = border_style.add_modifier(Modifier::BOLD);
+            }
+
+            let inner_width = if zoom.box_height == 1 { zoom.box_width } else { zoom.box_width - 2 };
+            let mut rows: Vec<Vec<(char, Style)>> = Vec::new();
+            let text = |s: &str, style: Style| -> Vec<(char, Style)> {
+                let mut chars: Vec<char> = s.chars().collect();
+                if chars.len() > inner_width as usize {
+                    chars.truncate(inner_width as usize - 1);
+                    chars.push('…');
+                }
+                let pad = inner_width as usize - chars.len();
+                chars
+                    .into_iter()
+                    .chain(std::iter::repeat_n(' ', pad))
+                    .map(|c| (c, style))
+                    .collect()
+            };
+            if zoom.box_height == 1 {
+                rows.push(text(&info.name, name_style));
+            } else {
+                let (h, v, corners) = if selected {
+                    ('═', '║', ['╔', '╗', '╚', '╝'])
+                } else {
+                    ('─', '│', ['┌', '┐', '└', '┘'])
+                };
+                let edge_row = |left: char, right: char| {
+                    std::iter::once(left)
+                        .chain(std::iter::repeat_n(h, inner_width as usize))
+                        .chain(std::iter::once(right))
+                        .map(|c| (c, border_style))
+                        .collect::<Vec<_>>()
+                };
+                let framed = |mut content: Vec<(char, Style)>| {
+                    content.insert(0, (v, border_style));
+                    content.push((v, border_style));
+                    content
+                };
+                rows.push(edge_row(corners[0], corners[1]));
+                rows.push(framed(text(&info.name, name_style)));
+                if zoom.box_height > 3 {
+                    let status = self.statuses.get(id).map(String::as_str).unwrap_or("not run");
+                    rows.push(framed(text(
+                        &format!("{} · {}", info.resource_type, status),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+                rows.push(edge_row(corners[2], corners[3]));
+            }
+
+            for (dy, row) in rows.iter().enumerate() {
+                for (dx, &(c, style)) in row.iter().enumerate() {
+                    if let Some((sx, sy)) = to_screen(x + dx as i32, y + dy as i32) {
+                        buf[(sx, sy)].set_char(c).set_style(style);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Move the viewport so the selected box is inside it
+    fn scroll_to_selection(&mut self, width: i32, height: i32) {
+        let zoom = self.zoom();
+        let (x, y) = self.position(self.selected);
+        let margin = zoom.layer_gap;
+        if x - margin < self.pan.0 || x + zoom.box_width + margin > self.pan.0 + width {
+            self.pan.0 = (x + zoom.box_width / 2 - width / 2).max(0);
+        }
+        if y < self.pan.1 || y + zoom.box_height > self.pan.1 + height {
+            self.pan.1 = (y + zoom.box_height / 2 - height / 2).max(0);
+        }
+    }
+
+    fn title(&self) -> String {
+        let count = self.layout.as_ref().map_or(0, |l| l.nodes.len());
+        let scope = match self.scope {
+            Scope::Neighborhood { ref root, depth } => {
+                format!("{} ±{}", self.info(root).name, depth)
+            }
+            Scope::Folder(ref folder) => format!("{}/", folder),
+        };
+        format!(
+            " DAG: {} ({} nodes, zoom {}/{}{}) ",
+            scope,
+            count,
+            self.zoom + 1,
+            ZOOMS.len(),
+            if self.show_tests { ", tests" } else { "" }
+        )
+    }
+}
+
+fn type_color(resource_type: &str) -> Color {
+    match resource_type {
+        "model" => Color::Blue,
+        "source" => Color::Green,
+        "seed" => Color::Yellow,
+        "test" => Color::Magenta,
+        "snapshot" => Color::Cyan,
+        _ => Color::White,
+    }
+}
+
+/// Box-drawing character joining the given edge directions
+fn edge_char(bits: u8) -> char {
+    match bits {
+        b if b == LEFT | RIGHT => '─',
+        b if b == UP | DOWN => '│',
+        b if b == DOWN | RIGHT => '┌',
+        b if b == DOWN | LEFT => '┐',
+        b if b == UP | RIGHT => '└',
+        b if b == UP | LEFT => '┘',
+        b if b == UP | DOWN | RIGHT => '├',
+        b if b == UP | DOWN | LEFT => '┤',
+        b if b == LEFT | RIGHT | DOWN => '┬',
+        b if b == LEFT | RIGHT | UP => '┴',
+        b if b == UP | DOWN | LEFT | RIGHT => '┼',
+        b if b & (UP | DOWN) != 0 => '│',
+        _ => '─',
+    }
+}
+
+impl Component for DagView {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let pan_step = (self.zoom().box_width + self.zoom().layer_gap) / 2;
+        let action = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(Action::CloseModal),
+            KeyCode::Char('j') | KeyCode::Down => self.select(self.vertical_neighbor(true)),
+            KeyCode::Char('k') | KeyCode::Up => self.select(self.vertical_neighbor(false)),
+            KeyCode::Char('l') | KeyCode::Right => self.select(self.horizontal_neighbor(true)),
+            KeyCode::Char('h') | KeyCode::Left => self.select(self.horizontal_neighbor(false)),
+            KeyCode::Char('H') => {
+                self.pan.0 -= pan_step;
+                None
+            }
+            KeyCode::Char('L') => {
+                self.pan.0 += pan_step;
+                None
+            }
+            KeyCode::Char('K') => {
+                self.pan.1 -= 4;
+                None
+            }
+            KeyCode::Char('J') => {
+                self.pan.1 += 4;
+                None
+            }
+            KeyCode::Char('z') => {
+                self.set_zoom(self.zoom + 1);
+                None
+            }
+            KeyCode::Char('Z') => {
+                self.set_zoom(self.zoom.saturating_sub(1));
+                None
+            }
+            KeyCode::Char('c') => {
+                self.follow = true;
+                self.pan = (0, 0);
+                None
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Char('-') => {
+                let selected = self.selected_id().unwrap_or_default().to_string();
+                if let Scope::Neighborhood { ref root, depth } = self.scope {
+                    let depth = if key.code == KeyCode::Char('-') {
+                        depth.saturating_sub(1).max(1)
+                    } else {
+                        (depth + 1).min(MAX_DEPTH)
+                    };
+                    let scope = Scope::Neighborhood { root: root.clone(), depth };
+                    self.set_scope(scope, &selected);
+                }
+                None
+            }
+            KeyCode::Enter => {
+                let selected = self.selected_id().unwrap_or_default().to_string();
+                let depth = match self.scope {
+                    Scope::Neighborhood { depth, .. } => depth,
+                    Scope::Folder(_) => DEFAULT_DEPTH,
+                };
+                let scope = Scope::Neighborhood { root: selected.clone(), depth };
+                self.set_scope(scope, &selected);
+                None
+            }
+            KeyCode::Char('f') => {
+                let selected = self.selected_id().unwrap_or_default().to_string();
+                let folder = self
+                    .info(&selected)
+                    .path
+                    .and_then(|p| Path::new(&p).parent().map(|f| f.to_string_lossy().to_string()));
+                match folder {
+                    Some(folder) if self.scope != Scope::Folder(folder.clone()) => {
+                        self.set_scope(Scope::Folder(folder), &selected);
+                    }
+                    _ => {
+                        let scope = Scope::Neighborhood { root: selected.clone(), depth: DEFAULT_DEPTH };
+                        self.set_scope(scope, &selected);
+                    }
+                }
+                None
+            }
+            KeyCode::Char('t') => {
+                self.show_tests = !self.show_tests;
+                let selected = self.selected_id().unwrap_or_default().to_string();
+                self.set_scope(self.scope.clone(), &selected);
+                None
+            }
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(self.title())
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(2)])
+            .split(inner);
+
+        if self.selected_id().is_none() {
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    " Nothing to show; press t to include tests",
+                    Style::default().fg(Color::DarkGray),
+                )),
+                chunks[0],
+            );
+        } else if self.follow {
+            self.scroll_to_selection(chunks[0].width as i32, chunks[0].height as i32);
+            self.follow = false;
+        }
+        self.render_graph(frame.buffer_mut(), chunks[0]);
+
+        let selected = self.selected_id().map(|id| {
+            let info = self.info(id);
+            let status = self.statuses.get(id).map(String::as_str).unwrap_or("not run");
+            Line::from(vec![
+                Span::styled(format!(" {}", info.name), Style::default().fg(type_color(&info.resource_type)).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("  {}  last run: ", info.resource_type), Style::default().fg(Color::DarkGray)),
+                Span::styled(status.to_string(), Style::default().fg(self.border_color(id))),
+                Span::styled(
+                    info.path.map(|p| format!("  {}", p)).unwrap_or_default(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])
+        });
+        let key = |k: &str| {
+            Span::styled(
+                format!(" {} ", k),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            )
+        };
+        let help = Line::from(vec![
+            key("hjkl"),
+            Span::raw("Select  "),
+            key("HJKL"),
+            Span::raw("Pan  "),
+            key("z/Z"),
+            Span::raw("Zoom  "),
+            key("+/-"),
+            Span::raw("Depth  "),
+            key("f"),
+            Span::raw("Folder  "),
+            key("t"),
+            Span::raw("Tests  "),
+            Span::styled(
+                " Enter ",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Center here  "),
+            Span::styled(
+                " Esc ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Close"),
+        ]);
+        frame.render_widget(
+            Paragraph::new(vec![selected.unwrap_or_default(), help]),
+            chunks[1],
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(name: &str, depends_on: &[&str]) -> Node {
+        Node {
+            original_file_path: Some(format!("models/{}.sql", name)),
+            ..Node::fixture_on_models(name, "model", depends_on)
+        }
+    }
+
+    fn render(view: &DagView) -> Vec<String> {
+        let area = Rect::new(0, 0, 60, 8);
+        let mut buf = Buffer::empty(area);
+        view.render_graph(&mut buf, area);
+        (0..area.height)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect::<String>())
+            .collect()
+    }
+
+    #[test]
+    fn test_dag_view_draws_boxes_and_edges() {
+        // a -> b, a -> c
+        let nodes = vec![node("a", &[]), node("b", &["a"]), node("c", &["a"])];
+        let graph = LineageGraph::build(&nodes);
+        let mut view = DagView::default();
+        view.open("model.shop.a", &graph, &nodes, &[], &[]);
+        view.set_zoom(0);
+
+        let lines = render(&view);
+        assert!(lines[0].starts_with("a           ──┬▶b"), "{:?}", lines);
+        assert!(lines[1].starts_with("              └▶c"), "{:?}", lines);
+
+        // Moving right selects the child on the nearest row
+        let action = view.handle_key_event(KeyEvent::from(KeyCode::Char('l'))).unwrap();
+        assert!(matches!(action, Some(Action::RevealNode(ref id)) if id == "model.shop.b"));
+
+        // The folder scope holds every model under models/
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('f'))).unwrap();
+        assert_eq!(view.scope, Scope::Folder("models".to_string()));
+        assert_eq!(view.layout.as_ref().unwrap().nodes.len(), 3);
+    }
+}
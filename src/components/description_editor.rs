@@ -0,0 +1,290 @@
+//! Description editor component
+//!
+//! A small multi-line text area for editing a node or column description.
+//! Ctrl+S saves (the App writes it back to schema YAML), Esc cancels.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::components::centered_popup;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// What is being documented
+#[derive(Debug, Clone, PartialEq)]
+pub struct DescriptionTarget {
+    pub unique_id: String,
+    pub node_name: String,
+    pub column: Option<String>,
+}
+
+/// Description editor dialog
+#[derive(Default)]
+pub struct DescriptionEditor {
+    pub target: Option<DescriptionTarget>,
+    lines: Vec<String>,
+    /// Cursor position: line and character index
+    row: usize,
+    col: usize,
+    scroll: usize,
+}
+
+impl DescriptionEditor {
+    pub fn open(&mut self, target: DescriptionTarget, text: &str) {
+        self.target = Some(target);
+        self.lines = text.trim_end().lines().map(String::from).collect();
+        if self.lines.is_empty() {
+            self.lines.push(String::new());
+        }
+        self.row = self.lines.len() - 1;
+        self.col = self.lines[self.row].chars().count();
+        self.scroll = 0;
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n").trim().to_string()
+    }
+
+    /// Byte offset of the cursor in the current line
+    fn byte_col(&self) -> usize {
+        let line = &self.lines[self.row];
+        line.char_indices()
+            .nth(self.col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len())
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    fn insert_char(&mut self, c: char) {
+        let at = self.byte_col();
+        self.lines[self.row].insert(at, c);
+        self.col += 1;
+    }
+
+    fn insert_newline(&mut self) {
+        let at = self.byte_col();
+        let rest = self.lines[self.row].split_off(at);
+        self.row += 1;
+        self.lines.insert(self.row, rest);
+        self.col = 0;
+    }
+
+    fn backspace(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            let at = self.byte_col();
+            self.lines[self.row].remove(at);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.col < self.line_len(self.row) {
+            let at = self.byte_col();
+            self.lines[self.row].remove(at);
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        }
+    }
+
+    fn move_vertical(&mut self, down: bool) {
+        if down && self.row + 1 < self.lines.len() {
+            self.row += 1;
+        } else if !down && self.row > 0 {
+            self.row -= 1;
+        }
+        self.col = self.col.min(self.line_len(self.row));
+    }
+}
+
+impl Component for DescriptionEditor {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let action = match key.code {
+            KeyCode::Esc => Some(Action::CloseModal),
+            KeyCode::Char('s') if ctrl => Some(Action::ConfirmModal),
+            KeyCode::Enter => {
+                self.insert_newline();
+                None
+            }
+            KeyCode::Backspace => {
+                self.backspace();
+                None
+            }
+            KeyCode::Delete => {
+                self.delete();
+                None
+            }
+            KeyCode::Left => {
+                if self.col > 0 {
+                    self.col -= 1;
+                } else if self.row > 0 {
+                    self.row -= 1;
+                    self.col = self.line_len(self.row);
+                }
+                None
+            }
+            KeyCode::Right => {
+                if self.col < self.line_len(self.row) {
+                    self.col += 1;
+                } else if self.row + 1 < self.lines.len() {
+                    self.row += 1;
+                    self.col = 0;
+                }
+                None
+            }
+            KeyCode::Up => {
+                self.move_vertical(false);
+                None
+            }
+            KeyCode::Down => {
+                self.move_vertical(true);
+                None
+            }
+            KeyCode::Home => {
+                self.col = 0;
+                None
+            }
+            KeyCode::End => {
+                self.col = self.line_len(self.row);
+                None
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.insert_char(c);
+                None
+            }
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let popup_width = 80.min(area.width.saturating_sub(4));
+        let popup_height = 18.min(area.height.saturating_sub(2));
+        let popup_area = centered_popup(area, popup_width, popup_height);
+
+        frame.render_widget(Clear, popup_area);
+
+        let title = match self.target {
+            Some(DescriptionTarget {
+                ref node_name,
+                column: Some(ref column),
+                ..
+            }) => format!(" Description: {}.{} ", node_name, column),
+            Some(ref target) => format!(" Description: {} ", target.node_name),
+            None => " Description ".to_string(),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(title)
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+        let text_area = chunks[0];
+
+        // Keep the cursor line visible
+        let visible = text_area.height as usize;
+        if self.row < self.scroll {
+            self.scroll = self.row;
+        } else if visible > 0 && self.row >= self.scroll + visible {
+            self.scroll = self.row + 1 - visible;
+        }
+
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .map(|l| Line::from(format!(" {}", l)))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(lines).scroll((self.scroll as u16, 0)),
+            text_area,
+        );
+
+        let before_cursor: String = self.lines[self.row].chars().take(self.col).collect();
+        let x = text_area.x + 1 + before_cursor.width() as u16;
+        let y = text_area.y + (self.row - self.scroll) as u16;
+        if x < text_area.right() && y < text_area.bottom() {
+            frame.set_cursor_position(Position::new(x, y));
+        }
+
+        let help = Line::from(vec![
+            Span::styled(
+                " Ctrl+S ",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Save to YAML  "),
+            Span::styled(
+                " Esc ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Cancel"),
+        ]);
+        frame.render_widget(Paragraph::new(help), chunks[1]);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_editing() {
+        let mut editor = DescriptionEditor::default();
+        let target = DescriptionTarget {
+            unique_id: "model.shop.orders".to_string(),
+            node_name: "orders".to_string(),
+            column: None,
+        };
+        editor.open(target, "One row\n");
+
+        for code in [
+            KeyCode::Char('s'),
+            KeyCode::Enter,
+            KeyCode::Char('x'),
+            KeyCode::Backspace,
+            KeyCode::Backspace,
+            KeyCode::Home,
+            KeyCode::Char('>'),
+        ] {
+            editor.handle_key_event(key(code)).unwrap();
+        }
+        assert_eq!(editor.text(), ">One rows");
+
+        let save = editor
+            .handle_key_event(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL))
+            .unwrap();
+        assert_eq!(save, Some(Action::ConfirmModal));
+    }
+}
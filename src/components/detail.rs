@@ -4,6 +4,7 @@
 
 use crate::action::Action;
 use crate::component::Component;
+use crate::model::yaml_index::YamlEntry;
 use crate::model::{CodeViewMode, Node};
 use super::sql_highlight;
 use anyhow::Result;
@@ -64,8 +65,13 @@ impl DetailComponent {
         }
     }
 
-    /// Update content based on the selected node
-    pub fn set_node(&mut self, node: Option<&Node>, code_view_mode: CodeViewMode) {
+    /// Update content based on the selected node and its YAML declaration
+    pub fn set_node(
+        &mut self,
+        node: Option<&Node>,
+        code_view_mode: CodeViewMode,
+        yaml: Option<&YamlEntry>,
+    ) {
         self.code_view_mode = code_view_mode;
         self.scroll = 0;
 
@@ -90,10 +96,10 @@ impl DetailComponent {
                     }
                 } else if n.resource_type == "test" {
                     self.content_type = ContentType::Test;
-                    self.content = self.render_node_detail(n);
+                    self.content = self.render_node_detail(n, yaml);
                 } else {
                     self.content_type = ContentType::Sql;
-                    self.content = self.render_node_detail(n);
+                    self.content = self.render_node_detail(n, yaml);
                 }
             }
             None => {
@@ -144,7 +150,7 @@ impl DetailComponent {
         ]
     }
 
-    fn render_node_detail(&mut self, node: &Node) -> Vec<Line<'static>> {
+    fn render_node_detail(&mut self, node: &Node, yaml: Option<&YamlEntry>) -> Vec<Line<'static>> {
         let mut lines = Vec::new();
 
         // Add shortcuts hint at the top
@@ -171,11 +177,22 @@ impl DetailComponent {
             ),
             Span::raw(" Docs"),
         ]));
+        if yaml.is_some() {
+            if let Some(hints) = lines.last_mut() {
+                hints.spans.push(Span::styled(
+                    "  'Y'",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ));
+                hints.spans.push(Span::raw(" YAML"));
+            }
+        }
         lines.push(Line::from(""));
 
         if node.resource_type == "test" {
             // For tests, show test info and YAML definition
-            self.render_test_detail(&mut lines, node);
+            self.render_test_detail(&mut lines, node, yaml);
         } else {
             // For models, show SQL
             self.render_model_detail(&mut lines, node);
@@ -184,7 +201,7 @@ impl DetailComponent {
         lines
     }
 
-    fn render_test_detail(&self, lines: &mut Vec<Line<'static>>, node: &Node) {
+    fn render_test_detail(&self, lines: &mut Vec<Line<'static>>, node: &Node, yaml: Option<&YamlEntry>) {
         lines.push(Line::from(Span::styled(
             "Test Information:",
             Style::default()
@@ -197,7 +214,11 @@ impl DetailComponent {
         )));
         lines.push(Line::from(""));
 
-        let test_type = extract_test_type(&node.name);
+        let test_type = node
+            .test_metadata
+            .as_ref()
+            .map(|m| m.name.clone())
+            .unwrap_or_else(|| extract_test_type(&node.name));
         lines.push(Line::from(vec![
             Span::styled("Test Type: ", Style::default().fg(Color::Cyan)),
             Span::raw(test_type),
@@ -211,8 +232,6 @@ impl DetailComponent {
             .map(|dep| dep.rsplit('.').next().unwrap_or(dep))
             .collect();
 
-        let model_name = tested_models.first().copied().unwrap_or("");
-
         if !tested_models.is_empty() {
             lines.push(Line::from(vec![
                 Span::styled("Tests Model: ", Style::default().fg(Color::Cyan)),
@@ -220,6 +239,13 @@ impl DetailComponent {
             ]));
         }
 
+        if let Some(ref column) = node.column_name {
+            lines.push(Line::from(vec![
+                Span::styled("Column: ", Style::default().fg(Color::Cyan)),
+                Span::raw(column.clone()),
+            ]));
+        }
+
         if let Some(ref path) = node.original_file_path {
             lines.push(Line::from(vec![
                 Span::styled("Source File: ", Style::default().fg(Color::Cyan)),
@@ -229,14 +255,24 @@ impl DetailComponent {
 
         lines.push(Line::from(""));
 
-        // Try to get YAML definition for schema tests
-        if let Some(yaml_content) = node.get_test_yaml_definition(model_name) {
-            lines.push(Line::from(Span::styled(
-                "YAML Definition:",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            )));
+        // YAML declaration of generic tests, from the index
+        let yaml_snippet = yaml.and_then(|entry| {
+            let root = node.root_path.as_ref()?;
+            Some((entry, entry.read_snippet(std::path::Path::new(root))?))
+        });
+        if let Some((entry, yaml_content)) = yaml_snippet {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "YAML Definition: ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("{}:{}", entry.file, entry.line),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
             lines.push(Line::from(Span::styled(
                 "═══════════════════════════════════════════════════════════",
                 Style::default().fg(Color::DarkGray),
@@ -345,10 +381,8 @@ impl Component for DetailComponent {
         let max_scroll = self.content.len().saturating_sub(1);
 
         match action {
-            Action::ScrollDown => {
-                if self.scroll < max_scroll {
-                    self.scroll += 1;
-                }
+            Action::ScrollDown if self.scroll < max_scroll => {
+                self.scroll += 1;
             }
             Action::ScrollUp => {
                 self.scroll = self.scroll.saturating_sub(1);
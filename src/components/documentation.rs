@@ -4,6 +4,8 @@
 
 use crate::action::Action;
 use crate::component::Component;
+use crate::model::column_lineage::{ColumnLineage, ColumnRef};
+use crate::model::yaml_index::YamlIndex;
 use crate::model::Node;
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
@@ -14,6 +16,10 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame,
 };
+use std::collections::HashSet;
+
+/// Levels of column lineage shown in each direction
+const COLUMN_LINEAGE_DEPTH: usize = 6;
 
 /// Documentation component for displaying node documentation
 pub struct DocumentationComponent {
@@ -21,6 +27,14 @@ pub struct DocumentationComponent {
     scroll: usize,
     /// Cached content lines
     content: Vec<Line<'static>>,
+    /// Node being shown, to reset the selection when it changes
+    node_id: Option<String>,
+    /// Selectable items: the node description (None), then columns by name
+    items: Vec<Option<String>>,
+    /// Index into `items`
+    selected: usize,
+    /// Content line of the selected item, kept visible when drawing
+    selected_line: usize,
 }
 
 impl Default for DocumentationComponent {
@@ -34,40 +48,106 @@ impl DocumentationComponent {
         Self {
             scroll: 0,
             content: Vec::new(),
+            node_id: None,
+            items: Vec::new(),
+            selected: 0,
+            selected_line: 0,
         }
     }
 
     /// Update content based on the selected node
-    pub fn set_node(&mut self, node: Option<&Node>) {
+    pub fn set_node(&mut self, node: Option<&Node>, yaml_index: &YamlIndex, lineage: &ColumnLineage) {
+        let node_id = node.map(|n| n.unique_id.clone());
+        if node_id != self.node_id {
+            self.node_id = node_id;
+            self.selected = 0;
+            self.scroll = 0;
+        }
         self.content = match node {
-            Some(n) => self.render_node_documentation(n),
-            None => vec![Line::from(Span::styled(
-                "No node selected",
-                Style::default().fg(Color::DarkGray),
-            ))],
+            Some(n) => self.render_node_documentation(n, yaml_index, lineage),
+            None => {
+                self.items.clear();
+                vec![Line::from(Span::styled(
+                    "No node selected",
+                    Style::default().fg(Color::DarkGray),
+                ))]
+            }
         };
     }
 
-    fn render_node_documentation(&self, node: &Node) -> Vec<Line<'static>> {
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.items.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Selected item: Some(None) for the node itself, Some(Some(column)) for a column
+    pub fn selected_item(&self) -> Option<Option<&str>> {
+        self.items.get(self.selected).map(|item| item.as_deref())
+    }
+
+    /// Marker and style for an item header
+    fn item_marker(&self, item: usize) -> (&'static str, Modifier) {
+        if item == self.selected {
+            ("▶ ", Modifier::REVERSED)
+        } else {
+            ("  ", Modifier::empty())
+        }
+    }
+
+    fn render_node_documentation(
+        &mut self,
+        node: &Node,
+        yaml_index: &YamlIndex,
+        lineage: &ColumnLineage,
+    ) -> Vec<Line<'static>> {
         let mut lines = Vec::new();
+        self.items = vec![None];
 
-        // Description section
-        if let Some(ref desc) = node.description {
-            if !desc.trim().is_empty() {
-                lines.push(Line::from(Span::styled(
-                    "Description:",
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
-                )));
-                lines.push(Line::from(""));
+        // Where the node is declared ('Y' opens it)
+        if let Some(entry) = yaml_index.find_node(node) {
+            lines.push(Line::from(vec![
+                Span::styled("Defined in: ", Style::default().fg(Color::Cyan)),
+                Span::styled(
+                    format!("{}:{}", entry.file, entry.line),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+            lines.push(Line::from(""));
+        }
+        let column_entries = yaml_index.columns(&node.name);
 
+        // Description section
+        let (marker, modifier) = self.item_marker(0);
+        if self.selected == 0 {
+            self.selected_line = lines.len();
+        }
+        lines.push(Line::from(vec![
+            Span::styled(marker, Style::default().fg(Color::Yellow)),
+            Span::styled(
+                "Description:",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD | modifier),
+            ),
+        ]));
+        lines.push(Line::from(""));
+        match node.description.as_deref().filter(|d| !d.trim().is_empty()) {
+            Some(desc) => {
                 for line in desc.lines() {
-                    lines.push(Line::from(line.to_string()));
+                    lines.push(Line::from(format!("  {}", line)));
                 }
-                lines.push(Line::from(""));
             }
+            None => lines.push(Line::from(Span::styled(
+                "  No description ('w' to write one)",
+                Style::default().fg(Color::DarkGray),
+            ))),
         }
+        lines.push(Line::from(""));
 
         // Columns section
         if !node.columns.is_empty() {
@@ -84,6 +164,19 @@ impl DocumentationComponent {
             column_vec.sort_by(|a, b| a.name.cmp(&b.name));
 
             for col in column_vec {
+                let item = self.items.len();
+                self.items.push(Some(col.name.clone()));
+                let (marker, modifier) = self.item_marker(item);
+                if item == self.selected {
+                    self.selected_line = lines.len();
+                }
+
+                // Only models' columns have upstream lineage to resolve
+                let unresolved = lineage.has_model(&node.unique_id)
+                    && !lineage
+                        .upstream(&node.unique_id, &col.name)
+                        .is_some_and(|u| u.resolved);
+
                 let type_info = col
                     .data_type
                     .as_ref()
@@ -91,13 +184,30 @@ impl DocumentationComponent {
                     .unwrap_or_default();
 
                 lines.push(Line::from(vec![
+                    Span::styled(marker, Style::default().fg(Color::Yellow)),
                     Span::styled(
-                        format!("  {} ", col.name),
+                        format!("{} ", col.name),
                         Style::default()
                             .fg(Color::Green)
-                            .add_modifier(Modifier::BOLD),
+                            .add_modifier(Modifier::BOLD | modifier),
                     ),
                     Span::styled(type_info, Style::default().fg(Color::DarkGray)),
+                    Span::styled(
+                        column_entries
+                            .iter()
+                            .find(|e| {
+                                e.column
+                                    .as_deref()
+                                    .is_some_and(|c| c.eq_ignore_ascii_case(&col.name))
+                            })
+                            .map(|e| format!("  :{}", e.line))
+                            .unwrap_or_default(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(
+                        if unresolved { "  ? lineage unresolved" } else { "" },
+                        Style::default().fg(Color::Yellow),
+                    ),
                 ]));
 
                 if let Some(ref desc) = col.description {
@@ -107,24 +217,85 @@ impl DocumentationComponent {
                         }
                     }
                 }
+                if item == self.selected {
+                    let column = ColumnRef {
+                        unique_id: node.unique_id.clone(),
+                        column: col.name.to_lowercase(),
+                    };
+                    for upstream in [true, false] {
+                        lines.push(Line::from(Span::styled(
+                            if upstream { "    Upstream columns:" } else { "    Downstream columns:" },
+                            Style::default().fg(Color::Cyan),
+                        )));
+                        let before = lines.len();
+                        let mut seen = HashSet::from([column.clone()]);
+                        column_tree(lineage, &column, upstream, 1, &mut seen, &mut lines);
+                        if lines.len() == before {
+                            lines.push(Line::from(Span::styled(
+                                "      none found",
+                                Style::default().fg(Color::DarkGray),
+                            )));
+                        }
+                    }
+                }
                 lines.push(Line::from(""));
             }
+        } else {
+            lines.push(Line::from("Add columns to your models in schema.yml files."));
         }
 
-        // If no documentation available
-        if lines.is_empty() {
-            lines.push(Line::from(Span::styled(
-                "No documentation available",
-                Style::default().fg(Color::DarkGray),
-            )));
-            lines.push(Line::from(""));
-            lines.push(Line::from("Add documentation to your models in schema.yml files."));
-        }
-
+        self.selected = self.selected.min(self.items.len() - 1);
         lines
     }
 }
 
+/// Columns a column is computed from (or feeds), indented by level
+fn column_tree(
+    lineage: &ColumnLineage,
+    column: &ColumnRef,
+    upstream: bool,
+    depth: usize,
+    seen: &mut HashSet<ColumnRef>,
+    lines: &mut Vec<Line<'static>>,
+) {
+    if depth > COLUMN_LINEAGE_DEPTH {
+        return;
+    }
+    let next = if upstream {
+        lineage
+            .upstream(&column.unique_id, &column.column)
+            .map(|u| u.sources)
+            .unwrap_or_default()
+    } else {
+        lineage.downstream(&column.unique_id, &column.column)
+    };
+    for child in next {
+        if !seen.insert(child.clone()) {
+            continue;
+        }
+        let unresolved = lineage.has_model(&child.unique_id)
+            && !lineage
+                .upstream(&child.unique_id, &child.column)
+                .is_some_and(|u| u.resolved);
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{}{} ", "  ".repeat(depth + 2), if upstream { "←" } else { "→" }),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!("{}.", lineage.name(&child.unique_id)),
+                Style::default().fg(Color::Blue),
+            ),
+            Span::styled(child.column.clone(), Style::default().fg(Color::Green)),
+            Span::styled(
+                if unresolved { " ?" } else { "" },
+                Style::default().fg(Color::Yellow),
+            ),
+        ]));
+        column_tree(lineage, &child, upstream, depth + 1, seen, lines);
+    }
+}
+
 impl Component for DocumentationComponent {
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
         let action = match key.code {
@@ -151,10 +322,8 @@ impl Component for DocumentationComponent {
         let max_scroll = self.content.len().saturating_sub(1);
 
         match action {
-            Action::ScrollDown => {
-                if self.scroll < max_scroll {
-                    self.scroll += 1;
-                }
+            Action::ScrollDown if self.scroll < max_scroll => {
+                self.scroll += 1;
             }
             Action::ScrollUp => {
                 self.scroll = self.scroll.saturating_sub(1);
@@ -173,11 +342,18 @@ impl Component for DocumentationComponent {
     fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
         let visible_height = area.height.saturating_sub(2) as usize;
 
+        // Keep the selected item in view
+        if self.selected_line < self.scroll {
+            self.scroll = self.selected_line;
+        } else if visible_height > 0 && self.selected_line >= self.scroll + visible_height {
+            self.scroll = self.selected_line + 1 - visible_height;
+        }
+
         let paragraph = Paragraph::new(self.content.clone())
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" Documentation ")
+                    .title(" Documentation ([/] select, w edit) ")
                     .border_style(Style::default().fg(Color::DarkGray)),
             )
             .scroll((self.scroll as u16, 0));
@@ -0,0 +1,195 @@
+//! Export dialog component
+//!
+//! Lets the user pick an export format and an output path for a run.
+//! Typing edits the path; switching format updates the file extension.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::components::centered_popup;
+use crate::model::run_summary::RunSummary;
+use crate::services::ExportFormat;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::path::{Path, PathBuf};
+
+/// Export dialog
+#[derive(Default)]
+pub struct ExportDialog {
+    /// Run being exported
+    pub summary: Option<RunSummary>,
+    pub format: ExportFormat,
+    /// Output path (editable)
+    pub path: String,
+}
+
+impl ExportDialog {
+    /// Prepare the dialog for a run; the default path is `<project>/target/<stem>.<ext>`
+    pub fn open(&mut self, summary: RunSummary, project_path: Option<&Path>) {
+        let dir = project_path
+            .map(|p| p.join("target"))
+            .unwrap_or_else(|| PathBuf::from("."));
+        self.path = dir
+            .join(format!("{}.{}", summary.file_stem(), self.format.extension()))
+            .to_string_lossy()
+            .to_string();
+        self.summary = Some(summary);
+    }
+
+    fn select_format(&mut self, format: ExportFormat) {
+        let old_ext = format!(".{}", self.format.extension());
+        if let Some(stem) = self.path.strip_suffix(&old_ext) {
+            self.path = format!("{}.{}", stem, format.extension());
+        }
+        self.format = format;
+    }
+
+    fn cycle_format(&mut self, forward: bool) {
+        let formats = ExportFormat::ALL;
+        let index = formats.iter().position(|f| *f == self.format).unwrap_or(0);
+        let next = if forward {
+            (index + 1) % formats.len()
+        } else {
+            (index + formats.len() - 1) % formats.len()
+        };
+        self.select_format(formats[next]);
+    }
+}
+
+impl Component for ExportDialog {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match key.code {
+            KeyCode::Esc => Some(Action::CloseModal),
+            KeyCode::Enter => Some(Action::ConfirmModal),
+            KeyCode::Down | KeyCode::Tab => {
+                self.cycle_format(true);
+                None
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                self.cycle_format(false);
+                None
+            }
+            KeyCode::Backspace => {
+                self.path.pop();
+                None
+            }
+            KeyCode::Char(c) => {
+                self.path.push(c);
+                None
+            }
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let popup_width = 70.min(area.width.saturating_sub(4));
+        let popup_area = centered_popup(area, popup_width, 13);
+
+        frame.render_widget(Clear, popup_area);
+
+        let mut content = Vec::new();
+
+        if let Some(ref summary) = self.summary {
+            content.push(Line::from(vec![
+                Span::styled(" Run: ", Style::default().fg(Color::DarkGray)),
+                Span::raw(summary.command.clone()),
+                Span::styled(
+                    format!("  ({} nodes)", summary.nodes.len()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+        content.push(Line::from(""));
+
+        content.push(Line::from(Span::styled(
+            " Format",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for format in ExportFormat::ALL {
+            let selected = format == self.format;
+            let (marker, style) = if selected {
+                (
+                    "▶",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                (" ", Style::default().fg(Color::White))
+            };
+            content.push(Line::from(Span::styled(
+                format!("  {} {}", marker, format.label()),
+                style,
+            )));
+        }
+        content.push(Line::from(""));
+
+        content.push(Line::from(Span::styled(
+            " Path",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )));
+        // Show the end of long paths, where the file name is
+        let max_path = popup_area.width.saturating_sub(6) as usize;
+        let path_chars = self.path.chars().count();
+        let shown_path = if path_chars > max_path {
+            let tail: String = self.path.chars().skip(path_chars - max_path + 1).collect();
+            format!("…{}", tail)
+        } else {
+            self.path.clone()
+        };
+        content.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(shown_path, Style::default().fg(Color::White)),
+            Span::styled("_", Style::default().fg(Color::Yellow)),
+        ]));
+        content.push(Line::from(""));
+
+        content.push(Line::from(vec![
+            Span::styled(
+                " ↑/↓ ",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Format  "),
+            Span::styled(
+                " Enter ",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Export  "),
+            Span::styled(
+                " Esc ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Cancel"),
+        ]));
+
+        let paragraph = Paragraph::new(content).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(" Export Run ")
+                .title_style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+        );
+
+        frame.render_widget(paragraph, popup_area);
+        Ok(())
+    }
+}
@@ -154,7 +154,18 @@ fn build_help_content() -> Vec<Line<'static>> {
     add_section(&mut lines, "View Toggles");
     add_shortcut(&mut lines, "c", "Toggle compiled/original SQL");
     add_shortcut(&mut lines, "l", "Toggle lineage panel");
+    add_shortcut(&mut lines, "x", "Explore lineage at any depth");
+    add_shortcut(&mut lines, "v", "Full-screen DAG graph");
+    add_shortcut(&mut lines, "X", "Export lineage (DOT, Mermaid, JSON)");
+    add_shortcut(&mut lines, "I", "Impact analysis of changing the node");
+    add_shortcut(&mut lines, "H", "Project health audit");
+    add_shortcut(&mut lines, "C", "Check modeling conventions");
+    add_shortcut(&mut lines, "O", "Dead and orphaned models, sources, macros");
+    add_shortcut(&mut lines, "m", "Test coverage matrix of the model");
     add_shortcut(&mut lines, "d", "Toggle documentation panel");
+    add_shortcut(&mut lines, "[ / ]", "Select description or column (docs panel)");
+    add_shortcut(&mut lines, "w", "Edit selected description (docs panel)");
+    add_shortcut(&mut lines, "T", "Add or remove tests on selected column (docs panel)");
 
     // Modals & Dialogs
     add_section(&mut lines, "Dialogs");
@@ -169,7 +180,12 @@ fn build_help_content() -> Vec<Line<'static>> {
     // Model Actions
     add_section(&mut lines, "Model Actions");
     add_shortcut(&mut lines, "e", "Edit file in $EDITOR");
-    add_shortcut(&mut lines, "p", "Preview sample data (dbt show)");
+    add_shortcut(&mut lines, "E", "Open related file (schema YAML, compiled SQL)");
+    add_shortcut(&mut lines, "Y", "Go to YAML definition (columns with docs open)");
+    add_shortcut(&mut lines, "S", "Generate schema YAML from warehouse columns");
+    add_shortcut(&mut lines, "N", "New model wizard");
+    add_shortcut(&mut lines, "M", "Rename / move model (updates refs)");
+    add_shortcut(&mut lines, "p", "Preview sample data, or a test's failing rows");
 
     // Search
     add_section(&mut lines, "Search");
@@ -194,6 +210,29 @@ fn build_help_content() -> Vec<Line<'static>> {
     add_section(&mut lines, "Project");
     add_shortcut(&mut lines, "R", "Refresh manifest");
 
+    // Run output section
+    add_section(&mut lines, "Run Output");
+    add_shortcut(&mut lines, "v", "Toggle raw/graphical view");
+    add_shortcut(&mut lines, "t", "Toggle timeline (per-thread Gantt)");
+    add_shortcut(&mut lines, "x", "Export (JUnit XML / JSON / Markdown)");
+    add_shortcut(&mut lines, "/", "Search raw log (n/N next/previous match)");
+    add_shortcut(&mut lines, "o", "Open file of the current match at its line");
+    add_shortcut(&mut lines, "] / [", "Next/previous error or warning");
+    add_shortcut(&mut lines, "f / m", "Filter errors, warnings / by model");
+    add_shortcut(&mut lines, "z / Z", "Fold/unfold long entry / all entries");
+    add_shortcut(&mut lines, "e", "Errors panel (Enter opens file at line)");
+
+    // Run history section
+    add_section(&mut lines, "Run History");
+    add_shortcut(&mut lines, "s / c / t", "Filter by status / command / target");
+    add_shortcut(&mut lines, "d", "Filter by date range");
+    add_shortcut(&mut lines, "m", "Filter by model name");
+    add_shortcut(&mut lines, "/", "Search run outputs");
+    add_shortcut(&mut lines, "r", "Reset filters");
+    add_shortcut(&mut lines, "Space", "Mark run for comparison");
+    add_shortcut(&mut lines, "D", "Compare marked runs (or marked vs selected)");
+    add_shortcut(&mut lines, "x", "Export selected run");
+
     // Footer
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
@@ -4,6 +4,7 @@
 
 use crate::action::Action;
 use crate::component::Component;
+use crate::model::history::{HistoryDateRange, HistoryFilter};
 use crate::model::{RunHistoryEntry, RunStatus};
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent};
@@ -27,15 +28,39 @@ fn strip_ansi_codes(s: &str) -> String {
     ANSI_REGEX.replace_all(s, "").to_string()
 }
 
+/// Text input currently being edited in the filter bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FilterInput {
+    Model,
+    Search,
+}
+
 /// Run history dialog
 #[derive(Default)]
 pub struct HistoryDialog {
     pub selected_index: usize,
     pub detail_scroll: usize,
+    pub filter: HistoryFilter,
+    /// Active text input, if any
+    input: Option<FilterInput>,
+    input_buffer: String,
+    /// Filtered entry indices, keyed by history length and newest entry id
+    visible: Option<((usize, String), Vec<usize>)>,
+    /// Distinct subcommands in history, for cycling the command filter
+    commands: Vec<String>,
+    /// Distinct targets in history, for cycling the target filter
+    targets: Vec<String>,
+    /// Ids of entries marked for comparison (at most two)
+    pub marked: Vec<String>,
 }
 
 impl Component for HistoryDialog {
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.input.is_some() {
+            self.handle_filter_input(key);
+            return Ok(None);
+        }
+
         let action = match key.code {
             KeyCode::Up => Some(Action::ModalUp),
             KeyCode::Down => Some(Action::ModalDown),
@@ -45,6 +70,46 @@ impl Component for HistoryDialog {
             KeyCode::PageDown => Some(Action::PageDown),
             KeyCode::Esc | KeyCode::Char('h') => Some(Action::CloseModal),
             KeyCode::Enter => Some(Action::OpenRunOutput),
+            KeyCode::Char(' ') => Some(Action::ToggleHistoryMark),
+            KeyCode::Char('D') => Some(Action::OpenRunComparison),
+            KeyCode::Char('x') => Some(Action::OpenExport),
+            KeyCode::Char('s') => {
+                self.filter.cycle_status();
+                self.filter_changed();
+                None
+            }
+            KeyCode::Char('c') => {
+                let commands = self.commands.clone();
+                self.filter.cycle_command(&commands);
+                self.filter_changed();
+                None
+            }
+            KeyCode::Char('t') => {
+                let targets = self.targets.clone();
+                self.filter.cycle_target(&targets);
+                self.filter_changed();
+                None
+            }
+            KeyCode::Char('d') => {
+                self.filter.date_range = self.filter.date_range.next();
+                self.filter_changed();
+                None
+            }
+            KeyCode::Char('m') => {
+                self.input_buffer = self.filter.model.clone();
+                self.input = Some(FilterInput::Model);
+                None
+            }
+            KeyCode::Char('/') => {
+                self.input_buffer = self.filter.search.clone();
+                self.input = Some(FilterInput::Search);
+                None
+            }
+            KeyCode::Char('r') => {
+                self.filter = HistoryFilter::default();
+                self.filter_changed();
+                None
+            }
             _ => None,
         };
         Ok(action)
@@ -52,11 +117,9 @@ impl Component for HistoryDialog {
 
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
-            Action::ModalUp => {
-                if self.selected_index > 0 {
-                    self.selected_index -= 1;
-                    self.detail_scroll = 0;
-                }
+            Action::ModalUp if self.selected_index > 0 => {
+                self.selected_index -= 1;
+                self.detail_scroll = 0;
             }
             Action::ModalDown => {
                 self.selected_index += 1;
@@ -86,8 +149,104 @@ impl Component for HistoryDialog {
 }
 
 impl HistoryDialog {
+    /// Indices into `history` of entries matching the current filter
+    pub fn visible_indices(&mut self, history: &[RunHistoryEntry]) -> &[usize] {
+        let key = (
+            history.len(),
+            history.first().map(|e| e.id.clone()).unwrap_or_default(),
+        );
+
+        let is_stale = self.visible.as_ref().is_none_or(|(k, _)| *k != key);
+        if is_stale {
+            self.commands = distinct(history.iter().filter_map(|e| e.subcommand()));
+            self.targets = distinct(history.iter().filter_map(|e| e.target.as_deref()));
+            self.visible = Some((key, self.filter.apply(history)));
+        }
+
+        self.visible.as_ref().map(|(_, v)| v.as_slice()).unwrap_or(&[])
+    }
+
+    /// Index into `history` of the selected entry
+    pub fn selected_entry_index(&mut self, history: &[RunHistoryEntry]) -> Option<usize> {
+        let selected = self.selected_index;
+        let visible = self.visible_indices(history);
+        visible
+            .get(selected.min(visible.len().saturating_sub(1)))
+            .copied()
+    }
+
+    /// Mark or unmark the selected entry. Marking a third entry drops the oldest mark.
+    pub fn toggle_mark(&mut self, history: &[RunHistoryEntry]) {
+        let Some(entry) = self.selected_entry_index(history).and_then(|i| history.get(i)) else {
+            return;
+        };
+
+        if let Some(pos) = self.marked.iter().position(|id| *id == entry.id) {
+            self.marked.remove(pos);
+        } else {
+            if self.marked.len() == 2 {
+                self.marked.remove(0);
+            }
+            self.marked.push(entry.id.clone());
+        }
+    }
+
+    /// Entries to compare: the two marked entries, or the marked entry and the selected one
+    pub fn comparison_entries<'a>(
+        &mut self,
+        history: &'a [RunHistoryEntry],
+    ) -> Option<(&'a RunHistoryEntry, &'a RunHistoryEntry)> {
+        let find = |id: &str| history.iter().find(|e| e.id == id);
+        let selected_index = self.selected_entry_index(history);
+
+        match self.marked.as_slice() {
+            [a, b] => Some((find(a)?, find(b)?)),
+            [a] => {
+                let selected = history.get(selected_index?)?;
+                let marked = find(a)?;
+                (marked.id != selected.id).then_some((marked, selected))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the filter matches against run outputs (text search, model filter)
+    pub fn searches_output(&self) -> bool {
+        !self.filter.search.is_empty() || !self.filter.model.is_empty()
+    }
+
+    fn filter_changed(&mut self) {
+        self.visible = None;
+        self.selected_index = 0;
+        self.detail_scroll = 0;
+    }
+
+    fn handle_filter_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.input = None;
+            }
+            KeyCode::Enter => {
+                let value = self.input_buffer.trim().to_string();
+                match self.input.take() {
+                    Some(FilterInput::Model) => self.filter.model = value,
+                    Some(FilterInput::Search) => self.filter.search = value,
+                    None => {}
+                }
+                self.filter_changed();
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
     pub fn draw_with_history(
-        &self,
+        &mut self,
         frame: &mut Frame,
         area: Rect,
         history: &[RunHistoryEntry],
@@ -122,24 +281,33 @@ impl HistoryDialog {
             return Ok(());
         }
 
+        let visible = self.visible_indices(history).to_vec();
+
         // Clamp selected index
-        let selected_idx = self.selected_index.min(history.len().saturating_sub(1));
+        let selected_idx = self.selected_index.min(visible.len().saturating_sub(1));
 
         let main_chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(1), Constraint::Length(3)])
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(1),
+                Constraint::Length(3),
+            ])
             .split(overlay_area);
 
+        frame.render_widget(self.render_filter_bar(), main_chunks[0]);
+
         let content_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
-            .split(main_chunks[0]);
+            .split(main_chunks[1]);
 
         // Render list
-        let items: Vec<ListItem> = history
+        let items: Vec<ListItem> = visible
             .iter()
             .enumerate()
-            .map(|(i, entry)| {
+            .map(|(i, &entry_idx)| {
+                let entry = &history[entry_idx];
                 let (icon, color) = match entry.status {
                     RunStatus::Running => ("⏳", Color::Yellow),
                     RunStatus::Success => ("✓", Color::Green),
@@ -161,23 +329,36 @@ impl HistoryDialog {
                     Style::default()
                 };
 
+                let mark = if self.marked.contains(&entry.id) { "● " } else { "  " };
+
                 ListItem::new(Line::from(vec![
+                    Span::styled(mark, Style::default().fg(Color::Magenta)),
                     Span::styled(format!("{} ", icon), Style::default().fg(color)),
                     Span::styled(
                         format!("{} ", entry.formatted_time()),
                         Style::default().fg(Color::DarkGray),
                     ),
                     Span::styled(short_cmd.to_string(), style),
+                    Span::styled(
+                        if entry.external { " ⇣ext" } else { "" },
+                        Style::default().fg(Color::DarkGray),
+                    ),
                 ]))
                 .style(style)
             })
             .collect();
 
+        let list_title = if self.filter.is_active() {
+            format!(" History ({}/{}) ", visible.len(), history.len())
+        } else {
+            " History ".to_string()
+        };
+
         let list = List::new(items)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" History ")
+                    .title(list_title)
                     .title_style(
                         Style::default()
                             .fg(Color::Magenta)
@@ -188,17 +369,29 @@ impl HistoryDialog {
         frame.render_widget(list, content_chunks[0]);
 
         // Render detail
-        if let Some(entry) = history.get(selected_idx) {
+        if let Some(entry) = visible.get(selected_idx).and_then(|&i| history.get(i)) {
             let detail_lines = render_history_detail(entry);
             let total = detail_lines.len();
             let visible_height = content_chunks[1].height.saturating_sub(2) as usize;
             let scroll = self.detail_scroll.min(total.saturating_sub(visible_height));
 
+            let detail_title = if self.filter.search.is_empty() {
+                " Details ".to_string()
+            } else {
+                let needle = self.filter.search.to_lowercase();
+                let matches = entry
+                    .output
+                    .lines()
+                    .filter(|l| l.to_lowercase().contains(&needle))
+                    .count();
+                format!(" Details ({} matching lines) ", matches)
+            };
+
             let detail = Paragraph::new(detail_lines)
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .title(" Details ")
+                        .title(detail_title)
                         .title_style(
                             Style::default()
                                 .fg(Color::Cyan)
@@ -221,41 +414,177 @@ impl HistoryDialog {
                     &mut scrollbar_state,
                 );
             }
+        } else {
+            let empty = Paragraph::new("No runs match the current filters. Press r to reset.")
+                .style(Style::default().fg(Color::DarkGray))
+                .block(Block::default().borders(Borders::ALL).title(" Details "));
+            frame.render_widget(empty, content_chunks[1]);
         }
 
         // Help bar
-        let help = Paragraph::new(Line::from(vec![
-            Span::styled(
-                " Esc/h ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
+        let help_spans = if self.input.is_some() {
+            vec![
+                Span::styled(
+                    " Enter ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Apply  "),
+                Span::styled(
+                    " Esc ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Cancel"),
+            ]
+        } else {
+            vec![
+                Span::styled(
+                    " Esc/h ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Close  "),
+                Span::styled(
+                    " ↑/↓ ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Select  "),
+                Span::styled(
+                    " j/k ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Scroll  "),
+                Span::styled(
+                    " Enter ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Open  "),
+                Span::styled(
+                    " Space ",
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Mark  "),
+                Span::styled(
+                    " D ",
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Compare  "),
+                Span::styled(
+                    " x ",
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Export  "),
+                Span::styled(
+                    " r ",
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Reset filters"),
+            ]
+        };
+        let help = Paragraph::new(Line::from(help_spans))
+            .alignment(ratatui::layout::Alignment::Left)
+            .block(Block::default().borders(Borders::ALL));
+
+        frame.render_widget(help, main_chunks[2]);
+
+        Ok(())
+    }
+
+    /// Render the filter bar showing each filter with its key
+    fn render_filter_bar(&self) -> Paragraph<'static> {
+        let key_style = Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD);
+        let value_style = |active: bool| {
+            if active {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            }
+        };
+
+        let status = match self.filter.status {
+            None => "All".to_string(),
+            Some(status) => format!("{:?}", status),
+        };
+        let text_value = |input: FilterInput, value: &str| -> (String, bool) {
+            if self.input == Some(input) {
+                (format!("{}▌", self.input_buffer), true)
+            } else if value.is_empty() {
+                ("-".to_string(), false)
+            } else {
+                (value.to_string(), true)
+            }
+        };
+        let (model, model_active) = text_value(FilterInput::Model, &self.filter.model);
+        let (search, search_active) = text_value(FilterInput::Search, &self.filter.search);
+
+        let fields = [
+            ("s", "Status", status, self.filter.status.is_some()),
+            (
+                "c",
+                "Command",
+                self.filter.command.clone().unwrap_or_else(|| "All".to_string()),
+                self.filter.command.is_some(),
             ),
-            Span::raw("Close  "),
-            Span::styled(
-                " ↑/↓ ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
+            (
+                "t",
+                "Target",
+                self.filter.target.clone().unwrap_or_else(|| "All".to_string()),
+                self.filter.target.is_some(),
             ),
-            Span::raw("Select  "),
-            Span::styled(
-                " j/k ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
+            (
+                "d",
+                "Date",
+                self.filter.date_range.label().to_string(),
+                self.filter.date_range != HistoryDateRange::All,
             ),
-            Span::raw("Scroll"),
-        ]))
-        .alignment(ratatui::layout::Alignment::Left)
-        .block(Block::default().borders(Borders::ALL));
+            ("m", "Model", model, model_active),
+            ("/", "Search", search, search_active),
+        ];
 
-        frame.render_widget(help, main_chunks[1]);
+        let mut spans = Vec::new();
+        for (key, label, value, active) in fields {
+            spans.push(Span::styled(format!(" {} ", key), key_style));
+            spans.push(Span::raw(format!("{}: ", label)));
+            spans.push(Span::styled(format!("{}  ", value), value_style(active)));
+        }
 
-        Ok(())
+        Paragraph::new(Line::from(spans)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Filters ")
+                .title_style(Style::default().fg(Color::Cyan)),
+        )
     }
 }
 
+/// Sorted distinct values
+fn distinct<'a>(values: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut values: Vec<String> = values.map(|v| v.to_string()).collect();
+    values.sort();
+    values.dedup();
+    values
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Helper functions
 // ─────────────────────────────────────────────────────────────────────────────
@@ -298,6 +627,31 @@ fn render_history_detail(entry: &RunHistoryEntry) -> Vec<Line<'static>> {
             },
         ),
     ]));
+    if let Some(ref target) = entry.target {
+        lines.push(Line::from(vec![
+            Span::styled(
+                "Target: ",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(target.clone()),
+        ]));
+    }
+    if entry.external {
+        lines.push(Line::from(vec![
+            Span::styled(
+                "Source: ",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                "external (imported from target/run_results.json)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
         "Command: ",
@@ -148,15 +148,14 @@ impl HomeComponent {
     }
 
     /// Extract the model name that a test is testing
-    fn get_test_model_name(test_node: &Node) -> String {
-        // Tests depend on the model they test via depends_on.nodes
-        if let Some(dep) = test_node.depends_on.nodes.first() {
+    pub fn get_test_model_name(test_node: &Node) -> String {
+        if let Some(dep) = test_node.tested_node_id() {
             if dep.starts_with("model.") {
                 if let Some(model_name) = dep.split('.').next_back() {
                     return model_name.to_string();
                 }
             }
-            return dep.clone();
+            return dep.to_string();
         }
         "unknown".to_string()
     }
@@ -273,6 +272,43 @@ impl HomeComponent {
         }
     }
 
+    /// Select a node by unique_id; false if it isn't in the current list
+    pub fn select_node(&mut self, all_nodes: &[Node], unique_id: &str) -> bool {
+        let (display_items, _) = self.build_display_list(all_nodes);
+        let filtered = self.get_filtered_nodes(all_nodes);
+        let position = display_items.iter().position(|item| match item {
+            DisplayListItem::Node(node_idx) => filtered
+                .get(*node_idx)
+                .is_some_and(|n| n.unique_id == unique_id),
+            DisplayListItem::Header(_) => false,
+        });
+        if let Some(position) = position {
+            self.list_state.select(Some(position));
+        }
+        position.is_some()
+    }
+
+    /// Select a node by unique_id, switching to its tab and dropping filters
+    /// that hide it; false if no tab lists it (e.g. sources)
+    pub fn reveal_node(&mut self, all_nodes: &[Node], unique_id: &str) -> bool {
+        let Some(node) = all_nodes.iter().find(|n| n.unique_id == unique_id) else {
+            return false;
+        };
+        let Some(tab) = Tab::all()
+            .into_iter()
+            .find(|t| t.resource_type() == Some(node.resource_type.as_str()))
+        else {
+            return false;
+        };
+        self.active_tab = tab;
+        if self.select_node(all_nodes, unique_id) {
+            return true;
+        }
+        self.search_query.clear();
+        self.tag_filter.clear();
+        self.select_node(all_nodes, unique_id)
+    }
+
     /// Select the last selectable item
     pub fn select_last(&mut self, all_nodes: &[Node]) {
         let (_, selectable_indices) = self.build_display_list(all_nodes);
@@ -427,8 +463,22 @@ impl Component for HomeComponent {
             // View toggles
             KeyCode::Char('c') => Some(Action::ToggleCodeView),
             KeyCode::Char('l') => Some(Action::ToggleLineage),
+            KeyCode::Char('x') => Some(Action::OpenLineageExplorer),
+            KeyCode::Char('v') => Some(Action::OpenDagView),
+            KeyCode::Char('X') => Some(Action::OpenLineageExport),
+            KeyCode::Char('I') => Some(Action::OpenImpactReport),
+            KeyCode::Char('H') => Some(Action::OpenHealthAudit),
+            KeyCode::Char('C') => Some(Action::OpenLint),
+            KeyCode::Char('O') => Some(Action::OpenCruftReport),
+            KeyCode::Char('m') => Some(Action::OpenTestMatrix),
             KeyCode::Char('d') => Some(Action::ToggleDocumentation),
 
+            // Documentation panel items
+            KeyCode::Char(']') if self.show_documentation => Some(Action::DocSelectNext),
+            KeyCode::Char('[') if self.show_documentation => Some(Action::DocSelectPrevious),
+            KeyCode::Char('w') if self.show_documentation => Some(Action::OpenDescriptionEditor),
+            KeyCode::Char('T') if self.show_documentation => Some(Action::OpenColumnTests),
+
             // Modals
             KeyCode::Char('q') => Some(Action::OpenQuitDialog),
             KeyCode::Char('r') | KeyCode::Enter => Some(Action::OpenRunOptions),
@@ -454,6 +504,11 @@ impl Component for HomeComponent {
 
             // Editor
             KeyCode::Char('e') => Some(Action::OpenEditor),
+            KeyCode::Char('E') => Some(Action::OpenRelatedFiles),
+            KeyCode::Char('Y') => Some(Action::OpenYamlDefinition),
+            KeyCode::Char('S') => Some(Action::OpenYamlScaffold),
+            KeyCode::Char('N') => Some(Action::OpenNewModelWizard),
+            KeyCode::Char('M') => Some(Action::OpenRefactor),
 
             // Sample data preview
             KeyCode::Char('p') => Some(Action::OpenSampleData),
@@ -493,6 +548,8 @@ pub struct HomeRenderContext<'a> {
     pub all_nodes: &'a [Node],
     pub project_name: Option<&'a str>,
     pub lineage_graph: Option<&'a crate::model::lineage::LineageGraph>,
+    pub yaml_index: &'a crate::model::yaml_index::YamlIndex,
+    pub column_lineage: &'a crate::model::column_lineage::ColumnLineage,
     pub error: Option<&'a str>,
     pub status_message: Option<&'a str>,
     pub git_branch: Option<&'a str>,
@@ -519,7 +576,8 @@ pub fn draw_home_screen(
 
     // Update and render detail panel
     let node = home.get_selected_node(ctx.all_nodes).cloned();
-    detail.set_node(node.as_ref(), home.code_view_mode);
+    let yaml = node.as_ref().and_then(|n| ctx.yaml_index.find_node(n));
+    detail.set_node(node.as_ref(), home.code_view_mode, yaml);
     detail.draw(frame, layout.detail)?;
 
     // Update and render lineage panel if visible
@@ -531,7 +589,7 @@ pub fn draw_home_screen(
 
     // Update and render documentation panel if visible
     if let Some(doc_area) = layout.documentation {
-        documentation.set_node(node.as_ref());
+        documentation.set_node(node.as_ref(), ctx.yaml_index, ctx.column_lineage);
         documentation.draw(frame, doc_area)?;
     }
 
@@ -0,0 +1,282 @@
+//! Impact analysis dialog component
+//!
+//! Shows everything downstream of the selected node before it is changed:
+//! counts, owners, expensive models and historical runtime. `b` builds the
+//! node and everything downstream of it.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::model::history::format_duration;
+use crate::model::impact::{ImpactReport, ImpactedNode};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
+
+/// Impact analysis dialog
+#[derive(Default)]
+pub struct ImpactDialog {
+    pub report: Option<ImpactReport>,
+    pub scroll_offset: usize,
+}
+
+impl ImpactDialog {
+    pub fn open(&mut self, report: ImpactReport) {
+        self.report = Some(report);
+        self.scroll_offset = 0;
+    }
+}
+
+impl Component for ImpactDialog {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(Action::CloseModal),
+            KeyCode::Char('b') => self
+                .report
+                .as_ref()
+                .map(|r| Action::BuildDownstream(r.root_name.clone())),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.scroll_offset = self.scroll_offset.saturating_add(1);
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(1);
+                None
+            }
+            KeyCode::PageDown => {
+                self.scroll_offset = self.scroll_offset.saturating_add(20);
+                None
+            }
+            KeyCode::PageUp => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(20);
+                None
+            }
+            KeyCode::Char('g') => {
+                self.scroll_offset = 0;
+                None
+            }
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        frame.render_widget(Clear, area);
+
+        let margin = 2;
+        let overlay_area = Rect::new(
+            margin,
+            margin,
+            area.width.saturating_sub(margin * 2),
+            area.height.saturating_sub(margin * 2),
+        );
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(3)])
+            .split(overlay_area);
+
+        let (title, content) = match self.report {
+            Some(ref report) => (
+                format!(" Impact of {} ", report.root_name),
+                render_report(report),
+            ),
+            None => (" Impact ".to_string(), vec![Line::from("Nothing to analyse")]),
+        };
+
+        let total = content.len();
+        let visible_height = chunks[0].height.saturating_sub(2) as usize;
+        let max_scroll = total.saturating_sub(visible_height);
+        if self.scroll_offset > max_scroll {
+            self.scroll_offset = max_scroll;
+        }
+
+        let paragraph = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Magenta))
+                    .title(title)
+                    .title_style(
+                        Style::default()
+                            .fg(Color::Magenta)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .scroll((self.scroll_offset as u16, 0));
+
+        frame.render_widget(paragraph, chunks[0]);
+
+        if total > visible_height {
+            let mut scrollbar_state = ScrollbarState::new(max_scroll).position(self.scroll_offset);
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight),
+                chunks[0].inner(ratatui::layout::Margin {
+                    vertical: 1,
+                    horizontal: 0,
+                }),
+                &mut scrollbar_state,
+            );
+        }
+
+        let help = Paragraph::new(Line::from(vec![
+            Span::styled(
+                " Esc/q ",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Close  "),
+            Span::styled(
+                " j/k ",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Scroll  "),
+            Span::styled(
+                " b ",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Build everything downstream"),
+        ]))
+        .alignment(ratatui::layout::Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+
+        frame.render_widget(help, chunks[1]);
+
+        Ok(())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helper functions
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn render_report(report: &ImpactReport) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let label = Style::default().fg(Color::DarkGray);
+
+    let expensive: Vec<&ImpactedNode> = report.expensive().collect();
+    lines.push(Line::from(vec![
+        Span::styled("  Downstream: ", label),
+        Span::raw(format!(
+            "{} models, {} tests, {} exposures",
+            report.models.len(),
+            report.tests.len(),
+            report.exposures.len()
+        )),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  Expensive:  ", label),
+        Span::styled(
+            format!("{} tables or incremental models", expensive.len()),
+            if expensive.is_empty() {
+                Style::default()
+            } else {
+                Style::default().fg(Color::Yellow)
+            },
+        ),
+    ]));
+    let (runtime, unknown) = report.total_runtime();
+    let mut runtime_text = format_duration(runtime);
+    if unknown > 0 {
+        runtime_text.push_str(&format!(" (+ {} without run history)", unknown));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("  Runtime:    ", label),
+        Span::raw(runtime_text),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  Owners:     ", label),
+        Span::raw(if report.owners.is_empty() {
+            "none recorded".to_string()
+        } else {
+            report.owners.join(", ")
+        }),
+    ]));
+
+    section_header(&mut lines, "Expensive to rebuild", expensive.len());
+    if expensive.is_empty() {
+        lines.push(empty_line("No downstream tables or incremental models"));
+    }
+    for node in &expensive {
+        lines.push(node_line(node));
+    }
+
+    section_header(&mut lines, "Models", report.models.len());
+    if report.models.is_empty() {
+        lines.push(empty_line("Nothing depends on this node"));
+    }
+    for node in &report.models {
+        lines.push(node_line(node));
+    }
+
+    section_header(&mut lines, "Tests", report.tests.len());
+    if report.tests.is_empty() {
+        lines.push(empty_line("No downstream tests"));
+    }
+    for node in &report.tests {
+        lines.push(node_line(node));
+    }
+
+    section_header(&mut lines, "Exposures", report.exposures.len());
+    if report.exposures.is_empty() {
+        lines.push(empty_line("No downstream exposures"));
+    }
+    for node in &report.exposures {
+        lines.push(node_line(node));
+    }
+
+    lines
+}
+
+fn node_line(node: &ImpactedNode) -> Line<'static> {
+    let materialized = node.materialized.clone().unwrap_or_default();
+    let materialized_style = if node.is_expensive() {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let runtime = node
+        .avg_runtime
+        .map(format_duration)
+        .unwrap_or_else(|| "-".to_string());
+    let mut spans = vec![
+        Span::styled(format!("  {:>2} ", node.depth), Style::default().fg(Color::DarkGray)),
+        Span::raw(format!("{:<40} ", node.name)),
+        Span::styled(format!("{:<12} ", materialized), materialized_style),
+        Span::styled(format!("{:>8}", runtime), Style::default().fg(Color::Cyan)),
+    ];
+    if let Some(ref owner) = node.owner {
+        spans.push(Span::styled(
+            format!("  {}", owner),
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+    Line::from(spans)
+}
+
+fn section_header(lines: &mut Vec<Line<'static>>, title: &str, count: usize) {
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("{} ({})", title, count),
+        Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD),
+    )));
+}
+
+fn empty_line(text: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        format!("  {}", text),
+        Style::default().fg(Color::DarkGray),
+    ))
+}
@@ -162,10 +162,8 @@ impl Component for LineageComponent {
         let max_scroll = self.content.len().saturating_sub(1);
 
         match action {
-            Action::ScrollDown => {
-                if self.scroll < max_scroll {
-                    self.scroll += 1;
-                }
+            Action::ScrollDown if self.scroll < max_scroll => {
+                self.scroll += 1;
             }
             Action::ScrollUp => {
                 self.scroll = self.scroll.saturating_sub(1);
@@ -0,0 +1,589 @@
+//! Lineage explorer component
+//!
+//! Walks upstream and downstream from a node to any depth as a collapsible
+//! tree. Branches open automatically up to the depth limit; deeper ones can
+//! still be expanded one at a time. A node reached along several paths is
+//! expanded once; later occurrences are marked "seen above", which keeps the
+//! tree linear in the number of edges. Nodes are tracked by unique_id, and
+//! moving the cursor asks the App to select that node in the main list.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::model::lineage::LineageGraph;
+use crate::model::node::SourceTable;
+use crate::model::Node;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Depth limit the explorer opens with
+const DEFAULT_DEPTH: usize = 2;
+const MAX_DEPTH: usize = 10;
+
+/// Which way a branch walks the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flow {
+    Upstream,
+    Downstream,
+}
+
+/// One visible node in the tree
+#[derive(Debug, Clone)]
+struct Row {
+    unique_id: String,
+    /// None for the root
+    flow: Option<Flow>,
+    /// Distance from the root
+    depth: usize,
+    /// Path of unique_ids from the root, so a node reached twice is two branches
+    key: String,
+    /// Tree guides drawn before the node
+    guide: String,
+    children: usize,
+    expanded: bool,
+    /// Already on the path from the root
+    cycle: bool,
+    /// Listed earlier in the same direction, so not expanded again
+    repeat: bool,
+}
+
+/// Lineage explorer
+pub struct LineageExplorer {
+    root: String,
+    graph: Option<LineageGraph>,
+    /// Display name and resource type by unique_id
+    labels: HashMap<String, (String, String)>,
+    max_depth: usize,
+    /// Branches within the depth limit the user closed
+    collapsed: HashSet<String>,
+    /// Branches past the depth limit the user opened
+    expanded: HashSet<String>,
+    show_tests: bool,
+    rows: Vec<Row>,
+    selected: usize,
+    scroll: usize,
+}
+
+impl Default for LineageExplorer {
+    fn default() -> Self {
+        Self {
+            root: String::new(),
+            graph: None,
+            labels: HashMap::new(),
+            max_depth: DEFAULT_DEPTH,
+            collapsed: HashSet::new(),
+            expanded: HashSet::new(),
+            show_tests: true,
+            rows: Vec::new(),
+            selected: 0,
+            scroll: 0,
+        }
+    }
+}
+
+impl LineageExplorer {
+    pub fn open(&mut self, root: &str, graph: &LineageGraph, all_nodes: &[Node], sources: &[SourceTable]) {
+        let mut labels: HashMap<String, (String, String)> = all_nodes
+            .iter()
+            .map(|n| (n.unique_id.clone(), (n.name.clone(), n.resource_type.clone())))
+            .collect();
+        for source in sources {
+            labels.insert(
+                source.unique_id.clone(),
+                (
+                    format!("{}.{}", source.source_name, source.name),
+                    "source".to_string(),
+                ),
+            );
+        }
+        *self = Self {
+            graph: Some(graph.clone()),
+            labels,
+            ..Self::default()
+        };
+        self.set_root(root);
+    }
+
+    /// Re-center the tree on another node
+    fn set_root(&mut self, root: &str) {
+        self.root = root.to_string();
+        self.collapsed.clear();
+        self.expanded.clear();
+        self.selected = 0;
+        self.scroll = 0;
+        self.rebuild();
+    }
+
+    /// unique_id under the cursor
+    pub fn selected_id(&self) -> Option<&str> {
+        self.rows.get(self.selected).map(|r| r.unique_id.as_str())
+    }
+
+    fn label(&self, unique_id: &str) -> (String, String) {
+        self.labels.get(unique_id).cloned().unwrap_or_else(|| {
+            let node = crate::model::lineage::LineageNode::from_unique_id(unique_id);
+            (node.name, node.resource_type)
+        })
+    }
+
+    fn children(&self, flow: Flow, unique_id: &str) -> Vec<String> {
+        let Some(ref graph) = self.graph else {
+            return Vec::new();
+        };
+        let nodes = match flow {
+            Flow::Upstream => graph.get_upstream(unique_id),
+            Flow::Downstream => graph.get_downstream(unique_id),
+        };
+        let mut ids: Vec<String> = nodes
+            .into_iter()
+            .filter(|n| self.show_tests || n.resource_type != "test")
+            .map(|n| n.unique_id)
+            .collect();
+        ids.sort_by_key(|id| self.label(id).0);
+        ids.dedup();
+        ids
+    }
+
+    fn is_expanded(&self, key: &str, depth: usize) -> bool {
+        if depth < self.max_depth {
+            !self.collapsed.contains(key)
+        } else {
+            self.expanded.contains(key)
+        }
+    }
+
+    fn set_expanded(&mut self, index: usize, open: bool) {
+        let Some(row) = self.rows.get(index) else {
+            return;
+        };
+        if row.flow.is_none()
+            || row.children == 0
+            || row.cycle
+            || row.repeat
+            || row.expanded == open
+        {
+            return;
+        }
+        let key = row.key.clone();
+        if row.depth < self.max_depth {
+            if open {
+                self.collapsed.remove(&key);
+            } else {
+                self.collapsed.insert(key);
+            }
+        } else if open {
+            self.expanded.insert(key);
+        } else {
+            self.expanded.remove(&key);
+        }
+        self.rebuild();
+    }
+
+    fn rebuild(&mut self) {
+        let selected_key = self.rows.get(self.selected).map(|r| r.key.clone());
+        let mut rows = vec![Row {
+            unique_id: self.root.clone(),
+            flow: None,
+            depth: 0,
+            key: String::new(),
+            guide: String::new(),
+            children: 0,
+            expanded: true,
+            cycle: false,
+            repeat: false,
+        }];
+        for flow in [Flow::Upstream, Flow::Downstream] {
+            let mut path = vec![self.root.clone()];
+            let key = match flow {
+                Flow::Upstream => "up",
+                Flow::Downstream => "down",
+            };
+            self.walk(flow, 1, &mut path, "", key, &mut rows);
+        }
+        self.selected = selected_key
+            .and_then(|key| rows.iter().position(|r| r.key == key))
+            .unwrap_or(self.selected.min(rows.len() - 1));
+        self.rows = rows;
+    }
+
+    fn walk(
+        &self,
+        flow: Flow,
+        depth: usize,
+        path: &mut Vec<String>,
+        guide: &str,
+        parent_key: &str,
+        rows: &mut Vec<Row>,
+    ) {
+        let parent = path.last().cloned().unwrap_or_default();
+        let children = self.children(flow, &parent);
+        let count = children.len();
+        for (i, unique_id) in children.into_iter().enumerate() {
+            let last = i + 1 == count;
+            let key = format!("{}/{}", parent_key, unique_id);
+            let cycle = path.contains(&unique_id);
+            let repeat = !cycle
+                && rows
+                    .iter()
+                    .any(|r| r.flow == Some(flow) && r.unique_id == unique_id);
+            let grandchildren = if cycle {
+                0
+            } else {
+                self.children(flow, &unique_id).len()
+            };
+            let expanded = grandchildren > 0 && !repeat && self.is_expanded(&key, depth);
+            rows.push(Row {
+                unique_id: unique_id.clone(),
+                flow: Some(flow),
+                depth,
+                key: key.clone(),
+                guide: format!("{}{}", guide, if last { "└─ " } else { "├─ " }),
+                children: grandchildren,
+                expanded,
+                cycle,
+                repeat,
+            });
+            if expanded {
+                path.push(unique_id);
+                let child_guide = format!("{}{}", guide, if last { "   " } else { "│  " });
+                self.walk(flow, depth + 1, path, &child_guide, &key, rows);
+                path.pop();
+            }
+        }
+    }
+
+    /// Index of the row's parent branch
+    fn parent_of(&self, index: usize) -> Option<usize> {
+        let row = self.rows.get(index)?;
+        if row.depth <= 1 {
+            return None;
+        }
+        self.rows[..index]
+            .iter()
+            .rposition(|r| r.flow == row.flow && r.depth == row.depth - 1)
+    }
+
+    fn set_depth(&mut self, depth: usize) {
+        self.max_depth = depth.clamp(1, MAX_DEPTH);
+        self.collapsed.clear();
+        self.expanded.clear();
+        self.rebuild();
+    }
+
+    /// Move the cursor; asks for the main view to follow when it lands on another node
+    fn select(&mut self, index: usize) -> Option<Action> {
+        let previous = self.selected_id().map(String::from);
+        self.selected = index.min(self.rows.len().saturating_sub(1));
+        let current = self.selected_id()?;
+        (previous.as_deref() != Some(current)).then(|| Action::RevealNode(current.to_string()))
+    }
+
+    fn row_line(&self, index: usize, row: &Row) -> Line<'static> {
+        let (name, resource_type) = self.label(&row.unique_id);
+        let color = match resource_type.as_str() {
+            "model" => Color::Blue,
+            "source" => Color::Green,
+            "seed" => Color::Yellow,
+            "test" => Color::Magenta,
+            "snapshot" => Color::Cyan,
+            _ => Color::White,
+        };
+        let marker = if row.cycle {
+            "↻ "
+        } else if row.children == 0 || row.repeat {
+            "  "
+        } else if row.expanded {
+            "▾ "
+        } else {
+            "▸ "
+        };
+        let mut name_style = Style::default().fg(color);
+        if row.flow.is_none() {
+            name_style = name_style.add_modifier(Modifier::BOLD);
+        }
+        if index == self.selected {
+            name_style = name_style.bg(Color::DarkGray).add_modifier(Modifier::BOLD);
+        }
+        let mut spans = vec![
+            Span::styled(format!(" {}", row.guide), Style::default().fg(Color::DarkGray)),
+            Span::styled(marker, Style::default().fg(Color::Cyan)),
+            Span::styled(name, name_style),
+            Span::styled(format!(" ({})", resource_type), Style::default().fg(Color::DarkGray)),
+        ];
+        if row.cycle {
+            spans.push(Span::styled(" cycle", Style::default().fg(Color::Red)));
+        } else if row.repeat {
+            spans.push(Span::styled(" seen above", Style::default().fg(Color::DarkGray)));
+        } else if row.children > 0 && !row.expanded {
+            spans.push(Span::styled(
+                format!(" +{}", row.children),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        Line::from(spans)
+    }
+
+    /// Tree lines with section headers, and the line the cursor is on
+    fn lines(&self) -> (Vec<Line<'static>>, usize) {
+        let header = |text: &str| {
+            Line::from(Span::styled(
+                format!(" {}", text),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ))
+        };
+        let mut lines = Vec::new();
+        let mut cursor_line = 0;
+        let mut flow = None;
+        for (index, row) in self.rows.iter().enumerate() {
+            if row.flow != flow {
+                flow = row.flow;
+                lines.push(Line::from(""));
+                lines.push(header(match row.flow {
+                    Some(Flow::Upstream) => "Upstream (depends on):",
+                    _ => "Downstream (used by):",
+                }));
+            }
+            if index == self.selected {
+                cursor_line = lines.len();
+            }
+            lines.push(self.row_line(index, row));
+        }
+        for (present, text) in [
+            (Flow::Upstream, "Upstream: none"),
+            (Flow::Downstream, "Downstream: none"),
+        ] {
+            if !self.rows.iter().any(|r| r.flow == Some(present)) {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    format!(" {}", text),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+        }
+        (lines, cursor_line)
+    }
+}
+
+impl Component for LineageExplorer {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(Action::CloseModal),
+            KeyCode::Char('j') | KeyCode::Down => self.select(self.selected + 1),
+            KeyCode::Char('k') | KeyCode::Up => self.select(self.selected.saturating_sub(1)),
+            KeyCode::PageDown => self.select(self.selected + 10),
+            KeyCode::PageUp => self.select(self.selected.saturating_sub(10)),
+            KeyCode::Char('g') | KeyCode::Home => self.select(0),
+            KeyCode::Char('G') | KeyCode::End => self.select(self.rows.len()),
+            KeyCode::Char('l') | KeyCode::Right => {
+                self.set_expanded(self.selected, true);
+                None
+            }
+            KeyCode::Char('h') | KeyCode::Left => {
+                let open = self.rows.get(self.selected).is_some_and(|r| r.expanded && r.flow.is_some());
+                if open {
+                    self.set_expanded(self.selected, false);
+                    None
+                } else {
+                    self.parent_of(self.selected).and_then(|parent| self.select(parent))
+                }
+            }
+            KeyCode::Char(' ') | KeyCode::Tab => {
+                let open = self.rows.get(self.selected).is_some_and(|r| r.expanded);
+                self.set_expanded(self.selected, !open);
+                None
+            }
+            KeyCode::Enter => {
+                let root = self.selected_id().filter(|id| *id != self.root).map(String::from);
+                if let Some(root) = root {
+                    self.set_root(&root);
+                }
+                None
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.set_depth(self.max_depth + 1);
+                None
+            }
+            KeyCode::Char('-') => {
+                self.set_depth(self.max_depth.saturating_sub(1));
+                None
+            }
+            KeyCode::Char('t') => {
+                self.show_tests = !self.show_tests;
+                self.rebuild();
+                None
+            }
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        // Right-hand side, so the node list stays visible as the cursor moves
+        let width = (area.width * 55 / 100).max(50).min(area.width);
+        let popup_area = Rect {
+            x: area.x + area.width - width,
+            y: area.y,
+            width,
+            height: area.height,
+        };
+
+        frame.render_widget(Clear, popup_area);
+
+        let (root_name, _) = self.label(&self.root);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(
+                " Lineage Explorer: {} (depth {}{}) ",
+                root_name,
+                self.max_depth,
+                if self.show_tests { "" } else { ", no tests" }
+            ))
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(2)])
+            .split(inner);
+
+        let (lines, cursor_line) = self.lines();
+        let visible = chunks[0].height as usize;
+        if cursor_line < self.scroll {
+            self.scroll = cursor_line;
+        } else if visible > 0 && cursor_line >= self.scroll + visible {
+            self.scroll = cursor_line + 1 - visible;
+        }
+        frame.render_widget(
+            Paragraph::new(lines).scroll((self.scroll as u16, 0)),
+            chunks[0],
+        );
+
+        let key = |k: &str| {
+            Span::styled(
+                format!(" {} ", k),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            )
+        };
+        let help = vec![
+            Line::from(vec![
+                key("j/k"),
+                Span::raw("Move  "),
+                key("h/l"),
+                Span::raw("Collapse/expand  "),
+                key("Space"),
+                Span::raw("Toggle  "),
+                key("+/-"),
+                Span::raw("Depth"),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    " Enter ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Explore from here  "),
+                key("t"),
+                Span::raw("Tests  "),
+                Span::styled(
+                    " Esc ",
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Close"),
+            ]),
+        ];
+        frame.render_widget(Paragraph::new(help), chunks[1]);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(explorer: &LineageExplorer) -> Vec<String> {
+        explorer
+            .rows
+            .iter()
+            .map(|r| format!("{}{}", r.guide, explorer.label(&r.unique_id).0))
+            .collect()
+    }
+
+    #[test]
+    fn test_explorer_depth_and_branches() {
+        // a -> b -> c -> d, and b -> e
+        let nodes = vec![
+            Node::fixture("a", "model", &[]),
+            Node::fixture("b", "model", &["model.shop.a"]),
+            Node::fixture("c", "model", &["model.shop.b"]),
+            Node::fixture("d", "model", &["model.shop.c"]),
+            Node::fixture("e", "model", &["model.shop.b"]),
+            Node::fixture("not_null_b_id", "test", &["model.shop.b"]),
+        ];
+        let graph = LineageGraph::build(&nodes);
+        let mut explorer = LineageExplorer::default();
+        explorer.open("model.shop.b", &graph, &nodes, &[]);
+
+        assert_eq!(
+            names(&explorer),
+            vec!["b", "└─ a", "├─ c", "│  └─ d", "├─ e", "└─ not_null_b_id"]
+        );
+
+        // Past the depth limit branches stay closed until opened
+        explorer.set_depth(1);
+        assert_eq!(names(&explorer), vec!["b", "└─ a", "├─ c", "├─ e", "└─ not_null_b_id"]);
+        let c = explorer.rows.iter().position(|r| r.unique_id == "model.shop.c").unwrap();
+        assert_eq!(explorer.rows[c].children, 1);
+        explorer.set_expanded(c, true);
+        assert_eq!(explorer.rows.len(), 6);
+
+        // Collapsing a branch keeps the cursor on it
+        explorer.selected = c;
+        explorer.set_expanded(c, false);
+        assert_eq!(explorer.selected_id(), Some("model.shop.c"));
+        assert_eq!(explorer.rows.len(), 5);
+
+        explorer.show_tests = false;
+        explorer.rebuild();
+        assert_eq!(names(&explorer), vec!["b", "└─ a", "├─ c", "└─ e"]);
+    }
+
+    #[test]
+    fn test_explorer_repeated_nodes() {
+        // a -> b -> d -> e and a -> c -> d: d is reached twice
+        let nodes = vec![
+            Node::fixture("a", "model", &[]),
+            Node::fixture("b", "model", &["model.shop.a"]),
+            Node::fixture("c", "model", &["model.shop.a"]),
+            Node::fixture("d", "model", &["model.shop.b", "model.shop.c"]),
+            Node::fixture("e", "model", &["model.shop.d"]),
+        ];
+        let graph = LineageGraph::build(&nodes);
+        let mut explorer = LineageExplorer::default();
+        explorer.open("model.shop.a", &graph, &nodes, &[]);
+        explorer.set_depth(5);
+
+        assert_eq!(
+            names(&explorer),
+            vec!["a", "├─ b", "│  └─ d", "│     └─ e", "└─ c", "   └─ d"]
+        );
+        let repeat = explorer.rows.last().unwrap();
+        assert!(repeat.repeat && !repeat.expanded);
+
+        explorer.set_expanded(explorer.rows.len() - 1, true);
+        assert_eq!(explorer.rows.len(), 6);
+    }
+}
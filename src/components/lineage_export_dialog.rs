@@ -0,0 +1,357 @@
+//! Lineage export dialog component
+//!
+//! Picks what to export (the selected node at a depth, the marked
+//! selection or the whole project), the format, which resource types to
+//! include and the output file. The App builds and writes the export.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::components::centered_popup;
+use crate::services::{LineageExportOptions, LineageFormat, LineageScope};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_DEPTH: usize = 2;
+const MAX_DEPTH: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum ScopeKind {
+    #[default]
+    Node,
+    Selection,
+    Project,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum Field {
+    #[default]
+    Scope,
+    Depth,
+    Format,
+    Tests,
+    Sources,
+    Exposures,
+    Path,
+}
+
+const FIELDS: [Field; 7] = [
+    Field::Scope,
+    Field::Depth,
+    Field::Format,
+    Field::Tests,
+    Field::Sources,
+    Field::Exposures,
+    Field::Path,
+];
+
+/// Lineage export dialog
+#[derive(Default)]
+pub struct LineageExportDialog {
+    /// Selected node as (unique_id, name)
+    root: Option<(String, String)>,
+    /// Nodes marked with Space in the main list
+    selection: Vec<String>,
+    scope: ScopeKind,
+    depth: usize,
+    pub format: LineageFormat,
+    pub options: LineageExportOptions,
+    /// Output path (editable)
+    pub path: String,
+    /// Set once the path is typed in, so it is no longer regenerated
+    path_edited: bool,
+    target_dir: PathBuf,
+    field: Field,
+}
+
+impl LineageExportDialog {
+    /// Prepare the dialog; the default path is `<project>/target/lineage_<stem>.<ext>`
+    pub fn open(
+        &mut self,
+        root: Option<(String, String)>,
+        mut selection: Vec<String>,
+        project_path: Option<&Path>,
+    ) {
+        selection.sort();
+        let scope = if selection.len() > 1 {
+            ScopeKind::Selection
+        } else if root.is_some() {
+            ScopeKind::Node
+        } else {
+            ScopeKind::Project
+        };
+        *self = Self {
+            root,
+            selection,
+            scope,
+            depth: DEFAULT_DEPTH,
+            format: self.format,
+            options: self.options,
+            target_dir: project_path
+                .map(|p| p.join("target"))
+                .unwrap_or_else(|| PathBuf::from(".")),
+            ..Self::default()
+        };
+        self.update_path();
+    }
+
+    /// What to export
+    pub fn scope(&self) -> LineageScope {
+        match (self.scope, &self.root) {
+            (ScopeKind::Node, Some((unique_id, _))) => LineageScope::Node {
+                unique_id: unique_id.clone(),
+                depth: self.depth,
+            },
+            (ScopeKind::Selection, _) => LineageScope::Selection {
+                unique_ids: self.selection.clone(),
+                depth: self.depth,
+            },
+            _ => LineageScope::Project,
+        }
+    }
+
+    fn scopes(&self) -> Vec<ScopeKind> {
+        let mut scopes = Vec::new();
+        if self.root.is_some() {
+            scopes.push(ScopeKind::Node);
+        }
+        if !self.selection.is_empty() {
+            scopes.push(ScopeKind::Selection);
+        }
+        scopes.push(ScopeKind::Project);
+        scopes
+    }
+
+    fn scope_label(&self, scope: ScopeKind) -> String {
+        match scope {
+            ScopeKind::Node => format!(
+                "Node {}",
+                self.root.as_ref().map(|(_, name)| name.as_str()).unwrap_or("")
+            ),
+            ScopeKind::Selection => format!("Selection ({} nodes)", self.selection.len()),
+            ScopeKind::Project => "Whole project".to_string(),
+        }
+    }
+
+    fn update_path(&mut self) {
+        if self.path_edited {
+            return;
+        }
+        let stem = match self.scope {
+            ScopeKind::Node => self.root.as_ref().map_or("node", |(_, name)| name.as_str()),
+            ScopeKind::Selection => "selection",
+            ScopeKind::Project => "project",
+        };
+        self.path = self
+            .target_dir
+            .join(format!("lineage_{}.{}", stem, self.format.extension()))
+            .to_string_lossy()
+            .to_string();
+    }
+
+    fn move_field(&mut self, forward: bool) {
+        let index = FIELDS.iter().position(|f| *f == self.field).unwrap_or(0);
+        let next = if forward {
+            (index + 1) % FIELDS.len()
+        } else {
+            (index + FIELDS.len() - 1) % FIELDS.len()
+        };
+        self.field = FIELDS[next];
+        if self.field == Field::Depth && self.scope == ScopeKind::Project {
+            self.move_field(forward);
+        }
+    }
+
+    /// Change the focused value; `forward` is false for Left
+    fn change(&mut self, forward: bool) {
+        match self.field {
+            Field::Scope => {
+                let scopes = self.scopes();
+                let index = scopes.iter().position(|s| *s == self.scope).unwrap_or(0);
+                let next = if forward {
+                    (index + 1) % scopes.len()
+                } else {
+                    (index + scopes.len() - 1) % scopes.len()
+                };
+                self.scope = scopes[next];
+                self.update_path();
+            }
+            Field::Depth if forward => self.depth = (self.depth + 1).min(MAX_DEPTH),
+            Field::Depth => self.depth = self.depth.saturating_sub(1).max(1),
+            Field::Format => {
+                let formats = LineageFormat::ALL;
+                let index = formats.iter().position(|f| *f == self.format).unwrap_or(0);
+                let next = if forward {
+                    (index + 1) % formats.len()
+                } else {
+                    (index + formats.len() - 1) % formats.len()
+                };
+                let old_ext = format!(".{}", self.format.extension());
+                self.format = formats[next];
+                if let Some(stem) = self.path.strip_suffix(&old_ext) {
+                    self.path = format!("{}.{}", stem, self.format.extension());
+                }
+            }
+            Field::Tests => self.options.tests = !self.options.tests,
+            Field::Sources => self.options.sources = !self.options.sources,
+            Field::Exposures => self.options.exposures = !self.options.exposures,
+            Field::Path => {}
+        }
+    }
+
+    fn row(&self, field: Field, label: &str, value: String, enabled: bool) -> Line<'static> {
+        let focused = field == self.field;
+        let marker = if focused { "▶" } else { " " };
+        let value_style = if !enabled {
+            Style::default().fg(Color::DarkGray)
+        } else if focused {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        Line::from(vec![
+            Span::styled(format!(" {} ", marker), Style::default().fg(Color::Yellow)),
+            Span::styled(format!("{:<10}", label), Style::default().fg(Color::Cyan)),
+            Span::styled(value, value_style),
+        ])
+    }
+}
+
+fn checkbox(on: bool) -> String {
+    if on { "[x]" } else { "[ ]" }.to_string()
+}
+
+impl Component for LineageExportDialog {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match key.code {
+            KeyCode::Esc => Some(Action::CloseModal),
+            KeyCode::Enter => Some(Action::ConfirmModal),
+            KeyCode::Down | KeyCode::Tab => {
+                self.move_field(true);
+                None
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                self.move_field(false);
+                None
+            }
+            KeyCode::Right if self.field != Field::Path => {
+                self.change(true);
+                None
+            }
+            KeyCode::Left if self.field != Field::Path => {
+                self.change(false);
+                None
+            }
+            KeyCode::Char(' ') if self.field != Field::Path => {
+                self.change(true);
+                None
+            }
+            KeyCode::Backspace if self.field == Field::Path => {
+                self.path.pop();
+                self.path_edited = true;
+                None
+            }
+            KeyCode::Char(c) if self.field == Field::Path => {
+                self.path.push(c);
+                self.path_edited = true;
+                None
+            }
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let popup_width = 70.min(area.width.saturating_sub(4));
+        let popup_area = centered_popup(area, popup_width, 14);
+
+        frame.render_widget(Clear, popup_area);
+
+        let depth_enabled = self.scope != ScopeKind::Project;
+        let mut content = vec![
+            self.row(Field::Scope, "Export", self.scope_label(self.scope), true),
+            self.row(
+                Field::Depth,
+                "Depth",
+                format!("{} levels up and down", self.depth),
+                depth_enabled,
+            ),
+            self.row(Field::Format, "Format", self.format.label().to_string(), true),
+            self.row(Field::Tests, "Tests", checkbox(self.options.tests), true),
+            self.row(Field::Sources, "Sources", checkbox(self.options.sources), true),
+            self.row(Field::Exposures, "Exposures", checkbox(self.options.exposures), true),
+            Line::from(""),
+        ];
+
+        // Show the end of long paths, where the file name is
+        let max_path = popup_area.width.saturating_sub(16) as usize;
+        let path_chars = self.path.chars().count();
+        let shown_path = if path_chars > max_path {
+            let tail: String = self.path.chars().skip(path_chars - max_path + 1).collect();
+            format!("…{}", tail)
+        } else {
+            self.path.clone()
+        };
+        let mut path_line = self.row(Field::Path, "Path", shown_path, true);
+        if self.field == Field::Path {
+            path_line
+                .spans
+                .push(Span::styled("_", Style::default().fg(Color::Yellow)));
+        }
+        content.push(path_line);
+        content.push(Line::from(""));
+
+        content.push(Line::from(vec![
+            Span::styled(
+                " ↑/↓ ",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Field  "),
+            Span::styled(
+                " ←/→ ",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Change  "),
+            Span::styled(
+                " Enter ",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Export  "),
+            Span::styled(
+                " Esc ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Cancel"),
+        ]));
+
+        let paragraph = Paragraph::new(content).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(" Export Lineage ")
+                .title_style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+        );
+
+        frame.render_widget(paragraph, popup_area);
+        Ok(())
+    }
+}
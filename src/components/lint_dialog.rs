@@ -0,0 +1,239 @@
+//! Convention lint panel component
+//!
+//! Lists the models breaking the project's modeling conventions, grouped
+//! by file. Enter shows the model in the list, `e` opens its file.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::model::lint::{LintRule, LintViolation, LINT_CONFIG_FILE};
+use crate::services::editor::EditorTarget;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Convention lint panel
+#[derive(Default)]
+pub struct LintDialog {
+    violations: Vec<LintViolation>,
+    /// Why the rules couldn't be checked
+    error: Option<String>,
+    selected: usize,
+    scroll_offset: usize,
+}
+
+impl LintDialog {
+    pub fn open(&mut self, result: Result<Vec<LintViolation>, String>) {
+        *self = Self::default();
+        match result {
+            Ok(violations) => self.violations = violations,
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    fn selected_violation(&self) -> Option<&LintViolation> {
+        self.violations.get(self.selected)
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.violations.is_empty() {
+            return;
+        }
+        self.selected = self
+            .selected
+            .saturating_add_signed(delta)
+            .min(self.violations.len() - 1);
+    }
+
+    /// Lines, with the index of the selected violation's line
+    fn lines(&self) -> (Vec<Line<'static>>, usize) {
+        if let Some(ref error) = self.error {
+            return (
+                vec![Line::from(Span::styled(
+                    format!(" {}", error),
+                    Style::default().fg(Color::Red),
+                ))],
+                0,
+            );
+        }
+        if self.violations.is_empty() {
+            return (
+                vec![Line::from(Span::styled(
+                    " No convention violations",
+                    Style::default().fg(Color::Green),
+                ))],
+                0,
+            );
+        }
+
+        let mut lines = Vec::new();
+        let mut selected_line = 0;
+        let mut current_path = None;
+        for (i, violation) in self.violations.iter().enumerate() {
+            if current_path != Some(&violation.path) {
+                if current_path.is_some() {
+                    lines.push(Line::from(""));
+                }
+                current_path = Some(&violation.path);
+                lines.push(Line::from(Span::styled(
+                    format!(" {}", violation.path.as_deref().unwrap_or(&violation.name)),
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::BOLD),
+                )));
+            }
+            let selected = i == self.selected;
+            if selected {
+                selected_line = lines.len();
+            }
+            let (marker, style) = if selected {
+                (
+                    "▶",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                (" ", Style::default().fg(Color::White))
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {} ", marker), style),
+                Span::styled(
+                    format!("{:<22}", violation.rule.label()),
+                    Style::default().fg(rule_color(violation.rule)),
+                ),
+                Span::styled(violation.message.clone(), style),
+            ]));
+        }
+        (lines, selected_line)
+    }
+}
+
+impl Component for LintDialog {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(Action::CloseModal),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.move_selection(1);
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.move_selection(-1);
+                None
+            }
+            KeyCode::PageDown => {
+                self.move_selection(20);
+                None
+            }
+            KeyCode::PageUp => {
+                self.move_selection(-20);
+                None
+            }
+            KeyCode::Char('g') => {
+                self.selected = 0;
+                None
+            }
+            KeyCode::Char('G') => {
+                self.selected = self.violations.len().saturating_sub(1);
+                None
+            }
+            KeyCode::Enter => self
+                .selected_violation()
+                .map(|v| Action::RevealNode(v.unique_id.clone())),
+            KeyCode::Char('e') => self
+                .selected_violation()
+                .and_then(|v| v.path.clone())
+                .map(|path| Action::OpenFileInEditor(EditorTarget::file(path))),
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        frame.render_widget(Clear, area);
+
+        let margin = 2;
+        let overlay_area = Rect::new(
+            margin,
+            margin,
+            area.width.saturating_sub(margin * 2),
+            area.height.saturating_sub(margin * 2),
+        );
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(3)])
+            .split(overlay_area);
+
+        let (content, selected_line) = self.lines();
+
+        // Keep the selected row, and its file heading, visible
+        let visible_height = chunks[0].height.saturating_sub(2) as usize;
+        if selected_line <= self.scroll_offset {
+            self.scroll_offset = selected_line.saturating_sub(1);
+        } else if visible_height > 0 && selected_line >= self.scroll_offset + visible_height {
+            self.scroll_offset = selected_line + 1 - visible_height;
+        }
+
+        let title = if self.error.is_some() {
+            " Conventions ".to_string()
+        } else {
+            format!(" Conventions ({} violations) ", self.violations.len())
+        };
+        let paragraph = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Magenta))
+                    .title(title)
+                    .title_style(
+                        Style::default()
+                            .fg(Color::Magenta)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .scroll((self.scroll_offset as u16, 0));
+        frame.render_widget(paragraph, chunks[0]);
+
+        let key_style = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+        let help = Paragraph::new(Line::from(vec![
+            Span::styled(
+                " Esc/q ",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Close  "),
+            Span::styled(" j/k ", key_style),
+            Span::raw("Select  "),
+            Span::styled(" Enter ", key_style),
+            Span::raw("Show in list  "),
+            Span::styled(" e ", key_style),
+            Span::raw("Edit  "),
+            Span::styled(
+                format!("Rules: {}", LINT_CONFIG_FILE),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]))
+        .alignment(ratatui::layout::Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(help, chunks[1]);
+
+        Ok(())
+    }
+}
+
+fn rule_color(rule: LintRule) -> Color {
+    match rule {
+        LintRule::Naming | LintRule::YamlEntry => Color::Yellow,
+        LintRule::RefDirection | LintRule::HardCodedReference => Color::Red,
+        LintRule::FanOut | LintRule::RootModel => Color::Cyan,
+    }
+}
@@ -3,15 +3,29 @@
 //! Each component encapsulates its own state, event handling, and rendering logic.
 //! Components communicate through Actions rather than direct state mutation.
 
+pub mod audit_dialog;
+pub mod column_tests_dialog;
+pub mod cruft_dialog;
+pub mod dag_view;
+pub mod description_editor;
 pub mod detail;
 pub mod documentation;
+pub mod export_dialog;
 pub mod help_dialog;
 pub mod history_dialog;
 pub mod home;
+pub mod impact_dialog;
 pub mod info;
 pub mod layout;
 pub mod lineage;
+pub mod lineage_explorer;
+pub mod lineage_export_dialog;
+pub mod lint_dialog;
+pub mod new_model_wizard;
 pub mod quit_dialog;
+pub mod refactor_dialog;
+pub mod related_files_dialog;
+pub mod run_comparison_dialog;
 pub mod run_options_dialog;
 pub mod run_output_dialog;
 pub mod sample_data_dialog;
@@ -21,16 +35,32 @@ pub mod sql_highlight;
 pub mod table;
 pub mod tag_filter_dialog;
 pub mod target_selector;
+pub mod test_matrix_dialog;
+pub mod yaml_scaffold_dialog;
 
+pub use audit_dialog::AuditDialog;
+pub use column_tests_dialog::ColumnTestsDialog;
+pub use cruft_dialog::CruftDialog;
+pub use dag_view::DagView;
+pub use description_editor::DescriptionEditor;
 pub use detail::DetailComponent;
 pub use documentation::DocumentationComponent;
+pub use export_dialog::ExportDialog;
 pub use help_dialog::HelpDialog;
 pub use history_dialog::HistoryDialog;
 pub use home::{draw_home_screen, HomeComponent, HomeRenderContext};
+pub use impact_dialog::ImpactDialog;
 pub use info::ProjectInfoDialog;
 pub use layout::{calculate_main_layout, centered_popup};
 pub use lineage::LineageComponent;
+pub use lineage_explorer::LineageExplorer;
+pub use lineage_export_dialog::LineageExportDialog;
+pub use lint_dialog::LintDialog;
+pub use new_model_wizard::NewModelWizard;
 pub use quit_dialog::QuitDialog;
+pub use refactor_dialog::RefactorDialog;
+pub use related_files_dialog::RelatedFilesDialog;
+pub use run_comparison_dialog::RunComparisonDialog;
 pub use run_options_dialog::RunOptionsDialog;
 pub use run_output_dialog::RunOutputDialog;
 pub use sample_data_dialog::SampleDataDialog;
@@ -39,3 +69,5 @@ pub use splash::SplashComponent;
 pub use table::TableComponent;
 pub use tag_filter_dialog::TagFilterDialog;
 pub use target_selector::TargetSelectorDialog;
+pub use test_matrix_dialog::TestMatrixDialog;
+pub use yaml_scaffold_dialog::YamlScaffoldDialog;
@@ -0,0 +1,458 @@
+//! New model wizard component
+//!
+//! Walks through template, source (for staging models), folder and name,
+//! then previews the SQL. The App writes the model file and its YAML stub.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::components::centered_popup;
+use crate::services::model_template::{default_model_name, render_model, ModelTemplate, StagingSource};
+use crate::services::yaml_edit::yaml_files_in;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum Step {
+    #[default]
+    Template,
+    Source,
+    Folder,
+    Name,
+    Review,
+}
+
+/// A model ready to be written
+#[derive(Debug, Clone)]
+pub struct NewModel {
+    pub folder: String,
+    pub name: String,
+    pub sql: String,
+    /// Schema file for the YAML stub, relative to the project root
+    pub yaml_file: String,
+    pub source: Option<StagingSource>,
+}
+
+/// New model wizard
+#[derive(Default)]
+pub struct NewModelWizard {
+    step: Step,
+    template: usize,
+    sources: Vec<StagingSource>,
+    folders: Vec<String>,
+    project_path: PathBuf,
+    /// Filter text or name being typed
+    input: String,
+    selected: usize,
+    source: Option<usize>,
+    folder: String,
+    name: String,
+    /// Candidate schema files and whether they exist
+    yaml_files: Vec<(String, bool)>,
+    selected_yaml: usize,
+    scroll: usize,
+    error: Option<String>,
+}
+
+impl NewModelWizard {
+    pub fn open(&mut self, sources: Vec<StagingSource>, folders: Vec<String>, project_path: &Path) {
+        *self = Self {
+            sources,
+            folders,
+            project_path: project_path.to_path_buf(),
+            ..Self::default()
+        };
+    }
+
+    fn template(&self) -> ModelTemplate {
+        ModelTemplate::ALL[self.template]
+    }
+
+    fn staging_source(&self) -> Option<&StagingSource> {
+        match self.template() {
+            ModelTemplate::Staging => self.source.and_then(|i| self.sources.get(i)),
+            _ => None,
+        }
+    }
+
+    fn sql(&self) -> String {
+        render_model(self.template(), self.staging_source())
+    }
+
+    /// The model to create, once reviewed
+    pub fn result(&self) -> Option<NewModel> {
+        if self.step != Step::Review {
+            return None;
+        }
+        Some(NewModel {
+            folder: self.folder.clone(),
+            name: self.name.clone(),
+            sql: self.sql(),
+            yaml_file: self.yaml_files.get(self.selected_yaml)?.0.clone(),
+            source: self.staging_source().cloned(),
+        })
+    }
+
+    /// Indices of sources matching the filter
+    fn filtered_sources(&self) -> Vec<usize> {
+        let filter = self.input.to_lowercase();
+        (0..self.sources.len())
+            .filter(|&i| {
+                let source = &self.sources[i];
+                format!("{}.{}", source.source_name, source.table)
+                    .to_lowercase()
+                    .contains(&filter)
+            })
+            .collect()
+    }
+
+    fn filtered_folders(&self) -> Vec<&str> {
+        let filter = self.input.to_lowercase();
+        self.folders
+            .iter()
+            .filter(|f| f.to_lowercase().contains(&filter))
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn go_to(&mut self, step: Step) {
+        self.step = step;
+        self.selected = 0;
+        self.scroll = 0;
+        self.error = None;
+        self.input = match step {
+            Step::Name => self.name.clone(),
+            _ => String::new(),
+        };
+    }
+
+    /// Move the selection within a list of `len` items, or edit the filter
+    fn picker_key(&mut self, key: KeyEvent, len: usize) {
+        match key.code {
+            KeyCode::Down if self.selected + 1 < len => self.selected += 1,
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Backspace => {
+                self.input.pop();
+                self.selected = 0;
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                self.selected = 0;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_template_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return Some(Action::CloseModal),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.template = (self.template + 1).min(ModelTemplate::ALL.len() - 1);
+            }
+            KeyCode::Char('k') | KeyCode::Up => self.template = self.template.saturating_sub(1),
+            KeyCode::Enter => {
+                if self.template() != ModelTemplate::Staging {
+                    self.source = None;
+                    self.name.clear();
+                    self.go_to(Step::Folder);
+                } else if self.sources.is_empty() {
+                    self.error = Some("No sources are declared in this project".to_string());
+                } else {
+                    self.go_to(Step::Source);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_source_key(&mut self, key: KeyEvent) {
+        let matches = self.filtered_sources();
+        match key.code {
+            KeyCode::Esc => self.go_to(Step::Template),
+            KeyCode::Enter => {
+                if let Some(&index) = matches.get(self.selected) {
+                    self.source = Some(index);
+                    self.name = default_model_name(self.sources.get(index));
+                    self.go_to(Step::Folder);
+                }
+            }
+            _ => self.picker_key(key, matches.len()),
+        }
+    }
+
+    fn handle_folder_key(&mut self, key: KeyEvent) {
+        let matches = self.filtered_folders();
+        match key.code {
+            KeyCode::Esc => match self.template() {
+                ModelTemplate::Staging => self.go_to(Step::Source),
+                _ => self.go_to(Step::Template),
+            },
+            KeyCode::Enter => {
+                // Typing a path that doesn't exist yet creates the folder
+                let folder = match matches.get(self.selected) {
+                    Some(folder) => folder.to_string(),
+                    None => self.input.trim().trim_matches('/').to_string(),
+                };
+                if !folder.is_empty() {
+                    self.folder = folder;
+                    self.go_to(Step::Name);
+                }
+            }
+            _ => self.picker_key(key, matches.len()),
+        }
+    }
+
+    fn handle_name_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => self.go_to(Step::Folder),
+            KeyCode::Enter => {
+                let name = self.input.trim().to_string();
+                if name.is_empty() {
+                    return;
+                }
+                self.name = name;
+                self.yaml_files = yaml_files_in(&self.project_path, Path::new(&self.folder), &self.name)
+                    .into_iter()
+                    .map(|f| {
+                        let exists = self.project_path.join(&f).exists();
+                        (f, exists)
+                    })
+                    .collect();
+                self.selected_yaml = 0;
+                self.go_to(Step::Review);
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            _ => {}
+        }
+    }
+
+    fn handle_review_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc => self.go_to(Step::Name),
+            KeyCode::Enter => return Some(Action::ConfirmModal),
+            KeyCode::Tab if !self.yaml_files.is_empty() => {
+                self.selected_yaml = (self.selected_yaml + 1) % self.yaml_files.len();
+            }
+            KeyCode::BackTab if !self.yaml_files.is_empty() => {
+                self.selected_yaml =
+                    (self.selected_yaml + self.yaml_files.len() - 1) % self.yaml_files.len();
+            }
+            KeyCode::Char('j') | KeyCode::Down if self.scroll + 1 < self.sql().lines().count() => {
+                self.scroll += 1;
+            }
+            KeyCode::Char('k') | KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+        None
+    }
+
+    fn list_line(text: String, selected: bool) -> Line<'static> {
+        if selected {
+            Line::from(Span::styled(
+                format!(" ▶ {}", text),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ))
+        } else {
+            Line::from(Span::styled(format!("   {}", text), Style::default().fg(Color::White)))
+        }
+    }
+
+    /// Filter prompt followed by the visible part of a list
+    fn picker_lines(&self, prompt: &str, items: Vec<String>, empty: &str, rows: usize) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!(" {}", prompt),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                format!(" > {}_", self.input),
+                Style::default().fg(Color::Cyan),
+            )),
+            Line::from(""),
+        ];
+        if items.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!("   {}", empty),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        let rows = rows.saturating_sub(lines.len()).max(1);
+        let first_row = (self.selected + 1).saturating_sub(rows);
+        for (i, item) in items.into_iter().enumerate().skip(first_row).take(rows) {
+            lines.push(Self::list_line(item, i == self.selected));
+        }
+        lines
+    }
+
+    fn review_lines(&self) -> Vec<Line<'static>> {
+        let label = Style::default().fg(Color::DarkGray);
+        let value = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let mut yaml = vec![Span::styled(" YAML:  ", label)];
+        if let Some((file, exists)) = self.yaml_files.get(self.selected_yaml) {
+            yaml.push(Span::styled(file.clone(), value));
+            if !exists {
+                yaml.push(Span::styled(" (new file)", label));
+            }
+        }
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(" Model: ", label),
+                Span::styled(format!("{}/{}.sql", self.folder, self.name), value),
+            ]),
+            Line::from(yaml),
+            Line::from(""),
+        ];
+        lines.extend(
+            self.sql()
+                .lines()
+                .skip(self.scroll)
+                .map(|l| Line::from(Span::styled(format!(" {}", l), Style::default().fg(Color::White)))),
+        );
+        lines
+    }
+}
+
+impl Component for NewModelWizard {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match self.step {
+            Step::Template => self.handle_template_key(key),
+            Step::Source => {
+                self.handle_source_key(key);
+                None
+            }
+            Step::Folder => {
+                self.handle_folder_key(key);
+                None
+            }
+            Step::Name => {
+                self.handle_name_key(key);
+                None
+            }
+            Step::Review => self.handle_review_key(key),
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let popup_width = 80.min(area.width.saturating_sub(4));
+        let popup_height = 26.min(area.height.saturating_sub(2));
+        let popup_area = centered_popup(area, popup_width, popup_height);
+
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" New Model ")
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(2)])
+            .split(inner);
+        let rows = chunks[0].height as usize;
+
+        let mut content = match self.step {
+            Step::Template => {
+                let mut lines = vec![
+                    Line::from(Span::styled(
+                        " Template",
+                        Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+                    )),
+                    Line::from(""),
+                ];
+                for (i, template) in ModelTemplate::ALL.iter().enumerate() {
+                    lines.push(Self::list_line(template.label().to_string(), i == self.template));
+                }
+                lines
+            }
+            Step::Source => {
+                let items = self
+                    .filtered_sources()
+                    .into_iter()
+                    .map(|i| format!("{}.{}", self.sources[i].source_name, self.sources[i].table))
+                    .collect();
+                self.picker_lines("Source table (type to filter)", items, "No matching sources", rows)
+            }
+            Step::Folder => {
+                let items = self.filtered_folders().into_iter().map(String::from).collect();
+                self.picker_lines(
+                    "Folder (type to filter, or a new path)",
+                    items,
+                    "No match: Enter creates this folder",
+                    rows,
+                )
+            }
+            Step::Name => vec![
+                Line::from(Span::styled(
+                    format!(" Model name (in {})", self.folder),
+                    Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+                )),
+                Line::from(Span::styled(
+                    format!(" > {}_", self.input),
+                    Style::default().fg(Color::Cyan),
+                )),
+            ],
+            Step::Review => self.review_lines(),
+        };
+        if let Some(ref error) = self.error {
+            content.push(Line::from(""));
+            content.push(Line::from(Span::styled(
+                format!(" {}", error),
+                Style::default().fg(Color::Red),
+            )));
+        }
+        frame.render_widget(Paragraph::new(content), chunks[0]);
+
+        let (enter_label, esc_label) = match self.step {
+            Step::Template => ("Next  ", "Cancel"),
+            Step::Review => ("Create  ", "Back"),
+            _ => ("Next  ", "Back"),
+        };
+        let mut help = vec![
+            Span::styled(
+                " Enter ",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(enter_label),
+        ];
+        if self.step == Step::Review {
+            help.push(Span::styled(
+                " Tab ",
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ));
+            help.push(Span::raw("YAML file  "));
+        }
+        help.push(Span::styled(
+            " Esc ",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+        help.push(Span::raw(esc_label));
+        frame.render_widget(
+            Paragraph::new(vec![Line::from(""), Line::from(help)]),
+            chunks[1],
+        );
+        Ok(())
+    }
+}
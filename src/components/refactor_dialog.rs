@@ -0,0 +1,286 @@
+//! Rename/move dialog component
+//!
+//! Asks for the model's new name and folder, then shows every file the
+//! refactoring touches as a diff. The App builds and applies the plan.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::components::centered_popup;
+use crate::model::diff::{diff_lines, with_context, DiffLine};
+use crate::services::refactor::RefactorPlan;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Unchanged lines shown around each edit
+const DIFF_CONTEXT: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum Field {
+    #[default]
+    Name,
+    Folder,
+}
+
+/// Rename/move dialog
+#[derive(Default)]
+pub struct RefactorDialog {
+    pub node_id: String,
+    old_name: String,
+    pub name: String,
+    pub folder: String,
+    focus: Field,
+    /// Set once the preview has been built
+    pub plan: Option<RefactorPlan>,
+    preview: Vec<Line<'static>>,
+    scroll: usize,
+    error: Option<String>,
+}
+
+impl RefactorDialog {
+    pub fn open(&mut self, node_id: &str, name: &str, folder: &str) {
+        *self = Self {
+            node_id: node_id.to_string(),
+            old_name: name.to_string(),
+            name: name.to_string(),
+            folder: folder.to_string(),
+            ..Self::default()
+        };
+    }
+
+    pub fn set_plan(&mut self, plan: RefactorPlan) {
+        self.preview = render_plan(&plan);
+        self.plan = Some(plan);
+        self.scroll = 0;
+        self.error = None;
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    fn input(&mut self) -> &mut String {
+        match self.focus {
+            Field::Name => &mut self.name,
+            Field::Folder => &mut self.folder,
+        }
+    }
+
+    fn handle_input_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc => return Some(Action::CloseModal),
+            KeyCode::Enter => return Some(Action::ConfirmModal),
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                self.focus = match self.focus {
+                    Field::Name => Field::Folder,
+                    Field::Folder => Field::Name,
+                };
+            }
+            KeyCode::Backspace => {
+                self.input().pop();
+            }
+            KeyCode::Char(c) => self.input().push(c),
+            _ => {}
+        }
+        self.error = None;
+        None
+    }
+
+    fn handle_preview_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let max_scroll = self.preview.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc => {
+                self.plan = None;
+                self.preview.clear();
+            }
+            KeyCode::Enter | KeyCode::Char('y') => return Some(Action::ConfirmModal),
+            KeyCode::Char('j') | KeyCode::Down => self.scroll = (self.scroll + 1).min(max_scroll),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageDown => self.scroll = (self.scroll + 10).min(max_scroll),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            _ => {}
+        }
+        None
+    }
+
+    fn input_lines(&self) -> Vec<Line<'static>> {
+        let field = |label: &str, value: &str, focused: bool| {
+            let style = if focused {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let cursor = if focused { "_" } else { "" };
+            Line::from(vec![
+                Span::styled(format!(" {:<8}", label), Style::default().fg(Color::DarkGray)),
+                Span::styled(format!("{}{}", value, cursor), style),
+            ])
+        };
+        vec![
+            Line::from(Span::styled(
+                format!(" Rename or move {}", self.old_name),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            field("Name", &self.name, self.focus == Field::Name),
+            field("Folder", &self.folder, self.focus == Field::Folder),
+            Line::from(""),
+            Line::from(Span::styled(
+                " refs in downstream models and tests and the YAML entry are updated",
+                Style::default().fg(Color::DarkGray),
+            )),
+        ]
+    }
+}
+
+/// The plan as a list of per-file diffs
+fn render_plan(plan: &RefactorPlan) -> Vec<Line<'static>> {
+    let header = |text: String| {
+        Line::from(Span::styled(
+            text,
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ))
+    };
+    let mut lines = Vec::new();
+    if plan.is_move() {
+        lines.push(header(format!(" Move {} → {}", plan.from, plan.to)));
+        lines.push(Line::from(""));
+    }
+    if plan.edits.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " No references to update",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for edit in &plan.edits {
+        lines.push(header(format!(" {}", edit.path)));
+        let before: Vec<&str> = edit.before.lines().collect();
+        let after: Vec<&str> = edit.after.lines().collect();
+        for diff_line in with_context(&diff_lines(&before, &after), DIFF_CONTEXT) {
+            lines.push(match diff_line {
+                DiffLine::Same(text) => Line::from(Span::styled(
+                    format!("   {}", text),
+                    Style::default().fg(Color::Gray),
+                )),
+                DiffLine::Removed(text) => Line::from(Span::styled(
+                    format!(" - {}", text),
+                    Style::default().fg(Color::Red),
+                )),
+                DiffLine::Added(text) => Line::from(Span::styled(
+                    format!(" + {}", text),
+                    Style::default().fg(Color::Green),
+                )),
+                DiffLine::Skipped(count) => Line::from(Span::styled(
+                    format!("   ⋯ {} unchanged lines", count),
+                    Style::default().fg(Color::DarkGray),
+                )),
+            });
+        }
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
+impl Component for RefactorDialog {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = if self.plan.is_some() {
+            self.handle_preview_key(key)
+        } else {
+            self.handle_input_key(key)
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let previewing = self.plan.is_some();
+        let (popup_width, popup_height) = if previewing {
+            (area.width.saturating_sub(8), area.height.saturating_sub(4))
+        } else {
+            (70.min(area.width.saturating_sub(4)), 12.min(area.height.saturating_sub(2)))
+        };
+        let popup_area = centered_popup(area, popup_width, popup_height);
+
+        frame.render_widget(Clear, popup_area);
+
+        let title = match self.plan {
+            Some(ref plan) => format!(
+                " Preview: {} → {} ({} files) ",
+                plan.old_name,
+                plan.new_name,
+                plan.edits.len() + usize::from(plan.is_move())
+            ),
+            None => " Rename / Move Model ".to_string(),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(title)
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let mut content = if previewing {
+            self.preview.iter().skip(self.scroll).cloned().collect()
+        } else {
+            self.input_lines()
+        };
+        if let Some(ref error) = self.error {
+            content.push(Line::from(""));
+            content.push(Line::from(Span::styled(
+                format!(" {}", error),
+                Style::default().fg(Color::Red),
+            )));
+        }
+        frame.render_widget(Paragraph::new(content), chunks[0]);
+
+        let (enter_label, esc_label) = if previewing {
+            ("Apply  ", "Back")
+        } else {
+            ("Preview  ", "Cancel")
+        };
+        let mut help = vec![
+            Span::styled(
+                " Enter ",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(enter_label),
+        ];
+        if previewing {
+            help.push(Span::styled(
+                " j/k ",
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ));
+            help.push(Span::raw("Scroll  "));
+        } else {
+            help.push(Span::styled(
+                " Tab ",
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ));
+            help.push(Span::raw("Switch field  "));
+        }
+        help.push(Span::styled(
+            " Esc ",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+        help.push(Span::raw(esc_label));
+        frame.render_widget(Paragraph::new(Line::from(help)), chunks[1]);
+        Ok(())
+    }
+}
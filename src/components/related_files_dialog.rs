@@ -0,0 +1,180 @@
+//! Related files dialog component
+//!
+//! Lists the files belonging to the selected node (source, schema YAML,
+//! compiled and run SQL) and opens the chosen one in the external editor.
+//! Also used to pick a YAML declaration (the model or one of its columns).
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::components::centered_popup;
+use crate::model::yaml_index::YamlIndex;
+use crate::model::Node;
+use crate::services::editor::EditorTarget;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::path::Path;
+
+/// A file that can be opened, relative to the project root
+#[derive(Debug, Clone)]
+pub struct RelatedFile {
+    pub label: String,
+    pub target: EditorTarget,
+}
+
+/// Related files dialog
+#[derive(Default)]
+pub struct RelatedFilesDialog {
+    pub title: String,
+    pub files: Vec<RelatedFile>,
+    pub selected_index: usize,
+}
+
+impl RelatedFilesDialog {
+    /// Collect the node's related files that exist in the project
+    pub fn open(&mut self, node: &Node, project_path: &Path, yaml_index: &YamlIndex) {
+        let files = node
+            .related_files()
+            .into_iter()
+            .filter(|(_, path)| project_path.join(path).exists())
+            .map(|(label, path)| {
+                // Land on the node's declaration in the schema file
+                let line = if label == "Schema YAML" {
+                    yaml_index.find_node(node).map(|entry| entry.line)
+                } else {
+                    None
+                };
+                RelatedFile {
+                    label: label.to_string(),
+                    target: EditorTarget::at_line(path, line),
+                }
+            })
+            .collect();
+        self.open_locations(format!("Files: {}", node.name), files);
+    }
+
+    /// Show a list of locations to choose from
+    pub fn open_locations(&mut self, title: String, files: Vec<RelatedFile>) {
+        self.title = title;
+        self.files = files;
+        self.selected_index = 0;
+    }
+}
+
+impl Component for RelatedFilesDialog {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match key.code {
+            KeyCode::Esc | KeyCode::Char('E') => Some(Action::CloseModal),
+            KeyCode::Enter => self
+                .files
+                .get(self.selected_index)
+                .map(|file| Action::OpenFileInEditor(file.target.clone())),
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.selected_index + 1 < self.files.len() {
+                    self.selected_index += 1;
+                }
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.selected_index = self.selected_index.saturating_sub(1);
+                None
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                let index = (c as usize).wrapping_sub('1' as usize);
+                self.files.get(index).map(|file| {
+                    self.selected_index = index;
+                    Action::OpenFileInEditor(file.target.clone())
+                })
+            }
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let popup_width = 70.min(area.width.saturating_sub(4));
+        let popup_height = (self.files.len() as u16 + 5)
+            .max(7)
+            .min(area.height.saturating_sub(2));
+        let popup_area = centered_popup(area, popup_width, popup_height);
+
+        frame.render_widget(Clear, popup_area);
+
+        let mut content = Vec::new();
+        if self.files.is_empty() {
+            content.push(Line::from(Span::styled(
+                " No files found for this node",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        let label_width = self.files.iter().map(|f| f.label.chars().count()).max().unwrap_or(0);
+        // Keep the selection visible in long lists
+        let visible_rows = (popup_area.height as usize).saturating_sub(5).max(1);
+        let first_row = (self.selected_index + 1).saturating_sub(visible_rows);
+        for (i, file) in self
+            .files
+            .iter()
+            .enumerate()
+            .skip(first_row)
+            .take(visible_rows)
+        {
+            let selected = i == self.selected_index;
+            let (marker, style) = if selected {
+                (
+                    "▶",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                (" ", Style::default().fg(Color::White))
+            };
+            let location = match file.target.line {
+                Some(line) => format!("{}:{}", file.target.path, line),
+                None => file.target.path.clone(),
+            };
+            content.push(Line::from(vec![
+                Span::styled(format!(" {} {} ", marker, i + 1), style),
+                Span::styled(format!("{:<label_width$} ", file.label), style),
+                Span::styled(location, Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+        content.push(Line::from(""));
+
+        content.push(Line::from(vec![
+            Span::styled(
+                " Enter ",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Open  "),
+            Span::styled(
+                " Esc ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Close"),
+        ]));
+
+        let paragraph = Paragraph::new(content).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(format!(" {} ", self.title))
+                .title_style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+        );
+
+        frame.render_widget(paragraph, popup_area);
+        Ok(())
+    }
+}
@@ -0,0 +1,332 @@
+//! Run comparison dialog component
+//!
+//! Shows the differences between two runs from history: status changes,
+//! duration deltas, new errors and an output diff.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::model::comparison::RunComparison;
+use crate::model::diff::DiffLine;
+use crate::model::history::format_duration;
+use crate::model::{RunHistoryEntry, RunStatus};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
+
+/// Maximum number of duration deltas listed
+const MAX_DURATION_ROWS: usize = 20;
+
+/// Run comparison dialog
+#[derive(Default)]
+pub struct RunComparisonDialog {
+    pub comparison: Option<RunComparison>,
+    pub scroll_offset: usize,
+}
+
+impl Component for RunComparisonDialog {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(Action::CloseModal),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.scroll_offset = self.scroll_offset.saturating_add(1);
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(1);
+                None
+            }
+            KeyCode::PageDown => {
+                self.scroll_offset = self.scroll_offset.saturating_add(20);
+                None
+            }
+            KeyCode::PageUp => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(20);
+                None
+            }
+            KeyCode::Char('g') => {
+                self.scroll_offset = 0;
+                None
+            }
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        frame.render_widget(Clear, area);
+
+        let margin = 2;
+        let overlay_area = Rect::new(
+            margin,
+            margin,
+            area.width.saturating_sub(margin * 2),
+            area.height.saturating_sub(margin * 2),
+        );
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(3)])
+            .split(overlay_area);
+
+        let content = match self.comparison {
+            Some(ref comparison) => render_comparison(comparison),
+            None => vec![Line::from("Nothing to compare")],
+        };
+
+        let total = content.len();
+        let visible_height = chunks[0].height.saturating_sub(2) as usize;
+        let max_scroll = total.saturating_sub(visible_height);
+        if self.scroll_offset > max_scroll {
+            self.scroll_offset = max_scroll;
+        }
+
+        let paragraph = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Magenta))
+                    .title(" Compare Runs ")
+                    .title_style(
+                        Style::default()
+                            .fg(Color::Magenta)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .scroll((self.scroll_offset as u16, 0));
+
+        frame.render_widget(paragraph, chunks[0]);
+
+        if total > visible_height {
+            let mut scrollbar_state = ScrollbarState::new(max_scroll).position(self.scroll_offset);
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight),
+                chunks[0].inner(ratatui::layout::Margin {
+                    vertical: 1,
+                    horizontal: 0,
+                }),
+                &mut scrollbar_state,
+            );
+        }
+
+        let help = Paragraph::new(Line::from(vec![
+            Span::styled(
+                " Esc/q ",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Close  "),
+            Span::styled(
+                " j/k ",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Scroll  "),
+            Span::styled(
+                " g ",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Top"),
+        ]))
+        .alignment(ratatui::layout::Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+
+        frame.render_widget(help, chunks[1]);
+
+        Ok(())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helper functions
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn render_comparison(comparison: &RunComparison) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+
+    lines.push(render_run_summary("Base", &comparison.base));
+    lines.push(render_run_summary("Head", &comparison.head));
+    let duration_delta = comparison.head.duration_secs - comparison.base.duration_secs;
+    lines.push(Line::from(vec![
+        Span::styled("  Total duration: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(format_delta(duration_delta), delta_style(duration_delta)),
+    ]));
+
+    // Status changes
+    section_header(&mut lines, "Status changes", comparison.status_changes.len());
+    if comparison.status_changes.is_empty() {
+        lines.push(empty_line("No node changed status"));
+    }
+    for change in &comparison.status_changes {
+        let before = change.before.clone().unwrap_or_else(|| "not run".to_string());
+        let after = change.after.clone().unwrap_or_else(|| "not run".to_string());
+        lines.push(Line::from(vec![
+            Span::raw(format!("  {:<40} ", change.name)),
+            Span::styled(before.clone(), Style::default().fg(node_status_color(&before))),
+            Span::styled(" → ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                after.clone(),
+                Style::default()
+                    .fg(node_status_color(&after))
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+
+    // Duration deltas
+    section_header(&mut lines, "Duration changes", comparison.duration_deltas.len());
+    if comparison.duration_deltas.is_empty() {
+        lines.push(empty_line("No duration information to compare"));
+    }
+    for delta in comparison.duration_deltas.iter().take(MAX_DURATION_ROWS) {
+        lines.push(Line::from(vec![
+            Span::raw(format!(
+                "  {:<40} {:>8.2}s → {:>8.2}s  ",
+                delta.name, delta.before, delta.after
+            )),
+            Span::styled(format_delta(delta.delta()), delta_style(delta.delta())),
+        ]));
+    }
+    if comparison.duration_deltas.len() > MAX_DURATION_ROWS {
+        lines.push(empty_line(&format!(
+            "… {} more",
+            comparison.duration_deltas.len() - MAX_DURATION_ROWS
+        )));
+    }
+
+    // New errors
+    section_header(&mut lines, "New errors", comparison.new_errors.len());
+    if comparison.new_errors.is_empty() {
+        lines.push(empty_line("No new errors"));
+    }
+    for error in &comparison.new_errors {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", error),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    // Output diff
+    section_header(&mut lines, "Output diff", 0);
+    let has_changes = comparison
+        .output_diff
+        .iter()
+        .any(|l| matches!(l, DiffLine::Added(_) | DiffLine::Removed(_)));
+    if !has_changes {
+        lines.push(empty_line("Outputs are identical (ignoring timestamps and timings)"));
+        return lines;
+    }
+    for diff_line in &comparison.output_diff {
+        let line = match diff_line {
+            DiffLine::Same(text) => Line::from(Span::styled(
+                format!("  {}", text),
+                Style::default().fg(Color::Gray),
+            )),
+            DiffLine::Removed(text) => Line::from(Span::styled(
+                format!("- {}", text),
+                Style::default().fg(Color::Red),
+            )),
+            DiffLine::Added(text) => Line::from(Span::styled(
+                format!("+ {}", text),
+                Style::default().fg(Color::Green),
+            )),
+            DiffLine::Skipped(count) => Line::from(Span::styled(
+                format!("  ⋯ {} unchanged lines", count),
+                Style::default().fg(Color::DarkGray),
+            )),
+        };
+        lines.push(line);
+    }
+
+    lines
+}
+
+fn render_run_summary(label: &str, entry: &RunHistoryEntry) -> Line<'static> {
+    let status_color = match entry.status {
+        RunStatus::Success => Color::Green,
+        RunStatus::Failed => Color::Red,
+        RunStatus::Running => Color::Yellow,
+    };
+    Line::from(vec![
+        Span::styled(
+            format!("{}: ", label),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("{} ", entry.status_icon()),
+            Style::default().fg(status_color),
+        ),
+        Span::raw(format!(
+            "{}  {}  ",
+            entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            entry.command
+        )),
+        Span::styled(
+            format!("({})", format_duration(entry.duration_secs)),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])
+}
+
+fn section_header(lines: &mut Vec<Line<'static>>, title: &str, count: usize) {
+    lines.push(Line::from(""));
+    let text = if count > 0 {
+        format!("{} ({})", title, count)
+    } else {
+        title.to_string()
+    };
+    lines.push(Line::from(Span::styled(
+        text,
+        Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD),
+    )));
+}
+
+fn empty_line(text: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        format!("  {}", text),
+        Style::default().fg(Color::DarkGray),
+    ))
+}
+
+fn format_delta(delta: f64) -> String {
+    if delta >= 0.0 {
+        format!("+{:.2}s", delta)
+    } else {
+        format!("{:.2}s", delta)
+    }
+}
+
+/// Slower is red, faster is green
+fn delta_style(delta: f64) -> Style {
+    if delta > 0.0 {
+        Style::default().fg(Color::Red)
+    } else {
+        Style::default().fg(Color::Green)
+    }
+}
+
+fn node_status_color(status: &str) -> Color {
+    match status {
+        "success" => Color::Green,
+        "error" => Color::Red,
+        "warn" => Color::Yellow,
+        _ => Color::DarkGray,
+    }
+}
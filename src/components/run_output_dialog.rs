@@ -1,37 +1,133 @@
 //! Run output dialog component
 //!
 //! Displays the output of a running or completed dbt command.
+//! The raw view is a log viewer with search, error/warning jumps, filters
+//! and folding of long multi-line entries. An errors panel lists the
+//! failures parsed from the output and opens them in the editor.
 
 use crate::action::Action;
 use crate::component::Component;
+use crate::model::estimate::RunEstimate;
+use crate::model::history::format_duration;
+use crate::model::log_view::{
+    build_rows, entry_of_line, file_location, find_matches, group_entries, LogEntry, LogFilter,
+    LogLevel, LogRow,
+};
+use crate::model::timeline::{RunTimeline, TimelineBar};
+use crate::model::run_errors::{parse_run_errors, RunError};
 use crate::model::{ModelRun, ModelRunStatus, RunOutput, RunOutputViewMode, RunStatus};
+use crate::services::editor::EditorTarget;
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    widgets::{
+        Block, Borders, Clear, Gauge, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+    },
     Frame,
 };
+use std::collections::{HashMap, HashSet};
 use unicode_width::UnicodeWidthStr;
 
+/// Lines above the log in the raw view (command, status, separator, blank)
+const LOG_HEADER_LINES: usize = 4;
+
+/// Lines kept above the target when jumping
+const JUMP_CONTEXT: usize = 2;
+
+/// Text input in the raw view
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LogInput {
+    Search,
+    Model,
+}
+
+/// Navigation requested by a key; resolved on the next draw, when the output is available
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LogCommand {
+    FirstMatch,
+    NextMatch,
+    PrevMatch,
+    NextProblem,
+    PrevProblem,
+    ToggleFold,
+    ToggleAllFolds,
+}
+
+/// Raw view state derived from the output, rebuilt only when the output,
+/// filter, search or folds change rather than on every draw
+#[derive(Default)]
+struct LogCache {
+    /// Output length the lines were split from
+    output_len: Option<usize>,
+    lines: Vec<String>,
+    entries: Vec<LogEntry>,
+    /// Filter, search and expanded entries the rows and matches were built for
+    view_key: Option<(LogFilter, String, HashSet<usize>)>,
+    rows: Vec<LogRow>,
+    /// Row of each displayed line
+    line_rows: HashMap<usize, usize>,
+    matches: Vec<usize>,
+}
+
 /// Run output dialog
 #[derive(Default)]
 pub struct RunOutputDialog {
     pub scroll_offset: usize,
+    /// Whether the last drawn view was the raw log
+    raw_view: bool,
+    input: Option<LogInput>,
+    input_buffer: String,
+    search: String,
+    filter: LogFilter,
+    /// Indices of expanded foldable entries
+    expanded: HashSet<usize>,
+    /// Position in the current search matches
+    match_cursor: Option<usize>,
+    match_count: usize,
+    /// File referenced by the current match, opened with `o`
+    match_target: Option<EditorTarget>,
+    pending: Option<LogCommand>,
+    /// Errors parsed from the output
+    errors: Vec<RunError>,
+    /// Output length when errors were last parsed
+    errors_parsed_len: usize,
+    log: LogCache,
+    show_errors: bool,
+    error_index: usize,
 }
 
 impl Component for RunOutputDialog {
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if let Some(input) = self.input {
+            self.handle_input(input, key);
+            return Ok(None);
+        }
+        if self.show_errors {
+            return Ok(self.handle_errors_key(key));
+        }
+        if key.code == KeyCode::Char('e') && !self.errors.is_empty() {
+            self.show_errors = true;
+            return Ok(None);
+        }
+        if self.raw_view {
+            if let Some(consumed) = self.handle_log_key(key) {
+                return Ok(consumed);
+            }
+        }
+
         let action = match key.code {
             KeyCode::Char('j') | KeyCode::Down => Some(Action::ScrollDown),
             KeyCode::Char('k') | KeyCode::Up => Some(Action::ScrollUp),
             KeyCode::PageUp => Some(Action::PageUp),
             KeyCode::PageDown => Some(Action::PageDown),
             KeyCode::Char('v') => Some(Action::ToggleOutputView),
+            KeyCode::Char('t') => Some(Action::ToggleTimelineView),
             KeyCode::Esc | KeyCode::Char('q') => Some(Action::CloseModal),
             KeyCode::Char('h') => Some(Action::OpenHistory),
+            KeyCode::Char('x') => Some(Action::OpenExport),
             _ => None,
         };
         Ok(action)
@@ -63,11 +159,418 @@ impl Component for RunOutputDialog {
 }
 
 impl RunOutputDialog {
+    /// Reset scroll and log viewer state for a new output
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Keys while the errors panel is open
+    fn handle_errors_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('e') => {
+                self.show_errors = false;
+                None
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.error_index + 1 < self.errors.len() {
+                    self.error_index += 1;
+                }
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.error_index = self.error_index.saturating_sub(1);
+                None
+            }
+            KeyCode::Enter => {
+                let error = self.errors.get(self.error_index)?;
+                let (path, line, column) = error.location();
+                Some(Action::OpenFileInEditor(EditorTarget {
+                    path: path.to_string(),
+                    line,
+                    column,
+                }))
+            }
+            KeyCode::Char('s') => {
+                let error = self.errors.get(self.error_index)?;
+                Some(Action::OpenFileInEditor(EditorTarget::at_line(
+                    error.file.clone(),
+                    error.source_line,
+                )))
+            }
+            KeyCode::Char('p') => {
+                let error = self.errors.get(self.error_index)?;
+                (error.resource_type == "test").then(|| Action::PreviewFailingRows(error.node.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Log viewer keys; returns None if the key is not handled here
+    fn handle_log_key(&mut self, key: KeyEvent) -> Option<Option<Action>> {
+        let command = match key.code {
+            KeyCode::Char('/') => {
+                self.input_buffer = self.search.clone();
+                self.input = Some(LogInput::Search);
+                return Some(None);
+            }
+            KeyCode::Char('m') => {
+                self.input_buffer = match self.filter {
+                    LogFilter::Model(ref name) => name.clone(),
+                    _ => String::new(),
+                };
+                self.input = Some(LogInput::Model);
+                return Some(None);
+            }
+            KeyCode::Char('f') => {
+                self.set_filter(self.filter.next());
+                return Some(None);
+            }
+            KeyCode::Char('o') => {
+                return Some(self.match_target.clone().map(Action::OpenFileInEditor));
+            }
+            KeyCode::Char('n') => LogCommand::NextMatch,
+            KeyCode::Char('N') => LogCommand::PrevMatch,
+            KeyCode::Char(']') => LogCommand::NextProblem,
+            KeyCode::Char('[') => LogCommand::PrevProblem,
+            KeyCode::Char('z') => LogCommand::ToggleFold,
+            KeyCode::Char('Z') => LogCommand::ToggleAllFolds,
+            _ => return None,
+        };
+        self.pending = Some(command);
+        Some(None)
+    }
+
+    fn handle_input(&mut self, input: LogInput, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.input = None;
+            }
+            KeyCode::Enter => {
+                self.input = None;
+                let value = self.input_buffer.trim().to_string();
+                match input {
+                    LogInput::Search => {
+                        self.search = value;
+                        self.match_cursor = None;
+                        self.pending = Some(LogCommand::FirstMatch);
+                    }
+                    LogInput::Model if value.is_empty() => self.set_filter(LogFilter::All),
+                    LogInput::Model => self.set_filter(LogFilter::Model(value)),
+                }
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                self.input_buffer.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn set_filter(&mut self, filter: LogFilter) {
+        self.filter = filter;
+        self.match_cursor = None;
+        self.match_target = None;
+        self.scroll_offset = 0;
+    }
+
+    /// Split and group the output if it changed since the last draw
+    fn refresh_log_lines(&mut self, output: &str) {
+        if self.log.output_len == Some(output.len()) {
+            return;
+        }
+        self.log.lines = output.lines().map(String::from).collect();
+        self.log.entries = group_entries(&self.log.lines);
+        self.log.output_len = Some(output.len());
+        self.log.view_key = None;
+    }
+
+    /// Rebuild rows and search matches if the lines, filter, search or folds changed
+    fn refresh_log_rows(&mut self) {
+        let key = (self.filter.clone(), self.search.clone(), self.expanded.clone());
+        if self.log.view_key.as_ref() == Some(&key) {
+            return;
+        }
+        let log = &mut self.log;
+        log.rows = build_rows(&log.lines, &log.entries, &self.filter, &self.expanded);
+        log.line_rows = log
+            .rows
+            .iter()
+            .enumerate()
+            .filter_map(|(row, r)| match *r {
+                LogRow::Line(line) => Some((line, row)),
+                LogRow::Folded { .. } => None,
+            })
+            .collect();
+        log.matches = find_matches(&log.lines, &log.entries, &self.filter, &self.search);
+        log.view_key = Some(key);
+    }
+
+    /// Apply a pending navigation command against the cached log
+    fn apply_command(&mut self, command: LogCommand) {
+        let top = self.scroll_offset.saturating_sub(LOG_HEADER_LINES);
+        let anchor = (self.scroll_offset + JUMP_CONTEXT).saturating_sub(LOG_HEADER_LINES);
+        let log = &self.log;
+        let row_of = |line: usize| log.line_rows.get(&line).copied();
+
+        match command {
+            LogCommand::FirstMatch | LogCommand::NextMatch | LogCommand::PrevMatch => {
+                let matches = &log.matches;
+                if matches.is_empty() {
+                    self.match_cursor = None;
+                    self.match_target = None;
+                    return;
+                }
+                let cursor = match (command, self.match_cursor) {
+                    (LogCommand::NextMatch, Some(c)) => (c + 1) % matches.len(),
+                    (LogCommand::PrevMatch, Some(c)) => (c + matches.len() - 1) % matches.len(),
+                    (LogCommand::PrevMatch, None) => matches.len() - 1,
+                    // First match at or below the top of the view
+                    _ => {
+                        let top_line = log.rows.get(top).map_or(0, |r| match *r {
+                            LogRow::Line(i) => i,
+                            LogRow::Folded { entry, .. } => log.entries[entry].start,
+                        });
+                        matches.iter().position(|&m| m >= top_line).unwrap_or(0)
+                    }
+                };
+                let line = matches[cursor];
+                self.match_cursor = Some(cursor);
+                self.match_target = file_location(&log.lines, &log.entries, line)
+                    .map(|(path, line, column)| EditorTarget { path, line, column });
+                self.jump_to_line(line);
+            }
+            LogCommand::NextProblem | LogCommand::PrevProblem => {
+                let mut problems = log
+                    .entries
+                    .iter()
+                    .filter(|e| e.level != LogLevel::Info)
+                    .filter_map(|e| row_of(e.start));
+                let target = if command == LogCommand::NextProblem {
+                    problems.find(|&row| row > anchor)
+                } else {
+                    problems.rev().find(|&row| row < anchor)
+                };
+                if let Some(row) = target {
+                    self.scroll_offset = (row + LOG_HEADER_LINES).saturating_sub(JUMP_CONTEXT);
+                }
+            }
+            LogCommand::ToggleFold => {
+                // First foldable entry at or below the top of the view
+                let target = log.entries.iter().enumerate().find(|(_, e)| {
+                    e.is_foldable() && row_of(e.start).is_some_and(|row| row >= top)
+                });
+                if let Some((index, _)) = target {
+                    if !self.expanded.remove(&index) {
+                        self.expanded.insert(index);
+                    }
+                }
+            }
+            LogCommand::ToggleAllFolds => {
+                let foldable: Vec<usize> = log
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, e)| e.is_foldable())
+                    .map(|(i, _)| i)
+                    .collect();
+                if foldable.iter().all(|i| self.expanded.contains(i)) {
+                    self.expanded.clear();
+                } else {
+                    self.expanded.extend(foldable);
+                }
+            }
+        }
+        self.refresh_log_rows();
+    }
+
+    /// Scroll to a line, expanding its entry if it is folded
+    fn jump_to_line(&mut self, line: usize) {
+        let entries = &self.log.entries;
+        if let Some(entry) = entry_of_line(entries, line) {
+            if entries[entry].is_foldable() && line != entries[entry].start {
+                self.expanded.insert(entry);
+                self.refresh_log_rows();
+            }
+        }
+        if let Some(&row) = self.log.line_rows.get(&line) {
+            self.scroll_offset = (row + LOG_HEADER_LINES).saturating_sub(JUMP_CONTEXT);
+        }
+    }
+
+    /// Render the parsed errors: list on the left, selected message on the right
+    fn render_errors_panel(&self, frame: &mut Frame, area: Rect) {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+            .split(area);
+
+        let list_lines: Vec<Line> = self
+            .errors
+            .iter()
+            .enumerate()
+            .map(|(i, error)| {
+                let style = if i == self.error_index {
+                    Style::default().bg(Color::Blue).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+                Line::from(vec![
+                    Span::styled("✗ ", Style::default().fg(Color::Red)),
+                    Span::styled(format!("{} {} ", error.resource_type, error.node), style),
+                    Span::styled(
+                        error.summary().to_string(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ])
+            })
+            .collect();
+
+        // Keep the selection visible
+        let visible = columns[0].height.saturating_sub(2) as usize;
+        let list_scroll = (self.error_index + 1).saturating_sub(visible);
+
+        let list = Paragraph::new(list_lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Red))
+                    .title(format!(" Errors ({}) ", self.errors.len())),
+            )
+            .scroll((list_scroll as u16, 0));
+        frame.render_widget(list, columns[0]);
+
+        let mut detail_lines = Vec::new();
+        if let Some(error) = self.errors.get(self.error_index) {
+            detail_lines.push(Line::from(Span::styled(
+                format!("{} in {} {}", error.kind, error.resource_type, error.node),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )));
+            detail_lines.push(Line::from(vec![
+                Span::styled("File: ", Style::default().fg(Color::Cyan)),
+                Span::raw(match error.source_line {
+                    Some(line) => format!("{}:{}", error.file, line),
+                    None => error.file.clone(),
+                }),
+            ]));
+            if let Some(ref compiled) = error.compiled_path {
+                detail_lines.push(Line::from(vec![
+                    Span::styled("Compiled: ", Style::default().fg(Color::Cyan)),
+                    Span::raw(match error.compiled_line {
+                        Some(line) => format!("{}:{}", compiled, line),
+                        None => compiled.clone(),
+                    }),
+                ]));
+            }
+            detail_lines.push(Line::from(""));
+            detail_lines.extend(error.message.iter().map(|l| Line::from(l.clone())));
+        }
+
+        let detail = Paragraph::new(detail_lines)
+            .block(Block::default().borders(Borders::ALL).title(" Message "))
+            .wrap(ratatui::widgets::Wrap { trim: false });
+        frame.render_widget(detail, columns[1]);
+    }
+
+    /// Render the raw view as a log viewer
+    fn render_log(&mut self, run_output: &RunOutput) -> Vec<Line<'static>> {
+        self.refresh_log_lines(&run_output.output);
+        self.refresh_log_rows();
+
+        if let Some(command) = self.pending.take() {
+            self.apply_command(command);
+        }
+
+        self.match_count = self.log.matches.len();
+        let current_match = self.match_cursor.and_then(|c| self.log.matches.get(c)).copied();
+
+        let mut output_lines: Vec<Line<'static>> = vec![
+            Line::from(vec![
+                Span::styled(
+                    "Command: ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(run_output.command.clone()),
+            ]),
+            self.render_log_status(),
+            Line::from(Span::styled(
+                "─".repeat(80),
+                Style::default().fg(Color::DarkGray),
+            )),
+            Line::from(""),
+        ];
+
+        let lines = &self.log.lines;
+        for row in &self.log.rows {
+            match *row {
+                LogRow::Line(i) => {
+                    let style = raw_line_style(&lines[i]);
+                    let match_style = if Some(i) == current_match {
+                        Style::default().fg(Color::Black).bg(Color::Cyan)
+                    } else {
+                        Style::default().fg(Color::Black).bg(Color::Yellow)
+                    };
+                    output_lines.push(highlight_matches(&lines[i], &self.search, style, match_style));
+                }
+                LogRow::Folded { hidden, .. } => {
+                    output_lines.push(Line::from(Span::styled(
+                        format!("    ⋯ {} more lines folded (z to expand)", hidden),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+            }
+        }
+
+        output_lines
+    }
+
+    /// Search input, filter and match count shown above the log
+    fn render_log_status(&self) -> Line<'static> {
+        let label_style = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+        let mut spans = Vec::new();
+
+        match self.input {
+            Some(input) => {
+                let label = match input {
+                    LogInput::Search => "Search: ",
+                    LogInput::Model => "Model: ",
+                };
+                spans.push(Span::styled(label, label_style));
+                spans.push(Span::raw(self.input_buffer.clone()));
+                spans.push(Span::styled("_", Style::default().fg(Color::Yellow)));
+            }
+            None => {
+                if self.filter != LogFilter::All {
+                    spans.push(Span::styled("Filter: ", label_style));
+                    spans.push(Span::raw(format!("{}  ", self.filter.label())));
+                }
+                if !self.search.is_empty() {
+                    spans.push(Span::styled("Search: ", label_style));
+                    let position = match self.match_cursor {
+                        Some(cursor) if self.match_count > 0 => {
+                            format!("{}/{}", cursor + 1, self.match_count)
+                        }
+                        _ => format!("{} matches", self.match_count),
+                    };
+                    spans.push(Span::raw(format!("\"{}\" ({})", self.search, position)));
+                }
+            }
+        }
+
+        Line::from(spans)
+    }
+
     pub fn draw_with_output(
         &mut self,
         frame: &mut Frame,
         area: Rect,
         run_output: &RunOutput,
+        estimate: Option<&RunEstimate>,
     ) -> Result<()> {
         // Clear the entire area first
         frame.render_widget(Clear, area);
@@ -80,12 +583,39 @@ impl RunOutputDialog {
             area.height.saturating_sub(margin * 2),
         );
 
+        let progress_height = if estimate.is_some() { 3 } else { 0 };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(1), Constraint::Length(3)])
+            .constraints([
+                Constraint::Length(progress_height),
+                Constraint::Min(1),
+                Constraint::Length(3),
+            ])
             .split(overlay_area);
 
-        let content_area = chunks[0];
+        if let Some(estimate) = estimate {
+            render_progress(frame, chunks[0], estimate);
+        }
+
+        self.raw_view = run_output.view_mode == RunOutputViewMode::Raw;
+
+        if run_output.output.len() != self.errors_parsed_len {
+            self.errors = parse_run_errors(&run_output.output);
+            self.errors_parsed_len = run_output.output.len();
+            self.error_index = self.error_index.min(self.errors.len().saturating_sub(1));
+        }
+        let show_errors = self.show_errors && !self.errors.is_empty();
+
+        let content_area = if show_errors {
+            let split = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(5), Constraint::Percentage(40)])
+                .split(chunks[1]);
+            self.render_errors_panel(frame, split[1]);
+            split[0]
+        } else {
+            chunks[1]
+        };
 
         // Get status
         let (status_text, status_color) = get_status_indicator(run_output.status);
@@ -93,8 +623,9 @@ impl RunOutputDialog {
 
         // Generate content
         let content_lines = match run_output.view_mode {
-            RunOutputViewMode::Raw => render_raw_output(run_output),
+            RunOutputViewMode::Raw => self.render_log(run_output),
             RunOutputViewMode::Graphical => render_graphical_output(run_output, content_area.width),
+            RunOutputViewMode::Timeline => render_timeline_output(run_output, content_area.width),
         };
 
         let total = content_lines.len();
@@ -106,7 +637,16 @@ impl RunOutputDialog {
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(status_color))
-                    .title(format!(" Run Output [{}] [{}] ", status_text, view_mode_text))
+                    .title(if self.errors.is_empty() {
+                        format!(" Run Output [{}] [{}] ", status_text, view_mode_text)
+                    } else {
+                        format!(
+                            " Run Output [{}] [{}] [{} errors: e] ",
+                            status_text,
+                            view_mode_text,
+                            self.errors.len()
+                        )
+                    })
                     .title_style(
                         Style::default()
                             .fg(status_color)
@@ -139,7 +679,75 @@ impl RunOutputDialog {
             "Close"
         };
 
-        let help = Paragraph::new(Line::from(vec![
+        let key_style = Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD);
+        let mut help_line = if show_errors {
+            let mut spans = vec![
+                Span::styled(
+                    " Esc/e ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Close errors  "),
+                Span::styled(" j/k ", key_style),
+                Span::raw("Select  "),
+                Span::styled(" Enter ", key_style),
+                Span::raw("Open location  "),
+                Span::styled(" s ", key_style),
+                Span::raw("Open source file"),
+            ];
+            if self
+                .errors
+                .get(self.error_index)
+                .is_some_and(|e| e.resource_type == "test")
+            {
+                spans.push(Span::styled("  p ", key_style));
+                spans.push(Span::raw("Failing rows"));
+            }
+            Line::from(spans)
+        } else if self.input.is_some() {
+            Line::from(vec![
+                Span::styled(
+                    " Enter ",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Apply  "),
+                Span::styled(
+                    " Esc ",
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw("Cancel"),
+            ])
+        } else if self.raw_view {
+            Line::from(vec![
+                Span::styled(
+                    " Esc/q ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!("{}  ", close_label)),
+                Span::styled(" v ", key_style),
+                Span::raw("View  "),
+                Span::styled(" / ", key_style),
+                Span::raw("Search  "),
+                Span::styled(" n/N ", key_style),
+                Span::raw("Match  "),
+                Span::styled(" ]/[ ", key_style),
+                Span::raw("Error/Warn  "),
+                Span::styled(" f ", key_style),
+                Span::raw("Filter  "),
+                Span::styled(" m ", key_style),
+                Span::raw("Model  "),
+                Span::styled(" z/Z ", key_style),
+                Span::raw("Fold"),
+            ])
+        } else {
+            Line::from(vec![
             Span::styled(
                 " Esc/q ",
                 Style::default()
@@ -154,6 +762,20 @@ impl RunOutputDialog {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("Toggle View  "),
+            Span::styled(
+                " t ",
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Timeline  "),
+            Span::styled(
+                " x ",
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Export  "),
             Span::styled(
                 " j/k ",
                 Style::default()
@@ -161,11 +783,22 @@ impl RunOutputDialog {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("Scroll"),
-        ]))
+            ])
+        };
+        if !show_errors && self.input.is_none() && !self.errors.is_empty() {
+            help_line.spans.push(Span::raw("  "));
+            help_line.spans.push(Span::styled(
+                " e ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+            help_line.spans.push(Span::raw("Errors"));
+        }
+
+        let help = Paragraph::new(help_line)
         .alignment(ratatui::layout::Alignment::Center)
         .block(Block::default().borders(Borders::ALL));
 
-        frame.render_widget(help, chunks[1]);
+        frame.render_widget(help, chunks[2]);
 
         Ok(())
     }
@@ -184,62 +817,80 @@ fn get_status_indicator(status: RunStatus) -> (&'static str, Color) {
     }
 }
 
+/// Render the progress bar with ETA
+fn render_progress(frame: &mut Frame, area: Rect, estimate: &RunEstimate) {
+    let eta = match estimate.remaining_secs {
+        Some(remaining) if remaining < 1.0 => "ETA <1s".to_string(),
+        Some(remaining) => format!("ETA ~{}", format_duration(remaining)),
+        None => "ETA unknown (no history)".to_string(),
+    };
+    let label = format!(
+        "{}/{} steps · {:.0}% · elapsed {} · {}",
+        estimate.completed,
+        estimate.total,
+        estimate.ratio() * 100.0,
+        format_duration(estimate.elapsed_secs),
+        eta
+    );
+
+    let gauge = Gauge::default()
+        .block(Block::default().borders(Borders::ALL).title(" Progress "))
+        .gauge_style(Style::default().fg(Color::Yellow).bg(Color::DarkGray))
+        .ratio(estimate.ratio())
+        .label(label);
+
+    frame.render_widget(gauge, area);
+}
+
 /// Get view mode text
 fn get_view_mode_text(view_mode: RunOutputViewMode) -> &'static str {
     match view_mode {
         RunOutputViewMode::Raw => "Raw",
         RunOutputViewMode::Graphical => "Graph",
+        RunOutputViewMode::Timeline => "Timeline",
     }
 }
 
-/// Render the raw text view of run output
-fn render_raw_output(run_output: &RunOutput) -> Vec<Line<'static>> {
-    let mut output_lines: Vec<Line> = vec![
-        Line::from(vec![
-            Span::styled(
-                "Command: ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(run_output.command.clone()),
-        ]),
-        Line::from(""),
-        Line::from(Span::styled(
-            "─".repeat(80),
-            Style::default().fg(Color::DarkGray),
-        )),
-        Line::from(""),
-    ];
+/// Color for a raw output line
+fn raw_line_style(line: &str) -> Style {
+    if line.contains("error") || line.contains("Error") || line.contains("FAILED") {
+        Style::default().fg(Color::Red)
+    } else if line.contains("warning") || line.contains("Warning") {
+        Style::default().fg(Color::Yellow)
+    } else if line.contains("SUCCESS")
+        || line.contains("Completed successfully")
+        || line.contains("PASS")
+    {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default()
+    }
+}
 
-    // Output content
-    for line in run_output.output.lines() {
-        let styled_line = if line.contains("error") || line.contains("Error") || line.contains("FAILED")
-        {
-            Line::from(Span::styled(
-                line.to_string(),
-                Style::default().fg(Color::Red),
-            ))
-        } else if line.contains("warning") || line.contains("Warning") {
-            Line::from(Span::styled(
-                line.to_string(),
-                Style::default().fg(Color::Yellow),
-            ))
-        } else if line.contains("SUCCESS")
-            || line.contains("Completed successfully")
-            || line.contains("PASS")
-        {
-            Line::from(Span::styled(
-                line.to_string(),
-                Style::default().fg(Color::Green),
-            ))
-        } else {
-            Line::from(line.to_string())
-        };
-        output_lines.push(styled_line);
+/// Split a line into spans, highlighting case-insensitive occurrences of `query`
+fn highlight_matches(line: &str, query: &str, style: Style, match_style: Style) -> Line<'static> {
+    if query.is_empty() {
+        return Line::from(Span::styled(line.to_string(), style));
     }
 
-    output_lines
+    // ASCII lowercasing keeps byte offsets aligned with the original line
+    let haystack = line.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+    let mut spans = Vec::new();
+    let mut cursor = 0;
+    while let Some(pos) = haystack[cursor..].find(&needle) {
+        let start = cursor + pos;
+        let end = start + needle.len();
+        if start > cursor {
+            spans.push(Span::styled(line[cursor..start].to_string(), style));
+        }
+        spans.push(Span::styled(line[start..end].to_string(), match_style));
+        cursor = end;
+    }
+    if cursor < line.len() {
+        spans.push(Span::styled(line[cursor..].to_string(), style));
+    }
+    Line::from(spans)
 }
 
 /// Render the graphical view of run output with model boxes
@@ -445,3 +1096,243 @@ fn render_model_box(
 
     lines
 }
+
+/// Render the timeline (Gantt) view of run output: one row per thread
+fn render_timeline_output(run_output: &RunOutput, area_width: u16) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = vec![
+        Line::from(vec![
+            Span::styled(
+                "Command: ",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(run_output.command.clone()),
+        ]),
+        Line::from(""),
+    ];
+
+    let Some(ref timeline) = run_output.timeline else {
+        let message = if run_output.status == RunStatus::Running {
+            "Timeline will be available when the run completes."
+        } else {
+            "No timing information available (target/run_results.json was not written by this run)."
+        };
+        lines.push(Line::from(Span::styled(
+            message,
+            Style::default().fg(Color::DarkGray),
+        )));
+        return lines;
+    };
+
+    let label_width = timeline
+        .threads
+        .iter()
+        .map(|t| t.width())
+        .max()
+        .unwrap_or(0)
+        .clamp(6, 24);
+    // Borders (2) + label + separator (3) + utilization suffix (6)
+    let chart_width = (area_width as usize)
+        .saturating_sub(2 + label_width + 3 + 6)
+        .max(10);
+    let scale = if timeline.total_secs > 0.0 {
+        chart_width as f64 / timeline.total_secs
+    } else {
+        0.0
+    };
+
+    // One row per thread
+    for (thread_idx, thread) in timeline.threads.iter().enumerate() {
+        let bars: Vec<&TimelineBar> = timeline
+            .bars
+            .iter()
+            .filter(|b| b.thread == thread_idx)
+            .collect();
+        let busy: f64 = bars.iter().map(|b| b.duration()).sum();
+        let utilization = if timeline.total_secs > 0.0 {
+            busy / timeline.total_secs * 100.0
+        } else {
+            0.0
+        };
+
+        let mut spans = vec![Span::styled(
+            format!("{:<width$} │", truncate_label(thread, label_width), width = label_width),
+            Style::default().fg(Color::Cyan),
+        )];
+
+        let mut cursor = 0;
+        for (bar_idx, bar) in bars.iter().enumerate() {
+            let start = ((bar.start_secs * scale) as usize).min(chart_width.saturating_sub(1));
+            let end = ((bar.end_secs * scale).round() as usize)
+                .max(start + 1)
+                .min(chart_width);
+            if start < cursor {
+                // Overlaps the previous bar at this resolution; draw what remains
+                if end <= cursor {
+                    continue;
+                }
+            } else if start > cursor {
+                spans.push(Span::raw(" ".repeat(start - cursor)));
+            }
+            let from = start.max(cursor);
+            let glyph = if bar.on_critical_path {
+                "▓"
+            } else if bar_idx % 2 == 0 {
+                "█"
+            } else {
+                "▇"
+            };
+            let mut style = Style::default().fg(timeline_status_color(&bar.status));
+            if bar.on_critical_path {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            spans.push(Span::styled(glyph.repeat(end - from), style));
+            cursor = end;
+        }
+        if cursor < chart_width {
+            spans.push(Span::raw(" ".repeat(chart_width - cursor)));
+        }
+        spans.push(Span::styled(
+            format!(" {:>3.0}%", utilization),
+            Style::default().fg(Color::DarkGray),
+        ));
+        lines.push(Line::from(spans));
+    }
+
+    // Time axis
+    let total_label = format!("{:.1}s", timeline.total_secs);
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{:<width$} └{}",
+            "",
+            "─".repeat(chart_width),
+            width = label_width
+        ),
+        Style::default().fg(Color::DarkGray),
+    )));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{:<width$}  0s{:>pad$}",
+            "",
+            total_label,
+            width = label_width,
+            pad = chart_width.saturating_sub(2)
+        ),
+        Style::default().fg(Color::DarkGray),
+    )));
+    lines.push(Line::from(""));
+
+    lines.extend(render_critical_path(timeline));
+    lines.push(Line::from(""));
+    lines.extend(render_timeline_table(timeline));
+
+    lines
+}
+
+/// Render the critical path summary
+fn render_critical_path(timeline: &RunTimeline) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            "Critical path ",
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "({:.1}s of {:.1}s wall clock, ▓ in chart)",
+                timeline.critical_secs, timeline.total_secs
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])];
+
+    for (idx, unique_id) in timeline.critical_path.iter().enumerate() {
+        let Some(bar) = timeline.bars.iter().find(|b| &b.unique_id == unique_id) else {
+            continue;
+        };
+        let arrow = if idx == 0 { "  " } else { "→ " };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}", arrow), Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                bar.name.clone(),
+                Style::default().fg(timeline_status_color(&bar.status)),
+            ),
+            Span::styled(
+                format!(" {:.2}s", bar.duration()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+
+    lines
+}
+
+/// Render per-node timing, slowest first
+fn render_timeline_table(timeline: &RunTimeline) -> Vec<Line<'static>> {
+    let mut bars: Vec<&TimelineBar> = timeline.bars.iter().collect();
+    bars.sort_by(|a, b| b.duration().total_cmp(&a.duration()));
+
+    let name_width = bars.iter().map(|b| b.name.width()).max().unwrap_or(4).clamp(4, 40);
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "   {:<name_width$}  {:>8}  {:>8}  {:<10}  Thread",
+            "Node", "Start", "Duration", "Status"
+        ),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    ))];
+
+    for bar in bars {
+        let marker = if bar.on_critical_path { " ▓ " } else { "   " };
+        lines.push(Line::from(vec![
+            Span::styled(marker, Style::default().fg(Color::Magenta)),
+            Span::raw(format!(
+                "{:<name_width$}  {:>7.2}s  {:>7.2}s  ",
+                truncate_label(&bar.name, name_width),
+                bar.start_secs,
+                bar.duration()
+            )),
+            Span::styled(
+                format!("{:<10}", bar.status),
+                Style::default().fg(timeline_status_color(&bar.status)),
+            ),
+            Span::styled(
+                format!("  {}", timeline.threads.get(bar.thread).cloned().unwrap_or_default()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+
+    lines
+}
+
+/// Color for a run_results.json status string
+fn timeline_status_color(status: &str) -> Color {
+    match status {
+        "success" | "pass" => Color::Green,
+        "error" | "fail" | "runtime error" => Color::Red,
+        "warn" => Color::Yellow,
+        "skipped" => Color::DarkGray,
+        _ => Color::Blue,
+    }
+}
+
+/// Truncate a label to a display width, marking the cut with an ellipsis
+fn truncate_label(label: &str, max_width: usize) -> String {
+    if label.width() <= max_width {
+        return label.to_string();
+    }
+    let mut truncated = String::new();
+    for c in label.chars() {
+        if truncated.width() + 2 > max_width {
+            break;
+        }
+        truncated.push(c);
+    }
+    truncated.push('…');
+    truncated
+}
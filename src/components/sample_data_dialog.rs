@@ -99,14 +99,14 @@ impl SampleDataDialog {
                 vec![
                     Line::from(""),
                     Line::from(Span::styled(
-                        "  Loading sample data...",
+                        format!("  Loading {}...", output.title.to_lowercase()),
                         Style::default()
                             .fg(Color::Yellow)
                             .add_modifier(Modifier::BOLD),
                     )),
                     Line::from(""),
                     Line::from(Span::styled(
-                        format!("  Running: dbt show --select {} --limit 100", output.model_name),
+                        format!("  Running: {}", output.display_command),
                         Style::default().fg(Color::DarkGray),
                     )),
                 ]
@@ -115,7 +115,7 @@ impl SampleDataDialog {
                 let mut lines = vec![
                     Line::from(""),
                     Line::from(Span::styled(
-                        "  Failed to fetch sample data",
+                        format!("  Failed to fetch {}", output.title.to_lowercase()),
                         Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
                     )),
                     Line::from(""),
@@ -171,8 +171,8 @@ impl SampleDataDialog {
 
         // Create block with title showing model name and status
         let title = format!(
-            " Sample Data: {} [{}] ",
-            output.model_name, status_text
+            " {}: {} [{}] ",
+            output.title, output.model_name, status_text
         );
 
         let paragraph = Paragraph::new(content_lines.clone())
@@ -0,0 +1,325 @@
+//! Test coverage matrix dialog component
+//!
+//! Shows a model's columns as rows and test types as columns, each cell
+//! colored by the latest result of its tests, followed by the model-level
+//! tests. Enter shows the test under the cursor in the list.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::model::test_matrix::{CellStatus, MatrixTest, TestMatrix, TestType};
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+const COLUMN_WIDTH: usize = 32;
+const CELL_WIDTH: usize = 17;
+
+/// Test coverage matrix dialog
+#[derive(Default)]
+pub struct TestMatrixDialog {
+    matrix: TestMatrix,
+    /// Column rows first, then model-level tests
+    selected_row: usize,
+    /// Test type under the cursor, on column rows
+    selected_type: usize,
+    scroll_offset: usize,
+}
+
+impl TestMatrixDialog {
+    pub fn open(&mut self, matrix: TestMatrix) {
+        *self = Self {
+            matrix,
+            ..Self::default()
+        };
+    }
+
+    fn row_count(&self) -> usize {
+        self.matrix.columns.len() + self.matrix.model_tests.len()
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.row_count();
+        if len == 0 {
+            return;
+        }
+        self.selected_row = self.selected_row.saturating_add_signed(delta).min(len - 1);
+    }
+
+    fn move_type(&mut self, delta: isize) {
+        self.selected_type = self
+            .selected_type
+            .saturating_add_signed(delta)
+            .min(TestType::ALL.len() - 1);
+    }
+
+    fn selected_test(&self) -> Option<&MatrixTest> {
+        match self.matrix.columns.get(self.selected_row) {
+            Some(row) => row
+                .tests_of(TestType::ALL[self.selected_type])
+                .first()
+                .copied(),
+            None => self
+                .matrix
+                .model_tests
+                .get(self.selected_row - self.matrix.columns.len()),
+        }
+    }
+
+    /// Lines, with the index of the selected row's line
+    fn lines(&self) -> (Vec<Line<'static>>, usize) {
+        let mut lines = vec![section("Column tests")];
+        let mut selected_line = 0;
+        if self.matrix.columns.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "   No documented or tested columns",
+                Style::default().fg(Color::DarkGray),
+            )));
+        } else {
+            let mut header = vec![Span::raw(format!("   {:<COLUMN_WIDTH$}", ""))];
+            for (i, test_type) in TestType::ALL.iter().enumerate() {
+                let style = if i == self.selected_type {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                header.push(Span::styled(
+                    format!("{:<CELL_WIDTH$}", test_type.label()),
+                    style,
+                ));
+            }
+            lines.push(Line::from(header));
+        }
+
+        for (i, row) in self.matrix.columns.iter().enumerate() {
+            let selected = i == self.selected_row;
+            if selected {
+                selected_line = lines.len();
+            }
+            let (marker, name_style) = if selected {
+                (
+                    "▶",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else if row.tests.is_empty() {
+                (" ", Style::default().fg(Color::Red))
+            } else {
+                (" ", Style::default().fg(Color::White))
+            };
+            let mut spans = vec![Span::styled(
+                format!(" {} {:<COLUMN_WIDTH$}", marker, truncate(&row.column, COLUMN_WIDTH - 1)),
+                name_style,
+            )];
+            for (t, test_type) in TestType::ALL.iter().enumerate() {
+                let count = row.tests_of(*test_type).len();
+                let mut span = cell_span(row.cell(*test_type), count);
+                if selected && t == self.selected_type {
+                    span.style = span.style.add_modifier(Modifier::REVERSED);
+                }
+                spans.push(span);
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let untested = self.matrix.untested_columns();
+        if !untested.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "   {} of {} columns have no tests: {}",
+                    untested.len(),
+                    self.matrix.columns.len(),
+                    untested.join(", ")
+                ),
+                Style::default().fg(Color::Red),
+            )));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(section("Model tests"));
+        if self.matrix.model_tests.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "   No singular or model-level tests",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        for (i, test) in self.matrix.model_tests.iter().enumerate() {
+            let selected = self.matrix.columns.len() + i == self.selected_row;
+            if selected {
+                selected_line = lines.len();
+            }
+            let (marker, style) = if selected {
+                (
+                    "▶",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                (" ", Style::default().fg(Color::White))
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {} {:<50} ", marker, test.name), style),
+                Span::styled(
+                    format!("{:<34}", test.kind),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                cell_span(CellStatus::of_test(test), 1),
+            ]));
+        }
+        (lines, selected_line)
+    }
+}
+
+impl Component for TestMatrixDialog {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(Action::CloseModal),
+            KeyCode::Char('j') | KeyCode::Down => {
+                self.move_selection(1);
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.move_selection(-1);
+                None
+            }
+            KeyCode::Char('l') | KeyCode::Right => {
+                self.move_type(1);
+                None
+            }
+            KeyCode::Char('h') | KeyCode::Left => {
+                self.move_type(-1);
+                None
+            }
+            KeyCode::PageDown => {
+                self.move_selection(20);
+                None
+            }
+            KeyCode::PageUp => {
+                self.move_selection(-20);
+                None
+            }
+            KeyCode::Enter => self
+                .selected_test()
+                .map(|test| Action::RevealNode(test.unique_id.clone())),
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        frame.render_widget(Clear, area);
+
+        let margin = 2;
+        let overlay_area = Rect::new(
+            margin,
+            margin,
+            area.width.saturating_sub(margin * 2),
+            area.height.saturating_sub(margin * 2),
+        );
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(3)])
+            .split(overlay_area);
+
+        let (content, selected_line) = self.lines();
+
+        // Keep the selected row visible
+        let visible_height = chunks[0].height.saturating_sub(2) as usize;
+        if selected_line < self.scroll_offset {
+            self.scroll_offset = selected_line.saturating_sub(2);
+        } else if visible_height > 0 && selected_line >= self.scroll_offset + visible_height {
+            self.scroll_offset = selected_line + 1 - visible_height;
+        }
+
+        let paragraph = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Magenta))
+                    .title(format!(" Test Coverage: {} ", self.matrix.model_name))
+                    .title_style(
+                        Style::default()
+                            .fg(Color::Magenta)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+            )
+            .scroll((self.scroll_offset as u16, 0));
+        frame.render_widget(paragraph, chunks[0]);
+
+        let key_style = Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD);
+        let help = Paragraph::new(Line::from(vec![
+            Span::styled(
+                " Esc/q ",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Close  "),
+            Span::styled(" j/k ", key_style),
+            Span::raw("Row  "),
+            Span::styled(" h/l ", key_style),
+            Span::raw("Test type  "),
+            Span::styled(" Enter ", key_style),
+            Span::raw("Show test in list  "),
+            Span::styled("✓ pass  ! warn  ✗ fail  · not run", Style::default().fg(Color::DarkGray)),
+        ]))
+        .alignment(ratatui::layout::Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(help, chunks[1]);
+
+        Ok(())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helper functions
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn section(title: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        format!(" {}", title),
+        Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD),
+    ))
+}
+
+/// Result symbol, with the number of tests when a cell holds several
+fn cell_span(status: CellStatus, count: usize) -> Span<'static> {
+    let (symbol, color) = match status {
+        CellStatus::Untested => ("-", Color::DarkGray),
+        CellStatus::NotRun => ("·", Color::White),
+        CellStatus::Pass => ("✓", Color::Green),
+        CellStatus::Warn => ("!", Color::Yellow),
+        CellStatus::Fail => ("✗", Color::Red),
+    };
+    let text = if count > 1 {
+        format!("{} ({})", symbol, count)
+    } else {
+        symbol.to_string()
+    };
+    Span::styled(format!("{:<CELL_WIDTH$}", text), Style::default().fg(color))
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        text.to_string()
+    } else {
+        let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+        truncated.push('…');
+        truncated
+    }
+}
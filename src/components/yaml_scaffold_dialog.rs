@@ -0,0 +1,214 @@
+//! YAML scaffold dialog component
+//!
+//! Previews a schema entry listing every warehouse column of a node, taken
+//! from catalog.json or `dbt show --limit 0`, and appends it to a YAML file
+//! next to the node.
+
+use crate::action::Action;
+use crate::component::Component;
+use crate::components::centered_popup;
+use crate::model::catalog::WarehouseColumn;
+use crate::services::yaml_edit::scaffold_entry;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::path::Path;
+
+/// YAML scaffold dialog
+#[derive(Default)]
+pub struct YamlScaffoldDialog {
+    pub node_id: String,
+    node_name: String,
+    pub columns: Vec<WarehouseColumn>,
+    /// Where the columns came from
+    source: String,
+    /// Waiting for `dbt show`
+    pub loading: bool,
+    error: Option<String>,
+    /// Candidate files, relative to the project root, and whether they exist
+    files: Vec<(String, bool)>,
+    selected_file: usize,
+    scroll: usize,
+}
+
+impl YamlScaffoldDialog {
+    pub fn open(&mut self, node_id: &str, node_name: &str, files: Vec<String>, project_path: &Path) {
+        self.node_id = node_id.to_string();
+        self.node_name = node_name.to_string();
+        self.columns.clear();
+        self.source.clear();
+        self.loading = true;
+        self.error = None;
+        self.files = files
+            .into_iter()
+            .map(|f| {
+                let exists = project_path.join(&f).exists();
+                (f, exists)
+            })
+            .collect();
+        self.selected_file = 0;
+        self.scroll = 0;
+    }
+
+    pub fn set_columns(&mut self, columns: Vec<WarehouseColumn>, source: &str) {
+        self.loading = false;
+        if columns.is_empty() {
+            self.error = Some(format!("No columns found via {}", source));
+        }
+        self.columns = columns;
+        self.source = source.to_string();
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.loading = false;
+        self.error = Some(error);
+    }
+
+    pub fn selected_file(&self) -> Option<&str> {
+        self.files.get(self.selected_file).map(|(f, _)| f.as_str())
+    }
+
+    fn preview_lines(&self) -> Vec<String> {
+        scaffold_entry(&self.node_name, &self.columns, 0)
+    }
+}
+
+impl Component for YamlScaffoldDialog {
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let action = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(Action::CloseModal),
+            KeyCode::Enter if !self.loading && !self.columns.is_empty() => {
+                Some(Action::ConfirmModal)
+            }
+            KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => {
+                if !self.files.is_empty() {
+                    self.selected_file = (self.selected_file + 1) % self.files.len();
+                }
+                None
+            }
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => {
+                if !self.files.is_empty() {
+                    self.selected_file =
+                        (self.selected_file + self.files.len() - 1) % self.files.len();
+                }
+                None
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                if self.scroll + 1 < self.preview_lines().len() {
+                    self.scroll += 1;
+                }
+                None
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                self.scroll = self.scroll.saturating_sub(1);
+                None
+            }
+            _ => None,
+        };
+        Ok(action)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let popup_width = 80.min(area.width.saturating_sub(4));
+        let popup_height = 26.min(area.height.saturating_sub(2));
+        let popup_area = centered_popup(area, popup_width, popup_height);
+
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" Generate YAML: {} ", self.node_name))
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(3),
+            ])
+            .split(inner);
+
+        let status = if self.loading {
+            Span::styled(
+                " Reading columns with dbt show --limit 0...",
+                Style::default().fg(Color::Yellow),
+            )
+        } else if let Some(ref error) = self.error {
+            Span::styled(format!(" {}", error), Style::default().fg(Color::Red))
+        } else {
+            Span::styled(
+                format!(" {} columns from {}", self.columns.len(), self.source),
+                Style::default().fg(Color::DarkGray),
+            )
+        };
+        frame.render_widget(Paragraph::new(Line::from(status)), chunks[0]);
+
+        if !self.loading && !self.columns.is_empty() {
+            let preview: Vec<Line> = self
+                .preview_lines()
+                .into_iter()
+                .map(|l| Line::from(Span::styled(format!(" {}", l), Style::default().fg(Color::White))))
+                .collect();
+            frame.render_widget(
+                Paragraph::new(preview).scroll((self.scroll as u16, 0)),
+                chunks[1],
+            );
+        }
+
+        let file = match self.files.get(self.selected_file) {
+            Some((file, true)) => Line::from(vec![
+                Span::raw(" Append to: "),
+                Span::styled(file.clone(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            ]),
+            Some((file, false)) => Line::from(vec![
+                Span::raw(" Append to: "),
+                Span::styled(file.clone(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::styled(" (new file)", Style::default().fg(Color::DarkGray)),
+            ]),
+            None => Line::from(Span::styled(
+                " No file location for this node",
+                Style::default().fg(Color::DarkGray),
+            )),
+        };
+        let help = Line::from(vec![
+            Span::styled(
+                " Enter ",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Append  "),
+            Span::styled(
+                " Tab ",
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Change file  "),
+            Span::styled(
+                " j/k ",
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Scroll  "),
+            Span::styled(
+                " Esc ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("Close"),
+        ]);
+        frame.render_widget(Paragraph::new(vec![Line::from(""), file, help]), chunks[2]);
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::model::history::DEFAULT_HISTORY_RETENTION;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs;
@@ -12,6 +13,17 @@ pub struct Config {
     /// Available targets for the project (dev, prod, staging, etc.)
     #[serde(default)]
     pub available_targets: Vec<String>,
+    /// Number of run history entries kept per target of a project
+    #[serde(default = "default_history_retention")]
+    pub history_retention: usize,
+    /// Editor command template with {file}, {line} and {col} placeholders,
+    /// e.g. "nvim +{line} {file}"; defaults to $VISUAL/$EDITOR
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor_command: Option<String>,
+}
+
+fn default_history_retention() -> usize {
+    DEFAULT_HISTORY_RETENTION
 }
 
 impl Default for Config {
@@ -27,6 +39,8 @@ impl Default for Config {
                 "staging".to_string(),
                 "test".to_string(),
             ],
+            history_retention: DEFAULT_HISTORY_RETENTION,
+            editor_command: None,
         }
     }
 }
@@ -15,13 +15,22 @@ mod tui;
 use crate::action::Action;
 use crate::app::App;
 use crate::component::Component;
+use crate::config::Config;
+use crate::services::editor::EditorTarget;
 use crate::tui::Tui;
 use anyhow::Result;
 use crossterm::event::Event;
+use std::path::PathBuf;
 use std::process::Command;
 use std::time::Duration;
 
 fn main() -> Result<()> {
+    // Headless convention check: `dbt-tui check [PROJECT_DIR]`
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("check") {
+        std::process::exit(run_check(args.get(1)));
+    }
+
     // Setup terminal
     let mut tui = Tui::new()?.with_tick_rate(Duration::from_millis(100));
     tui.enter()?;
@@ -56,8 +65,8 @@ fn run_app(tui: &mut Tui, app: &mut App) -> Result<()> {
         })?;
 
         // Check for pending external editor
-        if let Some(file_path) = app.pending_editor_file.take() {
-            launch_external_editor(tui, app, &file_path)?;
+        if let Some(target) = app.pending_editor.take() {
+            launch_external_editor(tui, app, &target)?;
             continue; // Redraw after editor closes
         }
 
@@ -88,19 +97,59 @@ fn run_app(tui: &mut Tui, app: &mut App) -> Result<()> {
     Ok(())
 }
 
-/// Launch an external editor for the given file
-fn launch_external_editor(tui: &mut Tui, app: &mut App, file_path: &str) -> Result<()> {
+/// Lint a project and print the violations. Exits with 1 when there are
+/// violations and 2 when the project can't be checked.
+fn run_check(project_dir: Option<&String>) -> i32 {
+    // The given directory, else the configured project, else the current directory
+    let project_path = project_dir
+        .map(PathBuf::from)
+        .or_else(|| {
+            Config::load()
+                .map(|c| c.project_path)
+                .filter(|p| !p.is_empty())
+                .map(PathBuf::from)
+        })
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    match services::check_project(&project_path) {
+        Ok(violations) if violations.is_empty() => {
+            println!("No convention violations");
+            0
+        }
+        Ok(violations) => {
+            for violation in &violations {
+                println!(
+                    "{}: {}: [{}] {}",
+                    violation.path.as_deref().unwrap_or("-"),
+                    violation.name,
+                    violation.rule.label(),
+                    violation.message
+                );
+            }
+            println!("{} convention violations", violations.len());
+            1
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            2
+        }
+    }
+}
+
+/// Launch an external editor for the given file (and line, if any)
+fn launch_external_editor(tui: &mut Tui, app: &mut App, target: &EditorTarget) -> Result<()> {
     // Determine the editor to use: $VISUAL, $EDITOR, or fallback
-    let editor = std::env::var("VISUAL")
-        .or_else(|_| std::env::var("EDITOR"))
-        .unwrap_or_else(|_| "vim".to_string());
+    let editor = services::editor::default_editor();
+    let template = app.editor_template();
+    let (program, args) =
+        services::editor::editor_command(&editor, template.as_deref(), target);
 
     // Suspend the TUI
     tui.suspend()?;
 
     // Launch the editor
-    let status = Command::new(&editor)
-        .arg(file_path)
+    let status = Command::new(&program)
+        .args(&args)
         .status();
 
     // Resume the TUI
@@ -0,0 +1,490 @@
+//! Project health audit
+//!
+//! Checks every model for a description, column descriptions, a primary
+//! key test and an owner, and every source table for freshness thresholds.
+//! When catalog.json is loaded, warehouse columns missing from YAML count as
+//! undocumented. Coverage is summarised per folder and per schema.
+
+use super::catalog::Catalog;
+use super::node::{Node, SourceTable};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+/// Kind of audit finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FindingKind {
+    NoDescription,
+    UndocumentedColumns,
+    NoPrimaryKeyTest,
+    NoFreshness,
+    NoOwner,
+}
+
+impl FindingKind {
+    pub const ALL: [FindingKind; 5] = [
+        FindingKind::NoDescription,
+        FindingKind::UndocumentedColumns,
+        FindingKind::NoPrimaryKeyTest,
+        FindingKind::NoFreshness,
+        FindingKind::NoOwner,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            FindingKind::NoDescription => "Models with no description",
+            FindingKind::UndocumentedColumns => "Models with undocumented columns",
+            FindingKind::NoPrimaryKeyTest => "Models without a primary key test",
+            FindingKind::NoFreshness => "Sources without freshness",
+            FindingKind::NoOwner => "Models without an owner",
+        }
+    }
+}
+
+/// A model or source failing a check
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub kind: FindingKind,
+    pub unique_id: String,
+    pub name: String,
+    /// Folder of a model, source name of a source
+    pub group: String,
+    /// Extra information, e.g. the undocumented column names
+    pub detail: Option<String>,
+}
+
+/// Coverage counts for a folder, schema or the whole project
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Coverage {
+    pub group: String,
+    pub models: usize,
+    pub described: usize,
+    /// Columns declared in YAML or found in the catalog
+    pub columns: usize,
+    pub documented_columns: usize,
+    pub primary_keys: usize,
+    pub owned: usize,
+}
+
+impl Coverage {
+    fn add(&mut self, check: &ModelCheck) {
+        self.models += 1;
+        self.described += check.described as usize;
+        self.columns += check.columns;
+        self.documented_columns += check.columns - check.undocumented.len();
+        self.primary_keys += check.primary_key as usize;
+        self.owned += check.owned as usize;
+    }
+}
+
+/// Percentage of `part` in `whole`, None when there is nothing to cover
+pub fn percent(part: usize, whole: usize) -> Option<f64> {
+    (whole > 0).then(|| part as f64 * 100.0 / whole as f64)
+}
+
+/// How coverage rows are grouped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AuditGrouping {
+    #[default]
+    Folder,
+    Schema,
+}
+
+/// Result of auditing the project
+#[derive(Debug, Clone, Default)]
+pub struct HealthAudit {
+    pub findings: Vec<Finding>,
+    pub by_folder: Vec<Coverage>,
+    pub by_schema: Vec<Coverage>,
+    pub total: Coverage,
+    pub sources: usize,
+    pub fresh_sources: usize,
+}
+
+struct ModelCheck {
+    described: bool,
+    columns: usize,
+    undocumented: Vec<String>,
+    primary_key: bool,
+    owned: bool,
+}
+
+impl HealthAudit {
+    pub fn build(all_nodes: &[Node], sources: &[SourceTable], catalog: Option<&Catalog>) -> Self {
+        let primary_keys = primary_key_models(all_nodes);
+
+        let mut audit = Self {
+            total: Coverage {
+                group: "Total".to_string(),
+                ..Coverage::default()
+            },
+            ..Self::default()
+        };
+        let mut by_folder: BTreeMap<String, Coverage> = BTreeMap::new();
+        let mut by_schema: BTreeMap<String, Coverage> = BTreeMap::new();
+
+        for node in all_nodes.iter().filter(|n| n.resource_type == "model") {
+            let mut undocumented: Vec<String> = node
+                .columns
+                .values()
+                .filter(|c| is_blank(c.description.as_deref()))
+                .map(|c| c.name.clone())
+                .collect();
+            // Warehouses may fold names to upper case, YAML usually doesn't
+            let declared: HashSet<String> =
+                node.columns.values().map(|c| c.name.to_lowercase()).collect();
+            let catalog_only: Vec<String> = catalog
+                .and_then(|c| c.columns(&node.unique_id))
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|c| !declared.contains(&c.name.to_lowercase()))
+                .map(|c| c.name.to_lowercase())
+                .collect();
+            let columns = node.columns.len() + catalog_only.len();
+            undocumented.extend(catalog_only);
+            undocumented.sort();
+            let check = ModelCheck {
+                described: !is_blank(node.description.as_deref()),
+                columns,
+                undocumented,
+                primary_key: primary_keys.contains(node.unique_id.as_str()),
+                owned: node.owner().is_some(),
+            };
+
+            let folder = node
+                .original_file_path
+                .as_deref()
+                .and_then(|p| Path::new(p).parent())
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let schema = node.group_schema();
+
+            let finding = |kind, detail| Finding {
+                kind,
+                unique_id: node.unique_id.clone(),
+                name: node.name.clone(),
+                group: folder.clone(),
+                detail,
+            };
+            if !check.described {
+                audit.findings.push(finding(FindingKind::NoDescription, None));
+            }
+            if check.columns == 0 {
+                audit.findings.push(finding(
+                    FindingKind::UndocumentedColumns,
+                    Some("no columns documented".to_string()),
+                ));
+            } else if !check.undocumented.is_empty() {
+                audit.findings.push(finding(
+                    FindingKind::UndocumentedColumns,
+                    Some(format!(
+                        "{} of {}: {}",
+                        check.undocumented.len(),
+                        check.columns,
+                        check.undocumented.join(", ")
+                    )),
+                ));
+            }
+            if !check.primary_key {
+                audit.findings.push(finding(FindingKind::NoPrimaryKeyTest, None));
+            }
+            if !check.owned {
+                audit.findings.push(finding(FindingKind::NoOwner, None));
+            }
+
+            audit.total.add(&check);
+            by_folder
+                .entry(folder.clone())
+                .or_insert_with(|| Coverage {
+                    group: folder.clone(),
+                    ..Coverage::default()
+                })
+                .add(&check);
+            by_schema
+                .entry(schema.clone())
+                .or_insert_with(|| Coverage {
+                    group: schema,
+                    ..Coverage::default()
+                })
+                .add(&check);
+        }
+
+        for source in sources {
+            audit.sources += 1;
+            if source.has_freshness() {
+                audit.fresh_sources += 1;
+            } else {
+                audit.findings.push(Finding {
+                    kind: FindingKind::NoFreshness,
+                    unique_id: source.unique_id.clone(),
+                    name: format!("{}.{}", source.source_name, source.name),
+                    group: source.source_name.clone(),
+                    detail: None,
+                });
+            }
+        }
+
+        audit.findings.sort_by(|a, b| a.group.cmp(&b.group).then(a.name.cmp(&b.name)));
+        audit.by_folder = by_folder.into_values().collect();
+        audit.by_schema = by_schema.into_values().collect();
+        audit
+    }
+
+    /// Findings of one kind, by group then name
+    pub fn findings_of(&self, kind: FindingKind) -> Vec<&Finding> {
+        self.findings.iter().filter(|f| f.kind == kind).collect()
+    }
+
+    pub fn coverage(&self, grouping: AuditGrouping) -> &[Coverage] {
+        match grouping {
+            AuditGrouping::Folder => &self.by_folder,
+            AuditGrouping::Schema => &self.by_schema,
+        }
+    }
+}
+
+fn is_blank(text: Option<&str>) -> bool {
+    text.is_none_or(|t| t.trim().is_empty())
+}
+
+/// Models with a primary key test: `unique` and `not_null` on the same
+/// column, `unique_combination_of_columns`, or a `primary_key` test
+fn primary_key_models(all_nodes: &[Node]) -> HashSet<&str> {
+    let mut column_tests: HashMap<(&str, &str), HashSet<&str>> = HashMap::new();
+    let mut models = HashSet::new();
+    for test in all_nodes.iter().filter(|n| n.resource_type == "test") {
+        let Some(ref metadata) = test.test_metadata else {
+            continue;
+        };
+        let Some(model) = test
+            .tested_node_id()
+            .filter(|id| id.starts_with("model."))
+        else {
+            continue;
+        };
+        match (metadata.name.as_str(), test.column_name.as_deref()) {
+            ("unique_combination_of_columns" | "primary_key", _) => {
+                models.insert(model);
+            }
+            (name @ ("unique" | "not_null"), Some(column)) => {
+                column_tests
+                    .entry((model, column))
+                    .or_default()
+                    .insert(name);
+            }
+            _ => {}
+        }
+    }
+    for ((model, _), tests) in column_tests {
+        if tests.len() == 2 {
+            models.insert(model);
+        }
+    }
+    models
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::node::{
+        ColumnInfo, FreshnessThreshold, SourceFreshness, TestMetadata,
+    };
+
+    fn node(name: &str, resource_type: &str, path: &str) -> Node {
+        Node {
+            original_file_path: Some(path.to_string()),
+            ..Node::fixture(name, resource_type, &[])
+        }
+    }
+
+    fn column(name: &str, description: &str) -> (String, ColumnInfo) {
+        (
+            name.to_string(),
+            ColumnInfo {
+                name: name.to_string(),
+                description: Some(description.to_string()),
+                data_type: None,
+            },
+        )
+    }
+
+    fn test(name: &str, test_name: &str, model: &str, column: &str) -> Node {
+        let mut test = node(name, "test", "models/schema.yml");
+        test.test_metadata = Some(TestMetadata {
+            name: test_name.to_string(),
+            namespace: None,
+            ..Default::default()
+        });
+        test.attached_node = Some(format!("model.shop.{}", model));
+        test.column_name = Some(column.to_string());
+        test
+    }
+
+    fn source(name: &str, warn_after: Option<u64>) -> SourceTable {
+        SourceTable {
+            unique_id: format!("source.shop.raw.{}", name),
+            name: name.to_string(),
+            source_name: "raw".to_string(),
+            columns: HashMap::new(),
+            freshness: Some(SourceFreshness {
+                warn_after: Some(FreshnessThreshold { count: warn_after }),
+                error_after: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_health_audit() {
+        let mut orders = node("orders", "model", "models/marts/orders.sql");
+        orders.description = Some("One row per order".to_string());
+        orders.columns = HashMap::from([column("id", "Key"), column("status", " ")]);
+        orders
+            .config
+            .meta
+            .insert("owner".to_string(), "finance".into());
+        let stg = node("stg_orders", "model", "models/staging/stg_orders.sql");
+        let nodes = vec![
+            orders,
+            stg,
+            test("unique_orders_id", "unique", "orders", "id"),
+            test("not_null_orders_id", "not_null", "orders", "id"),
+            test("unique_stg_orders_id", "unique", "stg_orders", "id"),
+        ];
+        let sources = vec![source("orders", Some(12)), source("payments", None)];
+
+        let audit = HealthAudit::build(&nodes, &sources, None);
+
+        let names = |kind| -> Vec<String> {
+            audit
+                .findings_of(kind)
+                .iter()
+                .map(|f| f.name.clone())
+                .collect()
+        };
+        assert_eq!(names(FindingKind::NoDescription), vec!["stg_orders"]);
+        assert_eq!(names(FindingKind::NoPrimaryKeyTest), vec!["stg_orders"]);
+        assert_eq!(names(FindingKind::NoOwner), vec!["stg_orders"]);
+        assert_eq!(names(FindingKind::NoFreshness), vec!["raw.payments"]);
+        let undocumented = audit.findings_of(FindingKind::UndocumentedColumns);
+        assert_eq!(undocumented.len(), 2);
+        assert_eq!(undocumented[0].detail.as_deref(), Some("1 of 2: status"));
+
+        assert_eq!(audit.total.models, 2);
+        assert_eq!(audit.total.primary_keys, 1);
+        assert_eq!(percent(audit.total.documented_columns, audit.total.columns), Some(50.0));
+        let folders: Vec<&str> = audit.by_folder.iter().map(|c| c.group.as_str()).collect();
+        assert_eq!(folders, vec!["models/marts", "models/staging"]);
+        assert_eq!(audit.by_schema.len(), 1);
+        assert_eq!(audit.fresh_sources, 1);
+    }
+
+    #[test]
+    fn test_primary_key_pairing() {
+        let mut combined = test("orders_key", "unique_combination_of_columns", "orders", "");
+        combined.column_name = None;
+        // Tests without attached_node are resolved through their model argument
+        let mut payments_unique = test("unique_payments_id", "unique", "payments", "id");
+        payments_unique.attached_node = None;
+        payments_unique.depends_on.nodes = vec!["model.shop.payments".to_string()];
+        if let Some(ref mut metadata) = payments_unique.test_metadata {
+            metadata.kwargs.insert(
+                "model".to_string(),
+                "{{ get_where_subquery(ref('payments')) }}".into(),
+            );
+        }
+        let mut source_unique = test("unique_raw_id", "unique", "raw", "id");
+        source_unique.attached_node = Some("source.shop.raw.customers".to_string());
+        let mut source_not_null = test("not_null_raw_id", "not_null", "raw", "id");
+        source_not_null.attached_node = Some("source.shop.raw.customers".to_string());
+
+        let nodes = vec![
+            node("orders", "model", "models/orders.sql"),
+            node("customers", "model", "models/customers.sql"),
+            node("payments", "model", "models/payments.sql"),
+            node("refunds", "model", "models/refunds.sql"),
+            combined,
+            // unique and not_null on different columns is not a key
+            test("unique_customers_id", "unique", "customers", "id"),
+            test("not_null_customers_email", "not_null", "customers", "email"),
+            payments_unique,
+            test("not_null_payments_id", "not_null", "payments", "id"),
+            test("refunds_pk", "primary_key", "refunds", "id"),
+            source_unique,
+            source_not_null,
+        ];
+
+        let mut keyed: Vec<&str> = primary_key_models(&nodes).into_iter().collect();
+        keyed.sort();
+        assert_eq!(
+            keyed,
+            vec!["model.shop.orders", "model.shop.payments", "model.shop.refunds"]
+        );
+    }
+
+    #[test]
+    fn test_coverage_by_folder_and_schema() {
+        let mut orders = node("orders", "model", "models/marts/orders.sql");
+        orders.description = Some("Orders".to_string());
+        let mut revenue = node("revenue", "model", "models/marts/revenue.sql");
+        revenue.config.schema = Some("finance".to_string());
+        let mut stg_orders = node("stg_orders", "model", "models/staging/stg_orders.sql");
+        stg_orders.config.schema = Some("finance".to_string());
+        stg_orders.description = Some("Staged orders".to_string());
+        let nodes = vec![orders, revenue, stg_orders];
+
+        let audit = HealthAudit::build(&nodes, &[], None);
+
+        let summary = |grouping| -> Vec<(String, usize, usize)> {
+            audit
+                .coverage(grouping)
+                .iter()
+                .map(|c| (c.group.clone(), c.models, c.described))
+                .collect()
+        };
+        assert_eq!(
+            summary(AuditGrouping::Folder),
+            vec![
+                ("models/marts".to_string(), 2, 1),
+                ("models/staging".to_string(), 1, 1),
+            ]
+        );
+        assert_eq!(
+            summary(AuditGrouping::Schema),
+            vec![("finance".to_string(), 2, 1), ("public".to_string(), 1, 1)]
+        );
+        assert_eq!(audit.total.models, 3);
+        assert_eq!(audit.total.described, 2);
+    }
+
+    #[test]
+    fn test_catalog_only_columns_are_undocumented() {
+        let mut orders = node("orders", "model", "models/orders.sql");
+        orders.columns = HashMap::from([column("id", "Key")]);
+        let catalog = Catalog::from_json(
+            r#"{
+                "nodes": {
+                    "model.shop.orders": {
+                        "columns": {
+                            "ID": {"type": "NUMBER", "index": 1, "name": "ID"},
+                            "STATUS": {"type": "TEXT", "index": 2, "name": "STATUS"}
+                        }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        let nodes = vec![orders];
+
+        let audit = HealthAudit::build(&nodes, &[], Some(&catalog));
+
+        let undocumented = audit.findings_of(FindingKind::UndocumentedColumns);
+        assert_eq!(undocumented.len(), 1);
+        assert_eq!(undocumented[0].detail.as_deref(), Some("1 of 2: status"));
+        assert_eq!(audit.total.columns, 2);
+        assert_eq!(audit.total.documented_columns, 1);
+
+        let audit = HealthAudit::build(&nodes, &[], None);
+        assert!(audit.findings_of(FindingKind::UndocumentedColumns).is_empty());
+        assert_eq!(audit.total.columns, 1);
+    }
+}
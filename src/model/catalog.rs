@@ -0,0 +1,113 @@
+//! Data models for the dbt catalog.json artifact
+//!
+//! `dbt docs generate` writes `target/catalog.json` with the columns and
+//! data types of every relation as they exist in the warehouse.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// A column as it exists in the warehouse
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarehouseColumn {
+    pub name: String,
+    /// Unknown when the columns come from `dbt show`
+    pub data_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CatalogColumn {
+    name: String,
+    #[serde(rename = "type", default)]
+    data_type: Option<String>,
+    #[serde(default)]
+    index: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct CatalogTable {
+    #[serde(default)]
+    columns: HashMap<String, CatalogColumn>,
+}
+
+/// Parsed catalog.json
+#[derive(Debug, Default, Deserialize)]
+pub struct Catalog {
+    #[serde(default)]
+    nodes: HashMap<String, CatalogTable>,
+    #[serde(default)]
+    sources: HashMap<String, CatalogTable>,
+}
+
+impl Catalog {
+    pub fn from_json(contents: &str) -> Result<Self, String> {
+        serde_json::from_str(contents).map_err(|e| format!("Failed to parse catalog.json: {}", e))
+    }
+
+    /// Columns of a node or source in warehouse order, if the catalog has it
+    pub fn columns(&self, unique_id: &str) -> Option<Vec<WarehouseColumn>> {
+        let table = self
+            .nodes
+            .get(unique_id)
+            .or_else(|| self.sources.get(unique_id))?;
+        let mut columns: Vec<&CatalogColumn> = table.columns.values().collect();
+        columns.sort_by_key(|c| c.index);
+        Some(
+            columns
+                .into_iter()
+                .map(|c| WarehouseColumn {
+                    name: c.name.clone(),
+                    data_type: c.data_type.clone(),
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_in_warehouse_order() {
+        let catalog = Catalog::from_json(
+            r#"{
+                "metadata": {"dbt_version": "1.7.4"},
+                "nodes": {
+                    "model.shop.orders": {
+                        "metadata": {"type": "VIEW", "name": "ORDERS"},
+                        "columns": {
+                            "STATUS": {"type": "TEXT", "index": 2, "name": "STATUS", "comment": null},
+                            "ORDER_ID": {"type": "NUMBER", "index": 1, "name": "ORDER_ID", "comment": null}
+                        }
+                    }
+                },
+                "sources": {
+                    "source.shop.raw.payments": {
+                        "metadata": {"type": "BASE TABLE", "name": "PAYMENTS"},
+                        "columns": {
+                            "ID": {"type": "NUMBER", "index": 1, "name": "ID", "comment": null}
+                        }
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let columns = catalog.columns("model.shop.orders").unwrap();
+        assert_eq!(
+            columns,
+            vec![
+                WarehouseColumn {
+                    name: "ORDER_ID".to_string(),
+                    data_type: Some("NUMBER".to_string()),
+                },
+                WarehouseColumn {
+                    name: "STATUS".to_string(),
+                    data_type: Some("TEXT".to_string()),
+                },
+            ]
+        );
+        assert_eq!(catalog.columns("source.shop.raw.payments").unwrap().len(), 1);
+        assert!(catalog.columns("model.shop.customers").is_none());
+    }
+}
@@ -0,0 +1,1092 @@
+//! Column-level lineage
+//!
+//! Each model's SQL (compiled, or raw with Jinja refs and sources replaced
+//! by their table names) is parsed just far enough to map every output
+//! column to the upstream columns it is computed from, following CTEs,
+//! subqueries, table aliases, `select *` and simple expressions. Tables are
+//! matched to the model's parents by the table part of their relation in
+//! compiled SQL, and by name in raw SQL.
+//!
+//! When a table's columns are unknown, `select *` from it is kept as a
+//! pass-through: any column read from the result is assumed to come from
+//! the column of the same name upstream.
+
+use super::node::{Node, SourceTable};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+/// Column of another node
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ColumnRef {
+    pub unique_id: String,
+    /// Lowercased column name
+    pub column: String,
+}
+
+/// Output column of a model
+#[derive(Debug, Clone, PartialEq)]
+struct ModelColumn {
+    /// Lowercased; "*" for a pass-through of an upstream table
+    name: String,
+    sources: Vec<ColumnRef>,
+    /// False when some part of the expression could not be traced
+    resolved: bool,
+}
+
+/// Upstream columns of one column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSources {
+    pub sources: Vec<ColumnRef>,
+    pub resolved: bool,
+}
+
+/// Column lineage for every model in the project
+#[derive(Debug, Clone, Default)]
+pub struct ColumnLineage {
+    columns: HashMap<String, Vec<ModelColumn>>,
+    /// Columns derived from each upstream column
+    downstream: HashMap<ColumnRef, Vec<ColumnRef>>,
+    /// Models that pass all columns of a parent through with `select *`
+    pass_through: HashMap<String, Vec<String>>,
+    names: HashMap<String, String>,
+}
+
+impl ColumnLineage {
+    pub fn build(all_nodes: &[Node], sources: &[SourceTable]) -> Self {
+        let mut builder = Builder {
+            nodes: all_nodes.iter().map(|n| (n.unique_id.as_str(), n)).collect(),
+            sources: sources.iter().map(|s| (s.unique_id.as_str(), s)).collect(),
+            done: HashMap::new(),
+            in_progress: HashSet::new(),
+        };
+        for node in all_nodes {
+            builder.model_columns(&node.unique_id);
+        }
+
+        let mut lineage = Self {
+            columns: builder.done.into_iter().filter_map(|(id, cols)| Some((id, cols?))).collect(),
+            ..Self::default()
+        };
+        for (id, columns) in &lineage.columns {
+            for column in columns {
+                for source in &column.sources {
+                    if column.name == "*" {
+                        lineage
+                            .pass_through
+                            .entry(source.unique_id.clone())
+                            .or_default()
+                            .push(id.clone());
+                    } else {
+                        lineage.downstream.entry(source.clone()).or_default().push(ColumnRef {
+                            unique_id: id.clone(),
+                            column: column.name.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        for children in lineage.downstream.values_mut() {
+            children.sort();
+            children.dedup();
+        }
+        lineage.names = all_nodes
+            .iter()
+            .map(|n| (n.unique_id.clone(), n.name.clone()))
+            .chain(
+                sources
+                    .iter()
+                    .map(|s| (s.unique_id.clone(), format!("{}.{}", s.source_name, s.name))),
+            )
+            .collect();
+        lineage
+    }
+
+    /// Whether the node's SQL was analysed
+    pub fn has_model(&self, unique_id: &str) -> bool {
+        self.columns.contains_key(unique_id)
+    }
+
+    /// Where a model column comes from; None if the column isn't in the
+    /// model's output as far as the SQL could be traced
+    pub fn upstream(&self, unique_id: &str, column: &str) -> Option<ColumnSources> {
+        let columns = self.columns.get(unique_id)?;
+        let column = column.to_lowercase();
+        if let Some(found) = columns.iter().find(|c| c.name == column) {
+            return Some(ColumnSources {
+                sources: found.sources.clone(),
+                resolved: found.resolved,
+            });
+        }
+        let stars: Vec<&ModelColumn> = columns.iter().filter(|c| c.name == "*").collect();
+        match stars.as_slice() {
+            [star] => Some(ColumnSources {
+                sources: star
+                    .sources
+                    .iter()
+                    .map(|s| ColumnRef {
+                        unique_id: s.unique_id.clone(),
+                        column: column.clone(),
+                    })
+                    .collect(),
+                resolved: star.resolved,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Columns of other models computed from this column
+    pub fn downstream(&self, unique_id: &str, column: &str) -> Vec<ColumnRef> {
+        let column = column.to_lowercase();
+        let key = ColumnRef {
+            unique_id: unique_id.to_string(),
+            column: column.clone(),
+        };
+        let mut children = self.downstream.get(&key).cloned().unwrap_or_default();
+        for child in self.pass_through.get(unique_id).into_iter().flatten() {
+            let shadowed = self
+                .columns
+                .get(child)
+                .is_some_and(|cols| cols.iter().any(|c| c.name == column));
+            if !shadowed {
+                children.push(ColumnRef {
+                    unique_id: child.clone(),
+                    column: column.clone(),
+                });
+            }
+        }
+        children.sort();
+        children.dedup();
+        children
+    }
+
+    /// Display name of a node or source
+    pub fn name<'a>(&'a self, unique_id: &'a str) -> &'a str {
+        self.names.get(unique_id).map_or(unique_id, String::as_str)
+    }
+}
+
+/// Memoised per-model analysis, so `select *` from a parent model can use
+/// the parent's own output columns
+struct Builder<'a> {
+    nodes: HashMap<&'a str, &'a Node>,
+    sources: HashMap<&'a str, &'a SourceTable>,
+    /// None for nodes that aren't analysed (seeds, tests, unparseable SQL)
+    done: HashMap<String, Option<Vec<ModelColumn>>>,
+    in_progress: HashSet<String>,
+}
+
+impl Builder<'_> {
+    fn model_columns(&mut self, unique_id: &str) -> Option<Vec<ModelColumn>> {
+        if let Some(done) = self.done.get(unique_id) {
+            return done.clone();
+        }
+        if !self.in_progress.insert(unique_id.to_string()) {
+            return None;
+        }
+        let result = self.analyse(unique_id);
+        self.in_progress.remove(unique_id);
+        self.done.insert(unique_id.to_string(), result.clone());
+        result
+    }
+
+    fn analyse(&mut self, unique_id: &str) -> Option<Vec<ModelColumn>> {
+        let node = *self.nodes.get(unique_id)?;
+        if !matches!(node.resource_type.as_str(), "model" | "snapshot") {
+            return None;
+        }
+        let compiled = node.compiled_code.is_some();
+        let sql = match node.compiled_code {
+            Some(ref code) => code.clone(),
+            None => strip_jinja(node.raw_code.as_deref()?),
+        };
+
+        // Table name (as it appears in SQL) -> parent. Compiled SQL names the
+        // relation, which an alias or identifier can make differ from the name.
+        let mut parents: HashMap<String, String> = HashMap::new();
+        for parent in &node.depends_on.nodes {
+            let table = match (self.nodes.get(parent.as_str()), self.sources.get(parent.as_str())) {
+                (Some(n), _) if compiled => relation_table(n.relation_name.as_deref())
+                    .or(n.config.alias.as_deref())
+                    .unwrap_or(&n.name)
+                    .to_string(),
+                (Some(n), _) => n.name.clone(),
+                (None, Some(s)) if compiled => relation_table(s.relation_name.as_deref())
+                    .or(s.identifier.as_deref())
+                    .unwrap_or(&s.name)
+                    .to_string(),
+                (None, Some(s)) => s.name.clone(),
+                _ => continue,
+            };
+            parents.insert(table.to_lowercase(), parent.clone());
+        }
+        let mut known: HashMap<String, Option<Vec<String>>> = HashMap::new();
+        for (table, parent) in &parents {
+            known.insert(table.clone(), self.parent_columns(parent));
+        }
+
+        let columns = parse_select_columns(&sql, &|table| known.get(table).cloned().flatten())?;
+        Some(
+            columns
+                .into_iter()
+                .map(|col| {
+                    let mut resolved = col.resolved;
+                    let mut sources: Vec<ColumnRef> = col
+                        .origins
+                        .into_iter()
+                        .filter_map(|(table, column)| match parents.get(&table) {
+                            Some(parent) => Some(ColumnRef {
+                                unique_id: parent.clone(),
+                                column,
+                            }),
+                            None => {
+                                resolved = false;
+                                None
+                            }
+                        })
+                        .collect();
+                    sources.sort();
+                    sources.dedup();
+                    ModelColumn {
+                        name: col.name,
+                        sources,
+                        resolved,
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Output columns of a parent, if known
+    fn parent_columns(&mut self, parent: &str) -> Option<Vec<String>> {
+        if let Some(source) = self.sources.get(parent) {
+            return (!source.columns.is_empty())
+                .then(|| source.columns.keys().map(|c| c.to_lowercase()).collect());
+        }
+        if let Some(columns) = self.model_columns(parent) {
+            return (!columns.iter().any(|c| c.name == "*"))
+                .then(|| columns.into_iter().map(|c| c.name).collect());
+        }
+        let node = self.nodes.get(parent)?;
+        (!node.columns.is_empty()).then(|| node.columns.keys().map(|c| c.to_lowercase()).collect())
+    }
+}
+
+/// Table part of a relation name, e.g. `orders_v2` of `"db"."public"."orders_v2"`
+fn relation_table(relation_name: Option<&str>) -> Option<&str> {
+    let table = relation_name?.rsplit('.').next()?;
+    Some(table.trim_matches(|c| matches!(c, '"' | '`' | '[' | ']')))
+}
+
+static JINJA_COMMENT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)\{#.*?#\}").unwrap());
+
+/// `{{ ref('orders') }}`, `{{ source('raw', 'orders') }}`; captures the table name
+static JINJA_REF_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"\{\{\s*(?:ref|source)\s*\(\s*(?:['"][^'"]*['"]\s*,\s*)?['"]([^'"]+)['"]\s*\)\s*\}\}"#,
+    )
+    .unwrap()
+});
+
+static JINJA_OTHER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)\{\{.*?\}\}|\{%.*?%\}").unwrap());
+
+/// Replace `{{ ref(...) }}` and `{{ source(...) }}` with the table name and
+/// drop other Jinja, so uncompiled models can still be analysed
+fn strip_jinja(raw: &str) -> String {
+    let sql = JINJA_COMMENT_REGEX.replace_all(raw, "");
+    let sql = JINJA_REF_REGEX.replace_all(&sql, "$1");
+    JINJA_OTHER_REGEX.replace_all(&sql, "").into_owned()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// SQL parsing
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Lowercased identifier or keyword; `quoted` ones are never keywords
+    Word { text: String, quoted: bool },
+    Literal,
+    Symbol(char),
+}
+
+fn tokenize(sql: &str) -> Vec<Token> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '-' && next == Some('-') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+        } else if c == '\'' {
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == '\'' && chars.get(i + 1) == Some(&'\'') {
+                    i += 2;
+                } else if chars[i] == '\'' {
+                    break;
+                } else {
+                    i += 1;
+                }
+            }
+            i += 1;
+            tokens.push(Token::Literal);
+        } else if c == '"' || c == '`' || c == '[' {
+            let close = if c == '[' { ']' } else { c };
+            let start = i + 1;
+            i = start;
+            while i < chars.len() && chars[i] != close {
+                i += 1;
+            }
+            tokens.push(Token::Word {
+                text: chars[start..i.min(chars.len())].iter().collect::<String>().to_lowercase(),
+                quoted: true,
+            });
+            i += 1;
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+            tokens.push(Token::Literal);
+        } else if c.is_alphanumeric() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$') {
+                i += 1;
+            }
+            tokens.push(Token::Word {
+                text: chars[start..i].iter().collect::<String>().to_lowercase(),
+                quoted: false,
+            });
+        } else {
+            tokens.push(Token::Symbol(c));
+            i += 1;
+        }
+    }
+    tokens
+}
+
+/// Words that are never column names in an expression
+const EXPRESSION_KEYWORDS: &[&str] = &[
+    "and", "or", "not", "null", "true", "false", "is", "in", "like", "ilike", "between",
+    "case", "when", "then", "else", "end", "distinct", "as", "over", "partition", "by",
+    "order", "asc", "desc", "nulls", "first", "last", "rows", "range", "unbounded",
+    "preceding", "following", "current", "row", "interval", "filter", "where", "within",
+    "group", "exists", "any", "all", "some", "similar", "escape", "date", "timestamp",
+    "time", "ignore", "respect", "separator", "from",
+];
+
+/// Words that end a FROM item or the FROM clause
+const CLAUSE_KEYWORDS: &[&str] = &[
+    "where", "group", "having", "qualify", "order", "limit", "offset", "window", "union",
+    "intersect", "except", "minus", "fetch", "on", "using", "join", "inner", "left", "right",
+    "full", "outer", "cross", "natural", "lateral", "pivot", "unpivot", "sample", "tablesample",
+];
+
+/// A column of a query's result
+#[derive(Debug, Clone, PartialEq)]
+struct QueryColumn {
+    /// Lowercased; "*" stands for every column of the tables in `origins`
+    name: String,
+    /// (physical table, column); column is "*" for pass-through entries
+    origins: Vec<(String, String)>,
+    resolved: bool,
+}
+
+/// Relation visible in a FROM clause
+#[derive(Debug, Clone)]
+struct Relation {
+    alias: String,
+    columns: Vec<QueryColumn>,
+}
+
+impl Relation {
+    /// Origins of one of the relation's columns
+    fn column(&self, name: &str) -> Option<(Vec<(String, String)>, bool)> {
+        if let Some(column) = self.columns.iter().find(|c| c.name == name) {
+            return Some((column.origins.clone(), column.resolved));
+        }
+        let stars: Vec<&QueryColumn> = self.columns.iter().filter(|c| c.name == "*").collect();
+        match stars.as_slice() {
+            [star] => Some((
+                star.origins.iter().map(|(table, _)| (table.clone(), name.to_string())).collect(),
+                star.resolved,
+            )),
+            _ => None,
+        }
+    }
+
+    fn has_star(&self) -> bool {
+        self.columns.iter().any(|c| c.name == "*")
+    }
+}
+
+/// Output columns of the model's final SELECT. `known` gives the columns
+/// of a physical table, when they are known.
+fn parse_select_columns(
+    sql: &str,
+    known: &dyn Fn(&str) -> Option<Vec<String>>,
+) -> Option<Vec<QueryColumn>> {
+    let mut parser = Parser {
+        tokens: tokenize(sql),
+        pos: 0,
+        known,
+    };
+    let columns = parser.query(&HashMap::new());
+    (!columns.is_empty()).then_some(columns)
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    known: &'a dyn Fn(&str) -> Option<Vec<String>>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn is_word(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word { text, quoted: false }) if text == word)
+    }
+
+    fn is_symbol(&self, symbol: char) -> bool {
+        self.peek() == Some(&Token::Symbol(symbol))
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        let found = self.is_word(word);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn eat_symbol(&mut self, symbol: char) -> bool {
+        let found = self.is_symbol(symbol);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    /// Skip to the matching close parenthesis; the open one is already consumed
+    fn skip_parens(&mut self) {
+        let mut depth = 1;
+        while let Some(token) = self.peek() {
+            match token {
+                Token::Symbol('(') => depth += 1,
+                Token::Symbol(')') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.pos += 1;
+                        return;
+                    }
+                }
+                _ => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Whether the query (or a parenthesised part of it) ends here
+    fn at_query_end(&self) -> bool {
+        matches!(self.peek(), None | Some(Token::Symbol(')')) | Some(Token::Symbol(';')))
+    }
+
+    /// `[WITH ctes] select [UNION select]...`
+    fn query(&mut self, outer_ctes: &HashMap<String, Vec<QueryColumn>>) -> Vec<QueryColumn> {
+        let mut ctes = outer_ctes.clone();
+        if self.eat_word("with") {
+            self.eat_word("recursive");
+            while let Some(Token::Word { text, .. }) = self.peek().cloned() {
+                self.pos += 1;
+                if self.eat_symbol('(') {
+                    self.skip_parens();
+                }
+                self.eat_word("as");
+                self.eat_word("materialized");
+                if !self.eat_symbol('(') {
+                    break;
+                }
+                let columns = self.query(&ctes);
+                self.eat_symbol(')');
+                ctes.insert(text, columns);
+                if !self.eat_symbol(',') {
+                    break;
+                }
+            }
+        }
+
+        let mut columns = self.select(&ctes);
+        loop {
+            let set_operation = ["union", "intersect", "except", "minus"]
+                .iter()
+                .any(|w| self.is_word(w));
+            if !set_operation {
+                break;
+            }
+            self.pos += 1;
+            let _ = self.eat_word("all") || self.eat_word("distinct");
+            let branch = self.select(&ctes);
+            // Later branches contribute positionally, unless stars make positions unknown
+            let positional = !columns.iter().chain(&branch).any(|c| c.name == "*")
+                && branch.len() == columns.len();
+            if positional {
+                for (column, other) in columns.iter_mut().zip(branch) {
+                    column.origins.extend(other.origins);
+                    column.resolved &= other.resolved;
+                }
+            } else {
+                for column in &mut columns {
+                    column.resolved = false;
+                }
+            }
+        }
+        // Anything left (e.g. ORDER BY after a set operation) doesn't change the columns
+        while !self.at_query_end() {
+            if self.eat_symbol('(') {
+                self.skip_parens();
+            } else {
+                self.pos += 1;
+            }
+        }
+        columns
+    }
+
+    /// One SELECT, or a parenthesised query
+    fn select(&mut self, ctes: &HashMap<String, Vec<QueryColumn>>) -> Vec<QueryColumn> {
+        if self.eat_symbol('(') {
+            let columns = self.query(ctes);
+            self.eat_symbol(')');
+            return columns;
+        }
+        if !self.eat_word("select") {
+            return Vec::new();
+        }
+        let _ = self.eat_word("distinct") || self.eat_word("all");
+        if self.eat_word("top") {
+            self.pos += 1;
+        }
+
+        // Collect select items as token ranges; resolve them once FROM is known
+        let mut items: Vec<Vec<Token>> = vec![Vec::new()];
+        let mut depth = 0;
+        while let Some(token) = self.peek().cloned() {
+            if depth == 0 {
+                // EXCEPT right after a star is BigQuery's column exclusion
+                let except = matches!(token, Token::Word { ref text, quoted: false } if text == "except");
+                let star_except =
+                    except && items.last().and_then(|i| i.last()) == Some(&Token::Symbol('*'));
+                let ends = matches!(token, Token::Word { ref text, quoted: false }
+                    if ["from", "where", "group", "having", "qualify", "order", "limit", "window",
+                        "union", "intersect", "minus"].contains(&text.as_str()))
+                    || matches!(token, Token::Symbol(')') | Token::Symbol(';'));
+                if ends || (except && !star_except) {
+                    break;
+                }
+                if token == Token::Symbol(',') {
+                    items.push(Vec::new());
+                    self.pos += 1;
+                    continue;
+                }
+            }
+            match token {
+                Token::Symbol('(') => depth += 1,
+                Token::Symbol(')') => depth -= 1,
+                _ => {}
+            }
+            items.last_mut().expect("never empty").push(token);
+            self.pos += 1;
+        }
+
+        let relations = if self.eat_word("from") {
+            self.relations(ctes)
+        } else {
+            Vec::new()
+        };
+
+        // Skip WHERE, GROUP BY and the rest up to a set operation or the end
+        while !self.at_query_end()
+            && !["union", "intersect", "except", "minus"].iter().any(|w| self.is_word(w))
+        {
+            if self.eat_symbol('(') {
+                self.skip_parens();
+            } else {
+                self.pos += 1;
+            }
+        }
+
+        let mut columns: Vec<QueryColumn> = Vec::new();
+        for item in items.into_iter().filter(|i| !i.is_empty()) {
+            self.select_item(&item, &relations, &mut columns);
+        }
+        columns
+    }
+
+    /// Relations of a FROM clause, up to the next clause keyword
+    fn relations(&mut self, ctes: &HashMap<String, Vec<QueryColumn>>) -> Vec<Relation> {
+        let mut relations = Vec::new();
+        loop {
+            while ["inner", "left", "right", "full", "outer", "cross", "natural", "join", "lateral"]
+                .iter()
+                .any(|w| self.is_word(w))
+            {
+                self.pos += 1;
+            }
+            if let Some(relation) = self.relation(ctes) {
+                relations.push(relation);
+            }
+            // Join condition
+            if self.eat_word("on") {
+                while !self.at_query_end()
+                    && !self.is_symbol(',')
+                    && !CLAUSE_KEYWORDS.iter().any(|w| *w != "on" && *w != "using" && self.is_word(w))
+                {
+                    if self.eat_symbol('(') {
+                        self.skip_parens();
+                    } else {
+                        self.pos += 1;
+                    }
+                }
+            } else if self.eat_word("using") && self.eat_symbol('(') {
+                self.skip_parens();
+            }
+            let next_join = ["inner", "left", "right", "full", "cross", "natural", "join", "lateral"]
+                .iter()
+                .any(|w| self.is_word(w));
+            if !self.eat_symbol(',') && !next_join {
+                break;
+            }
+        }
+        relations
+    }
+
+    fn relation(&mut self, ctes: &HashMap<String, Vec<QueryColumn>>) -> Option<Relation> {
+        let (default_alias, columns) = if self.eat_symbol('(') {
+            let subquery = self.is_word("select") || self.is_word("with") || self.is_symbol('(');
+            if !subquery {
+                self.skip_parens();
+                return None;
+            }
+            let columns = self.query(ctes);
+            self.eat_symbol(')');
+            (String::new(), columns)
+        } else {
+            let mut parts = Vec::new();
+            while let Some(Token::Word { text, quoted }) = self.peek().cloned() {
+                if !quoted && CLAUSE_KEYWORDS.contains(&text.as_str()) {
+                    break;
+                }
+                parts.push(text);
+                self.pos += 1;
+                if !self.eat_symbol('.') {
+                    break;
+                }
+            }
+            let table = parts.last()?.clone();
+            if self.eat_symbol('(') {
+                // Table function: its columns can't be known
+                self.skip_parens();
+                (table, Vec::new())
+            } else if parts.len() == 1 && ctes.contains_key(&table) {
+                (table.clone(), ctes[&table].clone())
+            } else {
+                let columns = match (self.known)(&table) {
+                    Some(names) => names
+                        .into_iter()
+                        .map(|name| QueryColumn {
+                            origins: vec![(table.clone(), name.clone())],
+                            name,
+                            resolved: true,
+                        })
+                        .collect(),
+                    None => vec![QueryColumn {
+                        name: "*".to_string(),
+                        origins: vec![(table.clone(), "*".to_string())],
+                        resolved: true,
+                    }],
+                };
+                (table, columns)
+            }
+        };
+
+        self.eat_word("as");
+        let alias = match self.peek().cloned() {
+            Some(Token::Word { text, quoted })
+                if quoted || !CLAUSE_KEYWORDS.contains(&text.as_str()) =>
+            {
+                self.pos += 1;
+                // Column aliases, e.g. `as t (a, b)`
+                if self.eat_symbol('(') {
+                    self.skip_parens();
+                }
+                text
+            }
+            _ => default_alias,
+        };
+        Some(Relation { alias, columns })
+    }
+
+    /// Add the columns produced by one select item
+    fn select_item(
+        &self,
+        item: &[Token],
+        relations: &[Relation],
+        columns: &mut Vec<QueryColumn>,
+    ) {
+        let word = |t: Option<&Token>| match t {
+            Some(Token::Word { text, .. }) => Some(text.clone()),
+            _ => None,
+        };
+
+        // `*`, `t.*`, optionally with EXCEPT/EXCLUDE (...)
+        let star_at = item.iter().position(|t| *t == Token::Symbol('*'));
+        if let Some(star_at) = star_at.filter(|&i| i == 0 || (i == 2 && item[1] == Token::Symbol('.'))) {
+            let excluded: HashSet<String> = item[star_at + 1..]
+                .iter()
+                .filter_map(|t| word(Some(t)))
+                .filter(|w| w != "except" && w != "exclude")
+                .collect();
+            let qualifier = (star_at == 2).then(|| word(item.first())).flatten();
+            for relation in relations {
+                if qualifier.as_ref().is_some_and(|q| *q != relation.alias) {
+                    continue;
+                }
+                columns.extend(
+                    relation
+                        .columns
+                        .iter()
+                        .filter(|c| !excluded.contains(&c.name))
+                        .cloned(),
+                );
+            }
+            return;
+        }
+
+        // Alias: `expr AS name` or `expr name`
+        let n = item.len();
+        let (expression, alias) = match (n >= 2).then(|| (&item[n - 2], &item[n - 1])) {
+            Some((Token::Word { text, quoted: false }, Token::Word { text: alias, .. })) if text == "as" => {
+                (&item[..n - 2], Some(alias.clone()))
+            }
+            Some((previous, Token::Word { text: alias, quoted }))
+                if (*quoted || !EXPRESSION_KEYWORDS.contains(&alias.as_str()))
+                    && matches!(previous, Token::Word { .. } | Token::Symbol(')') | Token::Literal)
+                    && !matches!(previous, Token::Word { text, quoted: false }
+                        if text != "end" && EXPRESSION_KEYWORDS.contains(&text.as_str())) =>
+            {
+                (&item[..n - 1], Some(alias.clone()))
+            }
+            _ => (item, None),
+        };
+
+        let mut origins = Vec::new();
+        let mut resolved = true;
+        let mut i = 0;
+        let mut depth = 0;
+        while i < expression.len() {
+            match &expression[i] {
+                Token::Symbol('(') => {
+                    let subquery = matches!(expression.get(i + 1), Some(Token::Word { text, quoted: false }) if text == "select");
+                    if subquery {
+                        resolved = false;
+                    }
+                    depth += 1;
+                    i += 1;
+                }
+                Token::Symbol(')') => {
+                    depth -= 1;
+                    i += 1;
+                }
+                Token::Word { text, quoted } => {
+                    // Identifier chain a.b.c
+                    let mut chain = vec![text.clone()];
+                    let mut end = i + 1;
+                    while expression.get(end) == Some(&Token::Symbol('.')) {
+                        match expression.get(end + 1) {
+                            Some(Token::Word { text, .. }) => {
+                                chain.push(text.clone());
+                                end += 2;
+                            }
+                            _ => break,
+                        }
+                    }
+                    let previous = i.checked_sub(1).and_then(|p| expression.get(p));
+                    let after_cast = previous == Some(&Token::Symbol(':'))
+                        || (depth > 0 && matches!(previous, Some(Token::Word { text, quoted: false }) if text == "as"));
+                    let function = expression.get(end) == Some(&Token::Symbol('('));
+                    let keyword = !quoted && chain.len() == 1 && EXPRESSION_KEYWORDS.contains(&text.as_str());
+                    // e.g. extract(day from ts)
+                    let date_part = matches!(expression.get(end), Some(Token::Word { text, quoted: false }) if text == "from");
+                    if !(after_cast || function || keyword || date_part) {
+                        match self.resolve(&chain, relations, columns) {
+                            Some((found, ok)) => {
+                                origins.extend(found);
+                                resolved &= ok;
+                            }
+                            None => resolved = false,
+                        }
+                    }
+                    i = end;
+                }
+                _ => i += 1,
+            }
+        }
+
+        let name = alias.or_else(|| match expression {
+            [Token::Word { text, .. }] => Some(text.clone()),
+            [.., Token::Symbol('.'), Token::Word { text, .. }] => Some(text.clone()),
+            _ => None,
+        });
+        if let Some(name) = name {
+            origins.sort();
+            origins.dedup();
+            columns.push(QueryColumn {
+                name,
+                origins,
+                resolved,
+            });
+        }
+    }
+
+    /// Origins of a column reference; None if no relation provides it
+    fn resolve(
+        &self,
+        chain: &[String],
+        relations: &[Relation],
+        earlier: &[QueryColumn],
+    ) -> Option<(Vec<(String, String)>, bool)> {
+        let column = chain.last()?;
+        if chain.len() >= 2 {
+            let qualifier = &chain[chain.len() - 2];
+            return relations.iter().find(|r| r.alias == *qualifier)?.column(column);
+        }
+        let exact: Vec<&Relation> = relations
+            .iter()
+            .filter(|r| r.columns.iter().any(|c| c.name == *column))
+            .collect();
+        if let Some(relation) = exact.first() {
+            return relation.column(column);
+        }
+        let starred: Vec<&Relation> = relations.iter().filter(|r| r.has_star()).collect();
+        if let [relation] = starred.as_slice() {
+            return relation.column(column);
+        }
+        // Lateral reference to an earlier select item
+        earlier
+            .iter()
+            .find(|c| c.name == *column)
+            .map(|c| (c.origins.clone(), c.resolved))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::node::ColumnInfo;
+
+    fn columns(sql: &str, known: &[(&str, &[&str])]) -> Vec<(String, Vec<String>, bool)> {
+        let known: HashMap<String, Vec<String>> = known
+            .iter()
+            .map(|(t, cols)| (t.to_string(), cols.iter().map(|c| c.to_string()).collect()))
+            .collect();
+        parse_select_columns(sql, &|t| known.get(t).cloned())
+            .unwrap()
+            .into_iter()
+            .map(|c| {
+                let origins = c.origins.iter().map(|(t, col)| format!("{}.{}", t, col)).collect();
+                (c.name, origins, c.resolved)
+            })
+            .collect()
+    }
+
+    fn col(name: &str, origins: &[&str], resolved: bool) -> (String, Vec<String>, bool) {
+        (
+            name.to_string(),
+            origins.iter().map(|o| o.to_string()).collect(),
+            resolved,
+        )
+    }
+
+    #[test]
+    fn test_ctes_aliases_and_expressions() {
+        let sql = r#"
+with orders as (
+    select * from "db"."raw"."orders"
+),
+payments as (
+    select order_id, sum(amount) as amount from analytics.stg_payments group by 1
+)
+select
+    o.id as order_id,
+    o.status,
+    coalesce(p.amount, 0)::numeric(10, 2) as amount, -- comment, with comma
+    case when o.status = 'done' then true else false end is_done,
+    cast(o.created_at as date) created_date,
+    extract(year from o.created_at) as order_year,
+    1 as one
+from orders o
+left join payments as p on p.order_id = o.id
+where o.id is not null
+"#;
+        assert_eq!(
+            columns(sql, &[("stg_payments", &["order_id", "amount"])]),
+            vec![
+                col("order_id", &["orders.id"], true),
+                col("status", &["orders.status"], true),
+                col("amount", &["stg_payments.amount"], true),
+                col("is_done", &["orders.status"], true),
+                col("created_date", &["orders.created_at"], true),
+                col("order_year", &["orders.created_at"], true),
+                col("one", &[], true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stars_unions_and_unresolved() {
+        let sql = "
+select a.*, b.extra from first_table a join second_table b using (id)
+union all
+select * except (note), missing_column, (select max(x) from other) as latest from third_table
+";
+        let result = columns(
+            sql,
+            &[("first_table", &["id", "name"]), ("second_table", &["id", "extra"]), ("third_table", &["id", "name", "note"])],
+        );
+        // Positions don't line up (star expansion), so nothing is trusted
+        assert_eq!(result.len(), 3);
+        assert!(result.iter().all(|(_, _, resolved)| !resolved));
+
+        assert_eq!(
+            columns("select id, name from unknown_table", &[]),
+            vec![
+                col("id", &["unknown_table.id"], true),
+                col("name", &["unknown_table.name"], true),
+            ]
+        );
+        assert_eq!(
+            columns("select a.id, b.id as other_id from x a, y b", &[("x", &["id"])]),
+            vec![
+                col("id", &["x.id"], true),
+                col("other_id", &["y.id"], true),
+            ]
+        );
+        assert_eq!(
+            columns("select nope from x", &[("x", &["id"])]),
+            vec![col("nope", &[], false)]
+        );
+    }
+
+    fn node(name: &str, resource_type: &str, sql: Option<&str>, depends_on: &[&str]) -> Node {
+        Node {
+            raw_code: sql.map(String::from),
+            ..Node::fixture(name, resource_type, depends_on)
+        }
+    }
+
+    #[test]
+    fn test_project_lineage() {
+        let source = SourceTable {
+            unique_id: "source.shop.raw.orders".to_string(),
+            name: "orders".to_string(),
+            source_name: "raw".to_string(),
+            columns: HashMap::from([(
+                "ID".to_string(),
+                ColumnInfo {
+                    name: "ID".to_string(),
+                    description: None,
+                    data_type: None,
+                },
+            )]),
+            ..Default::default()
+        };
+        let nodes = vec![
+            node(
+                "stg_orders",
+                "model",
+                Some("{{ config(materialized='view') }}\nselect id as order_id from {{ source('raw', 'orders') }}"),
+                &["source.shop.raw.orders"],
+            ),
+            node(
+                "orders_copy",
+                "model",
+                Some("select * from {{ ref('stg_orders') }}"),
+                &["model.shop.stg_orders"],
+            ),
+            node(
+                "fct_orders",
+                "model",
+                Some("select order_id, order_id * 2 as doubled from {{ ref('orders_copy') }}"),
+                &["model.shop.orders_copy"],
+            ),
+            node("countries", "seed", None, &[]),
+        ];
+        let lineage = ColumnLineage::build(&nodes, &[source]);
+
+        let stg = lineage.upstream("model.shop.stg_orders", "ORDER_ID").unwrap();
+        assert!(stg.resolved);
+        assert_eq!(
+            stg.sources,
+            vec![ColumnRef {
+                unique_id: "source.shop.raw.orders".to_string(),
+                column: "id".to_string(),
+            }]
+        );
+
+        // Star over a parent with known columns is expanded
+        let copied = lineage.upstream("model.shop.orders_copy", "order_id").unwrap();
+        assert_eq!(copied.sources[0].unique_id, "model.shop.stg_orders");
+        assert!(lineage.upstream("model.shop.orders_copy", "other").is_none());
+
+        let downstream = lineage.downstream("model.shop.orders_copy", "order_id");
+        assert_eq!(
+            downstream.iter().map(|c| c.column.as_str()).collect::<Vec<_>>(),
+            vec!["doubled", "order_id"]
+        );
+        assert!(!lineage.has_model("seed.shop.countries"));
+        assert_eq!(lineage.name("source.shop.raw.orders"), "raw.orders");
+    }
+
+    #[test]
+    fn test_aliased_parents() {
+        // Compiled SQL names the warehouse relation, not the dbt name
+        let source = SourceTable {
+            unique_id: "source.shop.raw.orders".to_string(),
+            name: "orders".to_string(),
+            source_name: "raw".to_string(),
+            identifier: Some("orders_v2".to_string()),
+            relation_name: Some("\"db\".\"raw\".\"orders_v2\"".to_string()),
+            ..Default::default()
+        };
+        let mut stg = node("stg_orders", "model", None, &["source.shop.raw.orders"]);
+        stg.compiled_code = Some("select id as order_id from \"db\".\"raw\".\"orders_v2\"".to_string());
+        stg.config.alias = Some("orders_staging".to_string());
+        let mut fct = node("fct_orders", "model", None, &["model.shop.stg_orders"]);
+        fct.compiled_code = Some("select s.order_id from db.public.orders_staging as s".to_string());
+
+        let lineage = ColumnLineage::build(&[stg, fct], &[source]);
+
+        let stg = lineage.upstream("model.shop.stg_orders", "order_id").unwrap();
+        assert!(stg.resolved);
+        assert_eq!(stg.sources[0].unique_id, "source.shop.raw.orders");
+        assert_eq!(stg.sources[0].column, "id");
+        let fct = lineage.upstream("model.shop.fct_orders", "order_id").unwrap();
+        assert!(fct.resolved);
+        assert_eq!(fct.sources[0].unique_id, "model.shop.stg_orders");
+    }
+}
@@ -0,0 +1,312 @@
+//! Comparison of two run history entries
+//!
+//! Compares per-node outcomes (status and duration), collects error lines
+//! that only appear in the newer run, and diffs the normalized output.
+
+use super::diff::{diff_lines, with_context, DiffLine};
+use super::history::RunHistoryEntry;
+use super::node::Node;
+use super::run::{ModelRunStatus, RunOutput, TIMESTAMP_REGEX};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::LazyLock;
+
+/// Durations that vary between runs, e.g. "in 1.23s" or "(1.23s)"
+static DURATION_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\d+(\.\d+)?s\b").unwrap());
+
+/// Minimum duration change (in seconds) reported as a delta
+const MIN_DURATION_DELTA: f64 = 0.05;
+
+/// Lines of unchanged output kept around each change
+const DIFF_CONTEXT: usize = 2;
+
+/// Outcome of a single node within a run
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeOutcome {
+    /// success, warn, error, skipped or running, whether it came from
+    /// run_results.json or the output
+    pub status: String,
+    pub duration: Option<f64>,
+}
+
+/// A node whose status differs between the two runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusChange {
+    pub name: String,
+    /// None if the node did not run in the base run
+    pub before: Option<String>,
+    /// None if the node did not run in the newer run
+    pub after: Option<String>,
+}
+
+/// Duration change of a node that ran in both runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct DurationDelta {
+    pub name: String,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl DurationDelta {
+    pub fn delta(&self) -> f64 {
+        self.after - self.before
+    }
+}
+
+/// Result of comparing two runs; `base` is the older run, `head` the newer one
+#[derive(Debug, Clone)]
+pub struct RunComparison {
+    pub base: RunHistoryEntry,
+    pub head: RunHistoryEntry,
+    pub status_changes: Vec<StatusChange>,
+    /// Sorted by absolute delta, largest first
+    pub duration_deltas: Vec<DurationDelta>,
+    /// Error lines present in head but not in base
+    pub new_errors: Vec<String>,
+    /// Diff of normalized output with context
+    pub output_diff: Vec<DiffLine>,
+}
+
+impl RunComparison {
+    /// Compare two entries; the older one becomes the base. Nodes are matched
+    /// by name, resolved through `all_nodes` for runs parsed from their output.
+    pub fn build(a: &RunHistoryEntry, b: &RunHistoryEntry, all_nodes: &[Node]) -> Self {
+        let (base, head) = if a.timestamp <= b.timestamp { (a, b) } else { (b, a) };
+
+        let base_outcomes = node_outcomes(base, all_nodes);
+        let head_outcomes = node_outcomes(head, all_nodes);
+
+        let mut names: Vec<&String> = base_outcomes.keys().chain(head_outcomes.keys()).collect();
+        names.sort();
+        names.dedup();
+
+        let mut status_changes = Vec::new();
+        let mut duration_deltas = Vec::new();
+        for name in names {
+            let before = base_outcomes.get(name);
+            let after = head_outcomes.get(name);
+
+            if before.map(|o| &o.status) != after.map(|o| &o.status) {
+                status_changes.push(StatusChange {
+                    name: name.clone(),
+                    before: before.map(|o| o.status.clone()),
+                    after: after.map(|o| o.status.clone()),
+                });
+            }
+
+            if let (Some(before), Some(after)) = (
+                before.and_then(|o| o.duration),
+                after.and_then(|o| o.duration),
+            ) {
+                if (after - before).abs() >= MIN_DURATION_DELTA {
+                    duration_deltas.push(DurationDelta {
+                        name: name.clone(),
+                        before,
+                        after,
+                    });
+                }
+            }
+        }
+        duration_deltas.sort_by(|a, b| b.delta().abs().total_cmp(&a.delta().abs()));
+
+        let base_lines = normalized_lines(&base.output);
+        let head_lines = normalized_lines(&head.output);
+
+        let base_errors: HashSet<&str> = base_lines
+            .iter()
+            .filter(|l| is_error_line(l))
+            .map(|l| l.as_str())
+            .collect();
+        let mut new_errors: Vec<String> = Vec::new();
+        for line in head_lines.iter().filter(|l| is_error_line(l)) {
+            if !base_errors.contains(line.as_str()) && !new_errors.contains(line) {
+                new_errors.push(line.clone());
+            }
+        }
+
+        let base_refs: Vec<&str> = base_lines.iter().map(|l| l.as_str()).collect();
+        let head_refs: Vec<&str> = head_lines.iter().map(|l| l.as_str()).collect();
+        let output_diff = with_context(&diff_lines(&base_refs, &head_refs), DIFF_CONTEXT);
+
+        RunComparison {
+            base: base.clone(),
+            head: head.clone(),
+            status_changes,
+            duration_deltas,
+            new_errors,
+            output_diff,
+        }
+    }
+}
+
+/// Per-node outcomes keyed by node name.
+/// Uses run_results.json data when available, otherwise parses the output.
+fn node_outcomes(entry: &RunHistoryEntry, all_nodes: &[Node]) -> BTreeMap<String, NodeOutcome> {
+    if !entry.node_results.is_empty() {
+        return entry
+            .node_results
+            .iter()
+            .map(|r| {
+                // Tests pass and fail where models succeed and error
+                let status = match r.status.as_str() {
+                    "pass" => "success",
+                    "fail" | "runtime error" => "error",
+                    status => status,
+                };
+                (
+                    r.name().to_string(),
+                    NodeOutcome {
+                        status: status.to_string(),
+                        duration: Some(r.execution_time),
+                    },
+                )
+            })
+            .collect();
+    }
+
+    // Output names are "schema.alias"
+    let names: HashMap<String, &str> = all_nodes
+        .iter()
+        .map(|n| (n.log_name(), n.name.as_str()))
+        .collect();
+
+    let mut run_output = RunOutput::new(entry.command.clone());
+    for line in entry.output.lines() {
+        run_output.parse_output_line(line);
+    }
+
+    run_output
+        .model_runs
+        .into_iter()
+        .map(|m| {
+            let name = match names.get(&m.name) {
+                Some(name) => name.to_string(),
+                None => m.name.rsplit('.').next().unwrap_or(&m.name).to_string(),
+            };
+            let status = match m.status {
+                ModelRunStatus::Running => "running",
+                ModelRunStatus::Success => "success",
+                ModelRunStatus::Failed => "error",
+                ModelRunStatus::Skipped => "skipped",
+            };
+            (
+                name,
+                NodeOutcome {
+                    status: status.to_string(),
+                    duration: m.duration,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Output lines without timestamps and timings, so equal lines compare equal across runs
+fn normalized_lines(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(|line| {
+            let line = TIMESTAMP_REGEX.replace(line, "");
+            DURATION_REGEX.replace_all(&line, "…s").trim_end().to_string()
+        })
+        .filter(|line| !line.trim().is_empty())
+        .collect()
+}
+
+fn is_error_line(line: &str) -> bool {
+    line.contains("Error") || line.contains("ERROR") || line.contains(" FAIL ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::run_results::NodeResult;
+    use chrono::{Duration, Local};
+
+    fn entry(minutes_ago: i64, output: &str) -> RunHistoryEntry {
+        let timestamp = Local::now() - Duration::minutes(minutes_ago);
+        RunHistoryEntry {
+            id: RunHistoryEntry::id_for(&timestamp),
+            timestamp,
+            ..RunHistoryEntry::fixture("dbt run", output)
+        }
+    }
+
+    #[test]
+    fn test_compare_runs() {
+        let good = entry(
+            10,
+            "10:00:00  1 of 2 OK created sql view model a.stg ... [CREATE VIEW in 1.00s]\n\
+             10:00:01  2 of 2 OK created sql table model a.orders ... [SELECT 5 in 2.00s]\n\
+             10:00:02  Completed successfully",
+        );
+        let bad = entry(
+            1,
+            "11:00:00  1 of 2 OK created sql view model a.stg ... [CREATE VIEW in 3.00s]\n\
+             11:00:01  2 of 2 ERROR creating sql table model a.orders ... [ERROR in 0.50s]\n\
+             11:00:02  Database Error in model orders",
+        );
+
+        // Argument order doesn't matter: the older run is the base
+        let comparison = RunComparison::build(&bad, &good, &[]);
+        assert_eq!(comparison.base.id, good.id);
+
+        assert_eq!(
+            comparison.status_changes,
+            vec![StatusChange {
+                name: "orders".to_string(),
+                before: Some("success".to_string()),
+                after: Some("error".to_string()),
+            }]
+        );
+
+        assert_eq!(comparison.duration_deltas.len(), 2);
+        assert_eq!(comparison.duration_deltas[0].name, "stg");
+        assert_eq!(comparison.duration_deltas[0].delta(), 2.0);
+
+        assert!(comparison
+            .new_errors
+            .contains(&"Database Error in model orders".to_string()));
+
+        // The unchanged stg line (after normalizing timings) is kept only as context
+        assert!(comparison.output_diff.contains(&DiffLine::Removed(
+            "Completed successfully".to_string()
+        )));
+        assert!(!comparison.output_diff.iter().any(|l| matches!(
+            l,
+            DiffLine::Removed(s) if s.contains("a.stg")
+        )));
+    }
+
+    #[test]
+    fn test_compare_run_results_with_parsed_output() {
+        let mut nodes = vec![Node::fixture("orders", "model", &[])];
+        nodes[0].schema = "a".to_string();
+        nodes[0].config.alias = Some("fct_orders".to_string());
+
+        let parsed = entry(
+            10,
+            "10:00:00  1 of 1 OK created sql table model a.fct_orders ... [SELECT 5 in 2.00s]",
+        );
+        let from_results = RunHistoryEntry {
+            node_results: vec![
+                NodeResult::fixture("model.shop.orders", "success", 2.0),
+                NodeResult::fixture("test.shop.unique_orders_id", "pass", 0.1),
+            ],
+            ..entry(1, "")
+        };
+
+        let comparison = RunComparison::build(&parsed, &from_results, &nodes);
+
+        // The aliased model is matched by name and passing tests count as successes
+        assert_eq!(
+            comparison.status_changes,
+            vec![StatusChange {
+                name: "unique_orders_id".to_string(),
+                before: None,
+                after: Some("success".to_string()),
+            }]
+        );
+        assert!(comparison.duration_deltas.is_empty());
+    }
+}
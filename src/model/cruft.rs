@@ -0,0 +1,366 @@
+//! Dead and orphaned resource detection
+//!
+//! Finds candidates for pruning: models nothing selects from and no
+//! exposure uses, models not run recently according to run history,
+//! sources nothing selects from, and project macros nothing calls.
+//!
+//! Macros dbt calls itself (schema/alias naming, materialization helpers)
+//! and dispatch implementations (`default__x`, `<adapter>__x`) are never
+//! reported: the first are overrides of dbt's own, and the second are
+//! reached through `adapter.dispatch` rather than a direct call.
+
+use super::history::RunHistoryEntry;
+use super::lineage::LineageGraph;
+use super::node::{Exposure, Macro, Node, SourceTable};
+use chrono::{DateTime, Duration, Local};
+use std::collections::{HashMap, HashSet};
+
+/// Default age, in days, after which a model counts as stale
+pub const DEFAULT_STALE_DAYS: i64 = 30;
+
+/// Macros dbt calls implicitly, which never show up as dependencies
+const IMPLICIT_MACROS: &[&str] = &[
+    "generate_schema_name",
+    "generate_alias_name",
+    "generate_database_name",
+    "get_custom_alias",
+    "create_table_as",
+    "create_view_as",
+    "get_create_table_as_sql",
+    "get_create_view_as_sql",
+    "get_columns_in_relation",
+    "get_catalog",
+    "alter_column_type",
+    "drop_relation",
+    "rename_relation",
+    "truncate_relation",
+    "make_temp_relation",
+    "get_merge_sql",
+    "get_delete_insert_merge_sql",
+    "get_insert_overwrite_merge_sql",
+    "get_incremental_default_sql",
+    "snapshot_merge_sql",
+    "get_test_sql",
+    "get_where_subquery",
+    "should_full_refresh",
+    "should_store_failures",
+];
+
+/// Kind of cruft
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CruftKind {
+    LeafModel,
+    StaleModel,
+    UnusedSource,
+    UnusedMacro,
+}
+
+impl CruftKind {
+    pub const ALL: [CruftKind; 4] = [
+        CruftKind::LeafModel,
+        CruftKind::StaleModel,
+        CruftKind::UnusedSource,
+        CruftKind::UnusedMacro,
+    ];
+
+    pub fn title(&self, stale_days: i64) -> String {
+        match self {
+            CruftKind::LeafModel => "Models with no downstream nodes or exposures".to_string(),
+            CruftKind::StaleModel => format!("Models not run in {} days", stale_days),
+            CruftKind::UnusedSource => "Sources nothing selects from".to_string(),
+            CruftKind::UnusedMacro => "Macros never called".to_string(),
+        }
+    }
+}
+
+/// A model, source or macro that may be unused
+#[derive(Debug, Clone, PartialEq)]
+pub struct CruftItem {
+    pub unique_id: String,
+    pub name: String,
+    /// File relative to the project root
+    pub path: Option<String>,
+    /// Last run recorded in history, for models
+    pub last_run: Option<DateTime<Local>>,
+}
+
+/// Pruning candidates across the project
+#[derive(Debug, Clone, Default)]
+pub struct CruftReport {
+    pub leaf_models: Vec<CruftItem>,
+    /// Every model, never run first, then oldest run first
+    pub models: Vec<CruftItem>,
+    pub unused_sources: Vec<CruftItem>,
+    pub unused_macros: Vec<CruftItem>,
+    /// Whether history has any node results to judge staleness by
+    pub has_history: bool,
+}
+
+impl CruftReport {
+    pub fn build(
+        all_nodes: &[Node],
+        sources: &[SourceTable],
+        exposures: &[Exposure],
+        macros: &[Macro],
+        graph: &LineageGraph,
+        history: &[RunHistoryEntry],
+    ) -> Self {
+        let kinds: HashMap<&str, &str> = all_nodes
+            .iter()
+            .map(|n| (n.unique_id.as_str(), n.resource_type.as_str()))
+            .collect();
+        let exposed: HashSet<&str> = exposures
+            .iter()
+            .flat_map(|e| e.depends_on.nodes.iter().map(String::as_str))
+            .collect();
+        // Tests on a node don't make it used
+        let used = |unique_id: &str| {
+            exposed.contains(unique_id)
+                || graph
+                    .get_downstream(unique_id)
+                    .iter()
+                    .any(|child| kinds.get(child.unique_id.as_str()) != Some(&"test"))
+        };
+        let last_runs = last_runs(history);
+
+        let mut report = Self {
+            has_history: !last_runs.is_empty(),
+            ..Self::default()
+        };
+        for node in all_nodes.iter().filter(|n| n.resource_type == "model") {
+            let item = CruftItem {
+                unique_id: node.unique_id.clone(),
+                name: node.name.clone(),
+                path: node.original_file_path.clone(),
+                last_run: last_runs.get(node.unique_id.as_str()).copied(),
+            };
+            if !used(&node.unique_id) {
+                report.leaf_models.push(item.clone());
+            }
+            report.models.push(item);
+        }
+        report.leaf_models.sort_by(|a, b| a.name.cmp(&b.name));
+        report
+            .models
+            .sort_by(|a, b| a.last_run.cmp(&b.last_run).then(a.name.cmp(&b.name)));
+
+        report.unused_sources = sources
+            .iter()
+            .filter(|s| !used(&s.unique_id))
+            .map(|s| CruftItem {
+                unique_id: s.unique_id.clone(),
+                name: format!("{}.{}", s.source_name, s.name),
+                path: None,
+                last_run: None,
+            })
+            .collect();
+
+        let callers: HashMap<&str, usize> =
+            macros.iter().map(|m| (m.name.as_str(), m.callers)).collect();
+        // Called directly, by dbt itself, or through dispatch of a called macro
+        let macro_used = |name: &str| -> bool {
+            if callers.get(name).is_some_and(|&n| n > 0)
+                || IMPLICIT_MACROS.contains(&name)
+                || name.starts_with("materialization_")
+            {
+                return true;
+            }
+            match name.split_once("__") {
+                // A dispatch implementation of a dbt or package macro, or of a
+                // project macro that is itself used
+                Some((_, dispatched)) if !dispatched.is_empty() => {
+                    callers.get(dispatched).is_none_or(|&n| n > 0)
+                        || IMPLICIT_MACROS.contains(&dispatched)
+                }
+                _ => false,
+            }
+        };
+        report.unused_macros = macros
+            .iter()
+            .filter(|m| !macro_used(&m.name))
+            .map(|m| CruftItem {
+                unique_id: m.unique_id.clone(),
+                name: m.name.clone(),
+                path: m.original_file_path.clone(),
+                last_run: None,
+            })
+            .collect();
+        report
+    }
+
+    /// Models never run, or last run more than `days` before `now`
+    pub fn stale_models(&self, days: i64, now: DateTime<Local>) -> Vec<&CruftItem> {
+        let cutoff = now - Duration::days(days);
+        self.models
+            .iter()
+            .filter(|m| m.last_run.is_none_or(|t| t < cutoff))
+            .collect()
+    }
+
+    pub fn items(&self, kind: CruftKind, stale_days: i64, now: DateTime<Local>) -> Vec<&CruftItem> {
+        match kind {
+            CruftKind::LeafModel => self.leaf_models.iter().collect(),
+            CruftKind::StaleModel if !self.has_history => Vec::new(),
+            CruftKind::StaleModel => self.stale_models(stale_days, now),
+            CruftKind::UnusedSource => self.unused_sources.iter().collect(),
+            CruftKind::UnusedMacro => self.unused_macros.iter().collect(),
+        }
+    }
+}
+
+/// Most recent run of each node that wasn't skipped (history is newest first)
+fn last_runs(history: &[RunHistoryEntry]) -> HashMap<&str, DateTime<Local>> {
+    let mut runs = HashMap::new();
+    for entry in history {
+        for result in entry.node_results.iter().filter(|r| r.status != "skipped") {
+            runs.entry(result.unique_id.as_str())
+                .or_insert(entry.timestamp);
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::node::DependsOn;
+    use crate::model::run_results::NodeResult;
+
+    fn source(name: &str) -> SourceTable {
+        SourceTable {
+            unique_id: format!("source.shop.raw.{}", name),
+            name: name.to_string(),
+            source_name: "raw".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn macro_def(name: &str, callers: usize) -> Macro {
+        Macro {
+            unique_id: format!("macro.shop.{}", name),
+            name: name.to_string(),
+            package_name: "shop".to_string(),
+            original_file_path: Some(format!("macros/{}.sql", name)),
+            depends_on: DependsOn::default(),
+            callers,
+        }
+    }
+
+    fn history_entry(days_ago: i64, results: &[(&str, &str)]) -> RunHistoryEntry {
+        RunHistoryEntry {
+            id: days_ago.to_string(),
+            timestamp: Local::now() - Duration::days(days_ago),
+            node_results: results
+                .iter()
+                .map(|(id, status)| NodeResult::fixture(id, status, 1.0))
+                .collect(),
+            ..RunHistoryEntry::fixture("dbt build", "")
+        }
+    }
+
+    #[test]
+    fn test_cruft_report() {
+        let nodes = vec![
+            Node::fixture("stg_orders", "model", &["source.shop.raw.orders"]),
+            Node::fixture("orders", "model", &["model.shop.stg_orders"]),
+            Node::fixture("old_report", "model", &["model.shop.stg_orders"]),
+            Node::fixture("unique_orders_id", "test", &["model.shop.orders"]),
+            Node::fixture("not_null_payments_id", "test", &["source.shop.raw.payments"]),
+        ];
+        let graph = LineageGraph::build(&nodes);
+        let exposures = vec![Exposure {
+            unique_id: "exposure.shop.dashboard".to_string(),
+            name: "dashboard".to_string(),
+            depends_on: DependsOn {
+                nodes: vec!["model.shop.orders".to_string()],
+                macros: Vec::new(),
+            },
+            ..Default::default()
+        }];
+        let sources = vec![source("orders"), source("payments")];
+        let macros = vec![
+            macro_def("cents_to_dollars", 2),
+            macro_def("legacy_pivot", 0),
+            macro_def("generate_schema_name", 0),
+            macro_def("create_table_as", 0),
+            // Dispatched by cents_to_dollars, which is called
+            macro_def("default__cents_to_dollars", 0),
+            macro_def("postgres__cents_to_dollars", 0),
+            // Dispatch implementations of an uncalled project macro
+            macro_def("old_pivot", 0),
+            macro_def("default__old_pivot", 0),
+            // Overrides dbt's own implementation
+            macro_def("postgres__get_columns_in_relation", 0),
+        ];
+        // Newest first
+        let history = vec![
+            history_entry(1, &[("model.shop.orders", "success"), ("model.shop.old_report", "skipped")]),
+            history_entry(40, &[("model.shop.old_report", "success"), ("model.shop.stg_orders", "success")]),
+        ];
+
+        let report = CruftReport::build(&nodes, &sources, &exposures, &macros, &graph, &history);
+
+        let names = |items: Vec<&CruftItem>| -> Vec<String> {
+            items.iter().map(|i| i.name.clone()).collect()
+        };
+        assert_eq!(names(report.leaf_models.iter().collect()), vec!["old_report"]);
+        assert_eq!(names(report.unused_sources.iter().collect()), vec!["raw.payments"]);
+        assert_eq!(
+            names(report.unused_macros.iter().collect()),
+            vec!["legacy_pivot", "old_pivot", "default__old_pivot"]
+        );
+        assert_eq!(
+            names(report.stale_models(30, Local::now())),
+            vec!["old_report", "stg_orders"]
+        );
+        assert!(report.stale_models(60, Local::now()).is_empty());
+    }
+
+    #[test]
+    fn test_stale_cutoff() {
+        let nodes = vec![
+            Node::fixture("orders", "model", &[]),
+            Node::fixture("customers", "model", &[]),
+            Node::fixture("payments", "model", &[]),
+        ];
+        let graph = LineageGraph::build(&nodes);
+        let mut history = vec![
+            history_entry(0, &[("model.shop.orders", "success")]),
+            history_entry(0, &[("model.shop.customers", "success")]),
+        ];
+        let now = Local::now();
+        history[0].timestamp = now - Duration::days(30);
+        history[1].timestamp = now - Duration::days(30) - Duration::seconds(1);
+
+        let report = CruftReport::build(&nodes, &[], &[], &[], &graph, &history);
+
+        let names = |items: Vec<&CruftItem>| -> Vec<String> {
+            items.iter().map(|i| i.name.clone()).collect()
+        };
+        // Never run first, then oldest run; a run exactly at the cutoff is recent enough
+        assert_eq!(names(report.stale_models(30, now)), vec!["payments", "customers"]);
+        assert_eq!(
+            names(report.items(CruftKind::StaleModel, 31, now)),
+            vec!["payments"]
+        );
+    }
+
+    #[test]
+    fn test_no_history() {
+        let nodes = vec![Node::fixture("orders", "model", &[])];
+        let graph = LineageGraph::build(&nodes);
+        let now = Local::now();
+
+        let report = CruftReport::build(&nodes, &[], &[], &[], &graph, &[]);
+        assert!(!report.has_history);
+        assert!(report.items(CruftKind::StaleModel, 30, now).is_empty());
+        // Leaf models don't depend on history
+        assert_eq!(report.items(CruftKind::LeafModel, 30, now).len(), 1);
+
+        // Only skipped results say nothing about when a model last ran
+        let history = vec![history_entry(1, &[("model.shop.orders", "skipped")])];
+        let report = CruftReport::build(&nodes, &[], &[], &[], &graph, &history);
+        assert!(!report.has_history);
+        assert_eq!(report.models[0].last_run, None);
+    }
+}
@@ -0,0 +1,269 @@
+//! Layered (Sugiyama-style) DAG layout
+//!
+//! Nodes are assigned to layers by longest path from the roots, so every
+//! edge points to a later layer. Edges spanning several layers are split
+//! with dummy vertices, one per layer crossed, which keeps the drawing to
+//! edges between adjacent layers. The order within each layer is then
+//! improved with barycenter sweeps to reduce crossings.
+
+use std::collections::HashMap;
+
+/// Number of down/up barycenter sweeps
+const SWEEPS: usize = 8;
+
+/// Vertex placement in the layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slot {
+    pub layer: usize,
+    /// Position within the layer
+    pub index: usize,
+}
+
+/// Layered layout of a graph
+#[derive(Debug, Clone)]
+pub struct DagLayout {
+    /// Real vertices: `0..nodes.len()` are the nodes, in the order given
+    pub nodes: Vec<String>,
+    /// Vertices per layer, in display order. Ids of `nodes.len()` and up are dummies.
+    pub layers: Vec<Vec<usize>>,
+    /// Each drawn edge as the chain of vertices from source through dummies to target
+    pub edges: Vec<Vec<usize>>,
+    slots: Vec<Slot>,
+}
+
+impl DagLayout {
+    /// Lay out `nodes` with `edges` given as (upstream, downstream) ids.
+    /// Edges that would close a cycle, or mention unknown ids, are dropped.
+    pub fn build(nodes: &[String], edges: &[(String, String)]) -> Self {
+        let index: HashMap<&str, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id.as_str(), i))
+            .collect();
+        let mut edge_pairs: Vec<(usize, usize)> = edges
+            .iter()
+            .filter_map(|(from, to)| Some((*index.get(from.as_str())?, *index.get(to.as_str())?)))
+            .filter(|(from, to)| from != to)
+            .collect();
+        edge_pairs.sort();
+        edge_pairs.dedup();
+
+        let node_layers = longest_path_layers(nodes.len(), &edge_pairs);
+
+        // Split long edges with dummies
+        let mut vertex_layers = node_layers.clone();
+        let mut chains = Vec::new();
+        for (from, to) in edge_pairs {
+            if node_layers[to] <= node_layers[from] {
+                continue;
+            }
+            let mut chain = vec![from];
+            for layer in node_layers[from] + 1..node_layers[to] {
+                chain.push(vertex_layers.len());
+                vertex_layers.push(layer);
+            }
+            chain.push(to);
+            chains.push(chain);
+        }
+
+        let layer_count = vertex_layers.iter().max().map_or(0, |l| l + 1);
+        let mut layers: Vec<Vec<usize>> = vec![Vec::new(); layer_count];
+        for (vertex, &layer) in vertex_layers.iter().enumerate() {
+            layers[layer].push(vertex);
+        }
+
+        let mut layout = Self {
+            nodes: nodes.to_vec(),
+            layers,
+            edges: chains,
+            slots: Vec::new(),
+        };
+        layout.index_slots();
+        layout.reduce_crossings();
+        layout
+    }
+
+    /// Where a vertex sits
+    pub fn slot(&self, vertex: usize) -> Slot {
+        self.slots[vertex]
+    }
+
+    pub fn is_dummy(&self, vertex: usize) -> bool {
+        vertex >= self.nodes.len()
+    }
+
+    /// Real vertex of a node id
+    pub fn vertex_of(&self, id: &str) -> Option<usize> {
+        self.nodes.iter().position(|n| n == id)
+    }
+
+    /// Length of the longest layer
+    pub fn height(&self) -> usize {
+        self.layers.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    fn index_slots(&mut self) {
+        let vertex_count = self.layers.iter().map(Vec::len).sum();
+        self.slots = vec![Slot { layer: 0, index: 0 }; vertex_count];
+        for (layer, vertices) in self.layers.iter().enumerate() {
+            for (index, &vertex) in vertices.iter().enumerate() {
+                self.slots[vertex] = Slot { layer, index };
+            }
+        }
+    }
+
+    /// Reorder each layer by the mean position of its neighbors in the
+    /// previous layer (sweeping down) or the next one (sweeping up)
+    fn reduce_crossings(&mut self) {
+        let vertex_count = self.slots.len();
+        let mut up: Vec<Vec<usize>> = vec![Vec::new(); vertex_count];
+        let mut down: Vec<Vec<usize>> = vec![Vec::new(); vertex_count];
+        for chain in &self.edges {
+            for pair in chain.windows(2) {
+                down[pair[0]].push(pair[1]);
+                up[pair[1]].push(pair[0]);
+            }
+        }
+
+        let mut best = self.layers.clone();
+        let mut best_crossings = self.crossings();
+        for sweep in 0..SWEEPS {
+            let downward = sweep % 2 == 0;
+            let layer_order: Vec<usize> = if downward {
+                (1..self.layers.len()).collect()
+            } else {
+                (0..self.layers.len().saturating_sub(1)).rev().collect()
+            };
+            for layer in layer_order {
+                let neighbors = if downward { &up } else { &down };
+                let mut keyed: Vec<(f64, usize)> = self.layers[layer]
+                    .iter()
+                    .map(|&vertex| {
+                        let adjacent = &neighbors[vertex];
+                        let key = if adjacent.is_empty() {
+                            self.slots[vertex].index as f64
+                        } else {
+                            adjacent.iter().map(|&a| self.slots[a].index as f64).sum::<f64>()
+                                / adjacent.len() as f64
+                        };
+                        (key, vertex)
+                    })
+                    .collect();
+                keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+                self.layers[layer] = keyed.into_iter().map(|(_, vertex)| vertex).collect();
+                self.index_slots();
+            }
+            let crossings = self.crossings();
+            if crossings < best_crossings {
+                best = self.layers.clone();
+                best_crossings = crossings;
+            }
+        }
+        self.layers = best;
+        self.index_slots();
+    }
+
+    /// Number of edge crossings between adjacent layers
+    pub fn crossings(&self) -> usize {
+        let segments: Vec<(Slot, Slot)> = self
+            .edges
+            .iter()
+            .flat_map(|chain| chain.windows(2).map(|p| (self.slots[p[0]], self.slots[p[1]])))
+            .collect();
+        let mut count = 0;
+        for (i, (a_from, a_to)) in segments.iter().enumerate() {
+            for (b_from, b_to) in &segments[i + 1..] {
+                if a_from.layer == b_from.layer
+                    && (a_from.index as isize - b_from.index as isize)
+                        * (a_to.index as isize - b_to.index as isize)
+                        < 0
+                {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+}
+
+/// Layer of each node: 0 for nodes without parents, else one past the
+/// deepest parent. Nodes left in a cycle go after everything else reached.
+fn longest_path_layers(count: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut in_degree = vec![0; count];
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); count];
+    for &(from, to) in edges {
+        in_degree[to] += 1;
+        children[from].push(to);
+    }
+    let mut layers = vec![0; count];
+    let mut queue: Vec<usize> = (0..count).filter(|&n| in_degree[n] == 0).collect();
+    let mut placed = vec![false; count];
+    while let Some(node) = queue.pop() {
+        placed[node] = true;
+        for &child in &children[node] {
+            layers[child] = layers[child].max(layers[node] + 1);
+            in_degree[child] -= 1;
+            if in_degree[child] == 0 {
+                queue.push(child);
+            }
+        }
+    }
+    let last = layers.iter().max().copied().unwrap_or(0);
+    for (node, layer) in layers.iter_mut().enumerate() {
+        if !placed[node] {
+            *layer = last + 1;
+        }
+    }
+    layers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn edges(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_layers_and_dummies() {
+        let layout = DagLayout::build(
+            &ids(&["a", "b", "c"]),
+            &edges(&[("a", "b"), ("b", "c"), ("a", "c")]),
+        );
+        assert_eq!(layout.layers.len(), 3);
+        assert_eq!(layout.slot(0).layer, 0);
+        assert_eq!(layout.slot(1).layer, 1);
+        assert_eq!(layout.slot(2).layer, 2);
+
+        // a -> c crosses layer 1 through a dummy
+        assert_eq!(layout.layers[1].len(), 2);
+        let long = layout.edges.iter().find(|e| e.len() == 3).unwrap();
+        assert!(layout.is_dummy(long[1]));
+        assert_eq!(layout.slot(long[1]).layer, 1);
+    }
+
+    #[test]
+    fn test_crossings_reduced() {
+        // Initial order puts x1 above x2 but their parents the other way round
+        let layout = DagLayout::build(
+            &ids(&["a1", "a2", "x2", "x1"]),
+            &edges(&[("a1", "x1"), ("a2", "x2")]),
+        );
+        assert_eq!(layout.crossings(), 0);
+    }
+
+    #[test]
+    fn test_cycle_does_not_hang() {
+        let layout = DagLayout::build(&ids(&["a", "b"]), &edges(&[("a", "b"), ("b", "a")]));
+        assert_eq!(layout.nodes.len(), 2);
+        assert!(layout.edges.is_empty());
+    }
+}
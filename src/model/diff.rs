@@ -0,0 +1,170 @@
+//! Line-based text diff
+//!
+//! A longest-common-subsequence diff over lines. Common prefix and suffix are
+//! trimmed first, so the quadratic table only covers the changed region.
+
+/// A single line in a diff
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    Same(String),
+    Added(String),
+    Removed(String),
+    /// A run of unchanged lines hidden by `with_context`
+    Skipped(usize),
+}
+
+/// Maximum size of the LCS table before falling back to remove-all/add-all
+const MAX_TABLE_CELLS: usize = 4_000_000;
+
+/// Diff two sequences of lines
+pub fn diff_lines(old: &[&str], new: &[&str]) -> Vec<DiffLine> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut result: Vec<DiffLine> = old[..prefix]
+        .iter()
+        .map(|l| DiffLine::Same(l.to_string()))
+        .collect();
+
+    if old_mid.len() * new_mid.len() > MAX_TABLE_CELLS {
+        result.extend(old_mid.iter().map(|l| DiffLine::Removed(l.to_string())));
+        result.extend(new_mid.iter().map(|l| DiffLine::Added(l.to_string())));
+    } else {
+        result.extend(lcs_diff(old_mid, new_mid));
+    }
+
+    result.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|l| DiffLine::Same(l.to_string())),
+    );
+    result
+}
+
+fn lcs_diff(old: &[&str], new: &[&str]) -> Vec<DiffLine> {
+    let (n, m) = (old.len(), new.len());
+    // lengths[i][j] = LCS length of old[i..] and new[j..]
+    let mut lengths = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            result.push(DiffLine::Same(old[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            result.push(DiffLine::Removed(old[i].to_string()));
+            i += 1;
+        } else {
+            result.push(DiffLine::Added(new[j].to_string()));
+            j += 1;
+        }
+    }
+    result.extend(old[i..].iter().map(|l| DiffLine::Removed(l.to_string())));
+    result.extend(new[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+    result
+}
+
+/// Keep `context` unchanged lines around each change and collapse the rest
+pub fn with_context(lines: &[DiffLine], context: usize) -> Vec<DiffLine> {
+    let mut keep = vec![false; lines.len()];
+    for (i, line) in lines.iter().enumerate() {
+        if matches!(line, DiffLine::Added(_) | DiffLine::Removed(_)) {
+            let end = (i + context + 1).min(lines.len());
+            keep[i.saturating_sub(context)..end].fill(true);
+        }
+    }
+
+    let mut result = Vec::new();
+    let mut skipped = 0;
+    for (line, keep) in lines.iter().zip(keep) {
+        if keep {
+            if skipped > 0 {
+                result.push(DiffLine::Skipped(skipped));
+                skipped = 0;
+            }
+            result.push(line.clone());
+        } else {
+            skipped += 1;
+        }
+    }
+    if skipped > 0 {
+        result.push(DiffLine::Skipped(skipped));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let old = vec!["a", "b", "c", "d"];
+        let new = vec!["a", "c", "x", "d"];
+
+        assert_eq!(
+            diff_lines(&old, &new),
+            vec![
+                DiffLine::Same("a".to_string()),
+                DiffLine::Removed("b".to_string()),
+                DiffLine::Same("c".to_string()),
+                DiffLine::Added("x".to_string()),
+                DiffLine::Same("d".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        let lines = vec!["a", "b"];
+        let diff = diff_lines(&lines, &lines);
+        assert!(diff.iter().all(|l| matches!(l, DiffLine::Same(_))));
+        assert!(with_context(&diff, 2).iter().all(|l| matches!(l, DiffLine::Skipped(2))));
+    }
+
+    #[test]
+    fn test_with_context() {
+        let old: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+        let mut new = old.clone();
+        new[5] = "five".to_string();
+        let old: Vec<&str> = old.iter().map(|s| s.as_str()).collect();
+        let new: Vec<&str> = new.iter().map(|s| s.as_str()).collect();
+
+        let collapsed = with_context(&diff_lines(&old, &new), 1);
+
+        assert_eq!(
+            collapsed,
+            vec![
+                DiffLine::Skipped(4),
+                DiffLine::Same("4".to_string()),
+                DiffLine::Removed("5".to_string()),
+                DiffLine::Added("five".to_string()),
+                DiffLine::Same("6".to_string()),
+                DiffLine::Skipped(3),
+            ]
+        );
+    }
+}
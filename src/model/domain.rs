@@ -1,10 +1,13 @@
 //! Domain state - business/data state separate from UI concerns
 
+use super::column_lineage::ColumnLineage;
+use super::estimate::ModelDurations;
 use super::history::RunHistoryEntry;
 use super::lineage::LineageGraph;
-use super::node::Node;
+use super::node::{Exposure, Macro, Node, SourceTable};
 use super::run::RunOutput;
 use super::sample_data::SampleDataOutput;
+use super::yaml_index::YamlIndex;
 use std::path::PathBuf;
 
 /// Project information for display
@@ -33,12 +36,33 @@ pub struct DomainState {
     /// All dbt nodes (models, tests, seeds)
     pub all_nodes: Vec<Node>,
 
+    /// Source tables declared in the project
+    pub sources: Vec<SourceTable>,
+
+    /// Exposures consuming project nodes
+    pub exposures: Vec<Exposure>,
+
+    /// Analyses, which reference models but are never run
+    pub analyses: Vec<Node>,
+
+    /// Macros defined in the project
+    pub macros: Vec<Macro>,
+
     /// Lineage graph built from node dependencies
     pub lineage_graph: Option<LineageGraph>,
 
+    /// Where resources, columns and tests are declared in schema YAML
+    pub yaml_index: YamlIndex,
+
+    /// Upstream and downstream columns of every model column
+    pub column_lineage: ColumnLineage,
+
     /// Run history entries
     pub run_history: Vec<RunHistoryEntry>,
 
+    /// Historical per-model durations derived from run history
+    pub model_durations: ModelDurations,
+
     /// Current run output (if any)
     pub run_output: Option<RunOutput>,
 
@@ -60,8 +84,15 @@ impl DomainState {
     pub fn new() -> Self {
         Self {
             all_nodes: Vec::new(),
+            sources: Vec::new(),
+            exposures: Vec::new(),
+            analyses: Vec::new(),
+            macros: Vec::new(),
             lineage_graph: None,
+            yaml_index: YamlIndex::default(),
+            column_lineage: ColumnLineage::default(),
             run_history: Vec::new(),
+            model_durations: ModelDurations::default(),
             run_output: None,
             sample_data_output: None,
             project_info: None,
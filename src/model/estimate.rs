@@ -0,0 +1,345 @@
+//! Progress and ETA estimation for running dbt commands
+//!
+//! Historical per-node durations are collected from the run history. The
+//! remaining time is estimated layer by layer: models within a dependency
+//! layer run in parallel, so each unfinished layer costs its slowest model,
+//! less the time it has already been running. Selected models that have not
+//! started are costed the same way, by their layer in the selection; other
+//! steps that have not started are spread across the available threads.
+
+use super::history::RunHistoryEntry;
+use super::node::Node;
+use super::run::{ModelRunStatus, RunOutput};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+
+/// Number of most recent runs averaged per node
+const MAX_SAMPLES: usize = 5;
+
+/// Average execution time per unique_id over its most recent runs (history is newest first)
+pub fn average_runtimes(history: &[RunHistoryEntry]) -> HashMap<String, f64> {
+    let mut samples: HashMap<&str, Vec<f64>> = HashMap::new();
+    for entry in history {
+        for result in &entry.node_results {
+            if result.status == "skipped" || result.execution_time <= 0.0 {
+                continue;
+            }
+            let node_samples = samples.entry(result.unique_id.as_str()).or_default();
+            if node_samples.len() < MAX_SAMPLES {
+                node_samples.push(result.execution_time);
+            }
+        }
+    }
+    samples
+        .into_iter()
+        .map(|(id, values)| (id.to_string(), values.iter().sum::<f64>() / values.len() as f64))
+        .collect()
+}
+
+/// Historical execution durations of models, seeds and snapshots (keyed by unique_id)
+#[derive(Debug, Clone, Default)]
+pub struct ModelDurations {
+    durations: HashMap<String, f64>,
+}
+
+impl ModelDurations {
+    /// Collect durations from history entries (newest first)
+    pub fn from_history(entries: &[RunHistoryEntry]) -> Self {
+        let mut durations = average_runtimes(entries);
+        durations.retain(|id, _| !id.starts_with("test."));
+        Self { durations }
+    }
+
+    /// Average historical duration of a node
+    pub fn get(&self, unique_id: &str) -> Option<f64> {
+        self.durations.get(unique_id).copied()
+    }
+
+    /// Average historical duration across all models
+    pub fn average(&self) -> Option<f64> {
+        if self.durations.is_empty() {
+            None
+        } else {
+            Some(self.durations.values().sum::<f64>() / self.durations.len() as f64)
+        }
+    }
+}
+
+/// Progress snapshot for a running command
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunEstimate {
+    pub completed: usize,
+    pub total: usize,
+    pub elapsed_secs: f64,
+    /// Estimated remaining time, if any duration information is available
+    pub remaining_secs: Option<f64>,
+}
+
+impl RunEstimate {
+    /// Estimate progress for a run as of `now`. Returns None until the total step count is known.
+    pub fn estimate(
+        run_output: &RunOutput,
+        durations: &ModelDurations,
+        all_nodes: &[Node],
+        elapsed_secs: f64,
+        threads: usize,
+        now: Instant,
+    ) -> Option<Self> {
+        let total = run_output.total_steps?;
+        let completed = run_output.completed_steps.min(total);
+
+        // The log names models by "schema.alias"
+        let ids: HashMap<String, &str> = all_nodes
+            .iter()
+            .map(|n| (n.log_name(), n.unique_id.as_str()))
+            .collect();
+        let history_duration = |name: &str| ids.get(name).and_then(|id| durations.get(id));
+
+        // Fallback for models without history: historical average, then this run's average
+        let finished: Vec<f64> = run_output
+            .model_runs
+            .iter()
+            .filter_map(|m| m.duration)
+            .collect();
+        let fallback = durations.average().or_else(|| {
+            if finished.is_empty() {
+                None
+            } else {
+                Some(finished.iter().sum::<f64>() / finished.len() as f64)
+            }
+        });
+
+        let mut remaining = Some(0.0);
+
+        // Cost of each unfinished model, by layer. Layers come from the
+        // selection when it is known, otherwise from the models seen so far.
+        let selection_layers: HashMap<&str, usize> = run_output
+            .selected_models
+            .iter()
+            .map(|(name, layer)| (name.as_str(), *layer))
+            .collect();
+        let mut layers: BTreeMap<usize, Vec<Option<f64>>> = BTreeMap::new();
+        for model in run_output.model_runs.iter().filter(|m| m.status == ModelRunStatus::Running) {
+            let running_secs = model.started.map_or(0.0, |s| now.duration_since(s).as_secs_f64());
+            let layer = selection_layers.get(model.name.as_str()).copied().unwrap_or(model.layer);
+            layers.entry(layer).or_default().push(
+                history_duration(&model.name).or(fallback).map(|d| (d - running_secs).max(0.0)),
+            );
+        }
+        let mut pending_count = 0;
+        for (name, layer) in &run_output.selected_models {
+            if !run_output.model_runs.iter().any(|m| &m.name == name) {
+                pending_count += 1;
+                layers.entry(*layer).or_default().push(history_duration(name).or(fallback));
+            }
+        }
+
+        // Each layer costs its slowest model, or its total spread across threads if longer
+        for costs in layers.values() {
+            let layer_cost = costs.iter().copied().collect::<Option<Vec<f64>>>().map(|costs| {
+                let slowest = costs.iter().copied().fold(0.0_f64, f64::max);
+                slowest.max(costs.iter().sum::<f64>() / threads.max(1) as f64)
+            });
+            remaining = remaining.zip(layer_cost).map(|(a, b)| a + b);
+        }
+
+        // Other steps not started yet (tests, or everything when the selection
+        // isn't known), spread across threads
+        let started = run_output.model_runs.len().max(completed);
+        let unseen = total.saturating_sub(started + pending_count);
+        if unseen > 0 {
+            let unseen_cost = fallback.map(|f| f * unseen as f64 / threads.max(1) as f64);
+            remaining = remaining.zip(unseen_cost).map(|(a, b)| a + b);
+        }
+
+        Some(RunEstimate {
+            completed,
+            total,
+            elapsed_secs,
+            remaining_secs: remaining,
+        })
+    }
+
+    /// Fraction of steps completed (0.0 - 1.0)
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (self.completed as f64 / self.total as f64).clamp(0.0, 1.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::run::RunSelectMode;
+    use crate::model::run_results::NodeResult;
+    use std::time::Duration;
+
+    fn history_entry(results: &[(&str, &str, f64)]) -> RunHistoryEntry {
+        RunHistoryEntry {
+            node_results: results
+                .iter()
+                .map(|(id, status, time)| NodeResult::fixture(id, status, *time))
+                .collect(),
+            ..RunHistoryEntry::fixture("dbt build", "")
+        }
+    }
+
+    /// Models in schema "analytics", as the log lines below name them
+    fn models(names: &[&str]) -> Vec<Node> {
+        names
+            .iter()
+            .map(|name| Node {
+                schema: "analytics".to_string(),
+                ..Node::fixture(name, "model", &[])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_average_runtimes() {
+        // Newest first: the five most recent runs are averaged
+        let mut history: Vec<RunHistoryEntry> = [1.0, 2.0, 3.0, 4.0, 5.0, 100.0, 100.0]
+            .iter()
+            .map(|&time| history_entry(&[("model.shop.orders", "success", time)]))
+            .collect();
+        // Skipped runs and runs without timing don't count as samples
+        history.insert(0, history_entry(&[("model.shop.orders", "skipped", 0.0)]));
+        history.insert(1, history_entry(&[("model.shop.orders", "success", 0.0)]));
+        history.push(history_entry(&[("model.shop.revenue", "skipped", 0.0)]));
+
+        let runtimes = average_runtimes(&history);
+
+        assert_eq!(runtimes.get("model.shop.orders"), Some(&3.0));
+        assert_eq!(runtimes.get("model.shop.revenue"), None);
+    }
+
+    #[test]
+    fn test_model_durations_from_history() {
+        let entries = vec![
+            history_entry(&[
+                ("model.shop.orders", "success", 4.0),
+                ("test.shop.unique_orders_id", "pass", 9.0),
+            ]),
+            history_entry(&[("model.shop.orders", "success", 2.0)]),
+        ];
+
+        let durations = ModelDurations::from_history(&entries);
+
+        assert_eq!(durations.get("model.shop.orders"), Some(3.0));
+        assert_eq!(durations.get("model.shop.customers"), None);
+        // Tests don't count towards the model average
+        assert_eq!(durations.average(), Some(3.0));
+    }
+
+    /// Mark a model as having run for `secs` as of `now`
+    fn running_for(run_output: &mut RunOutput, name: &str, now: Instant, secs: f64) {
+        let model = run_output.model_runs.iter_mut().find(|m| m.name == name).unwrap();
+        model.started = Some(now - Duration::from_secs_f64(secs));
+    }
+
+    #[test]
+    fn test_estimate_adjusts_as_models_finish() {
+        let durations = ModelDurations::from_history(&[history_entry(&[
+            ("model.shop.stg", "success", 2.0),
+            ("model.shop.orders", "success", 6.0),
+        ])]);
+        let nodes = models(&["stg", "orders"]);
+        let now = Instant::now();
+
+        let mut run_output = RunOutput::new("dbt run".to_string());
+        run_output.parse_output_line("1 of 2 START sql view model analytics.stg ... [RUN]");
+        running_for(&mut run_output, "analytics.stg", now, 0.0);
+
+        // stg running (2s) plus one unseen step at the 4s average
+        let estimate = RunEstimate::estimate(&run_output, &durations, &nodes, 0.5, 1, now).unwrap();
+        assert_eq!(estimate.completed, 0);
+        assert_eq!(estimate.total, 2);
+        assert_eq!(estimate.remaining_secs, Some(6.0));
+
+        run_output.parse_output_line(
+            "1 of 2 OK created sql view model analytics.stg ... [CREATE VIEW in 2.10s]",
+        );
+        run_output.parse_output_line("2 of 2 START sql table model analytics.orders ... [RUN]");
+
+        // orders has run for 1.5s of its usual 6s
+        running_for(&mut run_output, "analytics.orders", now, 1.5);
+        let estimate = RunEstimate::estimate(&run_output, &durations, &nodes, 2.6, 1, now).unwrap();
+        assert_eq!(estimate.completed, 1);
+        assert_eq!(estimate.ratio(), 0.5);
+        assert_eq!(estimate.remaining_secs, Some(4.5));
+
+        // Running past its usual duration doesn't go negative
+        running_for(&mut run_output, "analytics.orders", now, 10.0);
+        let estimate = RunEstimate::estimate(&run_output, &durations, &nodes, 11.1, 1, now).unwrap();
+        assert_eq!(estimate.remaining_secs, Some(0.0));
+    }
+
+    #[test]
+    fn test_estimate_costs_selected_models_by_layer() {
+        let durations = ModelDurations::from_history(&[history_entry(&[
+            ("model.shop.stg_a", "success", 1.0),
+            ("model.shop.stg_b", "success", 3.0),
+            ("model.shop.orders", "success", 8.0),
+            ("model.shop.revenue", "success", 2.0),
+        ])]);
+        let nodes = models(&["stg_a", "stg_b", "orders", "revenue"]);
+        let now = Instant::now();
+
+        let mut run_output = RunOutput::new("dbt run".to_string());
+        run_output.selected_models = vec![
+            ("analytics.stg_a".to_string(), 0),
+            ("analytics.stg_b".to_string(), 0),
+            ("analytics.orders".to_string(), 1),
+            ("analytics.revenue".to_string(), 1),
+        ];
+        run_output.parse_output_line("1 of 4 START sql view model analytics.stg_a ... [RUN]");
+        running_for(&mut run_output, "analytics.stg_a", now, 0.0);
+
+        // stg_a (1s) and stg_b (3s) run side by side, then orders (8s) and
+        // revenue (2s): per model, not the 3.5s average per step
+        let estimate = RunEstimate::estimate(&run_output, &durations, &nodes, 0.1, 2, now).unwrap();
+        assert_eq!(estimate.remaining_secs, Some(3.0 + 8.0));
+
+        // On one thread a layer takes the sum of its models
+        let estimate = RunEstimate::estimate(&run_output, &durations, &nodes, 0.1, 1, now).unwrap();
+        assert_eq!(estimate.remaining_secs, Some(4.0 + 10.0));
+    }
+
+    #[test]
+    fn test_estimate_without_history() {
+        let mut run_output = RunOutput::new("dbt run".to_string());
+        let now = Instant::now();
+        assert!(RunEstimate::estimate(&run_output, &ModelDurations::default(), &[], 0.0, 4, now).is_none());
+
+        run_output.parse_output_line("1 of 2 START sql view model analytics.stg ... [RUN]");
+        let estimate =
+            RunEstimate::estimate(&run_output, &ModelDurations::default(), &[], 0.0, 4, now).unwrap();
+        assert_eq!(estimate.remaining_secs, None);
+    }
+
+    #[test]
+    fn test_estimate_with_aliased_model() {
+        let mut nodes = models(&["stg_orders", "orders"]);
+        nodes[0].config.alias = Some("stg_orders_v2".to_string());
+        nodes[1].depends_on.nodes = vec!["model.shop.stg_orders".to_string()];
+        let durations = ModelDurations::from_history(&[history_entry(&[
+            ("model.shop.stg_orders", "success", 2.0),
+            ("model.shop.orders", "success", 6.0),
+        ])]);
+        let now = Instant::now();
+
+        let mut run_output = RunOutput::new("dbt run".to_string());
+        run_output.selected_models = RunSelectMode::Downstream.select_models(&[&nodes[0]], &nodes);
+        run_output
+            .parse_output_line("1 of 2 START sql view model analytics.stg_orders_v2 ... [RUN]");
+        running_for(&mut run_output, "analytics.stg_orders_v2", now, 0.5);
+
+        // The running model isn't also counted as pending: stg_orders has 1.5s
+        // of its usual 2s left, then orders takes 6s
+        let estimate = RunEstimate::estimate(&run_output, &durations, &nodes, 0.5, 1, now).unwrap();
+        assert_eq!(estimate.remaining_secs, Some(1.5 + 6.0));
+    }
+}
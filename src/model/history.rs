@@ -1,23 +1,130 @@
 //! Data models for run history persistence
+//!
+//! History is stored per project and target under
+//! `~/.dbt-tui/history/<project-key>/<target-key>/`:
+//! - `index.json` holds entry metadata (command, status, timing, ...)
+//! - `outputs/<id>.log` holds the full output of each run
+//!
+//! Outputs are written once when the entry is created, so saving a new run
+//! does not rewrite the output of earlier runs. They are read back on demand
+//! (when an entry is opened or searched), not when the history is loaded.
+//!
+//! Retention applies to each target separately, so runs against dev don't
+//! push prod runs out of the history.
 
-use super::run::RunStatus;
-use chrono::{DateTime, Local};
+use super::node::Node;
+use super::run::{dbt_subcommand, RunOutput, RunStatus};
+use super::run_results::{NodeResult, RunResults};
+use chrono::{DateTime, Duration, Local};
 use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Default number of entries kept per target of a project
+pub const DEFAULT_HISTORY_RETENTION: usize = 100;
 
 /// A single entry in the run history
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RunHistoryEntry {
+    /// Unique id, also used as the output file name
+    #[serde(default)]
+    pub id: String,
     pub timestamp: DateTime<Local>,
     pub command: String,
     pub status: RunStatus,
+    /// Full output, stored separately in `outputs/<id>.log`
+    #[serde(default, skip_serializing)]
     pub output: String,
+    /// Whether `output` has been read from disk (see `RunHistory::load_output`)
+    #[serde(skip)]
+    pub output_loaded: bool,
     pub duration_secs: f64,
+    /// dbt target active when the command ran
+    #[serde(default)]
+    pub target: Option<String>,
+    /// dbt invocation id from run_results.json
+    #[serde(default)]
+    pub invocation_id: Option<String>,
+    /// Per-node results from run_results.json, used for the timeline view
+    #[serde(default)]
+    pub node_results: Vec<NodeResult>,
+    /// Imported from a run executed outside dbt-tui (CI, plain terminal)
+    #[serde(default)]
+    pub external: bool,
 }
 
 impl RunHistoryEntry {
+    /// Build an id from a timestamp (e.g. "20240301-100005-123")
+    pub fn id_for(timestamp: &DateTime<Local>) -> String {
+        timestamp.format("%Y%m%d-%H%M%S-%3f").to_string()
+    }
+
+    /// Build an entry for an external invocation from its run_results.json.
+    /// Without a log, the output lists the node results.
+    pub fn from_run_results(results: RunResults, log: Option<String>) -> Self {
+        let elapsed = results.elapsed_time.unwrap_or_else(|| {
+            results.results.iter().map(|r| r.execution_time).sum()
+        });
+        // run_results.json is written when the invocation ends
+        let started_at = results
+            .results
+            .iter()
+            .filter_map(|r| r.started_at)
+            .min()
+            .or_else(|| {
+                results
+                    .generated_at
+                    .map(|t| t - Duration::milliseconds((elapsed * 1000.0) as i64))
+            });
+        let timestamp = started_at
+            .map(|t| t.with_timezone(&Local))
+            .unwrap_or_else(Local::now);
+
+        let failed = results
+            .results
+            .iter()
+            .any(|r| matches!(r.status.as_str(), "error" | "fail" | "runtime error"));
+
+        let output = log.unwrap_or_else(|| {
+            let mut lines =
+                vec!["Imported from target/run_results.json (no log available)".to_string()];
+            lines.extend(results.results.iter().map(|r| {
+                format!("{:<8} {} ({:.2}s)", r.status, r.unique_id, r.execution_time)
+            }));
+            lines.join("\n")
+        });
+
+        RunHistoryEntry {
+            id: Self::id_for(&timestamp),
+            timestamp,
+            command: results.args.command_line(),
+            status: if failed { RunStatus::Failed } else { RunStatus::Success },
+            output,
+            output_loaded: true,
+            duration_secs: elapsed,
+            target: results.args.target.clone(),
+            invocation_id: results.invocation_id,
+            node_results: results.results,
+            external: true,
+        }
+    }
+
+    /// Execution time of each model that completed in a run's output
+    pub fn parse_model_durations(output: &str) -> BTreeMap<String, f64> {
+        let mut run_output = RunOutput::new(String::new());
+        for line in output.lines() {
+            run_output.parse_output_line(line);
+        }
+        run_output
+            .model_runs
+            .into_iter()
+            .filter_map(|model| Some((model.name, model.duration?)))
+            .collect()
+    }
+
     pub fn status_icon(&self) -> &str {
         match self.status {
             RunStatus::Running => "⏳",
@@ -31,16 +138,190 @@ impl RunHistoryEntry {
     }
 
     pub fn formatted_duration(&self) -> String {
-        if self.duration_secs < 60.0 {
-            format!("{:.1}s", self.duration_secs)
-        } else {
-            let mins = (self.duration_secs / 60.0).floor();
-            let secs = self.duration_secs % 60.0;
-            format!("{}m {:.0}s", mins, secs)
+        format_duration(self.duration_secs)
+    }
+
+    /// dbt subcommand (run, test, build, ...) of this entry
+    pub fn subcommand(&self) -> Option<&str> {
+        dbt_subcommand(&self.command)
+    }
+
+    /// Whether the run selected or executed a node with this name
+    pub fn involves_model(&self, model_name: &str) -> bool {
+        if self.node_results.iter().any(|r| r.name() == model_name) {
+            return true;
         }
+
+        let qualified = format!(".{}", model_name);
+        let matches_token =
+            |token: &str| token == model_name || token.ends_with(qualified.as_str());
+
+        let selects_model = self
+            .command
+            .split_whitespace()
+            .skip_while(|t| *t != "--select")
+            .skip(1)
+            .take_while(|t| !t.starts_with("--"))
+            .any(|t| matches_token(t.trim_matches(|c: char| c == '+' || c == '@')));
+
+        selects_model
+            || self
+                .output
+                .lines()
+                .filter(|line| line.contains(" model "))
+                .any(|line| line.split_whitespace().any(matches_token))
     }
 }
 
+/// Format a duration in seconds as "12.3s" or "2m 5s"
+pub fn format_duration(duration_secs: f64) -> String {
+    if duration_secs < 60.0 {
+        format!("{:.1}s", duration_secs)
+    } else {
+        let mins = (duration_secs / 60.0).floor();
+        let secs = duration_secs % 60.0;
+        format!("{}m {:.0}s", mins, secs)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Filtering
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Date range filter for history entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HistoryDateRange {
+    #[default]
+    All,
+    Today,
+    Last7Days,
+    Last30Days,
+}
+
+impl HistoryDateRange {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HistoryDateRange::All => "All",
+            HistoryDateRange::Today => "Today",
+            HistoryDateRange::Last7Days => "7 days",
+            HistoryDateRange::Last30Days => "30 days",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            HistoryDateRange::All => HistoryDateRange::Today,
+            HistoryDateRange::Today => HistoryDateRange::Last7Days,
+            HistoryDateRange::Last7Days => HistoryDateRange::Last30Days,
+            HistoryDateRange::Last30Days => HistoryDateRange::All,
+        }
+    }
+
+    fn contains(&self, timestamp: &DateTime<Local>, now: &DateTime<Local>) -> bool {
+        match self {
+            HistoryDateRange::All => true,
+            HistoryDateRange::Today => timestamp.date_naive() == now.date_naive(),
+            HistoryDateRange::Last7Days => *now - *timestamp <= Duration::days(7),
+            HistoryDateRange::Last30Days => *now - *timestamp <= Duration::days(30),
+        }
+    }
+}
+
+/// Filters applied to the history list
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryFilter {
+    pub status: Option<RunStatus>,
+    /// dbt subcommand (run, test, build, ...)
+    pub command: Option<String>,
+    pub target: Option<String>,
+    pub date_range: HistoryDateRange,
+    /// Model name that must be selected or executed by the run
+    pub model: String,
+    /// Case-insensitive text searched in command and output
+    pub search: String,
+}
+
+impl HistoryFilter {
+    /// Whether any filter is set
+    pub fn is_active(&self) -> bool {
+        *self != HistoryFilter::default()
+    }
+
+    /// Cycle status filter: all → success → failed → all
+    pub fn cycle_status(&mut self) {
+        self.status = match self.status {
+            None => Some(RunStatus::Success),
+            Some(RunStatus::Success) => Some(RunStatus::Failed),
+            Some(_) => None,
+        };
+    }
+
+    /// Cycle command filter through the given options, then back to all
+    pub fn cycle_command(&mut self, options: &[String]) {
+        self.command = cycle_option(&self.command, options);
+    }
+
+    /// Cycle target filter through the given options, then back to all
+    pub fn cycle_target(&mut self, options: &[String]) {
+        self.target = cycle_option(&self.target, options);
+    }
+
+    pub fn matches(&self, entry: &RunHistoryEntry, now: &DateTime<Local>) -> bool {
+        if self.status.is_some_and(|s| s != entry.status) {
+            return false;
+        }
+        if let Some(ref command) = self.command {
+            if entry.subcommand() != Some(command.as_str()) {
+                return false;
+            }
+        }
+        if self.target.is_some() && entry.target != self.target {
+            return false;
+        }
+        if !self.date_range.contains(&entry.timestamp, now) {
+            return false;
+        }
+        if !self.model.is_empty() && !entry.involves_model(&self.model) {
+            return false;
+        }
+        if !self.search.is_empty() {
+            let needle = self.search.to_lowercase();
+            if !entry.command.to_lowercase().contains(&needle)
+                && !entry.output.to_lowercase().contains(&needle)
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Indices of entries matching the filter
+    pub fn apply(&self, entries: &[RunHistoryEntry]) -> Vec<usize> {
+        let now = Local::now();
+        entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| self.matches(entry, &now))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+fn cycle_option(current: &Option<String>, options: &[String]) -> Option<String> {
+    match current {
+        None => options.first().cloned(),
+        Some(value) => options
+            .iter()
+            .position(|o| o == value)
+            .and_then(|i| options.get(i + 1))
+            .cloned(),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Persistence
+// ─────────────────────────────────────────────────────────────────────────────
+
 /// Wrapper for persisting run history
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RunHistory {
@@ -53,40 +334,123 @@ impl RunHistory {
         Some(PathBuf::from(home).join(".dbt-tui"))
     }
 
-    fn history_path() -> Option<PathBuf> {
+    /// Global history file used before history was stored per project
+    fn legacy_history_path() -> Option<PathBuf> {
         Self::history_dir().map(|dir| dir.join("history.json"))
     }
 
-    pub fn load() -> Vec<RunHistoryEntry> {
-        let history_path = match Self::history_path() {
-            Some(p) => p,
-            None => return Vec::new(),
-        };
+    fn project_dir(project_path: &Path) -> Option<PathBuf> {
+        Self::history_dir().map(|dir| dir.join("history").join(project_key(project_path)))
+    }
 
-        if !history_path.exists() {
-            return Vec::new();
+    /// Load the history index of every target of a project, newest first.
+    /// Outputs are left unloaded.
+    pub fn load(project_path: &Path) -> Vec<RunHistoryEntry> {
+        match Self::project_dir(project_path) {
+            Some(project_dir) => Self::load_dir(&project_dir),
+            None => Vec::new(),
         }
+    }
 
-        let contents = match fs::read_to_string(&history_path) {
-            Ok(c) => c,
-            Err(_) => return Vec::new(),
-        };
+    fn load_dir(project_dir: &Path) -> Vec<RunHistoryEntry> {
+        let mut entries: Vec<RunHistoryEntry> = fs::read_dir(project_dir)
+            .map(|dirs| {
+                dirs.flatten()
+                    .filter_map(|dir| read_index(&dir.path()))
+                    .flatten()
+                    .collect()
+            })
+            .unwrap_or_default();
+        entries.sort_by_key(|e| Reverse(e.timestamp));
+        entries
+    }
 
-        match serde_json::from_str::<RunHistory>(&contents) {
-            Ok(history) => history.entries,
-            Err(_) => Vec::new(),
+    /// Read an entry's output from `<target>/outputs/<id>.log` unless it is already loaded
+    pub fn load_output(project_path: &Path, entry: &mut RunHistoryEntry) {
+        if let Some(project_dir) = Self::project_dir(project_path) {
+            Self::read_output(&project_dir, entry);
         }
+        entry.output_loaded = true;
     }
 
-    pub fn save(entries: &[RunHistoryEntry]) -> Result<(), String> {
-        let history_dir = Self::history_dir().ok_or("Could not determine home directory")?;
+    fn read_output(project_dir: &Path, entry: &mut RunHistoryEntry) {
+        if entry.output_loaded {
+            return;
+        }
+        let path = project_dir
+            .join(target_key(entry.target.as_deref()))
+            .join("outputs")
+            .join(format!("{}.log", entry.id));
+        entry.output = fs::read_to_string(path).unwrap_or_default();
+        entry.output_loaded = true;
+    }
+
+    /// Save history for a project, each target in its own directory.
+    ///
+    /// Output files are only written for entries that don't have one yet, and
+    /// files of entries no longer in the list (e.g. dropped by retention) are removed.
+    pub fn save(project_path: &Path, entries: &[RunHistoryEntry]) -> Result<(), String> {
+        let project_dir =
+            Self::project_dir(project_path).ok_or("Could not determine home directory")?;
+        Self::save_dir(&project_dir, entries)
+    }
+
+    fn save_dir(project_dir: &Path, entries: &[RunHistoryEntry]) -> Result<(), String> {
+        let mut by_target: BTreeMap<String, Vec<RunHistoryEntry>> = BTreeMap::new();
+        for entry in entries {
+            by_target
+                .entry(target_key(entry.target.as_deref()))
+                .or_default()
+                .push(entry.clone());
+        }
+
+        for (target, entries) in &by_target {
+            Self::save_target(&project_dir.join(target), entries)?;
+        }
+
+        // Targets whose entries are all gone
+        if let Ok(dirs) = fs::read_dir(project_dir) {
+            for dir in dirs.flatten() {
+                let path = dir.path();
+                let name = dir.file_name().to_string_lossy().to_string();
+                if path.join("index.json").exists() && !by_target.contains_key(&name) {
+                    let _ = fs::remove_dir_all(path);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn save_target(target_dir: &Path, entries: &[RunHistoryEntry]) -> Result<(), String> {
+        let outputs_dir = target_dir.join("outputs");
 
-        if !history_dir.exists() {
-            fs::create_dir_all(&history_dir)
+        if !outputs_dir.exists() {
+            fs::create_dir_all(&outputs_dir)
                 .map_err(|e| format!("Failed to create history directory: {}", e))?;
         }
 
-        let history_path = Self::history_path().ok_or("Could not determine history path")?;
+        for entry in entries {
+            let output_path = outputs_dir.join(format!("{}.log", entry.id));
+            if !output_path.exists() {
+                fs::write(&output_path, &entry.output)
+                    .map_err(|e| format!("Failed to write run output: {}", e))?;
+            }
+        }
+
+        let ids: HashSet<&str> = entries.iter().map(|e| e.id.as_str()).collect();
+        if let Ok(files) = fs::read_dir(&outputs_dir) {
+            for file in files.flatten() {
+                let path = file.path();
+                let is_referenced = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .is_some_and(|stem| ids.contains(stem));
+                if !is_referenced {
+                    let _ = fs::remove_file(path);
+                }
+            }
+        }
 
         let history = RunHistory {
             entries: entries.to_vec(),
@@ -95,9 +459,385 @@ impl RunHistory {
         let json = serde_json::to_string_pretty(&history)
             .map_err(|e| format!("Failed to serialize history: {}", e))?;
 
-        fs::write(&history_path, json)
+        fs::write(target_dir.join("index.json"), json)
             .map_err(|e| format!("Failed to write history file: {}", e))?;
 
         Ok(())
     }
+
+    /// Apply retention to entries ordered newest first: keep the newest
+    /// `retention` entries of each target
+    pub fn retain(entries: &mut Vec<RunHistoryEntry>, retention: usize) {
+        let mut counts: HashMap<Option<String>, usize> = HashMap::new();
+        entries.retain(|entry| {
+            let count = counts.entry(entry.target.clone()).or_default();
+            *count += 1;
+            *count <= retention.max(1)
+        });
+    }
+
+    /// Move this project's entries from the legacy global history.json into
+    /// its own history, if it doesn't have one yet.
+    ///
+    /// Legacy entries don't record a project path (their command has no
+    /// `--project-dir`), so an entry is attributed to the project when every
+    /// model it selected or ran is one of `nodes`. Other entries stay in the
+    /// legacy file for the projects they belong to.
+    pub fn migrate_legacy(project_path: &Path, nodes: &[Node]) -> Vec<RunHistoryEntry> {
+        let (Some(project_dir), Some(legacy_path)) =
+            (Self::project_dir(project_path), Self::legacy_history_path())
+        else {
+            return Vec::new();
+        };
+        if project_dir.exists() || nodes.is_empty() {
+            return Vec::new();
+        }
+
+        // Entries are kept as raw JSON so the ones left behind are written back unchanged
+        let Some(legacy) = fs::read_to_string(&legacy_path)
+            .ok()
+            .and_then(|c| serde_json::from_str::<LegacyHistory>(&c).ok())
+        else {
+            return Vec::new();
+        };
+
+        let names: HashSet<&str> = nodes.iter().map(|n| n.name.as_str()).collect();
+        let mut entries = Vec::new();
+        let mut remaining = Vec::new();
+        for value in legacy.entries {
+            match serde_json::from_value::<RunHistoryEntry>(value.clone()) {
+                Ok(entry) if belongs_to_project(&entry, &names) => entries.push(entry),
+                _ => remaining.push(value),
+            }
+        }
+        if entries.is_empty() {
+            return Vec::new();
+        }
+
+        for entry in &mut entries {
+            if entry.id.is_empty() {
+                entry.id = RunHistoryEntry::id_for(&entry.timestamp);
+            }
+            entry.output_loaded = true;
+        }
+
+        if Self::save(project_path, &entries).is_ok() {
+            if remaining.is_empty() {
+                let _ = fs::rename(&legacy_path, legacy_path.with_extension("json.migrated"));
+            } else if let Ok(json) =
+                serde_json::to_string_pretty(&LegacyHistory { entries: remaining })
+            {
+                let _ = fs::write(&legacy_path, json);
+            }
+        }
+
+        entries
+    }
+}
+
+/// Global history.json written before history was stored per project
+#[derive(Serialize, Deserialize)]
+struct LegacyHistory {
+    entries: Vec<serde_json::Value>,
+}
+
+/// Entries of a history index.json in `dir`, if it has one
+fn read_index(dir: &Path) -> Option<Vec<RunHistoryEntry>> {
+    let contents = fs::read_to_string(dir.join("index.json")).ok()?;
+    serde_json::from_str::<RunHistory>(&contents).ok().map(|h| h.entries)
+}
+
+/// Directory name for a target's history; entries without a target ran
+/// against the profile's default. Named targets always get a hash suffix, so
+/// they can't collide with each other or with `_default`.
+fn target_key(target: Option<&str>) -> String {
+    match target {
+        Some(target) => hashed_key(target, target),
+        None => "_default".to_string(),
+    }
+}
+
+/// Whether a legacy entry selected or ran models, all of them named in `names`
+fn belongs_to_project(entry: &RunHistoryEntry, names: &HashSet<&str>) -> bool {
+    let selected = entry
+        .command
+        .split_whitespace()
+        .skip_while(|t| *t != "--select")
+        .skip(1)
+        .take_while(|t| !t.starts_with("--"))
+        .map(|t| t.trim_matches(|c: char| c == '+' || c == '@'))
+        .filter(|t| !t.contains(':'));
+    let ran = RunHistoryEntry::parse_model_durations(&entry.output).into_keys();
+
+    let models: Vec<String> = selected
+        .map(str::to_string)
+        .chain(ran.map(|name| name.rsplit('.').next().unwrap_or_default().to_string()))
+        .collect();
+
+    !models.is_empty() && models.iter().all(|m| names.contains(m.as_str()))
+}
+
+/// Directory name for a project's history: readable folder name plus a hash of the full path
+fn project_key(project_path: &Path) -> String {
+    let name = project_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    hashed_key(&name, &project_path.to_string_lossy())
+}
+
+/// `name` with unsafe characters replaced, followed by a hash of `full` so
+/// names that only differ in replaced characters stay apart
+fn hashed_key(name: &str, full: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+
+    // FNV-1a: stable across builds, unlike std's DefaultHasher
+    let hash = full.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{}-{:016x}", name, hash)
+}
+
+#[cfg(test)]
+impl RunHistoryEntry {
+    /// Test fixture: a successful `command` that just ran, with its output loaded
+    pub fn fixture(command: &str, output: &str) -> Self {
+        let timestamp = Local::now();
+        Self {
+            id: Self::id_for(&timestamp),
+            timestamp,
+            command: command.to_string(),
+            status: RunStatus::Success,
+            output: output.to_string(),
+            output_loaded: true,
+            duration_secs: 1.0,
+            target: None,
+            invocation_id: None,
+            node_results: Vec::new(),
+            external: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn entry(command: &str, status: RunStatus, output: &str) -> RunHistoryEntry {
+        RunHistoryEntry {
+            status,
+            target: Some("dev".to_string()),
+            ..RunHistoryEntry::fixture(command, output)
+        }
+    }
+
+    #[test]
+    fn test_project_key() {
+        let key = project_key(Path::new("/home/me/my project"));
+        assert!(key.starts_with("my_project-"));
+        assert_eq!(key, project_key(Path::new("/home/me/my project")));
+        assert_ne!(key, project_key(Path::new("/tmp/my project")));
+    }
+
+    #[test]
+    fn test_target_key() {
+        assert_ne!(target_key(Some("dev.x")), target_key(Some("dev_x")));
+        assert_ne!(target_key(Some("_default")), target_key(None));
+        assert!(target_key(Some("prod")).starts_with("prod-"));
+    }
+
+    #[test]
+    fn test_subcommand() {
+        let e = entry("dbt run --select orders+", RunStatus::Success, "");
+        assert_eq!(e.subcommand(), Some("run"));
+
+        let e = entry("/usr/bin/dbt test", RunStatus::Success, "");
+        assert_eq!(e.subcommand(), Some("test"));
+    }
+
+    #[test]
+    fn test_involves_model() {
+        let e = entry("dbt run --select +orders", RunStatus::Success, "");
+        assert!(e.involves_model("orders"));
+        assert!(!e.involves_model("customers"));
+
+        let e = entry(
+            "dbt build",
+            RunStatus::Success,
+            "1 of 2 OK created sql view model analytics.stg_customers ... [CREATE VIEW in 0.1s]",
+        );
+        assert!(e.involves_model("stg_customers"));
+        assert!(!e.involves_model("customers"));
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let entries = vec![
+            entry("dbt run --select orders", RunStatus::Success, "Completed successfully"),
+            entry("dbt test --select orders", RunStatus::Failed, "Database Error in test"),
+            entry("dbt run --select customers", RunStatus::Failed, "Compilation Error"),
+        ];
+
+        let mut filter = HistoryFilter::default();
+        assert!(!filter.is_active());
+        assert_eq!(filter.apply(&entries), vec![0, 1, 2]);
+
+        filter.cycle_status();
+        filter.cycle_status();
+        assert_eq!(filter.status, Some(RunStatus::Failed));
+        assert_eq!(filter.apply(&entries), vec![1, 2]);
+
+        filter.command = Some("run".to_string());
+        assert_eq!(filter.apply(&entries), vec![2]);
+
+        let mut filter = HistoryFilter {
+            model: "orders".to_string(),
+            search: "database error".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(filter.apply(&entries), vec![1]);
+
+        filter.search.clear();
+        filter.target = Some("prod".to_string());
+        assert!(filter.apply(&entries).is_empty());
+    }
+
+    #[test]
+    fn test_retention_per_target() {
+        let mut entries: Vec<RunHistoryEntry> = ["dev", "dev", "prod", "dev"]
+            .iter()
+            .map(|target| RunHistoryEntry {
+                target: Some(target.to_string()),
+                ..RunHistoryEntry::fixture("dbt run", "")
+            })
+            .collect();
+
+        RunHistory::retain(&mut entries, 2);
+
+        let targets: Vec<&str> = entries.iter().filter_map(|e| e.target.as_deref()).collect();
+        assert_eq!(targets, vec!["dev", "dev", "prod"]);
+    }
+
+    #[test]
+    fn test_save_and_load_per_target() {
+        let dir = env::temp_dir().join(format!("dbt-tui-history-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let dev = entry("dbt run", RunStatus::Success, "dev output");
+        let prod = RunHistoryEntry {
+            id: "prod-run".to_string(),
+            timestamp: dev.timestamp - Duration::minutes(1),
+            target: Some("prod".to_string()),
+            ..dev.clone()
+        };
+        let default = RunHistoryEntry {
+            id: "default-run".to_string(),
+            timestamp: dev.timestamp - Duration::minutes(2),
+            target: None,
+            ..dev.clone()
+        };
+
+        RunHistory::save_dir(&dir, &[dev.clone(), prod.clone(), default]).unwrap();
+        let prod_dir = dir.join(target_key(Some("prod")));
+        assert!(prod_dir.join("outputs").join("prod-run.log").exists());
+        assert!(dir.join("_default").join("index.json").exists());
+
+        let mut loaded = RunHistory::load_dir(&dir);
+        let ids: Vec<&str> = loaded.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec![dev.id.as_str(), "prod-run", "default-run"]);
+        assert!(!loaded[0].output_loaded);
+        RunHistory::read_output(&dir, &mut loaded[0]);
+        assert_eq!(loaded[0].output, "dev output");
+
+        // A target with no entries left is removed
+        RunHistory::save_dir(&dir, &[dev, prod]).unwrap();
+        assert!(!dir.join("_default").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_belongs_to_project() {
+        let names: HashSet<&str> = ["orders", "customers"].into_iter().collect();
+
+        let e = entry("dbt run --select orders+", RunStatus::Success, "");
+        assert!(belongs_to_project(&e, &names));
+
+        let e = entry(
+            "dbt build",
+            RunStatus::Success,
+            "1 of 2 OK created sql table model analytics.customers ... [SELECT 3 in 1.00s]\n\
+             2 of 2 OK created sql table model analytics.invoices ... [SELECT 5 in 2.00s]",
+        );
+        assert!(!belongs_to_project(&e, &names));
+
+        // Nothing to attribute it by
+        let e = entry("dbt deps", RunStatus::Success, "Installing dbt_utils");
+        assert!(!belongs_to_project(&e, &names));
+    }
+
+    #[test]
+    fn test_filter_date_range() {
+        let now = Local.with_ymd_and_hms(2024, 3, 10, 12, 0, 0).unwrap();
+        let mut old = entry("dbt run", RunStatus::Success, "");
+        old.timestamp = Local.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+
+        let mut filter = HistoryFilter::default();
+        assert!(filter.matches(&old, &now));
+
+        filter.date_range = HistoryDateRange::Last7Days;
+        assert!(!filter.matches(&old, &now));
+
+        filter.date_range = HistoryDateRange::Last30Days;
+        assert!(filter.matches(&old, &now));
+    }
+
+    #[test]
+    fn test_cycle_option() {
+        let options = vec!["run".to_string(), "test".to_string()];
+        let mut filter = HistoryFilter::default();
+
+        filter.cycle_command(&options);
+        assert_eq!(filter.command.as_deref(), Some("run"));
+        filter.cycle_command(&options);
+        assert_eq!(filter.command.as_deref(), Some("test"));
+        filter.cycle_command(&options);
+        assert_eq!(filter.command, None);
+    }
+
+    #[test]
+    fn test_entry_from_run_results() {
+        let results = RunResults::from_json(
+            r#"{
+                "metadata": {"generated_at": "2024-03-01T10:00:05Z", "invocation_id": "abc-123"},
+                "results": [
+                    {"unique_id": "model.shop.orders", "status": "error", "execution_time": 1.5,
+                     "timing": [], "message": "Database Error"},
+                    {"unique_id": "model.shop.customers", "status": "success", "execution_time": 2.5,
+                     "timing": []}
+                ],
+                "elapsed_time": 5.0,
+                "args": {"which": "build", "select": "orders", "target": "ci"}
+            }"#,
+        )
+        .unwrap();
+
+        let entry = RunHistoryEntry::from_run_results(results, None);
+
+        assert!(entry.external);
+        assert_eq!(entry.command, "dbt build --select orders --target ci");
+        assert_eq!(entry.status, RunStatus::Failed);
+        assert_eq!(entry.target.as_deref(), Some("ci"));
+        assert_eq!(entry.invocation_id.as_deref(), Some("abc-123"));
+        // Started elapsed_time before run_results.json was generated
+        assert_eq!(
+            entry.timestamp.with_timezone(&Utc),
+            Utc.with_ymd_and_hms(2024, 3, 1, 10, 0, 0).unwrap()
+        );
+        assert!(entry.output.contains("error    model.shop.orders (1.50s)"));
+    }
 }
@@ -0,0 +1,271 @@
+//! Impact analysis of a node
+//!
+//! Everything transitively downstream of a node: models, tests and
+//! exposures, their owners, which of them are expensive to rebuild (tables
+//! and incremental models) and their average runtime from run history.
+
+use super::estimate::average_runtimes;
+use super::history::RunHistoryEntry;
+use super::lineage::LineageGraph;
+use super::node::{Exposure, Node};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// A node affected by a change to the root
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImpactedNode {
+    pub unique_id: String,
+    pub name: String,
+    pub resource_type: String,
+    /// Hops from the root along the shortest path
+    pub depth: usize,
+    pub materialized: Option<String>,
+    pub owner: Option<String>,
+    /// Average execution time over recent runs
+    pub avg_runtime: Option<f64>,
+}
+
+impl ImpactedNode {
+    /// Tables and incremental models take a full rebuild to refresh
+    pub fn is_expensive(&self) -> bool {
+        matches!(self.materialized.as_deref(), Some("table" | "incremental"))
+    }
+}
+
+/// Blast radius of changing a node
+#[derive(Debug, Clone)]
+pub struct ImpactReport {
+    pub root_name: String,
+    /// Models, seeds and snapshots, by depth then name
+    pub models: Vec<ImpactedNode>,
+    pub tests: Vec<ImpactedNode>,
+    pub exposures: Vec<ImpactedNode>,
+    /// Distinct owners of the affected models and exposures
+    pub owners: Vec<String>,
+}
+
+impl ImpactReport {
+    pub fn build(
+        root: &Node,
+        graph: &LineageGraph,
+        all_nodes: &[Node],
+        exposures: &[Exposure],
+        history: &[RunHistoryEntry],
+    ) -> Self {
+        let by_id: HashMap<&str, &Node> =
+            all_nodes.iter().map(|n| (n.unique_id.as_str(), n)).collect();
+        let runtimes = average_runtimes(history);
+
+        // Breadth-first, so each node gets its shortest distance
+        let mut depths: Vec<(String, usize)> = Vec::new();
+        let mut seen: HashSet<String> = HashSet::from([root.unique_id.clone()]);
+        let mut frontier = vec![root.unique_id.clone()];
+        let mut depth = 0;
+        while !frontier.is_empty() {
+            depth += 1;
+            let mut next = Vec::new();
+            for id in &frontier {
+                for child in graph.get_downstream(id) {
+                    if seen.insert(child.unique_id.clone()) {
+                        depths.push((child.unique_id.clone(), depth));
+                        next.push(child.unique_id);
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        let mut models = Vec::new();
+        let mut tests = Vec::new();
+        for (id, depth) in &depths {
+            let Some(node) = by_id.get(id.as_str()) else {
+                continue;
+            };
+            let impacted = ImpactedNode {
+                unique_id: node.unique_id.clone(),
+                name: node.name.clone(),
+                resource_type: node.resource_type.clone(),
+                depth: *depth,
+                materialized: node.config.materialized.clone(),
+                owner: node.owner().map(String::from),
+                avg_runtime: runtimes.get(id).copied(),
+            };
+            if node.resource_type == "test" {
+                tests.push(impacted);
+            } else {
+                models.push(impacted);
+            }
+        }
+
+        // Exposures hang off the root or anything downstream of it
+        let depth_of: HashMap<&str, usize> = depths
+            .iter()
+            .map(|(id, depth)| (id.as_str(), *depth))
+            .chain([(root.unique_id.as_str(), 0)])
+            .collect();
+        let mut impacted_exposures: Vec<ImpactedNode> = exposures
+            .iter()
+            .filter_map(|exposure| {
+                let parent_depth = exposure
+                    .depends_on
+                    .nodes
+                    .iter()
+                    .filter_map(|d| depth_of.get(d.as_str()))
+                    .min()?;
+                Some(ImpactedNode {
+                    unique_id: exposure.unique_id.clone(),
+                    name: exposure.name.clone(),
+                    resource_type: "exposure".to_string(),
+                    depth: parent_depth + 1,
+                    materialized: None,
+                    owner: exposure.owner_label().map(String::from),
+                    avg_runtime: None,
+                })
+            })
+            .collect();
+
+        let by_depth =
+            |a: &ImpactedNode, b: &ImpactedNode| a.depth.cmp(&b.depth).then(a.name.cmp(&b.name));
+        models.sort_by(by_depth);
+        tests.sort_by(by_depth);
+        impacted_exposures.sort_by(by_depth);
+
+        let owners: BTreeSet<String> = models
+            .iter()
+            .chain(&impacted_exposures)
+            .filter_map(|n| n.owner.clone())
+            .collect();
+
+        Self {
+            root_name: root.name.clone(),
+            models,
+            tests,
+            exposures: impacted_exposures,
+            owners: owners.into_iter().collect(),
+        }
+    }
+
+    /// Affected models that are tables or incremental
+    pub fn expensive(&self) -> impl Iterator<Item = &ImpactedNode> {
+        self.models.iter().filter(|n| n.is_expensive())
+    }
+
+    /// Sum of average runtimes of affected models and tests, and how many had none
+    pub fn total_runtime(&self) -> (f64, usize) {
+        let nodes = self.models.iter().chain(&self.tests);
+        let total = nodes.clone().filter_map(|n| n.avg_runtime).sum();
+        let unknown = nodes.filter(|n| n.avg_runtime.is_none()).count();
+        (total, unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::node::{DependsOn, ExposureOwner};
+    use crate::model::run_results::NodeResult;
+
+    fn node(name: &str, resource_type: &str, materialized: &str, depends_on: &[&str]) -> Node {
+        let mut node = Node::fixture_on_models(name, resource_type, depends_on);
+        node.config.materialized = Some(materialized.to_string());
+        if name == "orders" {
+            node.config.meta.insert("owner".to_string(), "finance".into());
+        }
+        node
+    }
+
+    fn history_entry(results: &[(&str, &str, f64)]) -> RunHistoryEntry {
+        RunHistoryEntry {
+            node_results: results
+                .iter()
+                .map(|(id, status, time)| NodeResult::fixture(id, status, *time))
+                .collect(),
+            ..RunHistoryEntry::fixture("dbt build", "")
+        }
+    }
+
+    #[test]
+    fn test_impact_report() {
+        let nodes = vec![
+            node("stg_orders", "model", "view", &[]),
+            node("orders", "model", "table", &["stg_orders"]),
+            node("revenue", "model", "incremental", &["orders", "stg_orders"]),
+            node("unique_orders_id", "test", "test", &["orders"]),
+        ];
+        let graph = LineageGraph::build(&nodes);
+        let exposures = vec![Exposure {
+            unique_id: "exposure.shop.dashboard".to_string(),
+            name: "dashboard".to_string(),
+            depends_on: DependsOn {
+                nodes: vec!["model.shop.revenue".to_string()],
+                macros: Vec::new(),
+            },
+            owner: ExposureOwner {
+                name: None,
+                email: Some("bi@shop.com".to_string()),
+            },
+            ..Default::default()
+        }];
+        let history = vec![
+            history_entry(&[("model.shop.orders", "success", 2.0)]),
+            history_entry(&[
+                ("model.shop.orders", "success", 4.0),
+                ("model.shop.revenue", "skipped", 0.0),
+            ]),
+        ];
+
+        let report = ImpactReport::build(&nodes[0], &graph, &nodes, &exposures, &history);
+
+        let models: Vec<(&str, usize)> = report
+            .models
+            .iter()
+            .map(|n| (n.name.as_str(), n.depth))
+            .collect();
+        assert_eq!(models, vec![("orders", 1), ("revenue", 1)]);
+        assert_eq!(report.tests.len(), 1);
+        assert_eq!(report.tests[0].depth, 2);
+        assert_eq!(report.exposures[0].depth, 2);
+        assert_eq!(report.owners, vec!["bi@shop.com", "finance"]);
+        assert_eq!(report.expensive().count(), 2);
+        assert_eq!(report.models[0].avg_runtime, Some(3.0));
+        assert_eq!(report.total_runtime(), (3.0, 2));
+    }
+
+    fn exposure(name: &str, depends_on: &[&str]) -> Exposure {
+        Exposure {
+            unique_id: format!("exposure.shop.{}", name),
+            name: name.to_string(),
+            depends_on: DependsOn {
+                nodes: depends_on.iter().map(|d| format!("model.shop.{}", d)).collect(),
+                macros: Vec::new(),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_exposure_depth() {
+        let nodes = vec![
+            node("stg_orders", "model", "view", &[]),
+            node("orders", "model", "table", &["stg_orders"]),
+            node("revenue", "model", "table", &["orders"]),
+            node("stg_customers", "model", "view", &[]),
+        ];
+        let graph = LineageGraph::build(&nodes);
+        let exposures = vec![
+            // On the root itself
+            exposure("ops", &["stg_orders"]),
+            // Shortest path wins: orders is 1 hop away, revenue 2
+            exposure("finance", &["revenue", "orders"]),
+            exposure("crm", &["stg_customers"]),
+        ];
+
+        let report = ImpactReport::build(&nodes[0], &graph, &nodes, &exposures, &[]);
+
+        let depths: Vec<(&str, usize)> = report
+            .exposures
+            .iter()
+            .map(|n| (n.name.as_str(), n.depth))
+            .collect();
+        assert_eq!(depths, vec![("ops", 1), ("finance", 2)]);
+    }
+}
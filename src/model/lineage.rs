@@ -6,6 +6,7 @@ use std::collections::HashMap;
 /// Represents a node in the lineage graph with metadata
 #[derive(Debug, Clone)]
 pub struct LineageNode {
+    pub unique_id: String,
     pub name: String,
     pub resource_type: String,
 }
@@ -13,6 +14,7 @@ pub struct LineageNode {
 impl LineageNode {
     pub fn from_node(node: &Node) -> Self {
         LineageNode {
+            unique_id: node.unique_id.clone(),
             name: node.name.clone(),
             resource_type: node.resource_type.clone(),
         }
@@ -35,6 +37,7 @@ impl LineageNode {
         };
 
         LineageNode {
+            unique_id: unique_id.to_string(),
             name,
             resource_type,
         }
@@ -127,13 +130,17 @@ mod tests {
             schema: "public".to_string(),
             compiled_code: None,
             raw_code: None,
-            depends_on: DependsOn { nodes: depends_on },
+            depends_on: DependsOn {
+                nodes: depends_on,
+                macros: Vec::new(),
+            },
             root_path: None,
             original_file_path: None,
             config: NodeConfig::default(),
             compiled_path: None,
             description: None,
             columns: std::collections::HashMap::new(),
+            ..Default::default()
         }
     }
 
@@ -0,0 +1,445 @@
+//! Modeling-convention linter
+//!
+//! Checks models against configurable rules: name patterns per folder,
+//! which layers a layer may select from, a fan-out limit, models selecting
+//! from no ref or source, hard-coded relation names in raw SQL, and one
+//! schema YAML entry per model. Rules are read from `dbt-tui-lint.yml` in
+//! the project root; without it the defaults below apply.
+
+use super::node::Node;
+use super::yaml_index::{YamlEntryKind, YamlIndex};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Name of the rules file in the project root
+pub const LINT_CONFIG_FILE: &str = "dbt-tui-lint.yml";
+
+/// Model names must match `pattern` under `folder`
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct NamingRule {
+    pub folder: String,
+    pub pattern: String,
+}
+
+/// A layer of models, identified by folder
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct Layer {
+    pub name: String,
+    pub folder: String,
+    /// Layer names, or "source", "seed" and "snapshot", this layer may select from.
+    /// Models outside every layer are always allowed.
+    #[serde(default)]
+    pub allowed_parents: Vec<String>,
+}
+
+/// Lint rules, as written in `dbt-tui-lint.yml`
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct LintConfig {
+    pub naming: Vec<NamingRule>,
+    pub layers: Vec<Layer>,
+    /// Maximum number of models selecting directly from one model
+    pub max_fan_out: Option<usize>,
+    /// Flag models that select from no ref or source
+    pub root_models: bool,
+    /// Flag relations named directly in SQL instead of through ref or source
+    pub hard_coded_references: bool,
+    /// Flag models without exactly one schema YAML entry
+    pub yaml_entries: bool,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        let layer = |name: &str, parents: &[&str]| Layer {
+            name: name.to_string(),
+            folder: format!("models/{}", name),
+            allowed_parents: parents.iter().map(|p| p.to_string()).collect(),
+        };
+        Self {
+            naming: vec![
+                NamingRule {
+                    folder: "models/staging".to_string(),
+                    pattern: "^stg_".to_string(),
+                },
+                NamingRule {
+                    folder: "models/intermediate".to_string(),
+                    pattern: "^int_".to_string(),
+                },
+            ],
+            layers: vec![
+                layer("staging", &["source", "seed"]),
+                layer("intermediate", &["staging", "intermediate", "seed", "snapshot"]),
+                layer("marts", &["intermediate", "marts", "seed", "snapshot"]),
+            ],
+            max_fan_out: Some(10),
+            root_models: true,
+            hard_coded_references: true,
+            yaml_entries: true,
+        }
+    }
+}
+
+/// Rule a violation breaks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintRule {
+    Naming,
+    RefDirection,
+    FanOut,
+    RootModel,
+    HardCodedReference,
+    YamlEntry,
+}
+
+impl LintRule {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LintRule::Naming => "naming",
+            LintRule::RefDirection => "ref-direction",
+            LintRule::FanOut => "fan-out",
+            LintRule::RootModel => "root-model",
+            LintRule::HardCodedReference => "hard-coded-reference",
+            LintRule::YamlEntry => "yaml-entry",
+        }
+    }
+}
+
+/// A model breaking a rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintViolation {
+    pub rule: LintRule,
+    pub unique_id: String,
+    pub name: String,
+    /// Model file relative to the project root
+    pub path: Option<String>,
+    pub message: String,
+}
+
+/// Jinja expressions, statements and comments
+static JINJA_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)\{\{.*?\}\}|\{%.*?%\}|\{#.*?#\}").unwrap());
+
+/// SQL comments and string literals
+static NOISE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)--[^\n]*|/\*.*?\*/|'(?:[^']|'')*'").unwrap());
+
+/// CTE names: `with name as (` and `, name as (`
+static CTE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:\bwith|,)\s*(?:recursive\s+)?([A-Za-z_]\w*)\s+as\s*\(").unwrap()
+});
+
+/// Relation after FROM or JOIN, possibly qualified and quoted
+static RELATION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\b(distinct\s+)?(?:from|join)\s+([A-Za-z_"`\[][\w."`\[\]]*)"#).unwrap()
+});
+
+/// Placeholder left where Jinja was removed
+const JINJA_PLACEHOLDER: &str = "__jinja__";
+
+/// Keywords that can open a subquery with no space before the parenthesis,
+/// e.g. `exists(select ...)` or `in(select ...)`
+const SUBQUERY_KEYWORDS: [&str; 11] = [
+    "exists", "in", "any", "all", "some", "not", "as", "from", "join", "lateral", "select",
+];
+
+/// Check every model against the rules
+pub fn lint(
+    config: &LintConfig,
+    all_nodes: &[Node],
+    yaml_index: &YamlIndex,
+) -> Result<Vec<LintViolation>, String> {
+    let naming: Vec<(&NamingRule, Regex)> = config
+        .naming
+        .iter()
+        .map(|rule| {
+            Regex::new(&rule.pattern)
+                .map(|regex| (rule, regex))
+                .map_err(|e| format!("Invalid naming pattern '{}': {}", rule.pattern, e))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let models: Vec<&Node> = all_nodes
+        .iter()
+        .filter(|n| n.resource_type == "model")
+        .collect();
+    let by_id: HashMap<&str, &Node> = all_nodes
+        .iter()
+        .map(|n| (n.unique_id.as_str(), n))
+        .collect();
+    let layer_of = |node: &Node| -> Option<&Layer> {
+        let path = Path::new(node.original_file_path.as_deref()?);
+        config
+            .layers
+            .iter()
+            .find(|layer| path.starts_with(&layer.folder))
+    };
+
+    let mut children: HashMap<&str, usize> = HashMap::new();
+    for model in &models {
+        for parent in model.depends_on.nodes.iter().collect::<BTreeSet<_>>() {
+            *children.entry(parent.as_str()).or_default() += 1;
+        }
+    }
+
+    let mut violations = Vec::new();
+    for model in &models {
+        let violation = |rule, message| LintViolation {
+            rule,
+            unique_id: model.unique_id.clone(),
+            name: model.name.clone(),
+            path: model.original_file_path.clone(),
+            message,
+        };
+        let path = model.original_file_path.as_deref().map(Path::new);
+
+        for (rule, regex) in &naming {
+            if path.is_some_and(|p| p.starts_with(&rule.folder)) && !regex.is_match(&model.name) {
+                violations.push(violation(
+                    LintRule::Naming,
+                    format!("name should match {} in {}", rule.pattern, rule.folder),
+                ));
+            }
+        }
+
+        if let Some(layer) = layer_of(model) {
+            for parent_id in &model.depends_on.nodes {
+                let parent_kind = match by_id.get(parent_id.as_str()) {
+                    Some(parent) if parent.resource_type == "model" => match layer_of(parent) {
+                        Some(parent_layer) => parent_layer.name.clone(),
+                        None => continue,
+                    },
+                    Some(parent) => parent.resource_type.clone(),
+                    None => parent_id.split('.').next().unwrap_or_default().to_string(),
+                };
+                if !layer.allowed_parents.contains(&parent_kind) {
+                    let parent_name = parent_id.rsplit('.').next().unwrap_or(parent_id);
+                    violations.push(violation(
+                        LintRule::RefDirection,
+                        format!(
+                            "{} model selects from {} {}",
+                            layer.name, parent_kind, parent_name
+                        ),
+                    ));
+                }
+            }
+        }
+
+        if let Some(max) = config.max_fan_out {
+            let count = children.get(model.unique_id.as_str()).copied().unwrap_or(0);
+            if count > max {
+                violations.push(violation(
+                    LintRule::FanOut,
+                    format!("{} models select from it (limit {})", count, max),
+                ));
+            }
+        }
+
+        if config.root_models && model.depends_on.nodes.is_empty() {
+            violations.push(violation(
+                LintRule::RootModel,
+                "selects from no ref or source".to_string(),
+            ));
+        }
+
+        if config.hard_coded_references {
+            if let Some(ref sql) = model.raw_code {
+                for relation in hard_coded_relations(sql) {
+                    violations.push(violation(
+                        LintRule::HardCodedReference,
+                        format!("{} is named directly; use ref() or source()", relation),
+                    ));
+                }
+            }
+        }
+
+        if config.yaml_entries {
+            let files: Vec<&str> = yaml_index
+                .entries
+                .iter()
+                .filter(|e| e.kind == YamlEntryKind::Model && e.resource == model.name)
+                .map(|e| e.file.as_str())
+                .collect();
+            let message = match files.len() {
+                0 if model.patch_path.is_none() => Some("has no schema YAML entry".to_string()),
+                0 | 1 => None,
+                n => Some(format!("has {} schema YAML entries: {}", n, files.join(", "))),
+            };
+            if let Some(message) = message {
+                violations.push(violation(LintRule::YamlEntry, message));
+            }
+        }
+    }
+
+    violations.sort_by(|a, b| a.path.cmp(&b.path).then(a.rule.cmp(&b.rule)));
+    Ok(violations)
+}
+
+/// Relations read in raw SQL without ref() or source(), excluding CTEs
+pub fn hard_coded_relations(sql: &str) -> Vec<String> {
+    let sql = JINJA_REGEX.replace_all(sql, format!(" {} ", JINJA_PLACEHOLDER));
+    let sql = NOISE_REGEX.replace_all(&sql, " ");
+
+    let ctes: BTreeSet<String> = CTE_REGEX
+        .captures_iter(&sql)
+        .map(|c| c[1].to_lowercase())
+        .collect();
+
+    let mut relations = Vec::new();
+    for captures in RELATION_REGEX.captures_iter(&sql) {
+        // `is distinct from x` compares values
+        if captures.get(1).is_some() {
+            continue;
+        }
+        let keyword = captures.get(0).unwrap();
+        let name_match = captures.get(2).unwrap();
+        // `extract(year from x)` and the like are function arguments
+        if inside_function_call(&sql[..keyword.start()]) {
+            continue;
+        }
+        // Table functions, e.g. `from unnest(...)`
+        if sql[name_match.end()..].trim_start().starts_with('(') {
+            continue;
+        }
+        let name: String = name_match
+            .as_str()
+            .chars()
+            .filter(|c| !matches!(c, '"' | '`' | '[' | ']'))
+            .collect();
+        let lower = name.to_lowercase();
+        if lower == JINJA_PLACEHOLDER
+            || lower == "lateral"
+            || (!lower.contains('.') && ctes.contains(&lower))
+            || relations.contains(&name)
+        {
+            continue;
+        }
+        relations.push(name);
+    }
+    relations
+}
+
+/// Whether the innermost unclosed parenthesis opens a function call
+fn inside_function_call(before: &str) -> bool {
+    let mut depth = 0;
+    for (i, c) in before.char_indices().rev() {
+        match c {
+            ')' => depth += 1,
+            '(' if depth > 0 => depth -= 1,
+            '(' => {
+                let prefix = &before[..i];
+                let word_start = prefix
+                    .rfind(|p: char| !(p.is_alphanumeric() || p == '_'))
+                    .map_or(0, |j| j + 1);
+                let word = prefix[word_start..].to_lowercase();
+                return !word.is_empty() && !SUBQUERY_KEYWORDS.contains(&word.as_str());
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(path: &str, sql: &str, depends_on: &[&str]) -> Node {
+        let name = Path::new(path).file_stem().unwrap().to_string_lossy().to_string();
+        Node {
+            raw_code: Some(sql.to_string()),
+            original_file_path: Some(path.to_string()),
+            patch_path: Some(format!("shop://{}.yml", path)),
+            ..Node::fixture(&name, "model", depends_on)
+        }
+    }
+
+    #[test]
+    fn test_hard_coded_relations() {
+        let sql = "with orders as (select * from {{ ref('stg_orders') }}),\n\
+                   totals as (select extract(year from ordered_at) as y from orders)\n\
+                   select * from totals t\n\
+                   join analytics.\"Payments\" p on a is distinct from b\n\
+                   left join lateral flatten(input => x) f\n\
+                   -- from commented.out\n\
+                   where s = 'from quoted' and id in (select id from raw_refunds)";
+        assert_eq!(
+            hard_coded_relations(sql),
+            vec!["analytics.Payments", "raw_refunds"]
+        );
+
+        // Subqueries opened by a keyword aren't function arguments
+        let sql = "select * from {{ ref('orders') }} o\n\
+                   where exists(select 1 from raw.t where t.id = o.id)\n\
+                   and o.customer_id in(select id from raw.customers)\n\
+                   and o.total > any(select total from raw.refunds)";
+        assert_eq!(
+            hard_coded_relations(sql),
+            vec!["raw.t", "raw.customers", "raw.refunds"]
+        );
+    }
+
+    #[test]
+    fn test_lint_rules() {
+        let nodes = vec![
+            model(
+                "models/staging/stg_orders.sql",
+                "select * from {{ source('raw', 'orders') }}",
+                &["source.shop.raw.orders"],
+            ),
+            model(
+                "models/staging/customers.sql",
+                "select * from raw.customers",
+                &[],
+            ),
+            model(
+                "models/marts/orders.sql",
+                "select * from {{ ref('stg_orders') }}",
+                &["model.shop.stg_orders"],
+            ),
+        ];
+        let config = LintConfig {
+            max_fan_out: Some(0),
+            yaml_entries: false,
+            ..LintConfig::default()
+        };
+
+        let violations = lint(&config, &nodes, &YamlIndex::default()).unwrap();
+        let found: Vec<(LintRule, &str)> = violations
+            .iter()
+            .map(|v| (v.rule, v.name.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (LintRule::RefDirection, "orders"),
+                (LintRule::Naming, "customers"),
+                (LintRule::RootModel, "customers"),
+                (LintRule::HardCodedReference, "customers"),
+                (LintRule::FanOut, "stg_orders"),
+            ]
+        );
+        assert_eq!(violations[0].message, "marts model selects from staging stg_orders");
+
+        let config = LintConfig {
+            naming: vec![NamingRule {
+                folder: "models".to_string(),
+                pattern: "(".to_string(),
+            }],
+            ..LintConfig::default()
+        };
+        assert!(lint(&config, &nodes, &YamlIndex::default()).is_err());
+    }
+
+    #[test]
+    fn test_config_from_yaml() {
+        let config: LintConfig = serde_yaml::from_str(
+            "naming:\n  - folder: models/marts\n    pattern: '^(fct|dim)_'\nmax_fan_out: 3\n",
+        )
+        .unwrap();
+        assert_eq!(config.naming.len(), 1);
+        assert_eq!(config.max_fan_out, Some(3));
+        assert_eq!(config.layers, LintConfig::default().layers);
+    }
+}
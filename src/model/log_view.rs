@@ -0,0 +1,317 @@
+//! Log viewer logic for raw run output
+//!
+//! The output is grouped into entries: a log line plus its continuation lines
+//! (multi-line error messages, compiled SQL). Entries are classified as
+//! errors or warnings, can be filtered, and long entries can be folded.
+
+use super::run::TIMESTAMP_REGEX;
+use super::run_errors::parse_run_errors;
+use regex::Regex;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// Summary counters like "ERROR=0" that don't indicate an error themselves
+static COUNTER_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b[A-Z]+=\d+").unwrap());
+
+/// Zero counts like "0 errors" in "Completed with 0 errors and 1 warning:"
+static ZERO_COUNT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b0 (errors?|warnings?)\b").unwrap());
+
+static ERROR_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(ERROR|FAIL|FAILED|Error|error|errors)\b").unwrap());
+
+static WARN_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(WARN|WARNING|Warning|warning|warnings)\b").unwrap());
+
+/// Project file in parentheses, e.g. "(models/marts/orders.sql)"
+static FILE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\(((?:[\w.-]+/)*[\w.-]+\.(?:sql|yml|yaml|csv|py|md))\)").unwrap()
+});
+
+/// Entries with more lines than this are foldable
+pub const FOLD_MIN_LINES: usize = 6;
+
+/// Severity of a log entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+}
+
+impl LogLevel {
+    pub fn of_line(line: &str) -> Self {
+        let line = COUNTER_REGEX.replace_all(line, "");
+        let line = ZERO_COUNT_REGEX.replace_all(&line, "");
+        if ERROR_REGEX.is_match(&line) {
+            LogLevel::Error
+        } else if WARN_REGEX.is_match(&line) {
+            LogLevel::Warn
+        } else {
+            LogLevel::Info
+        }
+    }
+}
+
+/// Which entries are shown
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum LogFilter {
+    #[default]
+    All,
+    Errors,
+    Warnings,
+    /// Entries mentioning a model name
+    Model(String),
+}
+
+impl LogFilter {
+    pub fn label(&self) -> String {
+        match self {
+            LogFilter::All => "all".to_string(),
+            LogFilter::Errors => "errors".to_string(),
+            LogFilter::Warnings => "warnings".to_string(),
+            LogFilter::Model(name) => format!("model: {}", name),
+        }
+    }
+
+    /// All → Errors → Warnings → All (a model filter goes back to All)
+    pub fn next(&self) -> Self {
+        match self {
+            LogFilter::All => LogFilter::Errors,
+            LogFilter::Errors => LogFilter::Warnings,
+            LogFilter::Warnings | LogFilter::Model(_) => LogFilter::All,
+        }
+    }
+
+    fn matches<S: AsRef<str>>(&self, entry: &LogEntry, lines: &[S]) -> bool {
+        match self {
+            LogFilter::All => true,
+            LogFilter::Errors => entry.level == LogLevel::Error,
+            LogFilter::Warnings => entry.level == LogLevel::Warn,
+            LogFilter::Model(name) => lines[entry.start..entry.end]
+                .iter()
+                .any(|line| mentions_identifier(line.as_ref(), name)),
+        }
+    }
+}
+
+/// A log line and its continuation lines (`start..end` in output lines)
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub start: usize,
+    pub end: usize,
+    pub level: LogLevel,
+}
+
+impl LogEntry {
+    pub fn is_foldable(&self) -> bool {
+        self.end - self.start > FOLD_MIN_LINES
+    }
+}
+
+/// A displayed row
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogRow {
+    /// Output line by index
+    Line(usize),
+    /// Placeholder for the hidden lines of a folded entry
+    Folded { entry: usize, hidden: usize },
+}
+
+/// Group output lines into entries.
+/// If the output has timestamps, lines without one continue the previous
+/// entry; otherwise indented lines do.
+pub fn group_entries<S: AsRef<str>>(lines: &[S]) -> Vec<LogEntry> {
+    let has_timestamps = lines.iter().any(|l| TIMESTAMP_REGEX.is_match(l.as_ref()));
+    let is_continuation = |line: &str| {
+        if has_timestamps {
+            !TIMESTAMP_REGEX.is_match(line)
+        } else {
+            line.starts_with(char::is_whitespace) || line.is_empty()
+        }
+    };
+
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for (i, line) in lines.iter().map(AsRef::as_ref).enumerate() {
+        match entries.last_mut() {
+            Some(entry) if is_continuation(line) => entry.end = i + 1,
+            _ => entries.push(LogEntry {
+                start: i,
+                end: i + 1,
+                level: LogLevel::of_line(line),
+            }),
+        }
+    }
+    entries
+}
+
+/// Rows to display for the given filter.
+/// Foldable entries show only their first line unless expanded.
+pub fn build_rows<S: AsRef<str>>(
+    lines: &[S],
+    entries: &[LogEntry],
+    filter: &LogFilter,
+    expanded: &HashSet<usize>,
+) -> Vec<LogRow> {
+    let mut rows = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        if !filter.matches(entry, lines) {
+            continue;
+        }
+        if entry.is_foldable() && !expanded.contains(&index) {
+            rows.push(LogRow::Line(entry.start));
+            rows.push(LogRow::Folded {
+                entry: index,
+                hidden: entry.end - entry.start - 1,
+            });
+        } else {
+            rows.extend((entry.start..entry.end).map(LogRow::Line));
+        }
+    }
+    rows
+}
+
+/// Indices of lines that pass the filter and contain the query (case-insensitive)
+pub fn find_matches<S: AsRef<str>>(
+    lines: &[S],
+    entries: &[LogEntry],
+    filter: &LogFilter,
+    query: &str,
+) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let query = query.to_ascii_lowercase();
+    entries
+        .iter()
+        .filter(|entry| filter.matches(entry, lines))
+        .flat_map(|entry| entry.start..entry.end)
+        .filter(|&i| lines[i].as_ref().to_ascii_lowercase().contains(&query))
+        .collect()
+}
+
+/// Index of the entry containing a line
+pub fn entry_of_line(entries: &[LogEntry], line: usize) -> Option<usize> {
+    entries.iter().position(|e| e.start <= line && line < e.end)
+}
+
+/// File to open for a line, as (path, line, column): the location of the dbt
+/// error reported by its entry, otherwise a file named on the line itself
+pub fn file_location<S: AsRef<str>>(
+    lines: &[S],
+    entries: &[LogEntry],
+    line: usize,
+) -> Option<(String, Option<usize>, Option<usize>)> {
+    if let Some(entry) = entry_of_line(entries, line).map(|i| &entries[i]) {
+        let text = lines[entry.start..entry.end]
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<&str>>()
+            .join("\n");
+        if let Some(error) = parse_run_errors(&text).first() {
+            let (path, line, column) = error.location();
+            return Some((path.to_string(), line, column));
+        }
+    }
+    let caps = FILE_REGEX.captures(lines.get(line)?.as_ref())?;
+    Some((caps[1].to_string(), None, None))
+}
+
+/// Whether a line contains `name` as a whole identifier (e.g. "analytics.orders" contains "orders")
+fn mentions_identifier(line: &str, name: &str) -> bool {
+    line.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .any(|token| token == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "\
+10:00:00  Running with dbt=1.7.4
+10:00:01  1 of 2 OK created sql view model analytics.stg_orders ... [CREATE VIEW in 0.10s]
+10:00:01  2 of 2 ERROR creating sql table model analytics.orders ... [ERROR in 0.20s]
+10:00:02  Database Error in model orders (models/orders.sql)
+  syntax error at or near \"form\"
+  LINE 3: select * form raw.orders
+  compiled Code at target/run/shop/models/orders.sql
+  select *
+  form raw.orders
+  where 1 = 1
+10:00:02  [WARNING]: Deprecated functionality
+10:00:02  Done. PASS=1 WARN=1 ERROR=1 SKIP=0 TOTAL=2";
+
+    #[test]
+    fn test_group_and_classify() {
+        let lines: Vec<&str> = OUTPUT.lines().collect();
+        let entries = group_entries(&lines);
+
+        assert_eq!(entries.len(), 6);
+        assert_eq!(entries[2].level, LogLevel::Error);
+        assert_eq!(entries[3].start, 3);
+        assert_eq!(entries[3].end, 10);
+        assert!(entries[3].is_foldable());
+        assert_eq!(entries[4].level, LogLevel::Warn);
+        // Summary counters are not errors
+        assert_eq!(entries[5].level, LogLevel::Info);
+        assert_eq!(
+            LogLevel::of_line("10:00:02  Completed with 0 errors and 1 warning:"),
+            LogLevel::Warn
+        );
+        assert_eq!(
+            LogLevel::of_line("10:00:02  Completed with 1 error and 0 warnings:"),
+            LogLevel::Error
+        );
+    }
+
+    #[test]
+    fn test_filter_and_fold() {
+        let lines: Vec<&str> = OUTPUT.lines().collect();
+        let entries = group_entries(&lines);
+
+        let rows = build_rows(&lines, &entries, &LogFilter::Errors, &HashSet::new());
+        assert_eq!(
+            rows,
+            vec![
+                LogRow::Line(2),
+                LogRow::Line(3),
+                LogRow::Folded { entry: 3, hidden: 6 },
+            ]
+        );
+
+        let expanded: HashSet<usize> = [3].into_iter().collect();
+        let rows = build_rows(&lines, &entries, &LogFilter::Errors, &expanded);
+        assert_eq!(rows.len(), 8);
+
+        let rows = build_rows(
+            &lines,
+            &entries,
+            &LogFilter::Model("stg_orders".to_string()),
+            &HashSet::new(),
+        );
+        assert_eq!(rows, vec![LogRow::Line(1)]);
+    }
+
+    #[test]
+    fn test_file_location() {
+        let lines: Vec<&str> = OUTPUT.lines().collect();
+        let entries = group_entries(&lines);
+
+        // A hit inside an error block opens where the database reported the error
+        assert_eq!(
+            file_location(&lines, &entries, 5),
+            Some(("target/run/shop/models/orders.sql".to_string(), Some(3), None))
+        );
+        assert_eq!(file_location(&lines, &entries, 1), None);
+    }
+
+    #[test]
+    fn test_find_matches() {
+        let lines: Vec<&str> = OUTPUT.lines().collect();
+        let entries = group_entries(&lines);
+
+        assert_eq!(find_matches(&lines, &entries, &LogFilter::All, "FORM"), vec![4, 5, 8]);
+        assert_eq!(find_matches(&lines, &entries, &LogFilter::Warnings, "form"), Vec::<usize>::new());
+        assert_eq!(entry_of_line(&entries, 8), Some(3));
+    }
+}
@@ -5,22 +5,40 @@
 //! - `UiState` - Presentation state (tabs, scroll, selections)
 //! - `ModalStack` - Modal overlay management
 
+pub mod audit;
+pub mod catalog;
+pub mod column_lineage;
+pub mod comparison;
+pub mod cruft;
+pub mod dag;
+pub mod diff;
 pub mod domain;
+pub mod estimate;
 pub mod history;
+pub mod impact;
 pub mod lineage;
+pub mod lint;
+pub mod log_view;
 pub mod modal;
 pub mod node;
 pub mod run;
+pub mod run_errors;
+pub mod run_results;
+pub mod run_summary;
 pub mod sample_data;
+pub mod test_matrix;
+pub mod timeline;
 pub mod ui;
+pub mod yaml_index;
 
 // Re-export commonly used types
 pub use domain::ProjectInfo;
 pub use history::RunHistoryEntry;
-pub use node::{Manifest, Node};
+pub use node::{Exposure, Macro, Manifest, Node, SourceTable};
 pub use run::{
     DbtCommand, ModelRun, ModelRunStatus, RunFlags, RunOutput, RunOutputViewMode,
     RunSelectMode, RunStatus,
 };
+pub use run_results::RunResults;
 pub use sample_data::SampleDataOutput;
 pub use ui::CodeViewMode;
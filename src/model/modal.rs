@@ -19,6 +19,40 @@ pub enum Modal {
         selected_index: usize,
         detail_scroll: usize,
     },
+    /// Comparison of two history entries
+    RunComparison,
+    /// Export run results dialog
+    Export,
+    /// Files related to the selected node
+    RelatedFiles,
+    /// Description editor for a node or column
+    DescriptionEditor,
+    /// Add or remove generic tests on a column
+    ColumnTests,
+    /// Offer to run a test that was just added
+    RunNewTest { test_name: String },
+    /// Schema YAML scaffold generated from warehouse columns
+    YamlScaffold,
+    /// New model wizard
+    NewModelWizard,
+    /// Rename/move a model, with a preview of every edit
+    Refactor,
+    /// Multi-level lineage explorer
+    LineageExplorer,
+    /// Full-screen DAG graph
+    DagView,
+    /// Export lineage to DOT, Mermaid or JSON
+    LineageExport,
+    /// Downstream impact of changing the selected node
+    Impact,
+    /// Project-wide documentation and test coverage audit
+    HealthAudit,
+    /// Modeling-convention violations
+    Lint,
+    /// Unused models, sources and macros
+    Cruft,
+    /// Tests of a model by column and test type
+    TestMatrix,
     /// Target selection dialog
     TargetSelector { selected_index: usize },
     /// Tag filter dialog
@@ -1,9 +1,21 @@
 //! Data models for dbt nodes (models, tests, seeds, etc.)
 
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// `ref('orders')` or `ref('shop', 'orders')` in a test's `model` argument; captures the name
+static REF_ARG_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\bref\(\s*(?:['"][^'"]+['"]\s*,\s*)?['"]([^'"]+)['"]"#).unwrap()
+});
+
+/// `source('raw', 'orders')` in a test's `model` argument
+static SOURCE_ARG_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\bsource\(\s*['"]([^'"]+)['"]\s*,\s*['"]([^'"]+)['"]"#).unwrap()
+});
 
 /// Column metadata from dbt manifest
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,7 +28,7 @@ pub struct ColumnInfo {
 }
 
 /// A dbt node (model, test, seed, snapshot)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Node {
     pub unique_id: String,
     pub name: String,
@@ -25,6 +37,9 @@ pub struct Node {
     pub package_name: String,
     #[serde(default)]
     pub schema: String,
+    /// Quoted database.schema.identifier, absent for ephemeral models
+    #[serde(default)]
+    pub relation_name: Option<String>,
     #[serde(default)]
     pub compiled_code: Option<String>,
     #[serde(default)]
@@ -39,10 +54,33 @@ pub struct Node {
     pub config: NodeConfig,
     #[serde(default)]
     pub compiled_path: Option<String>,
+    /// YAML file documenting the node, e.g. "shop://models/schema.yml"
+    #[serde(default)]
+    pub patch_path: Option<String>,
     #[serde(default)]
     pub description: Option<String>,
     #[serde(default)]
     pub columns: HashMap<String, ColumnInfo>,
+    /// Generic test name and package, for tests declared in YAML
+    #[serde(default)]
+    pub test_metadata: Option<TestMetadata>,
+    /// Column a generic test applies to
+    #[serde(default)]
+    pub column_name: Option<String>,
+    /// Node a generic test is attached to (dbt >= 1.5)
+    #[serde(default)]
+    pub attached_node: Option<String>,
+}
+
+/// Generic test metadata, e.g. name "accepted_values" or "expression_is_true" in namespace "dbt_utils"
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TestMetadata {
+    pub name: String,
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Arguments the test was declared with (`values`, `to`, `field`, ...)
+    #[serde(default)]
+    pub kwargs: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Node configuration from dbt_project.yml
@@ -52,6 +90,19 @@ pub struct NodeConfig {
     pub schema: Option<String>,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Materialization, e.g. "view", "table" or "incremental"
+    #[serde(default)]
+    pub materialized: Option<String>,
+    #[serde(default)]
+    pub meta: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub alias: Option<String>,
+    /// Tests: keep failing rows in a table in the audit schema
+    #[serde(default)]
+    pub store_failures: Option<bool>,
+    /// Tests: "table", "view" or "ephemeral" (dbt >= 1.7), overrides store_failures
+    #[serde(default)]
+    pub store_failures_as: Option<String>,
 }
 
 /// Dependency information for a node
@@ -59,12 +110,125 @@ pub struct NodeConfig {
 pub struct DependsOn {
     #[serde(default)]
     pub nodes: Vec<String>,
+    #[serde(default)]
+    pub macros: Vec<String>,
+}
+
+/// A source table, from the manifest's sources map
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SourceTable {
+    pub unique_id: String,
+    /// Table name
+    pub name: String,
+    pub source_name: String,
+    /// Table in the warehouse, when it differs from the name
+    #[serde(default)]
+    pub identifier: Option<String>,
+    /// Quoted database.schema.identifier
+    #[serde(default)]
+    pub relation_name: Option<String>,
+    /// YAML file declaring the source, relative to the project root
+    #[serde(default)]
+    pub original_file_path: Option<String>,
+    #[serde(default)]
+    pub columns: HashMap<String, ColumnInfo>,
+    #[serde(default)]
+    pub freshness: Option<SourceFreshness>,
+}
+
+/// Freshness thresholds of a source table
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SourceFreshness {
+    #[serde(default)]
+    pub warn_after: Option<FreshnessThreshold>,
+    #[serde(default)]
+    pub error_after: Option<FreshnessThreshold>,
+}
+
+/// e.g. 12 hours; dbt writes both as null when no threshold is set
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FreshnessThreshold {
+    #[serde(default)]
+    pub count: Option<u64>,
+}
+
+impl SourceTable {
+    /// Whether a warn or error freshness threshold is configured
+    pub fn has_freshness(&self) -> bool {
+        self.freshness.as_ref().is_some_and(|f| {
+            [&f.warn_after, &f.error_after]
+                .into_iter()
+                .flatten()
+                .any(|t| t.count.is_some())
+        })
+    }
+}
+
+/// An exposure (dashboard, notebook, application) consuming project nodes
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Exposure {
+    pub unique_id: String,
+    pub name: String,
+    /// YAML file declaring the exposure, relative to the project root
+    #[serde(default)]
+    pub original_file_path: Option<String>,
+    #[serde(default)]
+    pub depends_on: DependsOn,
+    #[serde(default)]
+    pub owner: ExposureOwner,
+}
+
+/// Owner of an exposure
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExposureOwner {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+/// A macro from the manifest's macros map
+#[derive(Debug, Clone, Deserialize)]
+pub struct Macro {
+    pub unique_id: String,
+    pub name: String,
+    pub package_name: String,
+    #[serde(default)]
+    pub original_file_path: Option<String>,
+    #[serde(default)]
+    pub depends_on: DependsOn,
+    /// Nodes and macros calling this macro, counted when the manifest is loaded
+    #[serde(skip)]
+    pub callers: usize,
+}
+
+/// Manifest metadata
+#[derive(Debug, Default, Deserialize)]
+pub struct ManifestMetadata {
+    /// Root project name (dbt >= 1.6)
+    #[serde(default)]
+    pub project_name: Option<String>,
 }
 
 /// The dbt manifest.json structure
 #[derive(Debug, Deserialize)]
 pub struct Manifest {
+    #[serde(default)]
+    pub metadata: ManifestMetadata,
     pub nodes: HashMap<String, Node>,
+    #[serde(default)]
+    pub sources: HashMap<String, SourceTable>,
+    #[serde(default)]
+    pub exposures: HashMap<String, Exposure>,
+    #[serde(default)]
+    pub macros: HashMap<String, Macro>,
+}
+
+impl Exposure {
+    /// Owner name, else email
+    pub fn owner_label(&self) -> Option<&str> {
+        self.owner.name.as_deref().or(self.owner.email.as_deref())
+    }
 }
 
 impl Node {
@@ -85,6 +249,11 @@ impl Node {
         self.name.clone()
     }
 
+    /// Owner from `meta.owner`, if set to a string
+    pub fn owner(&self) -> Option<&str> {
+        self.config.meta.get("owner").and_then(|v| v.as_str())
+    }
+
     /// Get the schema to use for grouping (prefers config.schema over schema)
     pub fn group_schema(&self) -> String {
         self.config
@@ -127,6 +296,114 @@ impl Node {
         self.raw_code.clone()
     }
 
+    /// Schema YAML file relative to the project root (patch_path without the package prefix)
+    pub fn schema_file_path(&self) -> Option<&str> {
+        let patch_path = self.patch_path.as_deref()?;
+        Some(
+            patch_path
+                .split_once("://")
+                .map(|(_, path)| path)
+                .unwrap_or(patch_path),
+        )
+    }
+
+    /// Files related to the node, relative to the project root: source, schema YAML,
+    /// compiled SQL and run SQL. Files may not exist (e.g. before a compile).
+    pub fn related_files(&self) -> Vec<(&'static str, String)> {
+        let mut files = Vec::new();
+        if let Some(ref original_path) = self.original_file_path {
+            files.push(("Source", original_path.clone()));
+        }
+        if let Some(schema_path) = self.schema_file_path() {
+            files.push(("Schema YAML", schema_path.to_string()));
+        }
+        if let Some(ref original_path) = self.original_file_path {
+            if original_path.ends_with(".sql") {
+                for (label, dir) in [("Compiled SQL", "compiled"), ("Run SQL", "run")] {
+                    files.push((
+                        label,
+                        format!("target/{}/{}/{}", dir, self.package_name, original_path),
+                    ));
+                }
+            }
+        }
+        files
+    }
+
+    /// Unique id of the node a test checks: its attached node, else (manifests
+    /// before dbt 1.5) the dependency its `model` argument refers to. A
+    /// relationships test also depends on its `to`, so the first dependency
+    /// isn't necessarily the tested one.
+    pub fn tested_node_id(&self) -> Option<&str> {
+        if let Some(ref attached) = self.attached_node {
+            return Some(attached);
+        }
+        // e.g. "{{ get_where_subquery(ref('orders')) }}"
+        let model = self.test_metadata.as_ref()?.kwargs.get("model")?.as_str()?;
+        let tested = if let Some(refs) = REF_ARG_REGEX.captures(model) {
+            let name = format!(".{}", &refs[1]);
+            self.depends_on
+                .nodes
+                .iter()
+                .find(|id| !id.starts_with("source.") && id.ends_with(&name))
+        } else {
+            let source = SOURCE_ARG_REGEX.captures(model)?;
+            let table = format!(".{}.{}", &source[1], &source[2]);
+            self.depends_on
+                .nodes
+                .iter()
+                .find(|id| id.starts_with("source.") && id.ends_with(&table))
+        };
+        tested.map(String::as_str)
+    }
+
+    /// Name of the resource a test checks: "orders" for a model, "raw.orders" for a source table
+    pub fn tested_resource(&self) -> Option<String> {
+        let unique_id = self.tested_node_id()?;
+        let parts: Vec<&str> = unique_id.split('.').collect();
+        match parts.as_slice() {
+            ["source", _, source, table] => Some(format!("{}.{}", source, table)),
+            [.., name] => Some(name.to_string()),
+            [] => None,
+        }
+    }
+
+    /// "schema.alias" the node is built as, which is how dbt's log names it
+    pub fn log_name(&self) -> String {
+        format!("{}.{}", self.schema, self.config.alias.as_deref().unwrap_or(&self.name))
+    }
+
+    /// Whether a test keeps its failing rows in the warehouse
+    pub fn stores_failures(&self) -> bool {
+        match self.config.store_failures_as.as_deref() {
+            Some(kind) => kind != "ephemeral",
+            None => self.config.store_failures == Some(true),
+        }
+    }
+
+    /// Query returning a test's failing rows: its failures relation when it stores
+    /// failures, otherwise its compiled SQL (from the manifest, or the compiled file)
+    pub fn failing_rows_query(&self, project_path: &Path) -> Result<String, String> {
+        if self.stores_failures() {
+            let relation = match self.relation_name {
+                Some(ref relation) => relation.clone(),
+                None => self.log_name(),
+            };
+            return Ok(format!("select * from {}", relation));
+        }
+        let compiled = match self.compiled_code {
+            Some(ref code) => Some(code.clone()),
+            None => self
+                .compiled_path
+                .as_ref()
+                .and_then(|path| fs::read_to_string(project_path.join(path)).ok()),
+        };
+        let sql = compiled
+            .filter(|sql| !sql.trim().is_empty())
+            .ok_or_else(|| format!("{} hasn't been compiled yet; run dbt compile first", self.name))?;
+        Ok(sql.trim().trim_end_matches(';').trim_end().to_string())
+    }
+
     /// Get the full path to the seed CSV file
     pub fn get_seed_path(&self) -> Option<PathBuf> {
         if self.resource_type != "seed" {
@@ -170,181 +447,33 @@ impl Node {
 
         Ok((headers, rows))
     }
+}
 
-    /// Get the YAML test definition for schema tests
-    /// Returns the relevant YAML snippet showing the test configuration
-    pub fn get_test_yaml_definition(&self, model_name: &str) -> Option<String> {
-        if self.resource_type != "test" {
-            return None;
-        }
-
-        // Get the schema file path from original_file_path
-        let (root, original_path) = match (&self.root_path, &self.original_file_path) {
-            (Some(r), Some(p)) => (r, p),
-            _ => return None,
-        };
-
-        // Check if this is a YAML-defined test (schema test)
-        if !original_path.ends_with(".yml") && !original_path.ends_with(".yaml") {
-            return None;
-        }
-
-        let mut path = PathBuf::from(root);
-        path.push(original_path);
-
-        if !path.exists() {
-            return None;
-        }
-
-        let content = fs::read_to_string(&path).ok()?;
-
-        // Extract test type from the test name (e.g., "not_null" from "not_null_customers_id")
-        let test_type = self.extract_test_type_from_name();
-
-        // Try to find the model section and the specific test
-        Self::extract_yaml_test_section(&content, model_name, &test_type, &self.name)
-    }
-
-    /// Extract test type from test name
-    fn extract_test_type_from_name(&self) -> String {
-        let known_types = [
-            "not_null",
-            "unique",
-            "accepted_values",
-            "relationships",
-            "dbt_expectations",
-            "dbt_utils",
-        ];
-
-        for test_type in known_types {
-            if self.name.starts_with(test_type) {
-                return test_type.to_string();
-            }
-        }
-
-        // Fallback: first word before underscore
-        self.name.split('_').next().unwrap_or("").to_string()
-    }
-
-    /// Extract the relevant YAML section for a test
-    /// Only extracts the specific column definition that contains the test
-    fn extract_yaml_test_section(
-        yaml_content: &str,
-        model_name: &str,
-        test_type: &str,
-        full_test_name: &str,
-    ) -> Option<String> {
-        // Try to extract column name from test name
-        // Pattern: {test_type}_{model_name}_{column_name} or {test_type}_{column_name}
-        let column_name = Self::extract_column_name_from_test(full_test_name, test_type, model_name);
-
-        let lines: Vec<&str> = yaml_content.lines().collect();
-        let mut in_model_section = false;
-        let mut in_target_column = false;
-        let mut model_indent = 0;
-        let mut column_indent = 0;
-        let mut column_start = 0;
-        let mut result = Vec::new();
-
-        for (i, line) in lines.iter().enumerate() {
-            let trimmed = line.trim();
-            let current_indent = line.len() - line.trim_start().len();
-
-            // Look for model name definition
-            if trimmed.starts_with("- name:") {
-                let name_value = trimmed.trim_start_matches("- name:").trim();
-                if name_value == model_name {
-                    in_model_section = true;
-                    model_indent = current_indent;
-                    continue;
-                } else if in_model_section && current_indent <= model_indent {
-                    // Exited model section
-                    break;
-                }
-            }
-
-            if in_model_section {
-                // Look for column definition
-                if trimmed.starts_with("- name:") && current_indent > model_indent {
-                    let col_name = trimmed.trim_start_matches("- name:").trim();
-
-                    // If we were in a target column, we're done
-                    if in_target_column {
-                        break;
-                    }
-
-                    // Check if this is the column we're looking for
-                    if let Some(ref target_col) = column_name {
-                        if col_name == target_col {
-                            in_target_column = true;
-                            column_indent = current_indent;
-                            column_start = i;
-                            result.push(line.to_string());
-                            continue;
-                        }
-                    }
-                }
-
-                // Collect lines for the target column
-                if in_target_column {
-                    // Check if we've exited the column section
-                    if !trimmed.is_empty() && current_indent <= column_indent && i > column_start {
-                        break;
-                    }
-                    result.push(line.to_string());
-                }
-            }
-        }
-
-        // If we didn't find a specific column, try to find just the test line
-        if result.is_empty() {
-            for (i, line) in lines.iter().enumerate() {
-                let trimmed = line.trim();
-                if trimmed == format!("- {}", test_type)
-                    || trimmed.starts_with(&format!("- {}:", test_type))
-                {
-                    // Include context: a few lines before and after
-                    let start = i.saturating_sub(5);
-                    let end = (i + 5).min(lines.len());
-                    for line in lines.iter().take(end).skip(start) {
-                        result.push(line.to_string());
-                    }
-                    break;
-                }
-            }
-        }
-
-        if !result.is_empty() {
-            Some(result.join("\n"))
-        } else {
-            None
+#[cfg(test)]
+impl Node {
+    /// Test fixture: `<resource_type>.shop.<name>` in schema "public",
+    /// depending on the given unique_ids
+    pub fn fixture(name: &str, resource_type: &str, depends_on: &[&str]) -> Self {
+        Self {
+            unique_id: format!("{}.shop.{}", resource_type, name),
+            name: name.to_string(),
+            resource_type: resource_type.to_string(),
+            package_name: "shop".to_string(),
+            schema: "public".to_string(),
+            depends_on: DependsOn {
+                nodes: depends_on.iter().map(|d| d.to_string()).collect(),
+                macros: Vec::new(),
+            },
+            ..Self::default()
         }
     }
 
-    /// Extract column name from test name
-    /// e.g., "not_null_customers_customer_id" -> Some("customer_id")
-    fn extract_column_name_from_test(
-        test_name: &str,
-        test_type: &str,
-        model_name: &str,
-    ) -> Option<String> {
-        // Remove test type prefix
-        let without_type = test_name.strip_prefix(test_type)?.trim_start_matches('_');
-
-        // Remove model name if present
-        let without_model = if without_type.starts_with(model_name) {
-            without_type
-                .strip_prefix(model_name)?
-                .trim_start_matches('_')
-        } else {
-            without_type
-        };
-
-        if without_model.is_empty() {
-            None
-        } else {
-            Some(without_model.to_string())
-        }
+    /// Test fixture like `fixture`, depending on the named models of package "shop"
+    pub fn fixture_on_models(name: &str, resource_type: &str, parent_models: &[&str]) -> Self {
+        let depends_on: Vec<String> =
+            parent_models.iter().map(|m| format!("model.shop.{}", m)).collect();
+        let depends_on: Vec<&str> = depends_on.iter().map(String::as_str).collect();
+        Self::fixture(name, resource_type, &depends_on)
     }
 }
 
@@ -369,55 +498,63 @@ mod tests {
             compiled_path: None,
             description: None,
             columns: HashMap::new(),
+            ..Default::default()
         };
 
         assert_eq!(node.display_name(), "customers");
     }
 
     #[test]
-    fn test_extract_column_name_from_test() {
-        // Test with model name in test name
+    fn test_related_files() {
+        let node = Node {
+            package_name: "analytics".to_string(),
+            original_file_path: Some("models/customers.sql".to_string()),
+            patch_path: Some("analytics://models/schema.yml".to_string()),
+            ..Node::fixture("customers", "model", &[])
+        };
+
+        assert_eq!(node.schema_file_path(), Some("models/schema.yml"));
         assert_eq!(
-            Node::extract_column_name_from_test("not_null_customers_customer_id", "not_null", "customers"),
-            Some("customer_id".to_string())
+            node.related_files(),
+            vec![
+                ("Source", "models/customers.sql".to_string()),
+                ("Schema YAML", "models/schema.yml".to_string()),
+                ("Compiled SQL", "target/compiled/analytics/models/customers.sql".to_string()),
+                ("Run SQL", "target/run/analytics/models/customers.sql".to_string()),
+            ]
         );
+    }
+
+    #[test]
+    fn test_failing_rows_query() {
+        let mut test = Node {
+            unique_id: "test.analytics.unique_customers_id".to_string(),
+            package_name: "analytics".to_string(),
+            schema: "analytics_dbt_test__audit".to_string(),
+            compiled_code: Some("select id from customers group by id having count(*) > 1;\n".to_string()),
+            original_file_path: Some("models/schema.yml".to_string()),
+            ..Node::fixture("unique_customers_id", "test", &[])
+        };
+        let project = Path::new("/nonexistent");
 
-        // Test with unique test
         assert_eq!(
-            Node::extract_column_name_from_test("unique_orders_order_id", "unique", "orders"),
-            Some("order_id".to_string())
+            test.failing_rows_query(project).unwrap(),
+            "select id from customers group by id having count(*) > 1"
         );
 
-        // Test without model name prefix
+        test.config.store_failures = Some(true);
         assert_eq!(
-            Node::extract_column_name_from_test("not_null_email", "not_null", "users"),
-            Some("email".to_string())
+            test.failing_rows_query(project).unwrap(),
+            "select * from analytics_dbt_test__audit.unique_customers_id"
+        );
+        test.relation_name = Some("\"warehouse\".\"analytics_dbt_test__audit\".\"unique_customers_id\"".to_string());
+        assert_eq!(
+            test.failing_rows_query(project).unwrap(),
+            "select * from \"warehouse\".\"analytics_dbt_test__audit\".\"unique_customers_id\""
         );
-    }
 
-    #[test]
-    fn test_extract_yaml_test_section() {
-        let yaml = r#"
-version: 2
-
-models:
-  - name: customers
-    description: Customer table
-    columns:
-      - name: customer_id
-        description: Primary key
-        tests:
-          - unique
-          - not_null
-      - name: email
-        tests:
-          - not_null
-"#;
-
-        let result = Node::extract_yaml_test_section(yaml, "customers", "not_null", "not_null_customers_customer_id");
-        assert!(result.is_some());
-        let content = result.unwrap();
-        assert!(content.contains("customer_id"));
-        assert!(content.contains("not_null"));
+        test.config.store_failures_as = Some("ephemeral".to_string());
+        test.compiled_code = None;
+        assert!(test.failing_rows_query(project).is_err());
     }
 }
@@ -1,10 +1,20 @@
 //! Data models for dbt run execution and output
 
+use chrono::{DateTime, Local};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::sync::mpsc::Receiver;
+use std::sync::LazyLock;
+use std::time::Instant;
 
 use crate::model::node::Node;
+use crate::model::run_results::{NodeResult, RunResults};
+use crate::model::timeline::RunTimeline;
+
+/// Leading log timestamp and the space after it, e.g. "22:09:24  " or "22:09:24.123456 "
+pub static TIMESTAMP_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\d{2}:\d{2}:\d{2}(\.\d+)?(\s+|$)").unwrap());
 
 /// Status of a dbt run
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
@@ -144,6 +154,90 @@ impl RunSelectMode {
         }
     }
 
+    /// Models selected from `roots` ("schema.alias", as the log names them), each with its layer:
+    /// the longest chain of selected models it depends on
+    pub fn select_models(&self, roots: &[&Node], all_nodes: &[Node]) -> Vec<(String, usize)> {
+        let by_id: HashMap<&str, &Node> = all_nodes.iter().map(|n| (n.unique_id.as_str(), n)).collect();
+        let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+        for node in all_nodes {
+            for parent in &node.depends_on.nodes {
+                children.entry(parent.as_str()).or_default().push(&node.unique_id);
+            }
+        }
+        let (upstream, downstream) = match self {
+            RunSelectMode::Single => (false, false),
+            RunSelectMode::Downstream => (false, true),
+            RunSelectMode::Upstream => (true, false),
+            RunSelectMode::UpstreamAndDownstream => (true, true),
+        };
+
+        let mut selected: HashSet<&str> = HashSet::new();
+        for root in roots {
+            selected.insert(&root.unique_id);
+            for (follow, up) in [(upstream, true), (downstream, false)] {
+                if !follow {
+                    continue;
+                }
+                let mut stack = vec![root.unique_id.as_str()];
+                while let Some(id) = stack.pop() {
+                    let next: Vec<&str> = if up {
+                        by_id
+                            .get(id)
+                            .map(|n| n.depends_on.nodes.iter().map(String::as_str).collect())
+                            .unwrap_or_default()
+                    } else {
+                        children.get(id).cloned().unwrap_or_default()
+                    };
+                    for id in next {
+                        if selected.insert(id) {
+                            stack.push(id);
+                        }
+                    }
+                }
+            }
+        }
+
+        let models: Vec<&Node> = selected
+            .iter()
+            .filter_map(|id| by_id.get(id).copied())
+            .filter(|n| n.resource_type == "model")
+            .collect();
+        let mut layers: HashMap<&str, usize> = HashMap::new();
+        // Each pass settles models whose selected parents all have a layer
+        while layers.len() < models.len() {
+            let before = layers.len();
+            for model in &models {
+                if layers.contains_key(model.unique_id.as_str()) {
+                    continue;
+                }
+                let parents: Option<Vec<usize>> = model
+                    .depends_on
+                    .nodes
+                    .iter()
+                    .filter(|p| models.iter().any(|m| &m.unique_id == *p))
+                    .map(|p| layers.get(p.as_str()).copied())
+                    .collect();
+                if let Some(parents) = parents {
+                    let layer = parents.iter().map(|l| l + 1).max().unwrap_or(0);
+                    layers.insert(&model.unique_id, layer);
+                }
+            }
+            if layers.len() == before {
+                break;
+            }
+        }
+
+        let mut result: Vec<(String, usize)> = models
+            .iter()
+            .map(|m| {
+                let layer = layers.get(m.unique_id.as_str()).copied().unwrap_or(0);
+                (m.log_name(), layer)
+            })
+            .collect();
+        result.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+        result
+    }
+
     pub fn shortcut(&self) -> char {
         match self {
             RunSelectMode::Single => '1',
@@ -161,6 +255,8 @@ pub enum RunOutputViewMode {
     Raw,
     /// Graphical view with model boxes
     Graphical,
+    /// Gantt chart of node execution per thread
+    Timeline,
 }
 
 /// Status of an individual model run
@@ -183,6 +279,8 @@ pub struct ModelRun {
     pub step: Option<String>,
     pub upstream_deps: Vec<String>,
     pub layer: usize,
+    /// When the START line was seen
+    pub started: Option<Instant>,
 }
 
 /// Output from a dbt run command
@@ -193,9 +291,31 @@ pub struct RunOutput {
     pub output: String,
     pub view_mode: RunOutputViewMode,
     pub model_runs: Vec<ModelRun>,
+    /// When the command was started (None for outputs not started by this app)
+    pub started_at: Option<DateTime<Local>>,
+    /// Wall-clock duration in seconds, recorded when the command finishes
+    pub duration_secs: Option<f64>,
+    /// dbt invocation id from run_results.json
+    pub invocation_id: Option<String>,
+    /// Per-node results from run_results.json
+    pub node_results: Vec<NodeResult>,
+    /// Timeline built from node_results
+    pub timeline: Option<RunTimeline>,
+    /// Total number of steps ("M" in "N of M"), once known
+    pub total_steps: Option<usize>,
+    /// Number of steps that have reported a result (OK, PASS, ERROR, SKIP, ...)
+    pub completed_steps: usize,
+    /// Models the command selects ("schema.name") with their dependency layer
+    /// within the selection; empty when the selection isn't known
+    pub selected_models: Vec<(String, usize)>,
 }
 
 impl RunOutput {
+    /// dbt subcommand (run, test, compile, ...) of the command
+    pub fn subcommand(&self) -> Option<&str> {
+        dbt_subcommand(&self.command)
+    }
+
     pub fn new(command: String) -> Self {
         Self {
             command,
@@ -203,6 +323,14 @@ impl RunOutput {
             output: String::new(),
             view_mode: RunOutputViewMode::Graphical,
             model_runs: Vec::new(),
+            started_at: None,
+            duration_secs: None,
+            invocation_id: None,
+            node_results: Vec::new(),
+            timeline: None,
+            total_steps: None,
+            completed_steps: 0,
+            selected_models: Vec::new(),
         }
     }
 
@@ -210,11 +338,38 @@ impl RunOutput {
         self.view_mode = match self.view_mode {
             RunOutputViewMode::Raw => RunOutputViewMode::Graphical,
             RunOutputViewMode::Graphical => RunOutputViewMode::Raw,
+            RunOutputViewMode::Timeline => RunOutputViewMode::Graphical,
         };
     }
 
+    pub fn toggle_timeline_view(&mut self) {
+        self.view_mode = match self.view_mode {
+            RunOutputViewMode::Timeline => RunOutputViewMode::Graphical,
+            _ => RunOutputViewMode::Timeline,
+        };
+    }
+
+    /// Attach run_results.json data to this output and build the timeline
+    pub fn apply_run_results(&mut self, results: RunResults, all_nodes: &[Node]) {
+        self.invocation_id = results.invocation_id;
+        self.set_node_results(results.results, all_nodes);
+    }
+
+    /// Replace node results (e.g. when reopening a history entry) and rebuild the timeline
+    pub fn set_node_results(&mut self, node_results: Vec<NodeResult>, all_nodes: &[Node]) {
+        self.timeline = RunTimeline::build(&node_results, all_nodes);
+        self.node_results = node_results;
+    }
+
     /// Parse a line of output and update model runs
     pub fn parse_output_line(&mut self, line: &str) {
+        if let Some((_, total, keyword)) = Self::extract_progress(line) {
+            self.total_steps = Some(total);
+            if keyword != "START" {
+                self.completed_steps = (self.completed_steps + 1).min(total);
+            }
+        }
+
         if line.contains(" START ") && line.contains(" model ") {
             if let Some(model_run) = Self::parse_start_line(line) {
                 if !self.model_runs.iter().any(|m| m.name == model_run.name) {
@@ -242,6 +397,7 @@ impl RunOutput {
             step,
             upstream_deps: Vec::new(),
             layer: 0,
+            started: Some(Instant::now()),
         })
     }
 
@@ -263,6 +419,7 @@ impl RunOutput {
                     step: Self::extract_step(line),
                     upstream_deps: Vec::new(),
                     layer: 0,
+                    started: None,
                 });
                 model_runs.last_mut().unwrap()
             }
@@ -305,6 +462,19 @@ impl RunOutput {
         None
     }
 
+    /// Extract (N, M, keyword) from a step line like "1 of 3 OK created ..."
+    fn extract_progress(line: &str) -> Option<(usize, usize, &str)> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        for i in 0..parts.len().saturating_sub(3) {
+            if parts[i + 1] == "of" {
+                if let (Ok(step), Ok(total)) = (parts[i].parse(), parts[i + 2].parse()) {
+                    return Some((step, total, parts[i + 3]));
+                }
+            }
+        }
+        None
+    }
+
     fn extract_model_type(line: &str) -> String {
         if line.contains(" view ") {
             "view".to_string()
@@ -477,11 +647,17 @@ pub struct BackgroundJob {
     pub start_instant: std::time::Instant,
 }
 
+/// Subcommand of a dbt command line: the token after the dbt binary
+pub fn dbt_subcommand(command: &str) -> Option<&str> {
+    let mut tokens = command.split_whitespace();
+    tokens.find(|t| t.ends_with("dbt"))?;
+    tokens.next()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::model::node::{DependsOn, NodeConfig};
-    use std::collections::HashMap;
 
     /// Helper to create a test Node
     fn create_test_node(name: &str, schema: &str, depends_on: Vec<String>) -> Node {
@@ -493,14 +669,27 @@ mod tests {
             schema: schema.to_string(),
             compiled_code: None,
             raw_code: None,
-            depends_on: DependsOn { nodes: depends_on },
+            depends_on: DependsOn {
+                nodes: depends_on,
+                macros: Vec::new(),
+            },
             root_path: None,
             original_file_path: None,
             config: NodeConfig::default(),
             compiled_path: None,
             description: None,
             columns: HashMap::new(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_timestamp_regex() {
+        for line in ["22:09:24  Running with dbt", "22:09:24.123456 Running with dbt", "22:09:24"] {
+            assert!(TIMESTAMP_REGEX.is_match(line), "{}", line);
         }
+        assert_eq!(TIMESTAMP_REGEX.replace("22:09:24    Database Error", ""), "Database Error");
+        assert!(!TIMESTAMP_REGEX.is_match("22:09:245 rows"));
     }
 
     #[test]
@@ -517,6 +706,26 @@ mod tests {
         assert_eq!(model.status, ModelRunStatus::Running);
     }
 
+    #[test]
+    fn test_step_progress_tracking() {
+        let mut run_output = RunOutput::new("dbt build".to_string());
+        assert_eq!(run_output.total_steps, None);
+
+        run_output.parse_output_line(
+            "22:09:24  1 of 3 START sql view model analytics.stg_customers ... [RUN]",
+        );
+        assert_eq!(run_output.total_steps, Some(3));
+        assert_eq!(run_output.completed_steps, 0);
+
+        run_output.parse_output_line(
+            "22:09:25  1 of 3 OK created sql view model analytics.stg_customers ... [CREATE VIEW in 0.10s]",
+        );
+        run_output.parse_output_line(
+            "22:09:26  2 of 3 PASS not_null_stg_customers_id ... [PASS in 0.05s]",
+        );
+        assert_eq!(run_output.completed_steps, 2);
+    }
+
     #[test]
     fn test_run_output_view_mode_toggle() {
         let mut run_output = RunOutput::new("dbt run".to_string());
@@ -529,6 +738,23 @@ mod tests {
         assert_eq!(run_output.view_mode, RunOutputViewMode::Graphical);
     }
 
+    #[test]
+    fn test_toggle_timeline_view() {
+        let mut run_output = RunOutput::new("dbt run".to_string());
+
+        run_output.toggle_timeline_view();
+        assert_eq!(run_output.view_mode, RunOutputViewMode::Timeline);
+
+        run_output.toggle_view_mode();
+        assert_eq!(run_output.view_mode, RunOutputViewMode::Graphical);
+
+        run_output.view_mode = RunOutputViewMode::Raw;
+        run_output.toggle_timeline_view();
+        assert_eq!(run_output.view_mode, RunOutputViewMode::Timeline);
+        run_output.toggle_timeline_view();
+        assert_eq!(run_output.view_mode, RunOutputViewMode::Graphical);
+    }
+
     #[test]
     fn test_compute_layers_single_model_no_deps() {
         let mut run_output = RunOutput::new("dbt run".to_string());
@@ -660,6 +886,49 @@ mod tests {
         assert_eq!(int.upstream_deps.len(), 2);
     }
 
+    #[test]
+    fn test_select_models() {
+        // stg_a -> int -> fct, stg_b -> fct, plus a test on int
+        let nodes = vec![
+            create_test_node("stg_a", "staging", vec![]),
+            create_test_node("stg_b", "staging", vec![]),
+            create_test_node("int", "analytics", vec!["model.test_project.stg_a".to_string()]),
+            create_test_node(
+                "fct",
+                "analytics",
+                vec![
+                    "model.test_project.int".to_string(),
+                    "model.test_project.stg_b".to_string(),
+                ],
+            ),
+            Node {
+                depends_on: DependsOn {
+                    nodes: vec!["model.test_project.int".to_string()],
+                    macros: Vec::new(),
+                },
+                ..Node::fixture("not_null_int_id", "test", &[])
+            },
+        ];
+        let int = &nodes[2];
+
+        assert_eq!(
+            RunSelectMode::Single.select_models(&[int], &nodes),
+            vec![("analytics.int".to_string(), 0)]
+        );
+        assert_eq!(
+            RunSelectMode::Downstream.select_models(&[int], &nodes),
+            vec![("analytics.int".to_string(), 0), ("analytics.fct".to_string(), 1)]
+        );
+        assert_eq!(
+            RunSelectMode::UpstreamAndDownstream.select_models(&[int], &nodes),
+            vec![
+                ("staging.stg_a".to_string(), 0),
+                ("analytics.int".to_string(), 1),
+                ("analytics.fct".to_string(), 2),
+            ]
+        );
+    }
+
     #[test]
     fn test_compute_layers_partial_run() {
         // Full graph: stg_a -> int_a -> fct_a
@@ -0,0 +1,221 @@
+//! Structured errors extracted from dbt output
+//!
+//! dbt reports failures as blocks like:
+//!
+//! ```text
+//!   Database Error in model orders (models/marts/orders.sql)
+//!   syntax error at or near "form"
+//!   LINE 3: select * form raw.orders
+//!   compiled Code at target/run/shop/models/marts/orders.sql
+//! ```
+//!
+//! Line numbers reported by the database refer to the compiled SQL; line
+//! numbers in Jinja compilation errors refer to the source file.
+
+use super::run::TIMESTAMP_REGEX;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// "Database Error in model orders (models/marts/orders.sql)"
+static HEADER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*((?:Database|Compilation|Runtime|Parsing|Dependency) Error|Failure|Warning) in (\w+) (\S+) \(([^)]+)\)",
+    )
+    .unwrap()
+});
+
+/// "compiled Code at target/run/..." (dbt >= 1.5) or "compiled SQL at ..."
+static COMPILED_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"compiled (?:Code|SQL) at (\S+)").unwrap());
+
+/// Database error position, e.g. "LINE 3: select * form raw.orders"
+static DB_LINE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^LINE (\d+):").unwrap());
+
+/// Jinja/YAML error position, e.g. "line 14" or "(line 14)"
+static SOURCE_LINE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bline (\d+)\b").unwrap());
+
+/// Maximum number of message lines collected per error
+const MAX_MESSAGE_LINES: usize = 30;
+
+/// A failure reported by dbt
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunError {
+    /// "Database Error", "Compilation Error", "Failure", ...
+    pub kind: String,
+    /// model, test, seed, snapshot, ...
+    pub resource_type: String,
+    pub node: String,
+    /// Source file relative to the project root
+    pub file: String,
+    /// Line in the source file (compilation errors)
+    pub source_line: Option<usize>,
+    /// Compiled SQL file relative to the project root
+    pub compiled_path: Option<String>,
+    /// Line in the compiled SQL (database errors)
+    pub compiled_line: Option<usize>,
+    /// Column in the compiled SQL, from the "^" marker under the LINE excerpt
+    pub compiled_column: Option<usize>,
+    pub message: Vec<String>,
+}
+
+impl RunError {
+    /// Best location to open (path, line, column): the compiled SQL when the
+    /// database reported a line in it, otherwise the source file
+    pub fn location(&self) -> (&str, Option<usize>, Option<usize>) {
+        match (&self.compiled_path, self.compiled_line) {
+            (Some(path), Some(line)) => (path, Some(line), self.compiled_column),
+            _ => (&self.file, self.source_line, None),
+        }
+    }
+
+    /// First line of the message
+    pub fn summary(&self) -> &str {
+        self.message.first().map(|s| s.as_str()).unwrap_or("")
+    }
+}
+
+/// Extract error blocks from dbt output, skipping repeats of the same node and kind
+pub fn parse_run_errors(output: &str) -> Vec<RunError> {
+    let lines: Vec<&str> = output.lines().collect();
+    let mut errors: Vec<RunError> = Vec::new();
+
+    let mut i = 0;
+    while i < lines.len() {
+        let has_timestamp = TIMESTAMP_REGEX.is_match(lines[i]);
+        let line = TIMESTAMP_REGEX.replace(lines[i], "");
+        let Some(caps) = HEADER_REGEX.captures(&line) else {
+            i += 1;
+            continue;
+        };
+        if &caps[1] == "Warning" {
+            i += 1;
+            continue;
+        }
+
+        let mut error = RunError {
+            kind: caps[1].to_string(),
+            resource_type: caps[2].to_string(),
+            node: caps[3].to_string(),
+            file: caps[4].to_string(),
+            source_line: None,
+            compiled_path: None,
+            compiled_line: None,
+            compiled_column: None,
+            message: Vec::new(),
+        };
+        // End of the "LINE n: " prefix in the last excerpt, for locating the caret
+        let mut excerpt_offset: Option<usize> = None;
+
+        // Continuation lines: until the next timestamped line, or the next
+        // unindented line when the output has no timestamps
+        i += 1;
+        while i < lines.len() {
+            let raw = lines[i];
+            let ends_block = if has_timestamp {
+                TIMESTAMP_REGEX.is_match(raw)
+            } else {
+                !raw.is_empty() && !raw.starts_with(char::is_whitespace)
+            };
+            if ends_block || HEADER_REGEX.is_match(raw) {
+                break;
+            }
+            i += 1;
+
+            let text = raw.trim();
+            if let Some(caps) = COMPILED_REGEX.captures(text) {
+                error.compiled_path = Some(caps[1].to_string());
+                continue;
+            }
+            if let Some(caps) = DB_LINE_REGEX.captures(text) {
+                error.compiled_line = error.compiled_line.or(caps[1].parse().ok());
+                excerpt_offset = raw.find("LINE ").map(|pos| pos + caps[0].len() + 1);
+            } else if let (Some(offset), true) = (excerpt_offset.take(), text == "^") {
+                let caret = raw.find('^').unwrap_or(0);
+                error.compiled_column = error
+                    .compiled_column
+                    .or(Some(caret.saturating_sub(offset) + 1));
+            } else if let Some(caps) = SOURCE_LINE_REGEX.captures(text) {
+                error.source_line = error.source_line.or(caps[1].parse().ok());
+            }
+            if !text.is_empty() && error.message.len() < MAX_MESSAGE_LINES {
+                error.message.push(text.to_string());
+            }
+        }
+
+        let is_repeat = errors
+            .iter()
+            .any(|e| e.node == error.node && e.kind == error.kind);
+        if !is_repeat {
+            errors.push(error);
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_database_error() {
+        let output = "\
+10:00:01  2 of 3 ERROR creating sql table model analytics.orders ... [ERROR in 0.20s]
+10:00:02  Completed with 2 errors and 0 warnings:
+10:00:02
+10:00:02    Database Error in model orders (models/marts/orders.sql)
+  syntax error at or near \"form\"
+  LINE 3: select * form raw.orders
+                   ^
+  compiled Code at target/run/shop/models/marts/orders.sql
+10:00:02
+10:00:02    Failure in test unique_orders_id (models/schema.yml)
+  Got 2 results, configured to fail if != 0
+10:00:02
+10:00:02  Done. PASS=1 WARN=0 ERROR=2 SKIP=0 TOTAL=3";
+
+        let errors = parse_run_errors(output);
+        assert_eq!(errors.len(), 2);
+
+        let db = &errors[0];
+        assert_eq!(db.kind, "Database Error");
+        assert_eq!(db.resource_type, "model");
+        assert_eq!(db.node, "orders");
+        assert_eq!(db.file, "models/marts/orders.sql");
+        assert_eq!(
+            db.compiled_path.as_deref(),
+            Some("target/run/shop/models/marts/orders.sql")
+        );
+        assert_eq!(db.compiled_line, Some(3));
+        assert_eq!(db.summary(), "syntax error at or near \"form\"");
+        assert_eq!(db.compiled_column, Some(10));
+        assert_eq!(
+            db.location(),
+            ("target/run/shop/models/marts/orders.sql", Some(3), Some(10))
+        );
+
+        let failure = &errors[1];
+        assert_eq!(failure.kind, "Failure");
+        assert_eq!(failure.resource_type, "test");
+        assert_eq!(failure.location(), ("models/schema.yml", None, None));
+    }
+
+    #[test]
+    fn test_parse_compilation_error_without_timestamps() {
+        let output = "\
+Compilation Error in model customers (models/customers.sql)
+  expected token 'end of print statement', got 'orders'
+    line 14
+      {{ ref('stg' orders) }}
+Done.
+Compilation Error in model customers (models/customers.sql)
+  expected token 'end of print statement', got 'orders'";
+
+        let errors = parse_run_errors(output);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].source_line, Some(14));
+        assert_eq!(errors[0].location(), ("models/customers.sql", Some(14), None));
+        assert_eq!(errors[0].message.len(), 3);
+    }
+}
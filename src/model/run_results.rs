@@ -0,0 +1,315 @@
+//! Data models for the dbt run_results.json artifact
+//!
+//! dbt writes `target/run_results.json` after every run, test, build, seed,
+//! snapshot or compile invocation. It contains per-node status, the thread
+//! that executed the node and compile/execute timing.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Result of a single node within a dbt invocation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeResult {
+    pub unique_id: String,
+    /// dbt status string (success, error, skipped, pass, fail, warn, ...)
+    pub status: String,
+    #[serde(default)]
+    pub thread_id: String,
+    #[serde(default)]
+    pub execution_time: f64,
+    /// Start of the earliest timing phase (compile or execute)
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
+    /// End of the latest timing phase
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub failures: Option<u64>,
+}
+
+impl NodeResult {
+    /// Node name (last segment of the unique_id)
+    pub fn name(&self) -> &str {
+        self.unique_id.rsplit('.').next().unwrap_or(&self.unique_id)
+    }
+}
+
+/// Invocation arguments recorded in run_results.json
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunArgs {
+    /// dbt subcommand (run, test, build, ...)
+    pub which: Option<String>,
+    /// Full command line, recorded by dbt 1.6+
+    pub invocation_command: Option<String>,
+    pub select: Vec<String>,
+    pub exclude: Vec<String>,
+    pub target: Option<String>,
+    pub full_refresh: bool,
+}
+
+impl RunArgs {
+    /// Whether the invocation executed nodes, as opposed to compile, parse,
+    /// docs generate, ls, show or run-operation, whose results aren't runs
+    pub fn is_run(&self) -> bool {
+        matches!(
+            self.which.as_deref(),
+            None | Some("run" | "build" | "test" | "seed" | "snapshot")
+        )
+    }
+
+    /// Command line of the invocation, reconstructed from the args if dbt did not record it
+    pub fn command_line(&self) -> String {
+        if let Some(ref command) = self.invocation_command {
+            return command.clone();
+        }
+
+        let mut command = format!("dbt {}", self.which.as_deref().unwrap_or("run"));
+        if !self.select.is_empty() {
+            command.push_str(&format!(" --select {}", self.select.join(" ")));
+        }
+        if !self.exclude.is_empty() {
+            command.push_str(&format!(" --exclude {}", self.exclude.join(" ")));
+        }
+        if self.full_refresh {
+            command.push_str(" --full-refresh");
+        }
+        if let Some(ref target) = self.target {
+            command.push_str(&format!(" --target {}", target));
+        }
+        command
+    }
+}
+
+/// Parsed contents of run_results.json
+#[derive(Debug, Clone, Default)]
+pub struct RunResults {
+    pub invocation_id: Option<String>,
+    pub generated_at: Option<DateTime<Utc>>,
+    /// Total wall-clock time of the invocation in seconds
+    pub elapsed_time: Option<f64>,
+    pub args: RunArgs,
+    pub results: Vec<NodeResult>,
+}
+
+impl RunResults {
+    /// Parse run_results.json contents
+    pub fn from_json(contents: &str) -> Result<Self, String> {
+        let artifact: RunResultsArtifact = serde_json::from_str(contents)
+            .map_err(|e| format!("Failed to parse run_results.json: {}", e))?;
+
+        Ok(RunResults {
+            invocation_id: artifact.metadata.invocation_id,
+            generated_at: artifact.metadata.generated_at,
+            elapsed_time: artifact.elapsed_time,
+            args: artifact.args.into(),
+            results: artifact.results.into_iter().map(NodeResult::from).collect(),
+        })
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Raw artifact structure
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct RunResultsArtifact {
+    #[serde(default)]
+    metadata: ArtifactMetadata,
+    #[serde(default)]
+    results: Vec<RawNodeResult>,
+    #[serde(default)]
+    elapsed_time: Option<f64>,
+    #[serde(default)]
+    args: RawArgs,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RawArgs {
+    #[serde(default)]
+    which: Option<String>,
+    #[serde(default)]
+    invocation_command: Option<String>,
+    /// A list in recent dbt versions, a single string in older ones
+    #[serde(default)]
+    select: Option<serde_json::Value>,
+    #[serde(default)]
+    exclude: Option<serde_json::Value>,
+    #[serde(default)]
+    target: Option<String>,
+    #[serde(default)]
+    full_refresh: Option<bool>,
+}
+
+impl From<RawArgs> for RunArgs {
+    fn from(raw: RawArgs) -> Self {
+        RunArgs {
+            which: raw.which,
+            invocation_command: raw.invocation_command,
+            select: selector_values(raw.select),
+            exclude: selector_values(raw.exclude),
+            target: raw.target,
+            full_refresh: raw.full_refresh.unwrap_or(false),
+        }
+    }
+}
+
+fn selector_values(value: Option<serde_json::Value>) -> Vec<String> {
+    match value {
+        Some(serde_json::Value::String(s)) => s.split_whitespace().map(String::from).collect(),
+        Some(serde_json::Value::Array(items)) => items
+            .into_iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ArtifactMetadata {
+    #[serde(default)]
+    invocation_id: Option<String>,
+    #[serde(default)]
+    generated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawNodeResult {
+    unique_id: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    thread_id: Option<String>,
+    #[serde(default)]
+    execution_time: f64,
+    #[serde(default)]
+    timing: Vec<TimingInfo>,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    failures: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimingInfo {
+    #[serde(default)]
+    started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<RawNodeResult> for NodeResult {
+    fn from(raw: RawNodeResult) -> Self {
+        let started_at = raw.timing.iter().filter_map(|t| t.started_at).min();
+        let completed_at = raw.timing.iter().filter_map(|t| t.completed_at).max();
+
+        NodeResult {
+            unique_id: raw.unique_id,
+            status: raw.status,
+            thread_id: raw.thread_id.unwrap_or_default(),
+            execution_time: raw.execution_time,
+            started_at,
+            completed_at,
+            message: raw.message,
+            failures: raw.failures,
+        }
+    }
+}
+
+#[cfg(test)]
+impl NodeResult {
+    /// Test fixture: a result without timing phases or a message
+    pub fn fixture(unique_id: &str, status: &str, execution_time: f64) -> Self {
+        Self {
+            unique_id: unique_id.to_string(),
+            status: status.to_string(),
+            thread_id: String::new(),
+            execution_time,
+            started_at: None,
+            completed_at: None,
+            message: None,
+            failures: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"{
+        "metadata": {
+            "dbt_version": "1.7.4",
+            "generated_at": "2024-03-01T10:00:05.000000Z",
+            "invocation_id": "abc-123"
+        },
+        "results": [
+            {
+                "status": "success",
+                "timing": [
+                    {"name": "compile", "started_at": "2024-03-01T10:00:00.000000Z", "completed_at": "2024-03-01T10:00:00.500000Z"},
+                    {"name": "execute", "started_at": "2024-03-01T10:00:00.500000Z", "completed_at": "2024-03-01T10:00:02.000000Z"}
+                ],
+                "thread_id": "Thread-1 (worker)",
+                "execution_time": 2.0,
+                "message": "CREATE VIEW",
+                "failures": null,
+                "unique_id": "model.shop.stg_orders"
+            },
+            {
+                "status": "skipped",
+                "timing": [],
+                "thread_id": "Thread-2 (worker)",
+                "execution_time": 0,
+                "message": null,
+                "failures": null,
+                "unique_id": "model.shop.orders"
+            }
+        ],
+        "elapsed_time": 5.1,
+        "args": {"which": "run", "select": ["stg_orders+"]}
+    }"#;
+
+    #[test]
+    fn test_parse_run_results() {
+        let results = RunResults::from_json(SAMPLE).unwrap();
+
+        assert_eq!(results.invocation_id.as_deref(), Some("abc-123"));
+        assert_eq!(results.results.len(), 2);
+
+        let first = &results.results[0];
+        assert_eq!(first.name(), "stg_orders");
+        assert_eq!(first.thread_id, "Thread-1 (worker)");
+        let duration = first.completed_at.unwrap() - first.started_at.unwrap();
+        assert_eq!(duration.num_milliseconds(), 2000);
+
+        let second = &results.results[1];
+        assert_eq!(second.status, "skipped");
+        assert!(second.started_at.is_none());
+
+        assert_eq!(results.elapsed_time, Some(5.1));
+        assert_eq!(results.args.command_line(), "dbt run --select stg_orders+");
+    }
+
+    #[test]
+    fn test_is_run() {
+        let args = |which: Option<&str>| RunArgs {
+            which: which.map(String::from),
+            ..RunArgs::default()
+        };
+        for which in ["run", "build", "test", "seed", "snapshot"] {
+            assert!(args(Some(which)).is_run(), "{}", which);
+        }
+        for which in ["compile", "parse", "generate", "list", "show", "run-operation"] {
+            assert!(!args(Some(which)).is_run(), "{}", which);
+        }
+        // Older artifacts without args default to a run, like command_line
+        assert!(args(None).is_run());
+    }
+
+    #[test]
+    fn test_parse_invalid_run_results() {
+        assert!(RunResults::from_json("not json").is_err());
+    }
+}
@@ -0,0 +1,199 @@
+//! Per-node summary of a run, used for exports
+//!
+//! Built from run_results.json data when available; otherwise the dbt
+//! output is parsed for step result lines.
+
+use super::history::RunHistoryEntry;
+use super::run::{RunOutput, RunStatus};
+use super::run_results::NodeResult;
+use chrono::{DateTime, Local};
+use regex::Regex;
+use serde::Serialize;
+use std::sync::LazyLock;
+
+/// Step result line, e.g.
+/// "3 of 5 FAIL 2 unique_orders_id ......... [FAIL 2 in 0.05s]"
+/// "1 of 5 OK created sql view model analytics.stg_orders ... [CREATE VIEW in 0.10s]"
+static STEP_RESULT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\d+ of \d+ (OK|PASS|FAIL|WARN|ERROR|SKIP)\b(.*?) \.+\s*\[(.*?)(?: in ([\d.]+)s)?\]",
+    )
+    .unwrap()
+});
+
+/// Outcome of a single node
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NodeSummary {
+    pub name: String,
+    pub unique_id: Option<String>,
+    /// model, test, seed, snapshot, ...
+    pub resource_type: String,
+    /// Normalized dbt status: success, error, skipped, pass, fail, warn
+    pub status: String,
+    pub duration_secs: Option<f64>,
+    pub message: Option<String>,
+}
+
+impl NodeSummary {
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status.as_str(), "error" | "fail" | "runtime error")
+    }
+
+    pub fn is_skipped(&self) -> bool {
+        self.status == "skipped"
+    }
+}
+
+/// Summary of a complete run
+#[derive(Debug, Clone, Serialize)]
+pub struct RunSummary {
+    pub command: String,
+    pub status: RunStatus,
+    pub timestamp: Option<DateTime<Local>>,
+    pub duration_secs: Option<f64>,
+    pub target: Option<String>,
+    pub invocation_id: Option<String>,
+    pub nodes: Vec<NodeSummary>,
+}
+
+impl RunSummary {
+    pub fn from_run_output(run_output: &RunOutput) -> Self {
+        Self {
+            command: run_output.command.clone(),
+            status: run_output.status,
+            timestamp: run_output.started_at,
+            duration_secs: run_output.duration_secs,
+            target: None,
+            invocation_id: run_output.invocation_id.clone(),
+            nodes: summarize_nodes(&run_output.node_results, &run_output.output),
+        }
+    }
+
+    pub fn from_history_entry(entry: &RunHistoryEntry) -> Self {
+        Self {
+            command: entry.command.clone(),
+            status: entry.status,
+            timestamp: Some(entry.timestamp),
+            duration_secs: Some(entry.duration_secs),
+            target: entry.target.clone(),
+            invocation_id: entry.invocation_id.clone(),
+            nodes: summarize_nodes(&entry.node_results, &entry.output),
+        }
+    }
+
+    /// Number of nodes with the given status
+    pub fn count(&self, status: &str) -> usize {
+        self.nodes.iter().filter(|n| n.status == status).count()
+    }
+
+    /// Suggested file name stem, e.g. "dbt-run-20240301-100005"
+    pub fn file_stem(&self) -> String {
+        let subcommand = self
+            .command
+            .split_whitespace()
+            .nth(1)
+            .unwrap_or("run")
+            .to_string();
+        match self.timestamp {
+            Some(ts) => format!("dbt-{}-{}", subcommand, ts.format("%Y%m%d-%H%M%S")),
+            None => format!("dbt-{}", subcommand),
+        }
+    }
+}
+
+fn summarize_nodes(node_results: &[NodeResult], output: &str) -> Vec<NodeSummary> {
+    if !node_results.is_empty() {
+        return node_results
+            .iter()
+            .map(|r| NodeSummary {
+                name: r.name().to_string(),
+                unique_id: Some(r.unique_id.clone()),
+                resource_type: r.unique_id.split('.').next().unwrap_or("").to_string(),
+                status: r.status.clone(),
+                duration_secs: Some(r.execution_time),
+                message: r.message.clone(),
+            })
+            .collect();
+    }
+
+    output.lines().filter_map(parse_step_result).collect()
+}
+
+/// Parse a step result line from dbt output
+fn parse_step_result(line: &str) -> Option<NodeSummary> {
+    let caps = STEP_RESULT_REGEX.captures(line)?;
+
+    let keyword = caps.get(1)?.as_str();
+    // e.g. "created sql view model analytics.stg_orders" or "2 unique_orders_id"
+    let description: Vec<&str> = caps.get(2)?.as_str().split_whitespace().collect();
+    let full_name = *description.last()?;
+    let info = caps.get(3).map(|m| m.as_str().to_string());
+    let duration_secs = caps.get(4).and_then(|m| m.as_str().parse().ok());
+
+    let has_word = |word: &str| description.contains(&word);
+    let resource_type = if matches!(keyword, "PASS" | "FAIL" | "WARN") || has_word("test") {
+        "test"
+    } else if has_word("seed") {
+        "seed"
+    } else if has_word("snapshot") {
+        "snapshot"
+    } else {
+        "model"
+    };
+
+    let status = match keyword {
+        "OK" => "success",
+        "PASS" => "pass",
+        "FAIL" => "fail",
+        "WARN" => "warn",
+        "SKIP" => "skipped",
+        _ => "error",
+    };
+
+    Some(NodeSummary {
+        name: full_name.rsplit('.').next().unwrap_or(full_name).to_string(),
+        unique_id: None,
+        resource_type: resource_type.to_string(),
+        status: status.to_string(),
+        duration_secs,
+        message: info,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_step_results() {
+        let output = "\
+10:00:00  1 of 4 OK created sql view model analytics.stg_orders ......... [CREATE VIEW in 0.10s]
+10:00:01  2 of 4 ERROR creating sql table model analytics.orders ......... [ERROR in 0.20s]
+10:00:01  3 of 4 FAIL 2 unique_orders_id .............................. [FAIL 2 in 0.05s]
+10:00:01  4 of 4 SKIP relation analytics.customers .................... [SKIP]
+10:00:02  Done. PASS=0 WARN=0 ERROR=1 SKIP=1 TOTAL=4";
+
+        let run_output = RunOutput {
+            output: output.to_string(),
+            duration_secs: Some(2.0),
+            ..RunOutput::new("dbt build".to_string())
+        };
+        let summary = RunSummary::from_run_output(&run_output);
+
+        assert_eq!(summary.nodes.len(), 4);
+        assert_eq!(summary.nodes[0].name, "stg_orders");
+        assert_eq!(summary.nodes[0].resource_type, "model");
+        assert_eq!(summary.nodes[0].status, "success");
+        assert_eq!(summary.nodes[0].duration_secs, Some(0.1));
+
+        assert_eq!(summary.nodes[1].status, "error");
+        assert!(summary.nodes[1].is_failure());
+
+        assert_eq!(summary.nodes[2].name, "unique_orders_id");
+        assert_eq!(summary.nodes[2].resource_type, "test");
+        assert_eq!(summary.nodes[2].status, "fail");
+
+        assert!(summary.nodes[3].is_skipped());
+        assert_eq!(summary.count("error"), 1);
+    }
+}
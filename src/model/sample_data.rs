@@ -6,6 +6,10 @@ use super::run::RunStatus;
 #[derive(Debug, Clone, Default)]
 pub struct SampleDataOutput {
     pub model_name: String,
+    /// Dialog title, e.g. "Sample Data" or "Failing Rows"
+    pub title: String,
+    /// Command shown while loading
+    pub display_command: String,
     pub status: RunStatus,
     pub raw_output: String,
     pub headers: Vec<String>,
@@ -17,11 +21,20 @@ impl SampleDataOutput {
     pub fn new(model_name: String) -> Self {
         Self {
             model_name,
+            title: "Sample Data".to_string(),
             status: RunStatus::Running,
             ..Default::default()
         }
     }
 
+    /// Output of a test's failing rows query
+    pub fn failing_rows(test_name: String) -> Self {
+        Self {
+            title: "Failing Rows".to_string(),
+            ..Self::new(test_name)
+        }
+    }
+
     /// Parse the dbt show output into headers and rows
     ///
     /// dbt show outputs data in a pipe-delimited table format:
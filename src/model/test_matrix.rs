@@ -0,0 +1,386 @@
+//! Test coverage matrix of a model
+//!
+//! Lays out a model's tests by column and test type, with the latest
+//! result of each test from run history. Tests not tied to a column
+//! (singular tests, or generic tests like `unique_combination_of_columns`)
+//! are listed separately as model-level tests.
+
+use super::history::RunHistoryEntry;
+use super::node::Node;
+use std::collections::{BTreeMap, HashMap};
+
+/// Column of the matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestType {
+    Unique,
+    NotNull,
+    Relationships,
+    AcceptedValues,
+    Custom,
+}
+
+impl TestType {
+    pub const ALL: [TestType; 5] = [
+        TestType::Unique,
+        TestType::NotNull,
+        TestType::Relationships,
+        TestType::AcceptedValues,
+        TestType::Custom,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TestType::Unique => "unique",
+            TestType::NotNull => "not_null",
+            TestType::Relationships => "relationships",
+            TestType::AcceptedValues => "accepted_values",
+            TestType::Custom => "custom",
+        }
+    }
+
+    fn of(test: &Node) -> Self {
+        match test.test_metadata.as_ref().map(|m| m.name.as_str()) {
+            Some("unique") => TestType::Unique,
+            Some("not_null") => TestType::NotNull,
+            Some("relationships") => TestType::Relationships,
+            Some("accepted_values") => TestType::AcceptedValues,
+            _ => TestType::Custom,
+        }
+    }
+}
+
+/// A test in the matrix
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatrixTest {
+    pub unique_id: String,
+    pub name: String,
+    pub test_type: TestType,
+    /// Generic test name, or "singular"
+    pub kind: String,
+    /// Status of the most recent run (pass, fail, warn, error, skipped)
+    pub last_status: Option<String>,
+}
+
+/// Outcome of the tests in one cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CellStatus {
+    /// No tests of this type
+    Untested,
+    /// Tests that haven't run yet
+    NotRun,
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl CellStatus {
+    pub fn of_test(test: &MatrixTest) -> Self {
+        match test.last_status.as_deref() {
+            Some("pass" | "success") => CellStatus::Pass,
+            Some("warn") => CellStatus::Warn,
+            Some("fail" | "error" | "runtime error") => CellStatus::Fail,
+            _ => CellStatus::NotRun,
+        }
+    }
+}
+
+/// A documented or tested column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnRow {
+    pub column: String,
+    pub tests: Vec<MatrixTest>,
+}
+
+impl ColumnRow {
+    pub fn tests_of(&self, test_type: TestType) -> Vec<&MatrixTest> {
+        self.tests
+            .iter()
+            .filter(|t| t.test_type == test_type)
+            .collect()
+    }
+
+    /// Worst outcome among the tests of a type
+    pub fn cell(&self, test_type: TestType) -> CellStatus {
+        self.tests_of(test_type)
+            .iter()
+            .map(|t| CellStatus::of_test(t))
+            .max()
+            .unwrap_or(CellStatus::Untested)
+    }
+}
+
+/// Tests of one model, by column and type
+#[derive(Debug, Clone, Default)]
+pub struct TestMatrix {
+    pub model_name: String,
+    /// Columns by name
+    pub columns: Vec<ColumnRow>,
+    /// Singular tests, and generic tests not tied to a column
+    pub model_tests: Vec<MatrixTest>,
+}
+
+impl TestMatrix {
+    pub fn build(model: &Node, all_nodes: &[Node], history: &[RunHistoryEntry]) -> Self {
+        let statuses = last_statuses(history);
+
+        // Keyed case-insensitively, keeping the YAML spelling
+        let mut columns: BTreeMap<String, ColumnRow> = model
+            .columns
+            .values()
+            .map(|c| {
+                (
+                    c.name.to_lowercase(),
+                    ColumnRow {
+                        column: c.name.clone(),
+                        tests: Vec::new(),
+                    },
+                )
+            })
+            .collect();
+        let mut model_tests = Vec::new();
+
+        for test in all_nodes.iter().filter(|n| n.resource_type == "test") {
+            // Singular tests belong to every model they select from
+            let tests_model = match test.test_metadata {
+                Some(_) => test.tested_node_id() == Some(model.unique_id.as_str()),
+                None => test.depends_on.nodes.contains(&model.unique_id),
+            };
+            if !tests_model {
+                continue;
+            }
+            let matrix_test = MatrixTest {
+                unique_id: test.unique_id.clone(),
+                name: test.name.clone(),
+                test_type: TestType::of(test),
+                kind: test
+                    .test_metadata
+                    .as_ref()
+                    .map(|m| m.name.clone())
+                    .unwrap_or_else(|| "singular".to_string()),
+                last_status: statuses.get(test.unique_id.as_str()).map(|s| s.to_string()),
+            };
+            match test.column_name {
+                Some(ref column) => columns
+                    .entry(column.to_lowercase())
+                    .or_insert_with(|| ColumnRow {
+                        column: column.clone(),
+                        tests: Vec::new(),
+                    })
+                    .tests
+                    .push(matrix_test),
+                None => model_tests.push(matrix_test),
+            }
+        }
+
+        let mut columns: Vec<ColumnRow> = columns.into_values().collect();
+        for row in &mut columns {
+            row.tests.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        model_tests.sort_by(|a, b| a.name.cmp(&b.name));
+        Self {
+            model_name: model.name.clone(),
+            columns,
+            model_tests,
+        }
+    }
+
+    /// Columns without a single test
+    pub fn untested_columns(&self) -> Vec<&str> {
+        self.columns
+            .iter()
+            .filter(|row| row.tests.is_empty())
+            .map(|row| row.column.as_str())
+            .collect()
+    }
+}
+
+/// Latest status of each node (history is newest first)
+fn last_statuses(history: &[RunHistoryEntry]) -> HashMap<&str, &str> {
+    let mut statuses = HashMap::new();
+    for entry in history {
+        for result in &entry.node_results {
+            statuses
+                .entry(result.unique_id.as_str())
+                .or_insert(result.status.as_str());
+        }
+    }
+    statuses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::node::{ColumnInfo, TestMetadata};
+    use crate::model::run_results::NodeResult;
+
+    fn test(name: &str, test_name: Option<&str>, column: Option<&str>) -> Node {
+        let mut test = Node::fixture(name, "test", &[]);
+        test.test_metadata = test_name.map(|name| TestMetadata {
+            name: name.to_string(),
+            namespace: None,
+            ..Default::default()
+        });
+        test.column_name = column.map(String::from);
+        match test_name {
+            Some(_) => test.attached_node = Some("model.shop.orders".to_string()),
+            None => test.depends_on.nodes = vec!["model.shop.orders".to_string()],
+        }
+        test
+    }
+
+    fn history_entry(results: &[(&str, &str)]) -> RunHistoryEntry {
+        RunHistoryEntry {
+            node_results: results
+                .iter()
+                .map(|(name, status)| {
+                    NodeResult::fixture(&format!("test.shop.{}", name), status, 1.0)
+                })
+                .collect(),
+            ..RunHistoryEntry::fixture("dbt test", "")
+        }
+    }
+
+    fn column(name: &str) -> (String, ColumnInfo) {
+        (
+            name.to_string(),
+            ColumnInfo {
+                name: name.to_string(),
+                description: None,
+                data_type: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_matrix() {
+        let mut orders = Node::fixture("orders", "model", &[]);
+        orders.columns = HashMap::from([column("id"), column("status"), column("amount")]);
+        let nodes = vec![
+            orders.clone(),
+            test("unique_orders_id", Some("unique"), Some("id")),
+            test("not_null_orders_id", Some("not_null"), Some("ID")),
+            test("accepted_values_orders_status", Some("accepted_values"), Some("status")),
+            test("positive_orders_status", Some("expect_positive"), Some("status")),
+            test("orders_total_matches", None, None),
+        ];
+        let history = vec![history_entry(&[
+            ("unique_orders_id", "pass"),
+            ("not_null_orders_id", "fail"),
+        ])];
+
+        let matrix = TestMatrix::build(&orders, &nodes, &history);
+
+        let columns: Vec<&str> = matrix.columns.iter().map(|r| r.column.as_str()).collect();
+        assert_eq!(columns, vec!["amount", "id", "status"]);
+        let id = &matrix.columns[1];
+        assert_eq!(id.cell(TestType::Unique), CellStatus::Pass);
+        assert_eq!(id.cell(TestType::NotNull), CellStatus::Fail);
+        assert_eq!(id.cell(TestType::Relationships), CellStatus::Untested);
+        let status = &matrix.columns[2];
+        assert_eq!(status.cell(TestType::AcceptedValues), CellStatus::NotRun);
+        assert_eq!(status.tests_of(TestType::Custom)[0].kind, "expect_positive");
+        assert_eq!(matrix.untested_columns(), vec!["amount"]);
+        assert_eq!(matrix.model_tests.len(), 1);
+        assert_eq!(matrix.model_tests[0].kind, "singular");
+    }
+
+    #[test]
+    fn test_attached_vs_singular() {
+        let orders = Node::fixture("orders", "model", &[]);
+        let customers = Node::fixture("customers", "model", &[]);
+        // A relationships test on customers depends on orders but isn't attached to it
+        let mut relationship = test(
+            "relationships_customers_order_id",
+            Some("relationships"),
+            Some("order_id"),
+        );
+        relationship.attached_node = Some("model.shop.customers".to_string());
+        relationship.depends_on.nodes =
+            vec!["model.shop.orders".to_string(), "model.shop.customers".to_string()];
+        // A singular test selecting from both models belongs to both
+        let mut reconciliation = test("orders_match_customers", None, None);
+        reconciliation.depends_on.nodes =
+            vec!["model.shop.orders".to_string(), "model.shop.customers".to_string()];
+        let mut unrelated = test("payments_positive", None, None);
+        unrelated.depends_on.nodes = vec!["model.shop.payments".to_string()];
+        // Manifests without attached_node name the tested model in the test's arguments
+        let mut legacy = test(
+            "relationships_customers_referrer_id",
+            Some("relationships"),
+            Some("referrer_id"),
+        );
+        legacy.attached_node = None;
+        legacy.depends_on.nodes =
+            vec!["model.shop.orders".to_string(), "model.shop.customers".to_string()];
+        if let Some(ref mut metadata) = legacy.test_metadata {
+            metadata.kwargs.insert(
+                "model".to_string(),
+                "{{ get_where_subquery(ref('customers')) }}".into(),
+            );
+        }
+        let nodes = vec![
+            orders.clone(),
+            customers.clone(),
+            relationship,
+            legacy,
+            reconciliation,
+            unrelated,
+        ];
+
+        let matrix = TestMatrix::build(&orders, &nodes, &[]);
+        assert!(matrix.columns.is_empty());
+        let names: Vec<&str> = matrix.model_tests.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["orders_match_customers"]);
+
+        let matrix = TestMatrix::build(&customers, &nodes, &[]);
+        let columns: Vec<&str> = matrix.columns.iter().map(|r| r.column.as_str()).collect();
+        assert_eq!(columns, vec!["order_id", "referrer_id"]);
+        assert_eq!(matrix.columns[1].tests[0].test_type, TestType::Relationships);
+        assert_eq!(matrix.model_tests.len(), 1);
+    }
+
+    #[test]
+    fn test_case_insensitive_columns() {
+        let mut orders = Node::fixture("orders", "model", &[]);
+        orders.columns = HashMap::from([column("Customer_ID")]);
+        let nodes = vec![
+            orders.clone(),
+            test("unique_orders_customer_id", Some("unique"), Some("customer_id")),
+            test("not_null_orders_customer_id", Some("not_null"), Some("CUSTOMER_ID")),
+            test("not_null_orders_email", Some("not_null"), Some("Email")),
+        ];
+
+        let matrix = TestMatrix::build(&orders, &nodes, &[]);
+
+        // YAML spelling wins; undocumented columns keep the test's spelling
+        let columns: Vec<(&str, usize)> = matrix
+            .columns
+            .iter()
+            .map(|r| (r.column.as_str(), r.tests.len()))
+            .collect();
+        assert_eq!(columns, vec![("Customer_ID", 2), ("Email", 1)]);
+        assert!(matrix.untested_columns().is_empty());
+    }
+
+    #[test]
+    fn test_latest_status() {
+        let orders = Node::fixture("orders", "model", &[]);
+        let nodes = vec![
+            orders.clone(),
+            test("unique_orders_id", Some("unique"), Some("id")),
+            test("not_null_orders_id", Some("not_null"), Some("id")),
+        ];
+        // Newest first: unique failed last time, not_null only ran in the older run
+        let history = vec![
+            history_entry(&[("unique_orders_id", "fail")]),
+            history_entry(&[("unique_orders_id", "pass"), ("not_null_orders_id", "warn")]),
+        ];
+
+        let matrix = TestMatrix::build(&orders, &nodes, &history);
+
+        let id = &matrix.columns[0];
+        assert_eq!(id.cell(TestType::Unique), CellStatus::Fail);
+        assert_eq!(id.cell(TestType::NotNull), CellStatus::Warn);
+        assert_eq!(id.tests_of(TestType::Unique)[0].last_status.as_deref(), Some("fail"));
+    }
+}
@@ -0,0 +1,217 @@
+//! Run timeline (Gantt) model
+//!
+//! Lays out per-node execution windows from run_results.json on the thread
+//! that executed them, and finds the critical path: the dependency chain
+//! whose summed durations bound the total run time.
+
+use super::node::Node;
+use super::run_results::NodeResult;
+use std::collections::HashMap;
+
+/// A single node execution on the timeline
+#[derive(Debug, Clone)]
+pub struct TimelineBar {
+    pub unique_id: String,
+    pub name: String,
+    /// Index into `RunTimeline::threads`
+    pub thread: usize,
+    /// Seconds since the first node started
+    pub start_secs: f64,
+    /// Seconds since the first node started
+    pub end_secs: f64,
+    pub status: String,
+    pub on_critical_path: bool,
+}
+
+impl TimelineBar {
+    pub fn duration(&self) -> f64 {
+        (self.end_secs - self.start_secs).max(0.0)
+    }
+}
+
+/// Gantt-style view of a completed run
+#[derive(Debug, Clone)]
+pub struct RunTimeline {
+    /// Thread names, sorted
+    pub threads: Vec<String>,
+    /// Bars sorted by start time
+    pub bars: Vec<TimelineBar>,
+    /// Wall-clock span from first start to last completion
+    pub total_secs: f64,
+    /// unique_ids along the critical path, in execution order
+    pub critical_path: Vec<String>,
+    /// Summed duration of the critical path
+    pub critical_secs: f64,
+}
+
+impl RunTimeline {
+    /// Build a timeline from node results. Nodes without timing (e.g. skipped
+    /// nodes) are left out. Returns None if no node has timing information.
+    pub fn build(results: &[NodeResult], all_nodes: &[Node]) -> Option<Self> {
+        let timed: Vec<&NodeResult> = results
+            .iter()
+            .filter(|r| r.started_at.is_some() && r.completed_at.is_some())
+            .collect();
+
+        let origin = timed.iter().filter_map(|r| r.started_at).min()?;
+
+        let mut threads: Vec<String> = timed.iter().map(|r| r.thread_id.clone()).collect();
+        threads.sort();
+        threads.dedup();
+
+        let mut bars: Vec<TimelineBar> = timed
+            .iter()
+            .map(|r| {
+                let offset = |t: chrono::DateTime<chrono::Utc>| {
+                    (t - origin).num_microseconds().unwrap_or(0) as f64 / 1_000_000.0
+                };
+                TimelineBar {
+                    unique_id: r.unique_id.clone(),
+                    name: r.name().to_string(),
+                    thread: threads.iter().position(|t| *t == r.thread_id).unwrap_or(0),
+                    start_secs: r.started_at.map(offset).unwrap_or(0.0),
+                    end_secs: r.completed_at.map(offset).unwrap_or(0.0),
+                    status: r.status.clone(),
+                    on_critical_path: false,
+                }
+            })
+            .collect();
+
+        bars.sort_by(|a, b| a.start_secs.total_cmp(&b.start_secs));
+
+        let total_secs = bars.iter().map(|b| b.end_secs).fold(0.0, f64::max);
+        let (critical_path, critical_secs) = Self::critical_path(&bars, all_nodes);
+
+        for bar in &mut bars {
+            bar.on_critical_path = critical_path.contains(&bar.unique_id);
+        }
+
+        Some(RunTimeline {
+            threads,
+            bars,
+            total_secs,
+            critical_path,
+            critical_secs,
+        })
+    }
+
+    /// Longest duration-weighted dependency chain among the nodes in this run.
+    ///
+    /// Bars are processed in start order; a node always starts after its
+    /// dependencies complete, so every dependency is visited first.
+    fn critical_path(bars: &[TimelineBar], all_nodes: &[Node]) -> (Vec<String>, f64) {
+        let deps_by_id: HashMap<&str, &[String]> = all_nodes
+            .iter()
+            .map(|n| (n.unique_id.as_str(), n.depends_on.nodes.as_slice()))
+            .collect();
+
+        // unique_id -> (longest path ending at this node, predecessor on that path)
+        let mut longest: HashMap<&str, (f64, Option<&str>)> = HashMap::new();
+
+        for bar in bars {
+            let deps = deps_by_id.get(bar.unique_id.as_str()).copied().unwrap_or(&[]);
+            let best_dep = deps
+                .iter()
+                .filter_map(|d| longest.get(d.as_str()).map(|(len, _)| (d.as_str(), *len)))
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+
+            let (base, pred) = match best_dep {
+                Some((dep, len)) => (len, Some(dep)),
+                None => (0.0, None),
+            };
+            longest.insert(bar.unique_id.as_str(), (base + bar.duration(), pred));
+        }
+
+        let Some((end, (length, _))) = longest
+            .iter()
+            .max_by(|a, b| a.1 .0.total_cmp(&b.1 .0))
+            .map(|(id, v)| (*id, *v))
+        else {
+            return (Vec::new(), 0.0);
+        };
+
+        let mut path = vec![end.to_string()];
+        let mut current = end;
+        while let Some((_, Some(pred))) = longest.get(current) {
+            path.push(pred.to_string());
+            current = pred;
+        }
+        path.reverse();
+
+        (path, length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn create_test_node(name: &str, depends_on: Vec<&str>) -> Node {
+        Node::fixture_on_models(name, "model", &depends_on)
+    }
+
+    fn result(name: &str, thread: &str, start: i64, end: i64) -> NodeResult {
+        NodeResult {
+            thread_id: thread.to_string(),
+            started_at: Some(Utc.timestamp_opt(start, 0).unwrap()),
+            completed_at: Some(Utc.timestamp_opt(end, 0).unwrap()),
+            ..NodeResult::fixture(&format!("model.shop.{}", name), "success", (end - start) as f64)
+        }
+    }
+
+    #[test]
+    fn test_timeline_threads_and_offsets() {
+        let results = vec![
+            result("a", "Thread-1", 100, 102),
+            result("b", "Thread-2", 100, 101),
+        ];
+        let timeline = RunTimeline::build(&results, &[]).unwrap();
+
+        assert_eq!(timeline.threads, vec!["Thread-1", "Thread-2"]);
+        assert_eq!(timeline.total_secs, 2.0);
+        assert_eq!(timeline.bars[0].start_secs, 0.0);
+        assert_eq!(timeline.bars[1].thread, 1);
+    }
+
+    #[test]
+    fn test_critical_path_follows_longest_chain() {
+        // a (2s) -> c (3s); b (4s) -> c. Longest chain is b -> c.
+        let nodes = vec![
+            create_test_node("a", vec![]),
+            create_test_node("b", vec![]),
+            create_test_node("c", vec!["a", "b"]),
+            create_test_node("d", vec![]),
+        ];
+        let results = vec![
+            result("a", "Thread-1", 0, 2),
+            result("b", "Thread-2", 0, 4),
+            result("d", "Thread-1", 2, 3),
+            result("c", "Thread-1", 4, 7),
+        ];
+
+        let timeline = RunTimeline::build(&results, &nodes).unwrap();
+
+        assert_eq!(
+            timeline.critical_path,
+            vec!["model.shop.b".to_string(), "model.shop.c".to_string()]
+        );
+        assert_eq!(timeline.critical_secs, 7.0);
+        assert!(timeline
+            .bars
+            .iter()
+            .find(|b| b.name == "b")
+            .unwrap()
+            .on_critical_path);
+        assert!(!timeline.bars.iter().find(|b| b.name == "a").unwrap().on_critical_path);
+    }
+
+    #[test]
+    fn test_timeline_without_timing() {
+        let mut skipped = result("a", "Thread-1", 0, 1);
+        skipped.started_at = None;
+        skipped.completed_at = None;
+
+        assert!(RunTimeline::build(&[skipped], &[]).is_none());
+    }
+}
@@ -0,0 +1,691 @@
+//! Index of where resources, columns and tests are declared in schema YAML
+//!
+//! Files are parsed with yaml-rust2, whose events carry positions, into a
+//! tree of nodes with the lines they span; declarations are found in it by
+//! their place in the structure:
+//!
+//! ```text
+//! models:                      section
+//!   - name: orders             resource (model)
+//!     columns:
+//!       - name: order_id       column
+//!         tests:
+//!           - unique           test
+//!           - not_null         test
+//! sources:
+//!   - name: raw                source
+//!     tables:
+//!       - name: orders         resource (source table "raw.orders")
+//! ```
+
+use super::node::Node;
+use std::fs;
+use std::path::Path;
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::{Marker, ScanError};
+
+/// Kind of a YAML declaration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YamlEntryKind {
+    Model,
+    Seed,
+    Snapshot,
+    Source,
+    SourceTable,
+    Column,
+    Test,
+}
+
+impl YamlEntryKind {
+    fn of_section(key: &str) -> Option<Self> {
+        match key {
+            "models" => Some(YamlEntryKind::Model),
+            "seeds" => Some(YamlEntryKind::Seed),
+            "snapshots" => Some(YamlEntryKind::Snapshot),
+            "sources" => Some(YamlEntryKind::Source),
+            _ => None,
+        }
+    }
+
+    fn of_resource_type(resource_type: &str) -> Option<Self> {
+        match resource_type {
+            "model" => Some(YamlEntryKind::Model),
+            "seed" => Some(YamlEntryKind::Seed),
+            "snapshot" => Some(YamlEntryKind::Snapshot),
+            "source" => Some(YamlEntryKind::SourceTable),
+            _ => None,
+        }
+    }
+}
+
+/// A declaration in a schema YAML file
+#[derive(Debug, Clone, PartialEq)]
+pub struct YamlEntry {
+    pub kind: YamlEntryKind,
+    /// Resource name; "source.table" for source tables
+    pub resource: String,
+    pub column: Option<String>,
+    /// Test name as written, e.g. "unique" or "dbt_utils.expression_is_true"
+    pub test: Option<String>,
+    /// Arguments of a test as written (`values`, `to`, `name`, ...), scalars as strings
+    pub args: serde_json::Map<String, serde_json::Value>,
+    /// File relative to the project root
+    pub file: String,
+    /// First line of the declaration (1-based)
+    pub line: usize,
+    /// Last non-blank line of the declaration (1-based)
+    pub end_line: usize,
+}
+
+impl YamlEntry {
+    /// Lines `line..=end_line` of the declaring file
+    pub fn read_snippet(&self, project_path: &Path) -> Option<String> {
+        let contents = fs::read_to_string(project_path.join(&self.file)).ok()?;
+        let lines: Vec<&str> = contents
+            .lines()
+            .skip(self.line - 1)
+            .take(self.end_line + 1 - self.line)
+            .collect();
+        Some(lines.join("\n"))
+    }
+
+    fn test_matches(&self, name: &str) -> bool {
+        self.test.as_deref().is_some_and(|test| {
+            test == name || test.rsplit('.').next() == Some(name)
+        })
+    }
+
+    /// Whether the written arguments agree with the test node's: its explicit
+    /// name, and every argument dbt recorded in the node's kwargs
+    fn args_match(&self, node: &Node) -> bool {
+        let Some(ref metadata) = node.test_metadata else {
+            return false;
+        };
+        self.args.iter().all(|(key, value)| match key.as_str() {
+            "name" => value.as_str() == Some(node.name.as_str()),
+            _ => metadata.kwargs.get(key).is_none_or(|kwarg| same_value(value, kwarg)),
+        })
+    }
+}
+
+/// A YAML value (scalars as strings) compared to the JSON dbt wrote for it
+pub fn same_value(yaml: &serde_json::Value, json: &serde_json::Value) -> bool {
+    use serde_json::Value as Json;
+    match (yaml, json) {
+        (Json::Array(a), Json::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_value(a, b))
+        }
+        (Json::Object(a), Json::Object(b)) => a
+            .iter()
+            .all(|(key, a)| b.get(key).is_some_and(|b| same_value(a, b))),
+        (Json::String(a), Json::String(b)) => a == b,
+        (Json::String(a), Json::Number(_) | Json::Bool(_)) => {
+            serde_json::from_str::<Json>(a).is_ok_and(|a| a == *json)
+        }
+        _ => false,
+    }
+}
+
+/// Declarations across all indexed files
+#[derive(Debug, Default)]
+pub struct YamlIndex {
+    pub entries: Vec<YamlEntry>,
+}
+
+/// A parsed YAML node and the lines it spans (1-based)
+#[derive(Debug)]
+struct Spanned {
+    value: Value,
+    line: usize,
+    end_line: usize,
+}
+
+#[derive(Debug)]
+enum Value {
+    /// Scalars, and aliases as empty scalars
+    Scalar(String),
+    Sequence(Vec<Spanned>),
+    /// Non-scalar keys are kept as empty strings
+    Mapping(Vec<(String, Spanned)>),
+}
+
+impl Spanned {
+    fn scalar(&self) -> Option<&str> {
+        match self.value {
+            Value::Scalar(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn items(&self) -> &[Spanned] {
+        match self.value {
+            Value::Sequence(ref items) => items,
+            _ => &[],
+        }
+    }
+
+    fn entries(&self) -> &[(String, Spanned)] {
+        match self.value {
+            Value::Mapping(ref entries) => entries,
+            _ => &[],
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&Spanned> {
+        self.entries().iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// The value as JSON, scalars as strings
+    fn to_json(&self) -> serde_json::Value {
+        match self.value {
+            Value::Scalar(ref s) => serde_json::Value::String(s.clone()),
+            Value::Sequence(ref items) => items.iter().map(Spanned::to_json).collect(),
+            Value::Mapping(ref entries) => serde_json::Value::Object(
+                entries.iter().map(|(k, v)| (k.clone(), v.to_json())).collect(),
+            ),
+        }
+    }
+}
+
+/// Collects parser events with their positions
+#[derive(Default)]
+struct Events(Vec<(Event, Marker)>);
+
+impl MarkedEventReceiver for Events {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        self.0.push((event, mark));
+    }
+}
+
+/// Builds the documents of a file from its events
+struct TreeBuilder<'a> {
+    lines: Vec<&'a str>,
+    events: Vec<(Event, Marker)>,
+    pos: usize,
+}
+
+impl TreeBuilder<'_> {
+    fn documents(&mut self) -> Vec<Spanned> {
+        let mut documents = Vec::new();
+        while let Some((event, _)) = self.events.get(self.pos) {
+            match event {
+                Event::Scalar(..) | Event::SequenceStart(..) | Event::MappingStart(..) | Event::Alias(_) => {
+                    documents.extend(self.node());
+                }
+                _ => self.pos += 1,
+            }
+        }
+        documents
+    }
+
+    fn node(&mut self) -> Option<Spanned> {
+        let (event, start) = self.events.get(self.pos).cloned()?;
+        self.pos += 1;
+        let value = match event {
+            Event::Scalar(value, ..) => Value::Scalar(value),
+            Event::Alias(_) => Value::Scalar(String::new()),
+            Event::SequenceStart(..) => {
+                let mut items = Vec::new();
+                while !matches!(self.events.get(self.pos), None | Some((Event::SequenceEnd, _))) {
+                    items.extend(self.node());
+                }
+                Value::Sequence(items)
+            }
+            Event::MappingStart(..) => {
+                let mut entries = Vec::new();
+                while !matches!(self.events.get(self.pos), None | Some((Event::MappingEnd, _))) {
+                    let key = self.node();
+                    let Some(value) = self.node() else {
+                        break;
+                    };
+                    let key = key.and_then(|k| k.scalar().map(String::from)).unwrap_or_default();
+                    entries.push((key, value));
+                }
+                Value::Mapping(entries)
+            }
+            _ => return None,
+        };
+        // Collections end at their end event, scalars where the next event starts
+        let end = match value {
+            Value::Scalar(_) => self.events.get(self.pos).map(|(_, mark)| *mark),
+            _ => {
+                self.pos += 1;
+                self.events.get(self.pos - 1).map(|(_, mark)| *mark)
+            }
+        };
+        Some(Spanned {
+            end_line: end.map_or(start.line(), |end| self.end_line(start, end)),
+            line: start.line(),
+            value,
+        })
+    }
+
+    /// Last line of a node starting at `start`, given where the next token is:
+    /// the line before it, or its own line when the node ends mid-line (flow
+    /// collections, `[a, b]`), less trailing blank and comment lines
+    fn end_line(&self, start: Marker, end: Marker) -> usize {
+        let char_at = |mark: Marker| {
+            self.lines
+                .get(mark.line().wrapping_sub(1))
+                .and_then(|line| line.chars().nth(mark.col()))
+        };
+        let text_before_end = self.lines.get(end.line().wrapping_sub(1)).is_some_and(|line| {
+            line.chars()
+                .take(end.col())
+                .any(|c| !c.is_whitespace() && c != '-')
+        });
+        let flow = matches!(char_at(start), Some('[' | '{'));
+        let mut line = if flow || text_before_end {
+            end.line()
+        } else {
+            end.line().saturating_sub(1)
+        }
+        .min(self.lines.len());
+        while line > start.line() {
+            let text = self.lines[line - 1].trim();
+            if !text.is_empty() && !text.starts_with('#') {
+                break;
+            }
+            line -= 1;
+        }
+        line.max(start.line())
+    }
+}
+
+/// Parse every document of a file into spanned nodes
+fn parse_documents(contents: &str) -> Result<Vec<Spanned>, ScanError> {
+    let mut events = Events::default();
+    Parser::new_from_str(contents).load(&mut events, true)?;
+    Ok(TreeBuilder {
+        lines: contents.lines().collect(),
+        events: events.0,
+        pos: 0,
+    }
+    .documents())
+}
+
+impl YamlIndex {
+    /// Index the declarations of one file; fails if it is not valid YAML
+    pub fn add_file(&mut self, file: &str, contents: &str) -> Result<(), String> {
+        let documents = parse_documents(contents).map_err(|e| format!("Failed to parse {}: {}", file, e))?;
+        for document in &documents {
+            for (key, section) in document.entries() {
+                let Some(kind) = YamlEntryKind::of_section(key) else {
+                    continue;
+                };
+                for item in section.items() {
+                    self.add_resource(file, kind, "", item);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, file: &str, node: &Spanned, kind: YamlEntryKind, resource: &str, column: Option<&str>, test: Option<&str>) {
+        self.entries.push(YamlEntry {
+            kind,
+            resource: resource.to_string(),
+            column: column.map(String::from),
+            test: test.map(String::from),
+            args: serde_json::Map::new(),
+            file: file.to_string(),
+            line: node.line,
+            end_line: node.end_line,
+        });
+    }
+
+    /// A resource item; source tables are named `prefix` + name
+    fn add_resource(&mut self, file: &str, kind: YamlEntryKind, prefix: &str, item: &Spanned) {
+        let name = format!(
+            "{}{}",
+            prefix,
+            item.get("name").and_then(Spanned::scalar).unwrap_or_default()
+        );
+        self.push(file, item, kind, &name, None, None);
+        for (key, value) in item.entries() {
+            match key.as_str() {
+                "tables" if kind == YamlEntryKind::Source => {
+                    for table in value.items() {
+                        self.add_resource(file, YamlEntryKind::SourceTable, &format!("{}.", name), table);
+                    }
+                }
+                "columns" => {
+                    for column in value.items() {
+                        let column_name = column.get("name").and_then(Spanned::scalar).unwrap_or_default();
+                        self.push(file, column, YamlEntryKind::Column, &name, Some(column_name), None);
+                        for key in ["tests", "data_tests"] {
+                            if let Some(tests) = column.get(key) {
+                                self.add_tests(file, &name, Some(column_name), tests);
+                            }
+                        }
+                    }
+                }
+                "tests" | "data_tests" => self.add_tests(file, &name, None, value),
+                _ => {}
+            }
+        }
+    }
+
+    /// Test items: `- unique`, or `- accepted_values: {...}` named by their key
+    fn add_tests(&mut self, file: &str, resource: &str, column: Option<&str>, tests: &Spanned) {
+        for test in tests.items() {
+            let (name, args) = match test.scalar() {
+                Some(name) => (name, None),
+                None => test
+                    .entries()
+                    .first()
+                    .map_or(("", None), |(key, args)| (key.as_str(), Some(args))),
+            };
+            self.push(file, test, YamlEntryKind::Test, resource, column, Some(name));
+            let entry = self.entries.last_mut().expect("pushed above");
+            for (key, value) in args.map_or(&[][..], Spanned::entries) {
+                // dbt >= 1.10 nests the test's own arguments under `arguments:`
+                if key == "arguments" {
+                    for (key, value) in value.entries() {
+                        entry.args.insert(key.clone(), value.to_json());
+                    }
+                } else {
+                    entry.args.insert(key.clone(), value.to_json());
+                }
+            }
+        }
+    }
+
+    /// Column declarations of a resource, in file order
+    pub fn columns(&self, resource: &str) -> Vec<&YamlEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.kind == YamlEntryKind::Column && e.resource == resource)
+            .collect()
+    }
+
+    /// Declaration of a node: the resource itself, or the generic test's entry.
+    /// None for a test whose declaration can't be told apart from another.
+    pub fn find_node(&self, node: &Node) -> Option<&YamlEntry> {
+        if node.resource_type == "test" {
+            return match self.test_entries(node).as_slice() {
+                [entry] => Some(entry),
+                _ => None,
+            };
+        }
+        let kind = YamlEntryKind::of_resource_type(&node.resource_type)?;
+        let schema_file = node.schema_file_path();
+        self.entries
+            .iter()
+            .filter(|e| e.kind == kind && e.resource == node.name)
+            .max_by_key(|e| schema_file == Some(e.file.as_str()))
+    }
+
+    /// Declarations that may be a generic test node: same resource, column and
+    /// test, narrowed by arguments when there are several
+    pub fn test_entries(&self, node: &Node) -> Vec<&YamlEntry> {
+        let (Some(metadata), Some(resource)) = (node.test_metadata.as_ref(), node.tested_resource())
+        else {
+            return Vec::new();
+        };
+        let candidates: Vec<&YamlEntry> = self
+            .entries
+            .iter()
+            .filter(|e| {
+                e.kind == YamlEntryKind::Test
+                    && e.resource == resource
+                    && e.test_matches(&metadata.name)
+                    && match (&e.column, &node.column_name) {
+                        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                        (None, None) => true,
+                        _ => false,
+                    }
+                    && node.original_file_path.as_deref().is_none_or(|f| f == e.file)
+            })
+            .collect();
+        if candidates.len() < 2 {
+            return candidates;
+        }
+        let matching: Vec<&YamlEntry> =
+            candidates.into_iter().filter(|e| e.args_match(node)).collect();
+        // An explicit `name:` wins over a declaration whose name dbt generated
+        let named: Vec<&YamlEntry> = matching
+            .iter()
+            .copied()
+            .filter(|e| e.args.contains_key("name"))
+            .collect();
+        if named.is_empty() { matching } else { named }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = "\
+version: 2
+
+models:
+  - name: orders
+    description: |
+      One row per order.
+      - name: not_a_column
+    columns:
+      - name: order_id
+        tests:
+          - unique
+          - not_null
+      - name: status
+        data_tests:
+          - accepted_values:
+              values: ['placed', 'shipped']
+    tests:
+      - dbt_utils.expression_is_true:
+          expression: \"amount >= 0\"
+
+sources:
+- name: raw
+  tables:
+  - name: orders
+    columns:
+    - name: id
+      tests: [unique]
+";
+
+    fn resource<'a>(index: &'a YamlIndex, kind: YamlEntryKind, name: &str) -> Option<&'a YamlEntry> {
+        index
+            .entries
+            .iter()
+            .find(|e| e.kind == kind && e.resource == name)
+    }
+
+    fn index() -> YamlIndex {
+        let mut index = YamlIndex::default();
+        index.add_file("models/schema.yml", SCHEMA).unwrap();
+        index
+    }
+
+    #[test]
+    fn test_index_resources_and_columns() {
+        let index = index();
+
+        let orders = resource(&index, YamlEntryKind::Model, "orders").unwrap();
+        assert_eq!((orders.line, orders.end_line), (4, 19));
+
+        let columns: Vec<_> = index
+            .columns("orders")
+            .iter()
+            .map(|c| (c.column.clone().unwrap(), c.line))
+            .collect();
+        assert_eq!(
+            columns,
+            vec![("order_id".to_string(), 9), ("status".to_string(), 13)]
+        );
+
+        let source = resource(&index, YamlEntryKind::Source, "raw").unwrap();
+        assert_eq!(source.line, 22);
+        let table = resource(&index, YamlEntryKind::SourceTable, "raw.orders").unwrap();
+        assert_eq!((table.line, table.end_line), (24, 27));
+        assert_eq!(index.columns("raw.orders")[0].line, 26);
+    }
+
+    #[test]
+    fn test_index_tests() {
+        let index = index();
+        let tests: Vec<_> = index
+            .entries
+            .iter()
+            .filter(|e| e.kind == YamlEntryKind::Test)
+            .map(|e| (e.resource.as_str(), e.column.as_deref(), e.test.as_deref().unwrap(), e.line, e.end_line))
+            .collect();
+        assert_eq!(
+            tests,
+            vec![
+                ("orders", Some("order_id"), "unique", 11, 11),
+                ("orders", Some("order_id"), "not_null", 12, 12),
+                ("orders", Some("status"), "accepted_values", 15, 16),
+                ("orders", None, "dbt_utils.expression_is_true", 18, 19),
+                ("raw.orders", Some("id"), "unique", 27, 27),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_node() {
+        let index = index();
+        let node = |json: serde_json::Value| -> Node { serde_json::from_value(json).unwrap() };
+
+        let model = node(serde_json::json!({
+            "unique_id": "model.shop.orders",
+            "name": "orders",
+            "resource_type": "model",
+            "patch_path": "shop://models/schema.yml"
+        }));
+        assert_eq!(index.find_node(&model).map(|e| e.line), Some(4));
+
+        let test = node(serde_json::json!({
+            "unique_id": "test.shop.accepted_values_orders_status__placed__shipped.abc",
+            "name": "accepted_values_orders_status__placed__shipped",
+            "resource_type": "test",
+            "original_file_path": "models/schema.yml",
+            "attached_node": "model.shop.orders",
+            "column_name": "status",
+            "test_metadata": { "name": "accepted_values" }
+        }));
+        assert_eq!(index.find_node(&test).map(|e| e.line), Some(15));
+
+        let source_test = node(serde_json::json!({
+            "unique_id": "test.shop.source_unique_raw_orders_id.def",
+            "name": "source_unique_raw_orders_id",
+            "resource_type": "test",
+            "original_file_path": "models/schema.yml",
+            "depends_on": { "nodes": ["source.shop.raw.orders"] },
+            "column_name": "id",
+            "test_metadata": {
+                "name": "unique",
+                "kwargs": { "model": "{{ get_where_subquery(source('raw', 'orders')) }}" }
+            }
+        }));
+        assert_eq!(index.find_node(&source_test).map(|e| e.line), Some(27));
+    }
+
+    #[test]
+    fn test_find_repeated_test() {
+        let mut index = YamlIndex::default();
+        let contents = "\
+models:
+  - name: orders
+    columns:
+      - name: status
+        tests:
+          - accepted_values:
+              values: ['placed', 'shipped']
+          - accepted_values:
+              arguments:
+                values: ['returned']
+              config:
+                severity: warn
+          - relationships:
+              to: ref('statuses')
+              field: code
+          - relationships:
+              to: ref('statuses')
+              field: code
+              name: status_code_exists
+";
+        index.add_file("models/schema.yml", contents).unwrap();
+        let test = |name: &str, test_name: &str, kwargs: serde_json::Value| -> Node {
+            serde_json::from_value(serde_json::json!({
+                "unique_id": format!("test.shop.{}", name),
+                "name": name,
+                "resource_type": "test",
+                "original_file_path": "models/schema.yml",
+                "attached_node": "model.shop.orders",
+                "column_name": "status",
+                "test_metadata": { "name": test_name, "kwargs": kwargs }
+            }))
+            .unwrap()
+        };
+
+        let returned = test(
+            "accepted_values_orders_status__returned",
+            "accepted_values",
+            serde_json::json!({ "values": ["returned"], "column_name": "status" }),
+        );
+        assert_eq!(index.find_node(&returned).map(|e| e.line), Some(8));
+
+        // Told apart by the explicit name only
+        let named = test(
+            "status_code_exists",
+            "relationships",
+            serde_json::json!({ "to": "ref('statuses')", "field": "code" }),
+        );
+        assert_eq!(index.find_node(&named).map(|e| e.line), Some(16));
+        let generated = test(
+            "relationships_orders_status__code__ref_statuses_",
+            "relationships",
+            serde_json::json!({ "to": "ref('statuses')", "field": "code" }),
+        );
+        assert_eq!(index.find_node(&generated).map(|e| e.line), Some(13));
+
+        // Without kwargs (older manifests) the two accepted_values can't be told apart
+        let unknown = test("accepted_values_orders_status__x", "accepted_values", serde_json::json!({}));
+        assert_eq!(index.find_node(&unknown), None);
+        assert_eq!(index.test_entries(&unknown).len(), 2);
+    }
+
+    #[test]
+    fn test_index_flow_style_and_quoted_keys() {
+        let mut index = YamlIndex::default();
+        let contents = "\
+version: 2
+# Sources live here too
+\"sources\":
+  - {name: raw, tables: [{name: payments, columns: [{name: id, tests: [unique, not_null]}]}]}
+models:
+  - \"name\": customers
+    columns: [{name: id}]
+
+    # trailing comment
+";
+        index.add_file("models/sources.yml", contents).unwrap();
+
+        let table = resource(&index, YamlEntryKind::SourceTable, "raw.payments").unwrap();
+        assert_eq!((table.line, table.end_line), (4, 4));
+        let tests: Vec<_> = index
+            .entries
+            .iter()
+            .filter(|e| e.kind == YamlEntryKind::Test)
+            .map(|e| (e.resource.as_str(), e.column.as_deref(), e.test.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            tests,
+            vec![("raw.payments", Some("id"), "unique"), ("raw.payments", Some("id"), "not_null")]
+        );
+        let customers = resource(&index, YamlEntryKind::Model, "customers").unwrap();
+        assert_eq!((customers.line, customers.end_line), (6, 7));
+        assert_eq!(index.columns("customers")[0].line, 7);
+    }
+
+    #[test]
+    fn test_invalid_yaml() {
+        let mut index = YamlIndex::default();
+        assert!(index.add_file("bad.yml", "models: [").is_err());
+        assert!(index.entries.is_empty());
+    }
+}
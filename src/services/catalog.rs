@@ -0,0 +1,13 @@
+//! catalog.json loading service
+
+use crate::model::catalog::Catalog;
+use std::fs;
+use std::path::Path;
+
+/// Load target/catalog.json from a dbt project directory
+pub fn load_catalog<P: AsRef<Path>>(project_path: P) -> Result<Catalog, String> {
+    let contents = fs::read_to_string(project_path.as_ref().join("target").join("catalog.json"))
+        .map_err(|e| format!("Failed to read catalog.json: {}", e))?;
+
+    Catalog::from_json(&contents)
+}
@@ -107,10 +107,132 @@ pub fn build_dbt_show_command(
     (full_command, display_command)
 }
 
+/// Build a dbt show command for an inline query, e.g. a test's failing rows
+///
+/// Returns (full_command, display_command) tuple
+pub fn build_dbt_show_inline_command(
+    dbt_binary_path: &str,
+    project_path: &Path,
+    sql: &str,
+    limit: usize,
+) -> (String, String) {
+    let dbt_cmd = if dbt_binary_path.is_empty() {
+        "dbt".to_string()
+    } else {
+        dbt_binary_path.to_string()
+    };
+
+    let project_dir_arg = format!("--project-dir \"{}\"", project_path.display());
+
+    let profiles_dir_arg = if project_path.join("profiles.yml").exists() {
+        format!(" --profiles-dir \"{}\"", project_path.display())
+    } else {
+        String::new()
+    };
+
+    let full_command = format!(
+        "{} show --inline {} --limit {} {}{}",
+        dbt_cmd,
+        shell_quote(sql),
+        limit,
+        project_dir_arg,
+        profiles_dir_arg
+    );
+
+    let display_command = format!("dbt show --inline <query> --limit {}", limit);
+
+    (full_command, display_command)
+}
+
+/// Quote a value as a single shell argument
+fn shell_quote(value: &str) -> String {
+    if cfg!(target_os = "windows") {
+        cmd_quote(value)
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Quote a value as a single cmd.exe argument
+fn cmd_quote(value: &str) -> String {
+    // cmd has no multi-line arguments, so comments have to go before joining lines
+    let single_line = strip_sql_comments(value)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    // cmd toggles quoting at every `"`, so a doubled quote keeps the rest quoted (and
+    // reaches dbt as one `"`). `%` expands variables even inside quotes and can only
+    // be escaped outside them, so the quote is closed around `^%`.
+    format!("\"{}\"", single_line.replace('"', "\"\"").replace('%', "\"^%\""))
+}
+
+/// Remove `--` and `/* */` comments, leaving string literals and quoted
+/// identifiers (which may contain either) untouched
+fn strip_sql_comments(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                out.push(c);
+                // A doubled quote is an escaped one and keeps the literal open
+                while let Some(inner) = chars.next() {
+                    out.push(inner);
+                    if inner == c {
+                        match chars.next_if_eq(&c) {
+                            Some(escaped) => out.push(escaped),
+                            None => break,
+                        }
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for skipped in chars.by_ref() {
+                    if skipped == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for skipped in chars.by_ref() {
+                    if prev == '*' && skipped == '/' {
+                        break;
+                    }
+                    prev = skipped;
+                }
+                out.push(' ');
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
 /// Build a simple dbt compile command (no selector, no flags)
 ///
 /// Returns (full_command, display_command) tuple
 pub fn build_dbt_compile_command(dbt_binary_path: &str, project_path: &Path) -> (String, String) {
+    build_dbt_project_command(dbt_binary_path, project_path, "compile")
+}
+
+/// Build a dbt parse command, which rewrites the manifest without compiling
+///
+/// Returns (full_command, display_command) tuple
+pub fn build_dbt_parse_command(dbt_binary_path: &str, project_path: &Path) -> (String, String) {
+    build_dbt_project_command(dbt_binary_path, project_path, "parse")
+}
+
+/// Build a project-wide dbt command with no selector or flags
+fn build_dbt_project_command(
+    dbt_binary_path: &str,
+    project_path: &Path,
+    subcommand: &str,
+) -> (String, String) {
     let dbt_cmd = if dbt_binary_path.is_empty() {
         "dbt".to_string()
     } else {
@@ -126,8 +248,11 @@ pub fn build_dbt_compile_command(dbt_binary_path: &str, project_path: &Path) ->
         String::new()
     };
 
-    let full_command = format!("{} compile {}{}", dbt_cmd, project_dir_arg, profiles_dir_arg);
-    let display_command = "dbt compile".to_string();
+    let full_command = format!(
+        "{} {} {}{}",
+        dbt_cmd, subcommand, project_dir_arg, profiles_dir_arg
+    );
+    let display_command = format!("dbt {}", subcommand);
 
     (full_command, display_command)
 }
@@ -168,3 +293,31 @@ fn build_extra_flags(command: DbtCommand, flags: &RunFlags) -> (String, String)
     (extra_flags, display_extra_flags)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cmd_quote() {
+        assert_eq!(
+            cmd_quote("select \"name\"\nfrom orders -- all\nwhere name like '%PATH%'"),
+            "\"select \"\"name\"\" from orders where name like '\"^%\"PATH\"^%\"'\""
+        );
+    }
+
+    #[test]
+    fn test_strip_sql_comments() {
+        let sql = "\
+select id, '--not a comment' as dashes, 'it''s -- still' as quoted -- trailing
+from \"orders--2024\" /* block
+comment */ where note <> '/* kept */'
+-- whole line";
+        assert_eq!(
+            strip_sql_comments(sql),
+            "\
+select id, '--not a comment' as dashes, 'it''s -- still' as quoted \n\
+from \"orders--2024\"   where note <> '/* kept */'
+"
+        );
+    }
+}
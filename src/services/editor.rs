@@ -0,0 +1,190 @@
+//! External editor invocation
+//!
+//! Builds the command line used to open a file, optionally at a line and
+//! column. A configured template such as `nvim +{line} {file}` or
+//! `code -g {file}:{line}:{col}` takes precedence; otherwise the arguments
+//! are derived from the editor name in `$VISUAL`/`$EDITOR`.
+
+use std::path::Path;
+
+/// A file to open in the external editor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditorTarget {
+    pub path: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl EditorTarget {
+    pub fn file(path: String) -> Self {
+        Self {
+            path,
+            line: None,
+            column: None,
+        }
+    }
+
+    pub fn at_line(path: String, line: Option<usize>) -> Self {
+        Self {
+            path,
+            line,
+            column: None,
+        }
+    }
+}
+
+/// Editor from $VISUAL or $EDITOR, falling back to vim
+pub fn default_editor() -> String {
+    std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vim".to_string())
+}
+
+/// Program and arguments to open the target.
+/// Uses `template` if configured, otherwise `editor` (which may include arguments, e.g. "code --wait").
+pub fn editor_command(
+    editor: &str,
+    template: Option<&str>,
+    target: &EditorTarget,
+) -> (String, Vec<String>) {
+    if let Some(template) = template.filter(|t| !t.trim().is_empty()) {
+        return expand_template(template, target);
+    }
+
+    let mut parts = editor.split_whitespace().map(String::from);
+    let program = parts.next().unwrap_or_else(|| "vim".to_string());
+    let mut args: Vec<String> = parts.collect();
+
+    let name = Path::new(&program)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    match target.line {
+        None => args.push(target.path.clone()),
+        Some(line) => match name.as_str() {
+            "code" | "code-insiders" | "codium" | "cursor" => {
+                args.push("-g".to_string());
+                args.push(format!(
+                    "{}:{}:{}",
+                    target.path,
+                    line,
+                    target.column.unwrap_or(1)
+                ));
+            }
+            "subl" | "hx" | "helix" | "zed" => args.push(format!(
+                "{}:{}:{}",
+                target.path,
+                line,
+                target.column.unwrap_or(1)
+            )),
+            // vi, vim, nvim, nano, emacs, micro, kak, ... accept +LINE
+            _ => {
+                args.push(format!("+{}", line));
+                args.push(target.path.clone());
+            }
+        },
+    }
+
+    (program, args)
+}
+
+/// Expand `{file}`, `{line}` and `{col}` in a command template.
+/// Without a line, `:{line}`/`:{col}` suffixes are removed and arguments that
+/// only carry a position (e.g. `+{line}`) are dropped.
+fn expand_template(template: &str, target: &EditorTarget) -> (String, Vec<String>) {
+    let line = target.line;
+    let column = target.column.or(line.map(|_| 1));
+
+    let mut has_file = false;
+    let mut tokens: Vec<String> = Vec::new();
+    for token in template.split_whitespace() {
+        let mut token = token.to_string();
+        for (placeholder, value) in [("{line}", line), ("{col}", column)] {
+            if !token.contains(placeholder) {
+                continue;
+            }
+            match value {
+                Some(value) => token = token.replace(placeholder, &value.to_string()),
+                None => token = token.replace(&format!(":{}", placeholder), ""),
+            }
+        }
+        if token.contains("{line}") || token.contains("{col}") {
+            // A position-only argument without a position
+            continue;
+        }
+        if token.contains("{file}") {
+            has_file = true;
+            token = token.replace("{file}", &target.path);
+        }
+        tokens.push(token);
+    }
+
+    if !has_file {
+        tokens.push(target.path.clone());
+    }
+
+    let program = tokens.remove(0);
+    (program, tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(line: Option<usize>, column: Option<usize>) -> EditorTarget {
+        EditorTarget {
+            path: "models/orders.sql".to_string(),
+            line,
+            column,
+        }
+    }
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_editor_command_from_name() {
+        assert_eq!(
+            editor_command("vim", None, &target(None, None)),
+            ("vim".to_string(), args(&["models/orders.sql"]))
+        );
+        assert_eq!(
+            editor_command("/usr/bin/nvim", None, &target(Some(3), None)),
+            ("/usr/bin/nvim".to_string(), args(&["+3", "models/orders.sql"]))
+        );
+        assert_eq!(
+            editor_command("code --wait", None, &target(Some(3), Some(7))),
+            (
+                "code".to_string(),
+                args(&["--wait", "-g", "models/orders.sql:3:7"])
+            )
+        );
+    }
+
+    #[test]
+    fn test_editor_command_from_template() {
+        assert_eq!(
+            editor_command("vim", Some("nvim +{line} {file}"), &target(Some(3), None)),
+            ("nvim".to_string(), args(&["+3", "models/orders.sql"]))
+        );
+        assert_eq!(
+            editor_command("vim", Some("nvim +{line} {file}"), &target(None, None)),
+            ("nvim".to_string(), args(&["models/orders.sql"]))
+        );
+        assert_eq!(
+            editor_command("vim", Some("code -g {file}:{line}:{col}"), &target(Some(3), None)),
+            ("code".to_string(), args(&["-g", "models/orders.sql:3:1"]))
+        );
+        assert_eq!(
+            editor_command("vim", Some("code -g {file}:{line}:{col}"), &target(None, None)),
+            ("code".to_string(), args(&["-g", "models/orders.sql"]))
+        );
+        // Without {file}, the path is appended
+        assert_eq!(
+            editor_command("vim", Some("emacsclient -n"), &target(None, None)),
+            ("emacsclient".to_string(), args(&["-n", "models/orders.sql"]))
+        );
+    }
+}
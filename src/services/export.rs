@@ -0,0 +1,360 @@
+//! Run result export service
+//!
+//! Writes a run summary as JUnit XML (for CI test reporters), JSON or a
+//! Markdown report.
+
+use crate::model::run_summary::{NodeSummary, RunSummary};
+use crate::model::RunStatus;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Supported export formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    JUnit,
+    Json,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [
+        ExportFormat::JUnit,
+        ExportFormat::Json,
+        ExportFormat::Markdown,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::JUnit => "JUnit XML",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Markdown => "Markdown",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::JUnit => "xml",
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+        }
+    }
+}
+
+/// Render the summary in the given format and write it to `path`
+pub fn export_run<P: AsRef<Path>>(
+    summary: &RunSummary,
+    format: ExportFormat,
+    path: P,
+) -> Result<(), String> {
+    let contents = match format {
+        ExportFormat::JUnit => to_junit_xml(summary),
+        ExportFormat::Json => to_json(summary)?,
+        ExportFormat::Markdown => to_markdown(summary),
+    };
+
+    let path = path.as_ref();
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// JUnit XML with one testcase per node
+pub fn to_junit_xml(summary: &RunSummary) -> String {
+    let failures = summary.nodes.iter().filter(|n| n.status == "fail").count();
+    let errors = summary
+        .nodes
+        .iter()
+        .filter(|n| n.is_failure() && n.status != "fail")
+        .count();
+    let skipped = summary.nodes.iter().filter(|n| n.is_skipped()).count();
+    let total_time: f64 = summary.nodes.iter().filter_map(|n| n.duration_secs).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"dbt\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n",
+        summary.nodes.len(),
+        failures,
+        errors,
+        skipped,
+        summary.duration_secs.unwrap_or(total_time)
+    ));
+
+    let timestamp = summary
+        .timestamp
+        .map(|ts| format!(" timestamp=\"{}\"", ts.format("%Y-%m-%dT%H:%M:%S")))
+        .unwrap_or_default();
+    xml.push_str(&format!(
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\"{}>\n",
+        xml_escape(&summary.command),
+        summary.nodes.len(),
+        failures,
+        errors,
+        skipped,
+        total_time,
+        timestamp
+    ));
+
+    for node in &summary.nodes {
+        let attrs = format!(
+            "classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+            xml_escape(&node.resource_type),
+            xml_escape(node.unique_id.as_deref().unwrap_or(&node.name)),
+            node.duration_secs.unwrap_or(0.0)
+        );
+        let message = xml_escape(node.message.as_deref().unwrap_or(&node.status));
+
+        if node.status == "fail" {
+            xml.push_str(&format!(
+                "    <testcase {}>\n      <failure message=\"{}\" type=\"fail\"/>\n    </testcase>\n",
+                attrs, message
+            ));
+        } else if node.is_failure() {
+            xml.push_str(&format!(
+                "    <testcase {}>\n      <error message=\"{}\" type=\"{}\"/>\n    </testcase>\n",
+                attrs,
+                message,
+                xml_escape(&node.status)
+            ));
+        } else if node.is_skipped() {
+            xml.push_str(&format!(
+                "    <testcase {}>\n      <skipped/>\n    </testcase>\n",
+                attrs
+            ));
+        } else {
+            xml.push_str(&format!("    <testcase {}/>\n", attrs));
+        }
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+/// Status counts included in the JSON export
+#[derive(Serialize)]
+struct StatusCounts {
+    total: usize,
+    success: usize,
+    pass: usize,
+    warn: usize,
+    fail: usize,
+    error: usize,
+    skipped: usize,
+}
+
+#[derive(Serialize)]
+struct JsonExport<'a> {
+    #[serde(flatten)]
+    summary: &'a RunSummary,
+    counts: StatusCounts,
+}
+
+/// Pretty-printed JSON with the summary and per-status counts
+pub fn to_json(summary: &RunSummary) -> Result<String, String> {
+    let export = JsonExport {
+        summary,
+        counts: StatusCounts {
+            total: summary.nodes.len(),
+            success: summary.count("success"),
+            pass: summary.count("pass"),
+            warn: summary.count("warn"),
+            fail: summary.count("fail"),
+            error: summary.count("error") + summary.count("runtime error"),
+            skipped: summary.count("skipped"),
+        },
+    };
+    serde_json::to_string_pretty(&export).map_err(|e| format!("Failed to serialize run: {}", e))
+}
+
+/// Markdown report with a header and one table row per node
+pub fn to_markdown(summary: &RunSummary) -> String {
+    let mut md = format!("# {}\n\n", inline_code(&summary.command));
+
+    md.push_str(&format!("- **Status:** {}\n", run_status_label(summary.status)));
+    if let Some(ts) = summary.timestamp {
+        md.push_str(&format!("- **Started:** {}\n", ts.format("%Y-%m-%d %H:%M:%S")));
+    }
+    if let Some(duration) = summary.duration_secs {
+        md.push_str(&format!("- **Duration:** {:.2}s\n", duration));
+    }
+    if let Some(ref target) = summary.target {
+        md.push_str(&format!("- **Target:** {}\n", target));
+    }
+    if let Some(ref invocation_id) = summary.invocation_id {
+        md.push_str(&format!("- **Invocation:** `{}`\n", invocation_id));
+    }
+
+    let failed = summary.nodes.iter().filter(|n| n.is_failure()).count();
+    let skipped = summary.nodes.iter().filter(|n| n.is_skipped()).count();
+    md.push_str(&format!(
+        "- **Nodes:** {} total, {} failed, {} skipped\n",
+        summary.nodes.len(),
+        failed,
+        skipped
+    ));
+
+    if summary.nodes.is_empty() {
+        md.push_str("\nNo node results.\n");
+        return md;
+    }
+
+    md.push_str("\n| Node | Type | Status | Duration | Message |\n");
+    md.push_str("|------|------|--------|----------|---------|\n");
+    for node in &summary.nodes {
+        md.push_str(&format!(
+            "| {} | {} | {} {} | {} | {} |\n",
+            markdown_escape(&node.name),
+            markdown_escape(&node.resource_type),
+            status_emoji(node),
+            node.status,
+            node.duration_secs
+                .map(|d| format!("{:.2}s", d))
+                .unwrap_or_default(),
+            markdown_escape(node.message.as_deref().unwrap_or(""))
+        ));
+    }
+    md
+}
+
+fn run_status_label(status: RunStatus) -> &'static str {
+    match status {
+        RunStatus::Running => "⏳ Running",
+        RunStatus::Success => "✅ Success",
+        RunStatus::Failed => "❌ Failed",
+    }
+}
+
+fn status_emoji(node: &NodeSummary) -> &'static str {
+    if node.is_failure() {
+        "❌"
+    } else if node.is_skipped() {
+        "⏭"
+    } else if node.status == "warn" {
+        "⚠️"
+    } else {
+        "✅"
+    }
+}
+
+/// Escape text for an attribute value. Line breaks and tabs are encoded so
+/// parsers don't normalize them to spaces; other control characters (e.g. the
+/// ESC of terminal colors) aren't allowed in XML and are dropped.
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\t' => escaped.push_str("&#9;"),
+            c if c < ' ' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Code span fenced with more backticks than any run inside the text
+fn inline_code(text: &str) -> String {
+    let longest_run = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest_run + 1);
+    // A space keeps a leading or trailing backtick from joining the fence
+    let padding = if text.starts_with('`') || text.ends_with('`') { " " } else { "" };
+    format!("{}{}{}{}{}", fence, padding, text, padding, fence)
+}
+
+/// Keep table cells on one line and avoid breaking the column layout
+fn markdown_escape(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\n', '\r'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(name: &str, resource_type: &str, status: &str, message: Option<&str>) -> NodeSummary {
+        NodeSummary {
+            name: name.to_string(),
+            unique_id: Some(format!("{}.proj.{}", resource_type, name)),
+            resource_type: resource_type.to_string(),
+            status: status.to_string(),
+            duration_secs: Some(0.5),
+            message: message.map(|m| m.to_string()),
+        }
+    }
+
+    fn summary() -> RunSummary {
+        RunSummary {
+            command: "dbt build".to_string(),
+            status: RunStatus::Failed,
+            timestamp: None,
+            duration_secs: Some(3.0),
+            target: Some("dev".to_string()),
+            invocation_id: Some("abc".to_string()),
+            nodes: vec![
+                node("orders", "model", "success", None),
+                node("unique_id", "test", "fail", Some("Got 2 results, configured to fail if != 0")),
+                node("customers", "model", "error", Some("relation \"x\" < y")),
+                node("stats", "model", "skipped", None),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_junit_xml() {
+        let xml = to_junit_xml(&summary());
+
+        assert!(xml.contains("tests=\"4\" failures=\"1\" errors=\"1\" skipped=\"1\""));
+        assert!(xml.contains("<testcase classname=\"model\" name=\"model.proj.orders\" time=\"0.500\"/>"));
+        assert!(xml.contains("<failure message=\"Got 2 results, configured to fail if != 0\""));
+        assert!(xml.contains("<error message=\"relation &quot;x&quot; &lt; y\" type=\"error\"/>"));
+        assert!(xml.contains("<skipped/>"));
+
+        // Line breaks survive in attributes; terminal colors aren't valid XML
+        let mut colored = summary();
+        colored.nodes[2].message = Some("\x1b[31mDatabase Error\x1b[0m\r\n  LINE 1".to_string());
+        let xml = to_junit_xml(&colored);
+        assert!(xml.contains("<error message=\"[31mDatabase Error[0m&#13;&#10;  LINE 1\""));
+    }
+
+    #[test]
+    fn test_json_counts() {
+        let json: serde_json::Value = serde_json::from_str(&to_json(&summary()).unwrap()).unwrap();
+
+        assert_eq!(json["command"], "dbt build");
+        assert_eq!(json["counts"]["total"], 4);
+        assert_eq!(json["counts"]["fail"], 1);
+        assert_eq!(json["nodes"][2]["status"], "error");
+    }
+
+    #[test]
+    fn test_markdown_table() {
+        let md = to_markdown(&summary());
+
+        assert!(md.starts_with("# `dbt build`"));
+        assert!(md.contains("- **Status:** ❌ Failed"));
+        assert!(md.contains("- **Nodes:** 4 total, 2 failed, 1 skipped"));
+        assert!(md.contains("| customers | model | ❌ error | 0.50s | relation \"x\" < y |"));
+    }
+
+    #[test]
+    fn test_markdown_inline_code() {
+        assert_eq!(inline_code("dbt run"), "`dbt run`");
+        assert_eq!(
+            inline_code("dbt run --vars '{a: `b`}'"),
+            "``dbt run --vars '{a: `b`}'``"
+        );
+        assert_eq!(inline_code("`x`"), "`` `x` ``");
+    }
+}
@@ -3,6 +3,7 @@
 //! Handles spawning and monitoring dbt commands in the background.
 
 use crate::model::run::{BackgroundJob, JobMessage, RunOutput, RunStatus};
+use chrono::Local;
 use regex::Regex;
 use std::io::{BufRead, BufReader};
 use std::process::{Command, Stdio};
@@ -57,7 +58,9 @@ impl JobRunner {
             start_instant: Instant::now(),
         });
 
-        RunOutput::new(display_command)
+        let mut run_output = RunOutput::new(display_command);
+        run_output.started_at = Some(Local::now());
+        run_output
     }
 
     /// Poll for job updates, returns true if there were updates
@@ -80,6 +83,7 @@ impl JobRunner {
                 }
                 Ok(JobMessage::Completed(exit_code)) => {
                     had_updates = true;
+                    run_output.duration_secs = Some(job.start_instant.elapsed().as_secs_f64());
                     run_output.status = if exit_code == Some(0) {
                         RunStatus::Success
                     } else {
@@ -88,6 +92,7 @@ impl JobRunner {
                 }
                 Ok(JobMessage::Error(err)) => {
                     had_updates = true;
+                    run_output.duration_secs = Some(job.start_instant.elapsed().as_secs_f64());
                     run_output.output.push_str(&format!("\nError: {}\n", err));
                     run_output.status = RunStatus::Failed;
                 }
@@ -95,6 +100,7 @@ impl JobRunner {
                 Err(std::sync::mpsc::TryRecvError::Disconnected) => {
                     if run_output.status == RunStatus::Running {
                         run_output.status = RunStatus::Failed;
+                        run_output.duration_secs = Some(job.start_instant.elapsed().as_secs_f64());
                     }
                     break;
                 }
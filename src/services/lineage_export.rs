@@ -0,0 +1,484 @@
+//! Lineage export service
+//!
+//! Collects the part of the lineage graph around a node, a selection or the
+//! whole project and renders it as a Graphviz DOT graph, a Mermaid flowchart
+//! or a JSON adjacency list, for embedding in design docs.
+
+use crate::model::lineage::{LineageGraph, LineageNode};
+use crate::model::{Exposure, Node, SourceTable};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Supported lineage export formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineageFormat {
+    #[default]
+    Dot,
+    Mermaid,
+    Json,
+}
+
+impl LineageFormat {
+    pub const ALL: [LineageFormat; 3] = [
+        LineageFormat::Dot,
+        LineageFormat::Mermaid,
+        LineageFormat::Json,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LineageFormat::Dot => "Graphviz DOT",
+            LineageFormat::Mermaid => "Mermaid flowchart",
+            LineageFormat::Json => "JSON adjacency list",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            LineageFormat::Dot => "dot",
+            LineageFormat::Mermaid => "mmd",
+            LineageFormat::Json => "json",
+        }
+    }
+}
+
+/// Which part of the graph to export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineageScope {
+    /// A node with its ancestors and descendants up to `depth` hops away
+    Node { unique_id: String, depth: usize },
+    /// Several nodes, each with its neighborhood up to `depth` hops away
+    Selection { unique_ids: Vec<String>, depth: usize },
+    /// Every node in the project
+    Project,
+}
+
+/// Resource types to include besides models, seeds and snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineageExportOptions {
+    pub tests: bool,
+    pub sources: bool,
+    pub exposures: bool,
+}
+
+impl Default for LineageExportOptions {
+    fn default() -> Self {
+        Self {
+            tests: false,
+            sources: true,
+            exposures: true,
+        }
+    }
+}
+
+/// A node in the exported graph
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportNode {
+    pub unique_id: String,
+    pub name: String,
+    pub resource_type: String,
+    /// Parents within the export
+    pub depends_on: Vec<String>,
+}
+
+/// The subgraph being exported, nodes sorted by unique_id
+#[derive(Debug, Clone, Serialize)]
+pub struct LineageExport {
+    /// Nodes the export was centered on (empty for the whole project)
+    pub roots: Vec<String>,
+    pub nodes: Vec<ExportNode>,
+}
+
+impl LineageExport {
+    /// Collect the nodes and edges in `scope`
+    pub fn build(
+        scope: &LineageScope,
+        options: LineageExportOptions,
+        graph: &LineageGraph,
+        all_nodes: &[Node],
+        sources: &[SourceTable],
+        exposures: &[Exposure],
+    ) -> Self {
+        let mut labels: HashMap<&str, (String, String)> = HashMap::new();
+        for node in all_nodes {
+            labels.insert(&node.unique_id, (node.name.clone(), node.resource_type.clone()));
+        }
+        for source in sources {
+            labels.insert(
+                &source.unique_id,
+                (
+                    format!("{}.{}", source.source_name, source.name),
+                    "source".to_string(),
+                ),
+            );
+        }
+        for exposure in exposures {
+            labels.insert(&exposure.unique_id, (exposure.name.clone(), "exposure".to_string()));
+        }
+        let label = |id: &str| -> (String, String) {
+            labels.get(id).cloned().unwrap_or_else(|| {
+                let node = LineageNode::from_unique_id(id);
+                (node.name, node.resource_type)
+            })
+        };
+        let included = |id: &str| match label(id).1.as_str() {
+            "test" => options.tests,
+            "source" => options.sources,
+            "exposure" => options.exposures,
+            _ => true,
+        };
+
+        let exposure_parents: HashMap<&str, &Vec<String>> = exposures
+            .iter()
+            .map(|e| (e.unique_id.as_str(), &e.depends_on.nodes))
+            .collect();
+        let upstream = |id: &str| -> Vec<String> {
+            match exposure_parents.get(id) {
+                Some(parents) => parents.to_vec(),
+                None => graph.get_upstream(id).into_iter().map(|n| n.unique_id).collect(),
+            }
+        };
+        let downstream = |id: &str| -> Vec<String> {
+            let mut children: Vec<String> = graph
+                .get_downstream(id)
+                .into_iter()
+                .map(|n| n.unique_id)
+                .collect();
+            children.extend(
+                exposures
+                    .iter()
+                    .filter(|e| e.depends_on.nodes.iter().any(|d| d == id))
+                    .map(|e| e.unique_id.clone()),
+            );
+            children
+        };
+
+        let (roots, depth) = match scope {
+            LineageScope::Node { unique_id, depth } => (vec![unique_id.clone()], Some(*depth)),
+            LineageScope::Selection { unique_ids, depth } => (unique_ids.clone(), Some(*depth)),
+            LineageScope::Project => (Vec::new(), None),
+        };
+
+        let members: BTreeSet<String> = match depth {
+            None => labels
+                .keys()
+                .filter(|id| included(id))
+                .map(|id| id.to_string())
+                .collect(),
+            Some(depth) => {
+                let mut members: BTreeSet<String> = roots.iter().cloned().collect();
+                // Walk each direction separately so siblings are not pulled in
+                for step in [&upstream as &dyn Fn(&str) -> Vec<String>, &downstream] {
+                    let mut seen: HashSet<String> = roots.iter().cloned().collect();
+                    let mut frontier = roots.clone();
+                    for _ in 0..depth {
+                        let mut next = Vec::new();
+                        for id in &frontier {
+                            for neighbor in step(id) {
+                                if included(&neighbor) && seen.insert(neighbor.clone()) {
+                                    next.push(neighbor);
+                                }
+                            }
+                        }
+                        members.extend(next.iter().cloned());
+                        frontier = next;
+                    }
+                }
+                members
+            }
+        };
+
+        let nodes = members
+            .iter()
+            .map(|id| {
+                let (name, resource_type) = label(id);
+                let mut depends_on: Vec<String> = upstream(id)
+                    .into_iter()
+                    .filter(|parent| members.contains(parent))
+                    .collect();
+                depends_on.sort();
+                depends_on.dedup();
+                ExportNode {
+                    unique_id: id.clone(),
+                    name,
+                    resource_type,
+                    depends_on,
+                }
+            })
+            .collect();
+
+        Self { roots, nodes }
+    }
+
+    /// Render in the given format
+    pub fn render(&self, format: LineageFormat) -> Result<String, String> {
+        match format {
+            LineageFormat::Dot => Ok(self.to_dot()),
+            LineageFormat::Mermaid => Ok(self.to_mermaid()),
+            LineageFormat::Json => self.to_json(),
+        }
+    }
+
+    /// Graphviz digraph, left to right, filled by resource type
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph lineage {\n");
+        dot.push_str("  rankdir=LR;\n");
+        dot.push_str("  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n");
+        for node in &self.nodes {
+            let mut attrs = format!(
+                "label=\"{}\", fillcolor=\"{}\"",
+                dot_escape(&node.name),
+                fill_color(&node.resource_type)
+            );
+            if self.roots.contains(&node.unique_id) {
+                attrs.push_str(", penwidth=2");
+            }
+            dot.push_str(&format!("  \"{}\" [{}];\n", dot_escape(&node.unique_id), attrs));
+        }
+        for node in &self.nodes {
+            for parent in &node.depends_on {
+                dot.push_str(&format!(
+                    "  \"{}\" -> \"{}\";\n",
+                    dot_escape(parent),
+                    dot_escape(&node.unique_id)
+                ));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Mermaid flowchart, left to right, with one class per resource type
+    pub fn to_mermaid(&self) -> String {
+        let ids: HashMap<&str, String> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.unique_id.as_str(), format!("n{}", i)))
+            .collect();
+
+        let mut mermaid = String::from("flowchart LR\n");
+        let mut classes = BTreeSet::new();
+        for node in &self.nodes {
+            let class = mermaid_class(&node.resource_type);
+            classes.insert((class, node.resource_type.as_str()));
+            mermaid.push_str(&format!(
+                "  {}[\"{}\"]:::{}\n",
+                ids[node.unique_id.as_str()],
+                node.name.replace('"', "#quot;"),
+                class
+            ));
+        }
+        for node in &self.nodes {
+            for parent in &node.depends_on {
+                mermaid.push_str(&format!(
+                    "  {} --> {}\n",
+                    ids[parent.as_str()],
+                    ids[node.unique_id.as_str()]
+                ));
+            }
+        }
+        let mut defined = HashSet::new();
+        for (class, resource_type) in classes {
+            if defined.insert(class) {
+                mermaid.push_str(&format!(
+                    "  classDef {} fill:{},stroke:#555\n",
+                    class,
+                    fill_color(resource_type)
+                ));
+            }
+        }
+        for root in &self.roots {
+            if let Some(id) = ids.get(root.as_str()) {
+                mermaid.push_str(&format!("  style {} stroke-width:3px\n", id));
+            }
+        }
+        mermaid
+    }
+
+    /// Pretty-printed JSON with the roots and each node's parents
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize lineage: {}", e))
+    }
+
+    /// Number of edges in the export
+    pub fn edge_count(&self) -> usize {
+        self.nodes.iter().map(|n| n.depends_on.len()).sum()
+    }
+}
+
+/// Render the export and write it to `path`
+pub fn export_lineage<P: AsRef<Path>>(
+    export: &LineageExport,
+    format: LineageFormat,
+    path: P,
+) -> Result<(), String> {
+    let contents = export.render(format)?;
+
+    let path = path.as_ref();
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn fill_color(resource_type: &str) -> &'static str {
+    match resource_type {
+        "model" => "#dbeafe",
+        "source" => "#dcfce7",
+        "seed" => "#fef9c3",
+        "snapshot" => "#f3e8ff",
+        "test" => "#f3f4f6",
+        "exposure" => "#ffedd5",
+        _ => "#ffffff",
+    }
+}
+
+fn mermaid_class(resource_type: &str) -> &'static str {
+    match resource_type {
+        "model" => "model",
+        "source" => "source",
+        "seed" => "seed",
+        "snapshot" => "snapshot",
+        "test" => "test",
+        "exposure" => "exposure",
+        _ => "other",
+    }
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::node::DependsOn;
+
+    fn project() -> (Vec<Node>, Vec<SourceTable>, Vec<Exposure>) {
+        let nodes = vec![
+            Node::fixture("stg_orders", "model", &["source.shop.raw.orders"]),
+            Node::fixture("orders", "model", &["model.shop.stg_orders"]),
+            Node::fixture("revenue", "model", &["model.shop.orders"]),
+            Node::fixture("not_null_stg_orders_id", "test", &["model.shop.stg_orders"]),
+        ];
+        let sources = vec![SourceTable {
+            unique_id: "source.shop.raw.orders".to_string(),
+            name: "orders".to_string(),
+            source_name: "raw".to_string(),
+            ..Default::default()
+        }];
+        let exposures = vec![Exposure {
+            unique_id: "exposure.shop.dashboard".to_string(),
+            name: "dashboard".to_string(),
+            depends_on: DependsOn {
+                nodes: vec!["model.shop.revenue".to_string()],
+                macros: Vec::new(),
+            },
+            ..Default::default()
+        }];
+        (nodes, sources, exposures)
+    }
+
+    fn ids(export: &LineageExport) -> Vec<&str> {
+        export.nodes.iter().map(|n| n.unique_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_node_scope_depth_and_options() {
+        let (nodes, sources, exposures) = project();
+        let graph = LineageGraph::build(&nodes);
+        let scope = LineageScope::Node {
+            unique_id: "model.shop.stg_orders".to_string(),
+            depth: 1,
+        };
+
+        let export = LineageExport::build(
+            &scope,
+            LineageExportOptions::default(),
+            &graph,
+            &nodes,
+            &sources,
+            &exposures,
+        );
+        assert_eq!(
+            ids(&export),
+            vec!["model.shop.orders", "model.shop.stg_orders", "source.shop.raw.orders"]
+        );
+        assert_eq!(export.edge_count(), 2);
+
+        let options = LineageExportOptions {
+            tests: true,
+            sources: false,
+            exposures: true,
+        };
+        let export = LineageExport::build(&scope, options, &graph, &nodes, &sources, &exposures);
+        assert_eq!(
+            ids(&export),
+            vec![
+                "model.shop.orders",
+                "model.shop.stg_orders",
+                "test.shop.not_null_stg_orders_id"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_project_scope_includes_exposures() {
+        let (nodes, sources, exposures) = project();
+        let graph = LineageGraph::build(&nodes);
+        let export = LineageExport::build(
+            &LineageScope::Project,
+            LineageExportOptions::default(),
+            &graph,
+            &nodes,
+            &sources,
+            &exposures,
+        );
+        assert_eq!(export.nodes.len(), 5);
+        let dashboard = &export.nodes[0];
+        assert_eq!(dashboard.resource_type, "exposure");
+        assert_eq!(dashboard.depends_on, vec!["model.shop.revenue"]);
+        assert_eq!(export.edge_count(), 4);
+    }
+
+    #[test]
+    fn test_renderers() {
+        let (nodes, sources, exposures) = project();
+        let graph = LineageGraph::build(&nodes);
+        let export = LineageExport::build(
+            &LineageScope::Node {
+                unique_id: "model.shop.orders".to_string(),
+                depth: 1,
+            },
+            LineageExportOptions::default(),
+            &graph,
+            &nodes,
+            &sources,
+            &exposures,
+        );
+
+        let dot = export.to_dot();
+        assert!(dot.starts_with("digraph lineage {\n"));
+        assert!(dot.contains(
+            "  \"model.shop.orders\" [label=\"orders\", fillcolor=\"#dbeafe\", penwidth=2];\n"
+        ));
+        assert!(dot.contains("  \"model.shop.stg_orders\" -> \"model.shop.orders\";\n"));
+
+        let mermaid = export.to_mermaid();
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("  n0[\"orders\"]:::model\n"));
+        assert!(mermaid.contains("  n2 --> n0\n"));
+        assert!(mermaid.contains("  n0 --> n1\n"));
+        assert!(mermaid.contains("  style n0 stroke-width:3px\n"));
+
+        let json: serde_json::Value = serde_json::from_str(&export.to_json().unwrap()).unwrap();
+        assert_eq!(json["roots"][0], "model.shop.orders");
+        assert_eq!(json["nodes"][1]["unique_id"], "model.shop.revenue");
+        assert_eq!(json["nodes"][1]["depends_on"][0], "model.shop.orders");
+    }
+}
@@ -0,0 +1,36 @@
+//! Modeling-convention lint service
+//!
+//! Loads the project's lint rules and runs the linter, both for the lint
+//! panel and for the headless `dbt-tui check` command.
+
+use crate::model::lint::{lint, LintConfig, LintViolation, LINT_CONFIG_FILE};
+use crate::services::{filter_nodes, list_sources, load_manifest, load_yaml_index};
+use std::fs;
+use std::path::Path;
+
+/// Rules from `dbt-tui-lint.yml` in the project root, or the defaults
+pub fn load_lint_config(project_path: &Path) -> Result<LintConfig, String> {
+    let path = project_path.join(LINT_CONFIG_FILE);
+    if !path.exists() {
+        return Ok(LintConfig::default());
+    }
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", LINT_CONFIG_FILE, e))?;
+    serde_yaml::from_str(&contents).map_err(|e| format!("Failed to parse {}: {}", LINT_CONFIG_FILE, e))
+}
+
+/// Lint a project from its manifest, without the UI
+pub fn check_project(project_path: &Path) -> Result<Vec<LintViolation>, String> {
+    let manifest_path = project_path.join("target").join("manifest.json");
+    if !manifest_path.exists() {
+        return Err(format!(
+            "{} not found; run `dbt parse` first",
+            manifest_path.display()
+        ));
+    }
+    let manifest = load_manifest(&manifest_path)?;
+    let nodes = filter_nodes(&manifest);
+    let yaml_index = load_yaml_index(project_path, &nodes, &list_sources(&manifest));
+    let config = load_lint_config(project_path)?;
+    lint(&config, &nodes, &yaml_index)
+}
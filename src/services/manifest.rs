@@ -1,6 +1,7 @@
 //! Manifest loading and node filtering services
 
-use crate::model::{Manifest, Node};
+use crate::model::{Exposure, Macro, Manifest, Node, SourceTable};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 
@@ -48,11 +49,80 @@ pub fn filter_nodes(manifest: &Manifest) -> Vec<Node> {
     nodes
 }
 
+/// Source tables from the manifest, sorted by source and table name
+pub fn list_sources(manifest: &Manifest) -> Vec<SourceTable> {
+    let mut sources: Vec<SourceTable> = manifest.sources.values().cloned().collect();
+    sources.sort_by(|a, b| {
+        a.source_name
+            .cmp(&b.source_name)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    sources
+}
+
+/// Exposures from the manifest, sorted by name
+pub fn list_exposures(manifest: &Manifest) -> Vec<Exposure> {
+    let mut exposures: Vec<Exposure> = manifest.exposures.values().cloned().collect();
+    exposures.sort_by(|a, b| a.name.cmp(&b.name));
+    exposures
+}
+
+/// Analyses from the manifest, sorted by name. They are compiled but never
+/// run, so they stay out of the node list.
+pub fn list_analyses(manifest: &Manifest) -> Vec<Node> {
+    let mut analyses: Vec<Node> = manifest
+        .nodes
+        .values()
+        .filter(|n| n.resource_type == "analysis")
+        .cloned()
+        .collect();
+    analyses.sort_by(|a, b| a.name.cmp(&b.name));
+    analyses
+}
+
+/// Macros of the root project, sorted by name, with how many nodes and
+/// macros call each one. The root project comes from the manifest metadata,
+/// or failing that is any package defining models.
+pub fn list_macros(manifest: &Manifest) -> Vec<Macro> {
+    let packages: HashSet<&str> = match manifest.metadata.project_name {
+        Some(ref name) => HashSet::from([name.as_str()]),
+        None => manifest
+            .nodes
+            .values()
+            .filter(|n| n.resource_type == "model")
+            .map(|n| n.package_name.as_str())
+            .collect(),
+    };
+
+    let mut callers: HashMap<&str, usize> = HashMap::new();
+    let calls = manifest
+        .nodes
+        .values()
+        .map(|n| &n.depends_on)
+        .chain(manifest.macros.values().map(|m| &m.depends_on));
+    for depends_on in calls {
+        for id in depends_on.macros.iter().collect::<HashSet<_>>() {
+            *callers.entry(id.as_str()).or_default() += 1;
+        }
+    }
+
+    let mut macros: Vec<Macro> = manifest
+        .macros
+        .values()
+        .filter(|m| packages.contains(m.package_name.as_str()))
+        .map(|m| Macro {
+            callers: callers.get(m.unique_id.as_str()).copied().unwrap_or(0),
+            ..m.clone()
+        })
+        .collect();
+    macros.sort_by(|a, b| a.name.cmp(&b.name));
+    macros
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::node::{DependsOn, NodeConfig};
-    use std::collections::HashMap;
+    use crate::model::node::{DependsOn, ManifestMetadata, NodeConfig};
 
     #[test]
     fn test_filter_nodes_only_includes_relevant_types() {
@@ -75,6 +145,7 @@ mod tests {
                 compiled_path: None,
                 description: None,
                 columns: HashMap::new(),
+                ..Default::default()
             },
         );
 
@@ -95,10 +166,17 @@ mod tests {
                 compiled_path: None,
                 description: None,
                 columns: HashMap::new(),
+                ..Default::default()
             },
         );
 
-        let manifest = Manifest { nodes };
+        let manifest = Manifest {
+            metadata: ManifestMetadata::default(),
+            nodes,
+            sources: HashMap::new(),
+            exposures: HashMap::new(),
+            macros: HashMap::new(),
+        };
         let filtered = filter_nodes(&manifest);
 
         assert_eq!(filtered.len(), 1);
@@ -6,20 +6,51 @@
 //! - Project information reading
 //! - Profile parsing
 //! - Background job execution
+//! - Run results and catalog artifact loading
 //! - Git repository operations
+//! - Run result export
+//! - Lineage export to DOT, Mermaid and JSON
+//! - External editor invocation
+//! - Schema YAML indexing and editing
+//! - New model templates
+//! - Model rename/move refactoring
+//! - Modeling-convention linting
 
+pub mod catalog;
 pub mod dbt;
+pub mod editor;
+pub mod export;
 pub mod git;
 pub mod job_runner;
+pub mod lineage_export;
+pub mod lint;
 pub mod manifest;
+pub mod model_template;
 pub mod profile;
 pub mod project;
+pub mod refactor;
+pub mod run_results;
+pub mod yaml_edit;
+pub mod yaml_index;
 
-pub use dbt::{build_dbt_command, build_dbt_compile_command, build_dbt_show_command};
+pub use catalog::load_catalog;
+pub use dbt::{
+    build_dbt_command, build_dbt_compile_command, build_dbt_parse_command, build_dbt_show_command,
+    build_dbt_show_inline_command,
+};
+pub use export::{export_run, ExportFormat};
 pub use git::{
     commit, get_file_full_diff, get_log, get_status, is_git_repo, stage_file, GitFileStatus,
 };
 pub use job_runner::JobRunner;
-pub use manifest::{filter_nodes, load_manifest};
+pub use lineage_export::{
+    export_lineage, LineageExport, LineageExportOptions, LineageFormat, LineageScope,
+};
+pub use lint::{check_project, load_lint_config};
+pub use manifest::{
+    filter_nodes, list_analyses, list_exposures, list_macros, list_sources, load_manifest,
+};
 pub use profile::{parse_profiles, TargetInfo};
 pub use project::get_project_info;
+pub use run_results::{load_invocation_log, load_run_results, run_results_modified};
+pub use yaml_index::load_yaml_index;
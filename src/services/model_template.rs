@@ -0,0 +1,328 @@
+//! New model templates
+//!
+//! Renders the SQL for models created by the new model wizard and writes
+//! the file into the project's model folders.
+
+use crate::model::catalog::WarehouseColumn;
+use crate::model::Node;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+/// Starting point for a new model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelTemplate {
+    Blank,
+    Staging,
+    Incremental,
+}
+
+impl ModelTemplate {
+    pub const ALL: [ModelTemplate; 3] = [
+        ModelTemplate::Blank,
+        ModelTemplate::Staging,
+        ModelTemplate::Incremental,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ModelTemplate::Blank => "Blank model",
+            ModelTemplate::Staging => "Staging model from a source",
+            ModelTemplate::Incremental => "Incremental model skeleton",
+        }
+    }
+}
+
+/// Source table a staging model selects from
+#[derive(Debug, Clone)]
+pub struct StagingSource {
+    pub source_name: String,
+    pub table: String,
+    pub columns: Vec<WarehouseColumn>,
+}
+
+impl StagingSource {
+    /// Columns as named in the staging model: snake_case, keeping data types
+    pub fn renamed_columns(&self) -> Vec<WarehouseColumn> {
+        self.columns
+            .iter()
+            .map(|c| WarehouseColumn {
+                name: snake_case(&c.name),
+                data_type: c.data_type.clone(),
+            })
+            .collect()
+    }
+}
+
+/// "OrderID" -> "order_id", "Created At" -> "created_at", "ID" -> "id"
+pub fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_alphanumeric() {
+            // Word boundary before an uppercase letter that starts a new word
+            let prev = i.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(i + 1);
+            let starts_word = c.is_uppercase()
+                && prev.is_some_and(|p| {
+                    p.is_lowercase() || p.is_ascii_digit()
+                        || (p.is_uppercase() && next.is_some_and(|n| n.is_lowercase()))
+                });
+            if starts_word && !out.ends_with('_') {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else if !out.is_empty() && !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    out.trim_end_matches('_').to_string()
+}
+
+/// Suggested name for a new model
+pub fn default_model_name(source: Option<&StagingSource>) -> String {
+    match source {
+        Some(source) => format!(
+            "stg_{}__{}",
+            snake_case(&source.source_name),
+            snake_case(&source.table)
+        ),
+        None => String::new(),
+    }
+}
+
+/// SQL for a new model
+pub fn render_model(template: ModelTemplate, source: Option<&StagingSource>) -> String {
+    match (template, source) {
+        (ModelTemplate::Staging, Some(source)) => render_staging(source),
+        (ModelTemplate::Incremental, _) => "\
+{{
+    config(
+        materialized='incremental',
+        unique_key='id'
+    )
+}}
+
+with source as (
+
+    -- Replace with the upstream model or source
+    select
+        1 as id,
+        current_timestamp as updated_at
+
+)
+
+select * from source
+
+{% if is_incremental() %}
+
+where updated_at > (select max(updated_at) from {{ this }})
+
+{% endif %}
+"
+        .to_string(),
+        _ => "\
+with source as (
+
+    -- Replace with the upstream model or source
+    select 1 as id
+
+)
+
+select * from source
+"
+        .to_string(),
+    }
+}
+
+/// Identifiers other than plain lowercase ones only match the warehouse
+/// column when quoted, in the adapter's own quoting style
+fn quote_identifier(name: &str) -> String {
+    let plain = name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if plain {
+        name.to_string()
+    } else {
+        format!("{{{{ adapter.quote('{}') }}}}", name.replace('\\', "\\\\").replace('\'', "\\'"))
+    }
+}
+
+fn render_staging(source: &StagingSource) -> String {
+    let columns = if source.columns.is_empty() {
+        "        -- No columns known for this source; run dbt docs generate to list them\n        *"
+            .to_string()
+    } else {
+        source
+            .columns
+            .iter()
+            .zip(source.renamed_columns())
+            .map(|(original, renamed)| {
+                if original.name == renamed.name {
+                    format!("        {}", original.name)
+                } else {
+                    format!("        {} as {}", quote_identifier(&original.name), renamed.name)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",\n")
+    };
+    format!(
+        "\
+with source as (
+
+    select * from {{{{ source('{}', '{}') }}}}
+
+),
+
+renamed as (
+
+    select
+{}
+
+    from source
+
+)
+
+select * from renamed
+",
+        source.source_name, source.table, columns
+    )
+}
+
+/// Folders models can be created in, relative to the project root: every
+/// directory under the model paths used by the project's models
+pub fn model_folders(project_path: &Path, nodes: &[Node]) -> Vec<String> {
+    let mut roots: BTreeSet<String> = nodes
+        .iter()
+        .filter(|n| n.resource_type == "model")
+        .filter_map(|n| n.original_file_path.as_deref())
+        .filter_map(|p| p.split('/').next().map(String::from))
+        .collect();
+    if roots.is_empty() {
+        roots.insert("models".to_string());
+    }
+
+    let mut folders = Vec::new();
+    for root in roots {
+        collect_folders(project_path, &root, &mut folders);
+    }
+    folders.sort();
+    folders
+}
+
+fn collect_folders(project_path: &Path, folder: &str, folders: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(project_path.join(folder)) else {
+        return;
+    };
+    folders.push(folder.to_string());
+    for entry in entries.filter_map(|e| e.ok()) {
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            collect_folders(project_path, &format!("{}/{}", folder, name), folders);
+        }
+    }
+}
+
+/// Whether a name can be used as a model name: an identifier of letters, digits and underscores
+pub fn is_valid_model_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Write a new model file. Returns its path relative to the project root.
+/// `nodes` are the project's nodes, whose model names can't be reused.
+pub fn create_model_file(
+    project_path: &Path,
+    nodes: &[Node],
+    folder: &str,
+    name: &str,
+    contents: &str,
+) -> Result<String, String> {
+    if !is_valid_model_name(name) {
+        return Err(format!("'{}' is not a valid model name", name));
+    }
+    if nodes.iter().any(|n| n.resource_type == "model" && n.name == name) {
+        return Err(format!("A model named {} already exists", name));
+    }
+
+    let file = format!("{}/{}.sql", folder.trim_end_matches('/'), name);
+    let path = project_path.join(&file);
+    if path.exists() {
+        return Err(format!("{} already exists", file));
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", file, e))?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_model_file() {
+        let path = std::env::temp_dir().join(format!("dbt-tui-new-model-{}", std::process::id()));
+        let nodes = vec![Node::fixture("orders", "model", &[])];
+
+        assert_eq!(
+            create_model_file(&path, &nodes, "models/marts", "orders", "select 1").unwrap_err(),
+            "A model named orders already exists"
+        );
+        assert!(create_model_file(&path, &nodes, "models", "1st_orders", "select 1").is_err());
+        assert_eq!(
+            create_model_file(&path, &nodes, "models/marts/", "customers", "select 1").unwrap(),
+            "models/marts/customers.sql"
+        );
+        assert!(create_model_file(&path, &nodes, "models/marts", "customers", "select 1").is_err());
+
+        fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("OrderID"), "order_id");
+        assert_eq!(snake_case("orderId"), "order_id");
+        assert_eq!(snake_case("Created At"), "created_at");
+        assert_eq!(snake_case("ID"), "id");
+        assert_eq!(snake_case("customer_id"), "customer_id");
+        assert_eq!(snake_case("HTTPStatus2xx"), "http_status2xx");
+    }
+
+    #[test]
+    fn test_render_staging() {
+        let source = StagingSource {
+            source_name: "raw".to_string(),
+            table: "Orders".to_string(),
+            columns: vec![
+                WarehouseColumn {
+                    name: "id".to_string(),
+                    data_type: None,
+                },
+                WarehouseColumn {
+                    name: "OrderDate".to_string(),
+                    data_type: Some("DATE".to_string()),
+                },
+                WarehouseColumn {
+                    name: "Created At".to_string(),
+                    data_type: None,
+                },
+                WarehouseColumn {
+                    name: "_loaded_at".to_string(),
+                    data_type: None,
+                },
+            ],
+        };
+        assert_eq!(default_model_name(Some(&source)), "stg_raw__orders");
+
+        let sql = render_model(ModelTemplate::Staging, Some(&source));
+        assert!(sql.contains("select * from {{ source('raw', 'Orders') }}"));
+        assert!(sql.contains(
+            "    select\n        id,\n        {{ adapter.quote('OrderDate') }} as order_date,\n        \
+             {{ adapter.quote('Created At') }} as created_at,\n        _loaded_at as loaded_at\n\n    from source"
+        ));
+    }
+}
@@ -0,0 +1,344 @@
+//! Model rename and move refactoring
+//!
+//! A plan is built first (every file edit with its old and new contents,
+//! plus the file move) so it can be previewed, then applied. Dependents are
+//! whatever the manifest says depends on the model: SQL models, snapshots,
+//! singular tests and analyses, the YAML files declaring generic tests such
+//! as `relationships` whose `to: ref(...)` points at the model, and the YAML
+//! files declaring exposures.
+
+use crate::model::domain::DomainState;
+use crate::model::Node;
+use crate::services::model_template::is_valid_model_name;
+use crate::services::yaml_edit::rename_entry;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// A file whose contents change
+#[derive(Debug, Clone)]
+pub struct FileEdit {
+    /// Relative to the project root
+    pub path: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Everything a rename or move changes
+#[derive(Debug, Clone)]
+pub struct RefactorPlan {
+    pub old_name: String,
+    pub new_name: String,
+    /// Model file, relative to the project root
+    pub from: String,
+    pub to: String,
+    pub edits: Vec<FileEdit>,
+}
+
+impl RefactorPlan {
+    pub fn is_move(&self) -> bool {
+        self.from != self.to
+    }
+}
+
+/// Rewrite `ref('old')`, `ref("old")` and `ref('package', 'old')` calls
+pub fn rewrite_refs(contents: &str, old_name: &str, new_name: &str) -> String {
+    let pattern = format!(
+        r#"(\bref\s*\(\s*(?:['"][^'"]*['"]\s*,\s*)?['"]){}(['"])"#,
+        regex::escape(old_name)
+    );
+    let regex = Regex::new(&pattern).expect("escaped model name");
+    regex
+        .replace_all(contents, format!("${{1}}{}${{2}}", new_name))
+        .into_owned()
+}
+
+/// Work out the edits for renaming `node` to `new_name` and moving it to `folder`
+pub fn plan_rename(
+    project_path: &Path,
+    node: &Node,
+    new_name: &str,
+    folder: &str,
+    domain: &DomainState,
+) -> Result<RefactorPlan, String> {
+    if !is_valid_model_name(new_name) {
+        return Err(format!("'{}' is not a valid model name", new_name));
+    }
+    let from = node
+        .original_file_path
+        .clone()
+        .ok_or_else(|| format!("No file known for {}", node.name))?;
+    // Python models stay Python models
+    let extension = Path::new(&from)
+        .extension()
+        .map(|e| e.to_string_lossy().to_string())
+        .unwrap_or_else(|| "sql".to_string());
+    let to = format!("{}/{}.{}", folder.trim_matches('/'), new_name, extension);
+    let renamed = new_name != node.name;
+
+    if !renamed && to == from {
+        return Err("Nothing to change".to_string());
+    }
+    if renamed
+        && domain
+            .all_nodes
+            .iter()
+            .any(|n| n.resource_type == "model" && n.name == new_name)
+    {
+        return Err(format!("A model named {} already exists", new_name));
+    }
+    if project_path.join(&to).exists() {
+        return Err(format!("{} already exists", to));
+    }
+
+    // Current contents of each touched file, and the edited version
+    let mut files: BTreeMap<String, (String, String)> = BTreeMap::new();
+    let mut edit = |path: &str, change: &dyn Fn(&str) -> Result<String, String>| {
+        if !files.contains_key(path) {
+            let contents = fs::read_to_string(project_path.join(path))
+                .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+            files.insert(path.to_string(), (contents.clone(), contents));
+        }
+        let (_, after) = files.get_mut(path).expect("inserted above");
+        *after = change(after)?;
+        Ok::<(), String>(())
+    };
+
+    if renamed {
+        let depends_on_node = |depends_on: &[String]| depends_on.contains(&node.unique_id);
+        let dependents = domain
+            .all_nodes
+            .iter()
+            .chain(&domain.analyses)
+            .filter(|n| depends_on_node(&n.depends_on.nodes))
+            .map(|n| n.original_file_path.as_deref())
+            .chain(
+                domain
+                    .exposures
+                    .iter()
+                    .filter(|e| depends_on_node(&e.depends_on.nodes))
+                    .map(|e| e.original_file_path.as_deref()),
+            );
+        for path in dependents.flatten() {
+            edit(path, &|contents| Ok(rewrite_refs(contents, &node.name, new_name)))?;
+        }
+        if let Some(entry) = domain.yaml_index.find_node(node) {
+            edit(&entry.file, &|contents| rename_entry(contents, entry, new_name))?;
+        }
+    }
+
+    let edits = files
+        .into_iter()
+        .filter(|(_, (before, after))| before != after)
+        .map(|(path, (before, after))| FileEdit { path, before, after })
+        .collect();
+
+    Ok(RefactorPlan {
+        old_name: node.name.clone(),
+        new_name: new_name.to_string(),
+        from,
+        to,
+        edits,
+    })
+}
+
+/// Move the model file, then write the edits. Refuses if any file changed
+/// since planning, and undoes the move and earlier writes if a write fails.
+pub fn apply_plan(project_path: &Path, plan: &RefactorPlan) -> Result<(), String> {
+    for edit in &plan.edits {
+        let current = fs::read_to_string(project_path.join(&edit.path))
+            .map_err(|e| format!("Failed to read {}: {}", edit.path, e))?;
+        if current != edit.before {
+            return Err(format!("{} changed since the preview; try again", edit.path));
+        }
+    }
+
+    let from = project_path.join(&plan.from);
+    let to = project_path.join(&plan.to);
+    if plan.is_move() {
+        if to.exists() {
+            return Err(format!("{} already exists", plan.to));
+        }
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        fs::rename(&from, &to)
+            .map_err(|e| format!("Failed to move {} to {}: {}", plan.from, plan.to, e))?;
+    }
+
+    for (written, edit) in plan.edits.iter().enumerate() {
+        if let Err(e) = fs::write(project_path.join(&edit.path), &edit.after) {
+            let mut message = format!("Failed to write {}: {}", edit.path, e);
+            for done in &plan.edits[..written] {
+                if let Err(e) = fs::write(project_path.join(&done.path), &done.before) {
+                    message.push_str(&format!("; failed to restore {}: {}", done.path, e));
+                }
+            }
+            if plan.is_move() {
+                if let Err(e) = fs::rename(&to, &from) {
+                    message.push_str(&format!("; failed to move {} back: {}", plan.to, e));
+                }
+            }
+            return Err(message);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::node::{DependsOn, Exposure};
+    use std::path::PathBuf;
+
+    const SCHEMA_YML: &str = "\
+models:
+  - name: orders
+    description: One row per order
+  - name: customers
+    columns:
+      - name: last_order_id
+        tests:
+          - relationships:
+              to: ref('orders')
+              field: id
+";
+
+    /// A project in a fresh temp dir: `customers`, a relationships test, an
+    /// analysis and an exposure all depend on `orders`
+    fn project(name: &str) -> (PathBuf, DomainState) {
+        let path = std::env::temp_dir().join(format!("dbt-tui-refactor-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&path);
+        let files = [
+            ("models/orders.sql", "select 1 as id\n"),
+            ("models/customers.sql", "select * from {{ ref('orders') }}\n"),
+            ("models/schema.yml", SCHEMA_YML),
+            ("models/exposures.yml", "exposures:\n  - name: dashboard\n    depends_on:\n      - ref('orders')\n"),
+            ("analyses/revenue.sql", "select sum(amount) from {{ ref('orders') }}\n"),
+        ];
+        for (file, contents) in files {
+            fs::create_dir_all(path.join(file).parent().unwrap()).unwrap();
+            fs::write(path.join(file), contents).unwrap();
+        }
+
+        let node = |name: &str, resource_type: &str, file: &str, depends_on: &[&str]| Node {
+            original_file_path: Some(file.to_string()),
+            ..Node::fixture(name, resource_type, depends_on)
+        };
+        let mut domain = DomainState::new();
+        domain.all_nodes = vec![
+            node("orders", "model", "models/orders.sql", &[]),
+            node("customers", "model", "models/customers.sql", &["model.shop.orders"]),
+            node(
+                "relationships_customers_last_order_id__id__ref_orders_",
+                "test",
+                "models/schema.yml",
+                &["model.shop.orders", "model.shop.customers"],
+            ),
+        ];
+        domain.analyses = vec![node("revenue", "analysis", "analyses/revenue.sql", &["model.shop.orders"])];
+        domain.exposures = vec![Exposure {
+            unique_id: "exposure.shop.dashboard".to_string(),
+            name: "dashboard".to_string(),
+            original_file_path: Some("models/exposures.yml".to_string()),
+            depends_on: DependsOn {
+                nodes: vec!["model.shop.orders".to_string()],
+                macros: Vec::new(),
+            },
+            ..Default::default()
+        }];
+        domain.yaml_index.add_file("models/schema.yml", SCHEMA_YML).unwrap();
+        (path, domain)
+    }
+
+    fn plan_orders(path: &Path, domain: &DomainState, new_name: &str, folder: &str) -> Result<RefactorPlan, String> {
+        plan_rename(path, &domain.all_nodes[0], new_name, folder, domain)
+    }
+
+    #[test]
+    fn test_plan_rename() {
+        let (path, domain) = project("plan");
+
+        let plan = plan_orders(&path, &domain, "fct_orders", "models/marts").unwrap();
+        assert_eq!(plan.from, "models/orders.sql");
+        assert_eq!(plan.to, "models/marts/fct_orders.sql");
+        let edits: Vec<&str> = plan.edits.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            edits,
+            vec!["analyses/revenue.sql", "models/customers.sql", "models/exposures.yml", "models/schema.yml"]
+        );
+        assert_eq!(plan.edits[1].after, "select * from {{ ref('fct_orders') }}\n");
+        assert!(plan.edits[2].after.contains("- ref('fct_orders')"));
+        let schema = &plan.edits[3].after;
+        assert!(schema.contains("  - name: fct_orders\n    description: One row per order"));
+        assert!(schema.contains("to: ref('fct_orders')"));
+        assert!(schema.contains("  - name: customers\n"));
+
+        assert_eq!(
+            plan_orders(&path, &domain, "customers", "models").unwrap_err(),
+            "A model named customers already exists"
+        );
+        assert_eq!(plan_orders(&path, &domain, "orders", "models/").unwrap_err(), "Nothing to change");
+        assert!(plan_orders(&path, &domain, "1orders", "models").is_err());
+
+        // A pure move edits nothing
+        let plan = plan_orders(&path, &domain, "orders", "models/marts").unwrap();
+        assert!(plan.is_move());
+        assert!(plan.edits.is_empty());
+
+        let mut python = domain.all_nodes[0].clone();
+        python.original_file_path = Some("models/orders.py".to_string());
+        let plan = plan_rename(&path, &python, "orders", "models/marts", &domain).unwrap();
+        assert_eq!(plan.to, "models/marts/orders.py");
+        fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn test_apply_plan() {
+        let (path, domain) = project("apply");
+
+        // Files edited after the preview are left alone, and nothing moves
+        let stale = plan_orders(&path, &domain, "fct_orders", "models/marts").unwrap();
+        fs::write(path.join("models/customers.sql"), "select 2\n").unwrap();
+        let err = apply_plan(&path, &stale).unwrap_err();
+        assert_eq!(err, "models/customers.sql changed since the preview; try again");
+        assert!(path.join("models/orders.sql").exists());
+        assert_eq!(fs::read_to_string(path.join("analyses/revenue.sql")).unwrap(), stale.edits[0].before);
+
+        fs::write(path.join("models/customers.sql"), "select * from {{ ref('orders') }}\n").unwrap();
+        let plan = plan_orders(&path, &domain, "fct_orders", "models/marts").unwrap();
+        apply_plan(&path, &plan).unwrap();
+        assert!(!path.join("models/orders.sql").exists());
+        assert_eq!(fs::read_to_string(path.join("models/marts/fct_orders.sql")).unwrap(), "select 1 as id\n");
+        for edit in &plan.edits {
+            assert_eq!(fs::read_to_string(path.join(&edit.path)).unwrap(), edit.after);
+        }
+        fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn test_rewrite_refs() {
+        let sql = "\
+select * from {{ ref('orders') }}
+join {{ ref(\"orders\") }} using (id)
+join {{ ref('shop', 'orders') }} using (id)
+join {{ ref('orders_daily') }} using (id)
+-- orders stays as is
+";
+        assert_eq!(
+            rewrite_refs(sql, "orders", "fct_orders"),
+            "\
+select * from {{ ref('fct_orders') }}
+join {{ ref(\"fct_orders\") }} using (id)
+join {{ ref('shop', 'fct_orders') }} using (id)
+join {{ ref('orders_daily') }} using (id)
+-- orders stays as is
+"
+        );
+        assert_eq!(
+            rewrite_refs("to: ref('orders')", "orders", "fct_orders"),
+            "to: ref('fct_orders')"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! run_results.json loading service
+//!
+//! Also reads the matching section of `logs/dbt.log`, used to import runs
+//! executed outside dbt-tui into history.
+
+use crate::model::RunResults;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::SystemTime;
+
+/// Invocation header in dbt.log, e.g.
+/// "============================== 10:00:00.123456 | abc-123 =============================="
+static LOG_HEADER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^=+ .* \| ([\w-]+) =+$").unwrap());
+
+/// Log line in dbt.log, e.g. "10:00:00.123456 [info ] [MainThread]: Running with dbt=1.7.4"
+static LOG_LINE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\d{2}:\d{2}:\d{2})\.\d+\s+\[(\w+)\s*\]\s+\[[^\]]*\]:\s?(.*)$").unwrap()
+});
+
+fn run_results_path(project_path: &Path) -> PathBuf {
+    project_path.join("target").join("run_results.json")
+}
+
+/// Load target/run_results.json from a dbt project directory
+pub fn load_run_results<P: AsRef<Path>>(project_path: P) -> Result<RunResults, String> {
+    let contents = fs::read_to_string(run_results_path(project_path.as_ref()))
+        .map_err(|e| format!("Failed to read run_results.json: {}", e))?;
+
+    RunResults::from_json(&contents)
+}
+
+/// Modification time of target/run_results.json, if it exists
+pub fn run_results_modified<P: AsRef<Path>>(project_path: P) -> Option<SystemTime> {
+    fs::metadata(run_results_path(project_path.as_ref()))
+        .and_then(|m| m.modified())
+        .ok()
+}
+
+/// Console output of an invocation, read from logs/dbt.log
+pub fn load_invocation_log<P: AsRef<Path>>(project_path: P, invocation_id: &str) -> Option<String> {
+    let contents = fs::read_to_string(project_path.as_ref().join("logs").join("dbt.log")).ok()?;
+    extract_invocation_log(&contents, invocation_id)
+}
+
+/// Extract the non-debug lines of one invocation from dbt.log contents,
+/// formatted like dbt's console output ("HH:MM:SS  message")
+fn extract_invocation_log(contents: &str, invocation_id: &str) -> Option<String> {
+    let mut lines = Vec::new();
+    let mut in_section = false;
+    let mut keep_continuation = false;
+
+    for line in contents.lines() {
+        if let Some(caps) = LOG_HEADER_REGEX.captures(line) {
+            if in_section {
+                break;
+            }
+            in_section = &caps[1] == invocation_id;
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+
+        match LOG_LINE_REGEX.captures(line) {
+            Some(caps) => {
+                keep_continuation = &caps[2] != "debug";
+                if keep_continuation {
+                    lines.push(format!("{}  {}", &caps[1], &caps[3]));
+                }
+            }
+            // Continuation of a multi-line message
+            None if keep_continuation => lines.push(line.to_string()),
+            None => {}
+        }
+    }
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_invocation_log() {
+        let log = "\
+============================== 09:00:00.000000 | old-1 ==============================
+09:00:00.100000 [info ] [MainThread]: Running with dbt=1.7.4
+============================== 10:00:00.000000 | abc-123 ==============================
+10:00:00.100000 [info ] [MainThread]: Running with dbt=1.7.4
+10:00:00.200000 [debug] [MainThread]: Acquiring new postgres connection
+10:00:01.000000 [error] [Thread-1 (worker)]: Database Error in model orders
+  relation \"raw.orders\" does not exist
+10:00:02.000000 [info ] [MainThread]: Done. PASS=0 WARN=0 ERROR=1 SKIP=0 TOTAL=1
+============================== 11:00:00.000000 | next-1 ==============================
+11:00:00.100000 [info ] [MainThread]: Running with dbt=1.7.4";
+
+        let output = extract_invocation_log(log, "abc-123").unwrap();
+        assert_eq!(
+            output,
+            "10:00:00  Running with dbt=1.7.4\n\
+             10:00:01  Database Error in model orders\n  \
+             relation \"raw.orders\" does not exist\n\
+             10:00:02  Done. PASS=0 WARN=0 ERROR=1 SKIP=0 TOTAL=1"
+        );
+
+        assert!(extract_invocation_log(log, "missing").is_none());
+    }
+}
@@ -0,0 +1,880 @@
+//! Schema YAML editing service
+//!
+//! Edits are made on the text, guided by the line positions from
+//! `YamlIndex`, so comments, quoting and layout elsewhere in the file are
+//! left untouched. The result is re-parsed before anything is written.
+
+use crate::model::catalog::WarehouseColumn;
+use crate::model::yaml_index::{YamlEntry, YamlEntryKind, YamlIndex};
+use crate::model::Node;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Section key and entry kind for a resource type that can be documented
+fn section_of(resource_type: &str) -> Option<(&'static str, YamlEntryKind)> {
+    match resource_type {
+        "model" => Some(("models", YamlEntryKind::Model)),
+        "seed" => Some(("seeds", YamlEntryKind::Seed)),
+        "snapshot" => Some(("snapshots", YamlEntryKind::Snapshot)),
+        _ => None,
+    }
+}
+
+/// Schema file a node's YAML is written to, relative to the project root:
+/// where it is declared, else its patch_path, else `schema.yml` next to its SQL
+pub fn schema_file_for(index: &YamlIndex, node: &Node) -> Option<String> {
+    if let Some(entry) = index.find_node(node) {
+        return Some(entry.file.clone());
+    }
+    if let Some(path) = node.schema_file_path() {
+        return Some(path.to_string());
+    }
+    let original = Path::new(node.original_file_path.as_ref()?);
+    let dir = original.parent().map(Path::to_path_buf).unwrap_or_default();
+    Some(dir.join("schema.yml").to_string_lossy().to_string())
+}
+
+/// Set the description of a node, or of one of its columns, in its schema
+/// YAML file. Creates the file, entry or column as needed.
+/// Returns the file written, relative to the project root.
+pub fn write_description(
+    project_path: &Path,
+    index: &YamlIndex,
+    node: &Node,
+    column: Option<&str>,
+    description: &str,
+) -> Result<String, String> {
+    edit_schema_file(project_path, index, node, column, |contents, file, target| {
+        set_description(contents, file, target, description)
+    })
+}
+
+/// Add a generic test to a column of a node in its schema YAML file.
+/// Returns the file written, relative to the project root.
+pub fn add_column_test(
+    project_path: &Path,
+    index: &YamlIndex,
+    node: &Node,
+    column: &str,
+    test: &GenericTest,
+) -> Result<String, String> {
+    edit_schema_file(project_path, index, node, Some(column), |contents, file, target| {
+        add_test(contents, file, target, test)
+    })
+}
+
+/// Remove the YAML declaration of a generic test node.
+/// Returns the file written, relative to the project root.
+pub fn remove_test(project_path: &Path, index: &YamlIndex, test: &Node) -> Result<String, String> {
+    let entry = match index.test_entries(test).as_slice() {
+        [entry] => *entry,
+        [] => return Err(format!("No YAML declaration found for {}", test.name)),
+        entries => {
+            return Err(format!(
+                "{} matches {} declarations in {}; remove it by hand",
+                test.name,
+                entries.len(),
+                entries[0].file
+            ))
+        }
+    };
+    let path = project_path.join(&entry.file);
+    let contents =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", entry.file, e))?;
+    let updated = remove_test_entry(&contents, &entry.file, entry)?;
+    fs::write(&path, updated).map_err(|e| format!("Failed to write {}: {}", entry.file, e))?;
+    Ok(entry.file.clone())
+}
+
+/// A generic test that can be added from the TUI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenericTest {
+    Unique,
+    NotNull,
+    AcceptedValues(Vec<String>),
+    Relationships { to: String, field: String },
+}
+
+impl GenericTest {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GenericTest::Unique => "unique",
+            GenericTest::NotNull => "not_null",
+            GenericTest::AcceptedValues(_) => "accepted_values",
+            GenericTest::Relationships { .. } => "relationships",
+        }
+    }
+
+    /// Arguments as they are written (and indexed), for spotting duplicates
+    fn args(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut args = serde_json::Map::new();
+        match self {
+            GenericTest::Unique | GenericTest::NotNull => {}
+            GenericTest::AcceptedValues(values) => {
+                args.insert("values".to_string(), values.iter().cloned().collect());
+            }
+            GenericTest::Relationships { to, field } => {
+                args.insert("to".to_string(), format!("ref('{}')", to).into());
+                args.insert("field".to_string(), field.clone().into());
+            }
+        }
+        args
+    }
+
+    /// List item lines with the dash at `indent`
+    fn render(&self, indent: usize) -> Vec<String> {
+        let pad = " ".repeat(indent);
+        let quote = |value: &str| format!("'{}'", value.replace('\'', "''"));
+        match self {
+            GenericTest::Unique | GenericTest::NotNull => vec![format!("{}- {}", pad, self.name())],
+            GenericTest::AcceptedValues(values) => vec![
+                format!("{}- accepted_values:", pad),
+                format!(
+                    "{}    values: [{}]",
+                    pad,
+                    values.iter().map(|v| quote(v)).collect::<Vec<_>>().join(", ")
+                ),
+            ],
+            GenericTest::Relationships { to, field } => vec![
+                format!("{}- relationships:", pad),
+                format!("{}    to: ref({})", pad, quote(to)),
+                format!("{}    field: {}", pad, field),
+            ],
+        }
+    }
+}
+
+/// YAML files a scaffold can be appended to, relative to the project root:
+/// those next to the node's SQL, then a new `<name>.yml` if it doesn't exist
+pub fn scaffold_files(project_path: &Path, node: &Node) -> Vec<String> {
+    let Some(original) = node.original_file_path.as_deref() else {
+        return Vec::new();
+    };
+    let dir = Path::new(original).parent().map(Path::to_path_buf).unwrap_or_default();
+    yaml_files_in(project_path, &dir, &node.name)
+}
+
+/// YAML files in a folder (relative to the project root), then a new
+/// `<name>.yml` if it doesn't exist
+pub fn yaml_files_in(project_path: &Path, dir: &Path, name: &str) -> Vec<String> {
+    let mut files: Vec<String> = fs::read_dir(project_path.join(dir))
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|name| name.ends_with(".yml") || name.ends_with(".yaml"))
+                .map(|name| dir.join(name).to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+
+    let new_file = dir.join(format!("{}.yml", name)).to_string_lossy().to_string();
+    if !files.contains(&new_file) {
+        files.push(new_file);
+    }
+    files
+}
+
+/// Schema entry listing every column with its data type, dash at `indent`
+pub fn scaffold_entry(node_name: &str, columns: &[WarehouseColumn], indent: usize) -> Vec<String> {
+    let pad = " ".repeat(indent);
+    let mut lines = vec![
+        format!("{}- name: {}", pad, yaml_scalar(node_name)),
+        format!("{}  description: \"\"", pad),
+    ];
+    if !columns.is_empty() {
+        lines.push(format!("{}  columns:", pad));
+    }
+    for column in columns {
+        lines.push(format!("{}    - name: {}", pad, yaml_scalar(&column.name.to_lowercase())));
+        if let Some(data_type) = &column.data_type {
+            lines.push(format!("{}      data_type: {}", pad, yaml_scalar(&data_type.to_lowercase())));
+        }
+        lines.push(format!("{}      description: \"\"", pad));
+    }
+    lines
+}
+
+/// Append a scaffolded entry for the node to a YAML file (created if missing).
+/// `file` is relative to the project root.
+pub fn append_scaffold(
+    project_path: &Path,
+    index: &YamlIndex,
+    node: &Node,
+    file: &str,
+    columns: &[WarehouseColumn],
+) -> Result<(), String> {
+    let (section, _) = section_of(&node.resource_type)
+        .ok_or_else(|| format!("Schema YAML can't be edited for {}s", node.resource_type))?;
+    if let Some(entry) = index.find_node(node) {
+        return Err(format!("{} is already declared in {}:{}", node.name, entry.file, entry.line));
+    }
+    append_entry(project_path, file, section, &node.name, columns)
+}
+
+/// Append a new entry to a section of a YAML file (created if missing).
+/// `file` is relative to the project root.
+pub fn append_entry(
+    project_path: &Path,
+    file: &str,
+    section: &str,
+    name: &str,
+    columns: &[WarehouseColumn],
+) -> Result<(), String> {
+    let path = project_path.join(file);
+    let contents = fs::read_to_string(&path).unwrap_or_else(|_| "version: 2\n".to_string());
+    let updated = add_scaffold(&contents, file, section, name, columns)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(&path, updated).map_err(|e| format!("Failed to write {}: {}", file, e))
+}
+
+/// Read the node's schema file (or start a new one), apply `edit` and write it back
+fn edit_schema_file(
+    project_path: &Path,
+    index: &YamlIndex,
+    node: &Node,
+    column: Option<&str>,
+    edit: impl FnOnce(&str, &str, &YamlTarget) -> Result<String, String>,
+) -> Result<String, String> {
+    let (section, kind) = section_of(&node.resource_type)
+        .ok_or_else(|| format!("Schema YAML can't be edited for {}s", node.resource_type))?;
+    let file = schema_file_for(index, node)
+        .ok_or_else(|| format!("No schema file found for {}", node.name))?;
+    let path: PathBuf = project_path.join(&file);
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) => "version: 2\n".to_string(),
+    };
+    let target = YamlTarget {
+        section,
+        kind,
+        resource: &node.name,
+        column,
+    };
+    let updated = edit(&contents, &file, &target)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(&path, updated).map_err(|e| format!("Failed to write {}: {}", file, e))?;
+    Ok(file)
+}
+
+/// The entry being edited
+struct YamlTarget<'a> {
+    section: &'a str,
+    kind: YamlEntryKind,
+    resource: &'a str,
+    column: Option<&'a str>,
+}
+
+/// Apply a line edit to indexed file contents and check the result still parses.
+/// The file's line endings and final newline are kept as they were.
+fn edit_lines(
+    contents: &str,
+    file: &str,
+    edit: impl FnOnce(&mut Vec<String>, &YamlIndex) -> Result<(), String>,
+) -> Result<String, String> {
+    let mut index = YamlIndex::default();
+    index.add_file(file, contents)?;
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+
+    edit(&mut lines, &index)?;
+
+    let newline = if contents.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut updated = lines.join(newline);
+    if contents.is_empty() || contents.ends_with('\n') {
+        updated.push_str(newline);
+    }
+    serde_yaml::from_str::<serde_yaml::Value>(&updated)
+        .map_err(|e| format!("Edit would produce invalid YAML: {}", e))?;
+    Ok(updated)
+}
+
+/// Set a description in the file contents, returning the new contents
+fn set_description(
+    contents: &str,
+    file: &str,
+    target: &YamlTarget,
+    description: &str,
+) -> Result<String, String> {
+    edit_lines(contents, file, |lines, index| {
+        let mut item = ensure_resource(lines, index, target);
+        if let Some(column) = target.column {
+            item = ensure_column(lines, item, column);
+        }
+        replace_description(lines, item, description);
+        Ok(())
+    })
+}
+
+/// Add a test to the target column, returning the new contents
+fn add_test(
+    contents: &str,
+    file: &str,
+    target: &YamlTarget,
+    test: &GenericTest,
+) -> Result<String, String> {
+    let column = target.column.unwrap_or_default();
+    edit_lines(contents, file, |lines, index| {
+        // dbt rejects two tests with the same name and arguments on a column
+        let args = test.args();
+        let is_duplicate = index.entries.iter().any(|e| {
+            e.kind == YamlEntryKind::Test
+                && e.resource == target.resource
+                && e.column.as_deref().is_some_and(|c| c.eq_ignore_ascii_case(column))
+                && e.test.as_deref() == Some(test.name())
+                && args.iter().all(|(key, value)| e.args.get(key) == Some(value))
+        });
+        if is_duplicate {
+            return Err(format!("{} already has this {} test", column, test.name()));
+        }
+        // Follow the file's convention (dbt >= 1.8 uses data_tests)
+        let tests_key = if lines.iter().any(|l| l.trim_start().starts_with("data_tests:")) {
+            "data_tests"
+        } else {
+            "tests"
+        };
+
+        let resource = ensure_resource(lines, index, target);
+        let item = ensure_column(lines, resource, column);
+        let key_indent = item_key_indent(&lines[item]);
+        let end = block_end(lines, item, indent_of(&lines[item]), false);
+
+        let existing = find_key(lines, item + 1, end, key_indent, "data_tests")
+            .or_else(|| find_key(lines, item + 1, end, key_indent, "tests"));
+        match existing {
+            Some(tests) => {
+                let (_, value) = lines[tests].split_once(':').unwrap_or_default();
+                if !value.trim().is_empty() {
+                    return Err(format!(
+                        "Tests for {} are written inline; edit {} by hand",
+                        column, file
+                    ));
+                }
+                let tests_end = block_end(lines, tests, key_indent, true);
+                let dash_indent = first_child_indent(lines, tests, tests_end).unwrap_or(key_indent + 2);
+                lines.splice(tests_end..tests_end, test.render(dash_indent));
+            }
+            None => {
+                let mut new_lines = vec![format!("{}{}:", " ".repeat(key_indent), tests_key)];
+                new_lines.extend(test.render(key_indent + 2));
+                lines.splice(end..end, new_lines);
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Append a scaffolded entry to a section, returning the new contents
+fn add_scaffold(
+    contents: &str,
+    file: &str,
+    section: &str,
+    node_name: &str,
+    columns: &[WarehouseColumn],
+) -> Result<String, String> {
+    edit_lines(contents, file, |lines, _| {
+        let (position, dash_indent) = section_insert_point(lines, section);
+        lines.splice(position..position, scaffold_entry(node_name, columns, dash_indent));
+        Ok(())
+    })
+}
+
+/// Change the name of a declared resource, returning the new contents
+pub fn rename_entry(contents: &str, entry: &YamlEntry, new_name: &str) -> Result<String, String> {
+    edit_lines(contents, &entry.file, |lines, _| {
+        let item = entry.line - 1;
+        let key_indent = item_key_indent(&lines[item]);
+        let name_line = if lines[item].trim_start().trim_start_matches('-').trim_start().starts_with("name:") {
+            Some(item)
+        } else {
+            find_key(lines, item + 1, entry.end_line, key_indent, "name")
+        }
+        .ok_or_else(|| format!("No name key found at {}:{}", entry.file, entry.line))?;
+
+        let line = &lines[name_line];
+        let at = line.find("name:").unwrap_or(0) + "name:".len();
+        let comment = line[at..].find(" #").map(|i| line[at + i..].to_string()).unwrap_or_default();
+        lines[name_line] = format!("{} {}{}", &line[..at], yaml_scalar(new_name), comment);
+        Ok(())
+    })
+}
+
+/// Remove a test declaration (and its tests: key if it was the last one)
+fn remove_test_entry(contents: &str, file: &str, entry: &YamlEntry) -> Result<String, String> {
+    edit_lines(contents, file, |lines, index| {
+        // The test must still be declared where it was indexed
+        let current = index
+            .entries
+            .iter()
+            .find(|e| {
+                e.kind == YamlEntryKind::Test
+                    && e.resource == entry.resource
+                    && e.column == entry.column
+                    && e.test == entry.test
+                    && e.line == entry.line
+            })
+            .ok_or_else(|| format!("{} has changed; refresh and try again", file))?;
+        let start = current.line - 1;
+        if !lines[start].trim_start().starts_with('-') {
+            return Err(format!("Test at {}:{} is written inline; edit it by hand", file, current.line));
+        }
+        let dash_indent = indent_of(&lines[start]);
+        lines.drain(start..current.end_line);
+
+        // The tests: key the item belonged to, dropped if now empty
+        let parent = (0..start).rev().find(|&i| {
+            let line = &lines[i];
+            is_content(line)
+                && (indent_of(line) < dash_indent || (indent_of(line) == dash_indent && !is_item(line)))
+        });
+        if let Some(key) = parent {
+            let is_tests_key = matches!(lines[key].trim(), "tests:" | "data_tests:");
+            if is_tests_key && block_end(lines, key, indent_of(&lines[key]), true) == key + 1 {
+                lines.remove(key);
+            }
+        }
+        Ok(())
+    })
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn is_content(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    !trimmed.is_empty() && !trimmed.starts_with('#')
+}
+
+fn is_item(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("- ") || trimmed == "-"
+}
+
+/// Indentation of the mapping in an item line: "  - name: x" -> 4
+fn item_key_indent(line: &str) -> usize {
+    let trimmed = line.trim_start();
+    let rest = trimmed.strip_prefix('-').unwrap_or(trimmed);
+    indent_of(line) + 1 + indent_of(rest)
+}
+
+/// End (exclusive) of the block opened at `start`: the following lines
+/// indented deeper than `indent`, plus list items at `indent` when the
+/// block is a key whose list sits at its own indentation
+fn block_end(lines: &[String], start: usize, indent: usize, items_at_indent: bool) -> usize {
+    let mut end = start + 1;
+    for (i, line) in lines.iter().enumerate().skip(start + 1) {
+        if !is_content(line) {
+            continue;
+        }
+        let line_indent = indent_of(line);
+        if line_indent > indent || (items_at_indent && line_indent == indent && is_item(line)) {
+            end = i + 1;
+        } else {
+            break;
+        }
+    }
+    end
+}
+
+/// Indentation of the first content line in `start + 1..end`
+fn first_child_indent(lines: &[String], start: usize, end: usize) -> Option<usize> {
+    lines[start + 1..end]
+        .iter()
+        .find(|l| is_content(l))
+        .map(|l| indent_of(l))
+}
+
+/// First line in `start..end` at `indent` whose key is `key`
+fn find_key(lines: &[String], start: usize, end: usize, indent: usize, key: &str) -> Option<usize> {
+    (start..end).find(|&i| {
+        let line = &lines[i];
+        is_content(line)
+            && indent_of(line) == indent
+            && line
+                .trim_start()
+                .strip_prefix(key)
+                .is_some_and(|rest| rest.starts_with(':'))
+    })
+}
+
+/// Value of the item's name, from "- name: x" or a name: key below the dash
+fn item_name(lines: &[String], item: usize) -> Option<String> {
+    let key_indent = item_key_indent(&lines[item]);
+    let end = block_end(lines, item, indent_of(&lines[item]), false);
+    let content = lines[item].trim_start().trim_start_matches('-').trim_start();
+    let value = match content.strip_prefix("name:") {
+        Some(value) => value,
+        None => {
+            let line = find_key(lines, item + 1, end, key_indent, "name")?;
+            lines[line].trim_start().strip_prefix("name:")?
+        }
+    };
+    let value = value.split(" #").next().unwrap_or(value).trim();
+    Some(value.trim_matches(|c| c == '"' || c == '\'').to_string())
+}
+
+/// Line of the resource's item, adding it (with its section if needed) when missing
+fn ensure_resource(lines: &mut Vec<String>, index: &YamlIndex, target: &YamlTarget) -> usize {
+    if let Some(entry) = index
+        .entries
+        .iter()
+        .find(|e| e.kind == target.kind && e.resource == target.resource)
+    {
+        return entry.line - 1;
+    }
+
+    let (position, dash_indent) = section_insert_point(lines, target.section);
+    lines.insert(
+        position,
+        format!("{}- name: {}", " ".repeat(dash_indent), yaml_scalar(target.resource)),
+    );
+    position
+}
+
+/// Where a new item goes at the end of a top-level section, and the
+/// indentation of its dash. Adds the section when missing.
+fn section_insert_point(lines: &mut Vec<String>, section: &str) -> (usize, usize) {
+    match find_key(lines, 0, lines.len(), 0, section) {
+        Some(start) => {
+            let end = block_end(lines, start, 0, true);
+            (end, first_child_indent(lines, start, end).unwrap_or(2))
+        }
+        None => {
+            while lines.last().is_some_and(|l| l.trim().is_empty()) {
+                lines.pop();
+            }
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(format!("{}:", section));
+            (lines.len(), 2)
+        }
+    }
+}
+
+/// Line of the column's item under the resource item, adding it when missing
+fn ensure_column(lines: &mut Vec<String>, item: usize, column: &str) -> usize {
+    let key_indent = item_key_indent(&lines[item]);
+    let end = block_end(lines, item, indent_of(&lines[item]), false);
+
+    match find_key(lines, item + 1, end, key_indent, "columns") {
+        Some(columns) => {
+            let columns_end = block_end(lines, columns, key_indent, true);
+            let dash_indent = first_child_indent(lines, columns, columns_end).unwrap_or(key_indent + 2);
+            let existing = (columns + 1..columns_end).find(|&i| {
+                is_item(&lines[i])
+                    && indent_of(&lines[i]) == dash_indent
+                    && item_name(lines, i).is_some_and(|name| name.eq_ignore_ascii_case(column))
+            });
+            if let Some(existing) = existing {
+                return existing;
+            }
+            lines.insert(
+                columns_end,
+                format!("{}- name: {}", " ".repeat(dash_indent), yaml_scalar(column)),
+            );
+            columns_end
+        }
+        None => {
+            let pad = " ".repeat(key_indent);
+            lines.splice(
+                end..end,
+                [
+                    format!("{}columns:", pad),
+                    format!("{}  - name: {}", pad, yaml_scalar(column)),
+                ],
+            );
+            end + 1
+        }
+    }
+}
+
+/// Replace (or add) the description of the item starting at `item`
+fn replace_description(lines: &mut Vec<String>, item: usize, description: &str) {
+    let key_indent = item_key_indent(&lines[item]);
+    let end = block_end(lines, item, indent_of(&lines[item]), false);
+    let rendered = render_description(key_indent, description);
+
+    match find_key(lines, item + 1, end, key_indent, "description") {
+        Some(existing) => {
+            let existing_end = block_end(lines, existing, key_indent, false);
+            lines.splice(existing..existing_end, rendered);
+        }
+        None => {
+            // After the name, wherever it is
+            let name_line = if lines[item].contains("name:") {
+                item
+            } else {
+                find_key(lines, item + 1, end, key_indent, "name").unwrap_or(item)
+            };
+            lines.splice(name_line + 1..name_line + 1, rendered);
+        }
+    }
+}
+
+/// `description: ...` at the given indentation; multi-line text becomes a block scalar
+fn render_description(indent: usize, description: &str) -> Vec<String> {
+    let pad = " ".repeat(indent);
+    let description = description.trim_end();
+    if !description.contains('\n') {
+        return vec![format!("{}description: {}", pad, yaml_scalar(description))];
+    }
+
+    let mut lines = vec![format!("{}description: |", pad)];
+    for line in description.lines() {
+        if line.trim().is_empty() {
+            lines.push(String::new());
+        } else {
+            lines.push(format!("{}  {}", pad, line));
+        }
+    }
+    lines
+}
+
+/// A single-line string as a YAML scalar, quoted only when needed
+fn yaml_scalar(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value != value.trim()
+        || value.contains(": ")
+        || value.contains(" #")
+        || value.ends_with(':')
+        || value.starts_with([
+            '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%',
+            '@', '`',
+        ])
+        || matches!(
+            value.to_ascii_lowercase().as_str(),
+            "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "~"
+        )
+        || value.parse::<f64>().is_ok();
+
+    if needs_quotes {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = "\
+version: 2
+
+models:
+  # Orders fact table
+  - name: orders
+    description: Old description
+    columns:
+      - name: order_id  # primary key
+        tests:
+          - unique
+  - name: customers
+    config:
+      tags: ['core']
+";
+
+    fn target<'a>(resource: &'a str, column: Option<&'a str>) -> YamlTarget<'a> {
+        YamlTarget {
+            section: "models",
+            kind: YamlEntryKind::Model,
+            resource,
+            column,
+        }
+    }
+
+    #[test]
+    fn test_replace_and_add_descriptions() {
+        let updated = set_description(
+            SCHEMA,
+            "schema.yml",
+            &target("orders", None),
+            "One row per order.\nIncludes refunds.",
+        )
+        .unwrap();
+        assert!(updated.contains(
+            "  - name: orders\n    description: |\n      One row per order.\n      Includes refunds.\n    columns:"
+        ));
+
+        let updated =
+            set_description(&updated, "schema.yml", &target("orders", Some("order_id")), "Key: unique").unwrap();
+        assert!(updated.contains(
+            "      - name: order_id  # primary key\n        description: \"Key: unique\"\n        tests:"
+        ));
+        // Comments are kept
+        assert!(updated.contains("  # Orders fact table\n"));
+
+        let updated =
+            set_description(&updated, "schema.yml", &target("customers", None), "Customers").unwrap();
+        assert!(updated.ends_with(
+            "  - name: customers\n    description: Customers\n    config:\n      tags: ['core']\n"
+        ));
+    }
+
+    #[test]
+    fn test_add_missing_column_and_model() {
+        let updated =
+            set_description(SCHEMA, "schema.yml", &target("orders", Some("status")), "Order status").unwrap();
+        assert!(updated.contains(
+            "          - unique\n      - name: status\n        description: Order status\n  - name: customers"
+        ));
+
+        let updated =
+            set_description(SCHEMA, "schema.yml", &target("customers", Some("id")), "Id").unwrap();
+        assert!(updated.ends_with(
+            "      tags: ['core']\n    columns:\n      - name: id\n        description: Id\n"
+        ));
+
+        let updated =
+            set_description(SCHEMA, "schema.yml", &target("payments", None), "true").unwrap();
+        assert!(updated.ends_with("  - name: payments\n    description: \"true\"\n"));
+
+        // Names that YAML would read as another type are quoted
+        let updated =
+            set_description(SCHEMA, "schema.yml", &target("customers", Some("on")), "Flag").unwrap();
+        assert!(updated.contains("    columns:\n      - name: \"on\"\n        description: Flag\n"));
+
+        let updated =
+            set_description("version: 2\n", "schema.yml", &target("payments", None), "Payments").unwrap();
+        assert_eq!(
+            updated,
+            "version: 2\n\nmodels:\n  - name: payments\n    description: Payments\n"
+        );
+    }
+
+    #[test]
+    fn test_add_column_tests() {
+        let updated = add_test(
+            SCHEMA,
+            "schema.yml",
+            &target("orders", Some("order_id")),
+            &GenericTest::NotNull,
+        )
+        .unwrap();
+        assert!(updated.contains("        tests:\n          - unique\n          - not_null\n  - name: customers"));
+
+        let duplicate = add_test(&updated, "schema.yml", &target("orders", Some("order_id")), &GenericTest::Unique);
+        assert!(duplicate.is_err());
+
+        let values = GenericTest::AcceptedValues(vec!["placed".to_string(), "o'clock".to_string()]);
+        let updated = add_test(&updated, "schema.yml", &target("orders", Some("status")), &values).unwrap();
+        assert!(updated.contains(
+            "      - name: status\n        tests:\n          - accepted_values:\n              values: ['placed', 'o''clock']\n"
+        ));
+        // The same values again would be a duplicate test; other values are a new one
+        assert!(add_test(&updated, "schema.yml", &target("orders", Some("status")), &values).is_err());
+        let other = GenericTest::AcceptedValues(vec!["returned".to_string()]);
+        assert!(add_test(&updated, "schema.yml", &target("orders", Some("status")), &other).is_ok());
+
+        let relationship = GenericTest::Relationships {
+            to: "customers".to_string(),
+            field: "id".to_string(),
+        };
+        let updated =
+            add_test(&updated, "schema.yml", &target("customers", Some("id")), &relationship).unwrap();
+        assert!(updated.ends_with(
+            "    columns:\n      - name: id\n        tests:\n          - relationships:\n              to: ref('customers')\n              field: id\n"
+        ));
+        assert!(add_test(&updated, "schema.yml", &target("customers", Some("id")), &relationship).is_err());
+
+        let data_tests = SCHEMA.replace("tests:", "data_tests:");
+        let updated =
+            add_test(&data_tests, "schema.yml", &target("customers", Some("id")), &GenericTest::Unique).unwrap();
+        assert!(updated.ends_with("      - name: id\n        data_tests:\n          - unique\n"));
+    }
+
+    #[test]
+    fn test_keep_line_endings() {
+        let crlf = SCHEMA.replace('\n', "\r\n");
+        let target = target("orders", Some("status"));
+        let updated = set_description(&crlf, "schema.yml", &target, "Order status").unwrap();
+        assert!(updated.contains("      - name: status\r\n        description: Order status\r\n"));
+        assert!(updated.ends_with("\r\n"));
+        assert!(!updated.replace("\r\n", "").contains('\n'));
+
+        let unterminated = SCHEMA.trim_end();
+        let updated = set_description(unterminated, "schema.yml", &target, "Order status").unwrap();
+        assert!(!updated.ends_with('\n'));
+    }
+
+    fn test_entry(contents: &str, name: &str) -> YamlEntry {
+        let mut index = YamlIndex::default();
+        index.add_file("schema.yml", contents).unwrap();
+        index
+            .entries
+            .into_iter()
+            .find(|e| e.test.as_deref() == Some(name))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_remove_test() {
+        let updated = add_test(
+            SCHEMA,
+            "schema.yml",
+            &target("orders", Some("order_id")),
+            &GenericTest::NotNull,
+        )
+        .unwrap();
+
+        let updated = remove_test_entry(&updated, "schema.yml", &test_entry(&updated, "unique")).unwrap();
+        assert!(updated.contains("        tests:\n          - not_null\n  - name: customers"));
+
+        let updated = remove_test_entry(&updated, "schema.yml", &test_entry(&updated, "not_null")).unwrap();
+        assert!(updated.contains("      - name: order_id  # primary key\n  - name: customers"));
+    }
+
+    #[test]
+    fn test_add_scaffold() {
+        let columns = vec![
+            WarehouseColumn {
+                name: "ORDER_ID".to_string(),
+                data_type: Some("NUMBER(38,0)".to_string()),
+            },
+            WarehouseColumn {
+                name: "status".to_string(),
+                data_type: None,
+            },
+        ];
+        let updated = add_scaffold(SCHEMA, "schema.yml", "models", "payments", &columns).unwrap();
+        assert!(updated.ends_with(
+            "      tags: ['core']
+  - name: payments
+    description: \"\"
+    columns:
+      - name: order_id
+        data_type: number(38,0)
+        description: \"\"
+      - name: status
+        description: \"\"
+"
+        ));
+
+        let updated = add_scaffold("version: 2\n", "payments.yml", "models", "payments", &columns).unwrap();
+        assert!(updated.starts_with("version: 2\n\nmodels:\n  - name: payments\n"));
+    }
+
+    #[test]
+    fn test_rename_entry() {
+        let mut index = YamlIndex::default();
+        index.add_file("schema.yml", SCHEMA).unwrap();
+        let orders = index
+            .entries
+            .iter()
+            .find(|e| e.kind == YamlEntryKind::Model && e.resource == "orders")
+            .unwrap();
+
+        let updated = rename_entry(SCHEMA, orders, "fct_orders").unwrap();
+        assert!(updated.contains("  # Orders fact table\n  - name: fct_orders\n    description: Old description\n"));
+        assert!(updated.contains("      - name: order_id  # primary key\n"));
+    }
+}
@@ -0,0 +1,33 @@
+//! Schema YAML index loading service
+
+use crate::model::yaml_index::YamlIndex;
+use crate::model::node::SourceTable;
+use crate::model::Node;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+/// Index the schema YAML files referenced by the manifest: each node's
+/// `patch_path`, the files declaring generic tests, and the files declaring
+/// sources. Files that can't be read or parsed are skipped.
+pub fn load_yaml_index(project_path: &Path, nodes: &[Node], sources: &[SourceTable]) -> YamlIndex {
+    let files: BTreeSet<&str> = nodes
+        .iter()
+        .filter_map(|node| {
+            node.schema_file_path().or_else(|| {
+                node.original_file_path
+                    .as_deref()
+                    .filter(|p| p.ends_with(".yml") || p.ends_with(".yaml"))
+            })
+        })
+        .chain(sources.iter().filter_map(|s| s.original_file_path.as_deref()))
+        .collect();
+
+    let mut index = YamlIndex::default();
+    for file in files {
+        if let Ok(contents) = fs::read_to_string(project_path.join(file)) {
+            let _ = index.add_file(file, &contents);
+        }
+    }
+    index
+}